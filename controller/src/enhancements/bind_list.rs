@@ -0,0 +1,216 @@
+use std::time::Instant;
+
+use obfstr::obfstr;
+use utils_state::{
+    State,
+    StateCacheType,
+    StateRegistry,
+};
+
+use super::{
+    Enhancement,
+    EnhancementStatus,
+};
+use crate::settings::{
+    ActivationMode,
+    AppSettings,
+    OverlayCorner,
+};
+
+/// Distance to the screen edges in pixels
+const SCREEN_MARGIN: f32 = 10.0;
+const TEXT_PADDING: [f32; 2] = [8.0, 4.0];
+
+/// Time in seconds for an entry to fade in or out
+const FADE_DURATION: f32 = 0.25;
+
+/// Status of all enhancements reporting a status.
+/// Will be updated by the application after every update cycle.
+#[derive(Default)]
+pub struct EnhancementStatusList {
+    pub entries: Vec<EnhancementStatus>,
+}
+
+impl State for EnhancementStatusList {
+    type Parameter = ();
+
+    fn create(_states: &StateRegistry, _param: Self::Parameter) -> anyhow::Result<Self> {
+        Ok(Default::default())
+    }
+
+    fn cache_type() -> StateCacheType {
+        StateCacheType::Persistent
+    }
+}
+
+struct BindListEntry {
+    status: EnhancementStatus,
+
+    /// Current visibility in [0.0;1.0]
+    alpha: f32,
+}
+
+/// List of all currently active features and their key binding mode.
+pub struct BindList {
+    entries: Vec<BindListEntry>,
+    last_update: Instant,
+}
+
+impl BindList {
+    pub fn new() -> Self {
+        Self {
+            entries: Default::default(),
+            last_update: Instant::now(),
+        }
+    }
+
+    fn mode_label(mode: ActivationMode) -> String {
+        match mode {
+            ActivationMode::Always => obfstr!("always").to_string(),
+            ActivationMode::Toggle => obfstr!("toggle").to_string(),
+            ActivationMode::Hold => obfstr!("hold").to_string(),
+            ActivationMode::HoldInverted => obfstr!("release").to_string(),
+            ActivationMode::DoubleTap => obfstr!("double tap").to_string(),
+            ActivationMode::LongPress => obfstr!("long press").to_string(),
+            ActivationMode::Off => obfstr!("off").to_string(),
+        }
+    }
+}
+
+impl Enhancement for BindList {
+    fn update(&mut self, ctx: &crate::UpdateContext) -> anyhow::Result<()> {
+        let time_delta = self.last_update.elapsed().as_secs_f32();
+        self.last_update = Instant::now();
+
+        let settings = ctx.states.resolve::<AppSettings>(())?;
+        if !settings.bind_list {
+            self.entries.clear();
+            return Ok(());
+        }
+
+        let status_list = ctx.states.resolve::<EnhancementStatusList>(())?;
+        for status in status_list.entries.iter() {
+            match self
+                .entries
+                .iter_mut()
+                .find(|entry| entry.status.name == status.name)
+            {
+                Some(entry) => entry.status = status.clone(),
+                None => self.entries.push(BindListEntry {
+                    status: status.clone(),
+                    alpha: 0.0,
+                }),
+            }
+        }
+
+        let fade_step = time_delta / FADE_DURATION;
+        for entry in self.entries.iter_mut() {
+            let reported = status_list
+                .entries
+                .iter()
+                .any(|status| status.name == entry.status.name);
+
+            if reported && entry.status.active {
+                entry.alpha = (entry.alpha + fade_step).min(1.0);
+            } else {
+                entry.alpha = (entry.alpha - fade_step).max(0.0);
+            }
+        }
+
+        /* keep entries which are still fading out */
+        self.entries
+            .retain(|entry| entry.alpha > 0.0 || entry.status.active);
+
+        Ok(())
+    }
+
+    fn render(&self, states: &utils_state::StateRegistry, ui: &imgui::Ui) -> anyhow::Result<()> {
+        let settings = states.resolve::<AppSettings>(())?;
+        if !settings.bind_list {
+            return Ok(());
+        }
+
+        let lines = self
+            .entries
+            .iter()
+            .filter(|entry| entry.alpha > 0.0)
+            .map(|entry| {
+                (
+                    format!(
+                        "{} [{}]",
+                        entry.status.name,
+                        Self::mode_label(entry.status.mode)
+                    ),
+                    entry.alpha,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        if lines.is_empty() {
+            return Ok(());
+        }
+
+        let line_height = ui.text_line_height_with_spacing();
+        let list_width = lines
+            .iter()
+            .map(|(text, _)| ui.calc_text_size(text)[0])
+            .fold(0.0, f32::max)
+            + TEXT_PADDING[0] * 2.0;
+        let list_height = lines
+            .iter()
+            .map(|(_, alpha)| line_height * alpha)
+            .sum::<f32>()
+            + TEXT_PADDING[1] * 2.0;
+
+        let [display_width, display_height] = ui.io().display_size;
+        let list_position = match settings.bind_list_position {
+            OverlayCorner::TopLeft => [SCREEN_MARGIN, SCREEN_MARGIN],
+            OverlayCorner::TopRight => [display_width - list_width - SCREEN_MARGIN, SCREEN_MARGIN],
+            OverlayCorner::BottomLeft => {
+                [SCREEN_MARGIN, display_height - list_height - SCREEN_MARGIN]
+            }
+            OverlayCorner::BottomRight => [
+                display_width - list_width - SCREEN_MARGIN,
+                display_height - list_height - SCREEN_MARGIN,
+            ],
+        };
+
+        let draw = ui.get_window_draw_list();
+        draw.add_rect(
+            list_position,
+            [
+                list_position[0] + list_width,
+                list_position[1] + list_height,
+            ],
+            [
+                0.0,
+                0.0,
+                0.0,
+                settings.bind_list_background_alpha.clamp(0.0, 1.0),
+            ],
+        )
+        .filled(true)
+        .rounding(3.0)
+        .build();
+
+        let text_color = settings.bind_list_color.as_f32();
+        let mut offset_y = list_position[1] + TEXT_PADDING[1];
+        for (text, alpha) in lines.iter() {
+            draw.add_text(
+                [list_position[0] + TEXT_PADDING[0], offset_y],
+                [
+                    text_color[0],
+                    text_color[1],
+                    text_color[2],
+                    text_color[3] * alpha,
+                ],
+                text,
+            );
+
+            /* entries collapse while fading out */
+            offset_y += line_height * alpha;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,234 @@
+use std::cell::Cell;
+
+use cs2::{
+    DroppedC4,
+    PlantedC4,
+    PlantedC4State,
+};
+use imgui::{
+    Condition,
+    MouseButton,
+    WindowFlags,
+};
+use obfstr::obfstr;
+
+use super::Enhancement;
+use crate::{
+    settings::AppSettings,
+    utils::DrawOpacity,
+    view::ViewController,
+};
+
+/// Time in seconds required to defuse the bomb without a defuse kit
+pub(super) const DEFUSE_TIME: f32 = 10.0;
+
+/// Time in seconds required to defuse the bomb with a defuse kit
+pub(super) const DEFUSE_TIME_KIT: f32 = 5.0;
+
+pub(super) const COLOR_CRITICAL: [f32; 4] = [0.79, 0.11, 0.11, 1.0];
+pub(super) const COLOR_WARNING: [f32; 4] = [0.95, 0.62, 0.07, 1.0];
+pub(super) const COLOR_OK: [f32; 4] = [0.11, 0.79, 0.26, 1.0];
+
+/// Marker at the planted (or dropped) C4 and a panel showing the remaining time
+/// as well as if the bomb can still be defused.
+/// The panel position is stored within the imgui settings of the app config.
+pub struct BombESP {
+    /// Panel position of the last frame
+    window_position: Cell<Option<[f32; 2]>>,
+
+    /// Panel position which has been saved to the settings
+    saved_window_position: Option<[f32; 2]>,
+}
+
+impl BombESP {
+    pub fn new() -> Self {
+        Self {
+            window_position: Default::default(),
+            saved_window_position: None,
+        }
+    }
+
+    /// Color for the remaining time until detonation
+    fn time_color(time_detonation: f32, defuse_time: f32) -> [f32; 4] {
+        if time_detonation < defuse_time {
+            COLOR_CRITICAL
+        } else if time_detonation < DEFUSE_TIME {
+            COLOR_WARNING
+        } else {
+            COLOR_OK
+        }
+    }
+}
+
+impl Enhancement for BombESP {
+    fn update(&mut self, _ctx: &crate::UpdateContext) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    fn update_settings(
+        &mut self,
+        ui: &imgui::Ui,
+        _settings: &mut AppSettings,
+    ) -> anyhow::Result<bool> {
+        let window_position = match self.window_position.get() {
+            Some(position) => position,
+            None => return Ok(false),
+        };
+
+        if self.saved_window_position.is_none() {
+            /* initial position, nothing has been moved */
+            self.saved_window_position = Some(window_position);
+            return Ok(false);
+        }
+
+        if self.saved_window_position == Some(window_position)
+            || ui.is_mouse_down(MouseButton::Left)
+        {
+            return Ok(false);
+        }
+
+        /* panel has been moved, save the settings (including the imgui window positions) */
+        self.saved_window_position = Some(window_position);
+        Ok(true)
+    }
+
+    fn render(&self, states: &utils_state::StateRegistry, ui: &imgui::Ui) -> anyhow::Result<()> {
+        let settings = states.resolve::<AppSettings>(())?;
+        if !settings.bomb_esp {
+            self.window_position.set(None);
+            return Ok(());
+        }
+
+        let view = states.resolve::<ViewController>(())?;
+        let draw = ui.get_window_draw_list();
+
+        let dropped_bomb = states.resolve::<DroppedC4>(())?;
+        if let Some(position) = &dropped_bomb.position {
+            if let Some(position) = view.world_to_screen(position, false) {
+                let text = obfstr!("C4 (dropped)").to_string();
+                let [text_width, text_height] = ui.calc_text_size(&text);
+
+                draw.add_circle(position, 4.0, COLOR_WARNING)
+                    .filled(true)
+                    .build();
+                draw.add_text(
+                    [
+                        position.x - text_width / 2.0,
+                        position.y - text_height - 6.0,
+                    ],
+                    COLOR_WARNING,
+                    &text,
+                );
+            }
+        }
+
+        let bomb_state = states.resolve::<PlantedC4>(())?;
+        let time_detonation = match &bomb_state.state {
+            PlantedC4State::Active { time_detonation } => *time_detonation,
+            _ => {
+                self.window_position.set(None);
+                return Ok(());
+            }
+        };
+
+        let bomb_site = if bomb_state.bomb_site == 0 { "A" } else { "B" };
+        let defuse_time = match &bomb_state.defuser {
+            Some(defuser) if defuser.has_kit => DEFUSE_TIME_KIT,
+            Some(_) => DEFUSE_TIME,
+            None => DEFUSE_TIME_KIT,
+        };
+        let time_color = Self::time_color(time_detonation, defuse_time);
+
+        if let Some(position) = view.world_to_screen(&bomb_state.position, false) {
+            let text = format!("C4 {} {:.1}s", bomb_site, time_detonation);
+            let [text_width, text_height] = ui.calc_text_size(&text);
+
+            draw.add_circle(position, 4.0, time_color)
+                .filled(true)
+                .build();
+            draw.add_text(
+                [
+                    position.x - text_width / 2.0,
+                    position.y - text_height - 6.0,
+                ],
+                time_color,
+                &text,
+            );
+        }
+
+        if !settings.bomb_esp_panel {
+            self.window_position.set(None);
+            return Ok(());
+        }
+
+        ui.window(obfstr!("Bomb"))
+            .position([10.0, 450.0], Condition::FirstUseEver)
+            .flags(
+                WindowFlags::ALWAYS_AUTO_RESIZE
+                    | WindowFlags::NO_COLLAPSE
+                    | WindowFlags::NO_FOCUS_ON_APPEARING,
+            )
+            .build(|| {
+                let _opacity = DrawOpacity::window(ui, settings.overlay_opacity);
+                ui.text(format!("{} {}", obfstr!("Planted at site"), bomb_site));
+                ui.text_colored(
+                    time_color,
+                    format!("{:.1}s {}", time_detonation, obfstr!("remaining")),
+                );
+
+                let progress = if bomb_state.timer_length > 0.0 {
+                    (time_detonation / bomb_state.timer_length).clamp(0.0, 1.0)
+                } else {
+                    0.0
+                };
+                imgui::ProgressBar::new(progress)
+                    .size([180.0, 4.0])
+                    .overlay_text("")
+                    .build(ui);
+                ui.separator();
+
+                match &bomb_state.defuser {
+                    Some(defuser) => {
+                        let color = if defuser.time_remaining > time_detonation {
+                            COLOR_CRITICAL
+                        } else {
+                            COLOR_OK
+                        };
+
+                        ui.text_colored(
+                            color,
+                            format!(
+                                "{} {} ({})",
+                                obfstr!("Defused by"),
+                                defuser.player_name,
+                                if defuser.has_kit {
+                                    obfstr!("kit").to_string()
+                                } else {
+                                    obfstr!("no kit").to_string()
+                                }
+                            ),
+                        );
+                        ui.text_colored(
+                            color,
+                            format!("{} {:.1}s", obfstr!("Defused in"), defuser.time_remaining),
+                        );
+                    }
+                    None => {
+                        ui.text_disabled(obfstr!("Not defusing"));
+                    }
+                }
+
+                if time_detonation >= DEFUSE_TIME {
+                    ui.text_colored(COLOR_OK, obfstr!("Defusable without kit"));
+                } else if time_detonation >= DEFUSE_TIME_KIT {
+                    ui.text_colored(COLOR_WARNING, obfstr!("Defusable with kit only"));
+                } else {
+                    ui.text_colored(COLOR_CRITICAL, obfstr!("Not defusable"));
+                }
+
+                self.window_position.set(Some(ui.window_pos()));
+            });
+
+        Ok(())
+    }
+}
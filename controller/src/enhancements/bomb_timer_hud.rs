@@ -0,0 +1,237 @@
+use std::cell::Cell;
+
+use cs2::{
+    PlantedC4,
+    PlantedC4State,
+};
+use imgui::{
+    Condition,
+    MouseButton,
+    WindowFlags,
+};
+use obfstr::obfstr;
+
+use super::{
+    bomb_esp::{
+        COLOR_CRITICAL,
+        COLOR_OK,
+        COLOR_WARNING,
+        DEFUSE_TIME,
+        DEFUSE_TIME_KIT,
+    },
+    Enhancement,
+};
+use crate::{
+    settings::AppSettings,
+    utils::DrawOpacity,
+};
+
+const BAR_WIDTH: f32 = 220.0;
+const BAR_HEIGHT: f32 = 8.0;
+const BAR_BACKGROUND: [f32; 4] = [0.2, 0.2, 0.2, 0.8];
+const BAR_MARKER: [f32; 4] = [1.0, 1.0, 1.0, 1.0];
+
+/// HUD widget with the remaining C4 time and the defuse windows.
+/// The widget position is stored within the imgui settings of the app config.
+pub struct BombTimerHud {
+    /// Widget position of the last frame
+    window_position: Cell<Option<[f32; 2]>>,
+
+    /// Widget position which has been saved to the settings
+    saved_window_position: Option<[f32; 2]>,
+}
+
+impl BombTimerHud {
+    pub fn new() -> Self {
+        Self {
+            window_position: Default::default(),
+            saved_window_position: None,
+        }
+    }
+
+    /// Render a horizontal bar filled by the given fraction.
+    /// Markers are fractions of the bar where a vertical line will be drawn.
+    fn render_bar(ui: &imgui::Ui, fraction: f32, color: [f32; 4], markers: &[f32]) {
+        let draw = ui.get_window_draw_list();
+        let [x, y] = ui.cursor_screen_pos();
+
+        draw.add_rect([x, y], [x + BAR_WIDTH, y + BAR_HEIGHT], BAR_BACKGROUND)
+            .filled(true)
+            .build();
+        draw.add_rect(
+            [x, y],
+            [x + BAR_WIDTH * fraction.clamp(0.0, 1.0), y + BAR_HEIGHT],
+            color,
+        )
+        .filled(true)
+        .build();
+
+        for marker in markers {
+            let marker_x = x + BAR_WIDTH * marker.clamp(0.0, 1.0);
+            draw.add_line(
+                [marker_x, y - 2.0],
+                [marker_x, y + BAR_HEIGHT + 2.0],
+                BAR_MARKER,
+            )
+            .thickness(1.5)
+            .build();
+        }
+
+        ui.dummy([BAR_WIDTH, BAR_HEIGHT]);
+    }
+}
+
+impl Enhancement for BombTimerHud {
+    fn update(&mut self, _ctx: &crate::UpdateContext) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    fn update_settings(
+        &mut self,
+        ui: &imgui::Ui,
+        _settings: &mut AppSettings,
+    ) -> anyhow::Result<bool> {
+        let window_position = match self.window_position.get() {
+            Some(position) => position,
+            None => return Ok(false),
+        };
+
+        if self.saved_window_position.is_none() {
+            /* initial position, nothing has been moved */
+            self.saved_window_position = Some(window_position);
+            return Ok(false);
+        }
+
+        if self.saved_window_position == Some(window_position)
+            || ui.is_mouse_down(MouseButton::Left)
+        {
+            return Ok(false);
+        }
+
+        /* widget has been moved, save the settings (including the imgui window positions) */
+        self.saved_window_position = Some(window_position);
+        Ok(true)
+    }
+
+    fn render(&self, states: &utils_state::StateRegistry, ui: &imgui::Ui) -> anyhow::Result<()> {
+        let settings = states.resolve::<AppSettings>(())?;
+        if !settings.bomb_timer_hud {
+            self.window_position.set(None);
+            return Ok(());
+        }
+
+        let bomb_state = states.resolve::<PlantedC4>(())?;
+        let time_detonation = match &bomb_state.state {
+            PlantedC4State::Active { time_detonation } => *time_detonation,
+            _ => {
+                self.window_position.set(None);
+                return Ok(());
+            }
+        };
+
+        let timer_length = if bomb_state.timer_length > 0.0 {
+            bomb_state.timer_length
+        } else {
+            40.0
+        };
+
+        /* red as soon as the bomb can not be defused without a kit any more */
+        let time_color = if time_detonation < DEFUSE_TIME {
+            COLOR_CRITICAL
+        } else {
+            COLOR_OK
+        };
+
+        ui.window(obfstr!("Bomb Timer"))
+            .position([10.0, 300.0], Condition::FirstUseEver)
+            .flags(
+                WindowFlags::ALWAYS_AUTO_RESIZE
+                    | WindowFlags::NO_TITLE_BAR
+                    | WindowFlags::NO_COLLAPSE
+                    | WindowFlags::NO_FOCUS_ON_APPEARING,
+            )
+            .build(|| {
+                let _opacity = DrawOpacity::window(ui, settings.overlay_opacity);
+                ui.text_colored(
+                    time_color,
+                    format!(
+                        "C4 {} {:.1}s",
+                        if bomb_state.bomb_site == 0 { "A" } else { "B" },
+                        time_detonation
+                    ),
+                );
+
+                /* total time remaining including the defuse windows */
+                Self::render_bar(
+                    ui,
+                    time_detonation / timer_length,
+                    time_color,
+                    &[DEFUSE_TIME_KIT / timer_length, DEFUSE_TIME / timer_length],
+                );
+
+                ui.dummy([0.0, 2.0]);
+
+                match &bomb_state.defuser {
+                    Some(defuser) => {
+                        let defuse_time = if defuser.has_kit {
+                            DEFUSE_TIME_KIT
+                        } else {
+                            DEFUSE_TIME
+                        };
+                        let color = if defuser.time_remaining > time_detonation {
+                            COLOR_CRITICAL
+                        } else {
+                            COLOR_OK
+                        };
+
+                        Self::render_bar(
+                            ui,
+                            1.0 - defuser.time_remaining / defuse_time,
+                            color,
+                            &[],
+                        );
+                        ui.text_colored(
+                            color,
+                            format!(
+                                "{} ({:.1}s {})",
+                                obfstr!("DEFUSING"),
+                                defuser.time_remaining,
+                                obfstr!("left")
+                            ),
+                        );
+                    }
+                    None => {
+                        /* the defuse window: how much of a no kit defuse is still possible */
+                        let window_color = if time_detonation < DEFUSE_TIME {
+                            if time_detonation < DEFUSE_TIME_KIT {
+                                COLOR_CRITICAL
+                            } else {
+                                COLOR_WARNING
+                            }
+                        } else {
+                            COLOR_OK
+                        };
+
+                        Self::render_bar(
+                            ui,
+                            time_detonation / DEFUSE_TIME,
+                            window_color,
+                            &[DEFUSE_TIME_KIT / DEFUSE_TIME],
+                        );
+
+                        if time_detonation >= DEFUSE_TIME {
+                            ui.text_colored(COLOR_OK, obfstr!("Defusable without kit"));
+                        } else if time_detonation >= DEFUSE_TIME_KIT {
+                            ui.text_colored(COLOR_WARNING, obfstr!("Kit required"));
+                        } else {
+                            ui.text_colored(COLOR_CRITICAL, obfstr!("Too late to defuse"));
+                        }
+                    }
+                }
+
+                self.window_position.set(Some(ui.window_pos()));
+            });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,161 @@
+use cs2::{
+    CEntityIdentityEx,
+    ClassNameCache,
+    EntitySystem,
+};
+use cs2_schema_generated::cs2::client::C_BaseEntity;
+use obfstr::obfstr;
+
+use super::Enhancement;
+use crate::{
+    settings::{
+        AppSettings,
+        EspChickenSettings,
+        EspConfig,
+        EspSelector,
+    },
+    view::ViewController,
+};
+
+const UNITS_TO_METERS: f32 = 0.01905;
+
+/// Height of the marker above the chicken origin
+const CHICKEN_MARKER_HEIGHT: f32 = 12.0;
+
+/// Small marker for every chicken on the map.
+pub struct ChickenESP {
+    /// World positions of all chickens
+    chickens: Vec<nalgebra::Vector3<f32>>,
+}
+
+impl ChickenESP {
+    pub fn new() -> Self {
+        Self {
+            chickens: Default::default(),
+        }
+    }
+
+    fn resolve_esp_config(settings: &AppSettings) -> Option<&EspChickenSettings> {
+        let config_key = EspSelector::Chicken.config_key();
+        if !settings
+            .esp_settings_enabled
+            .get(&config_key)
+            .cloned()
+            .unwrap_or_default()
+        {
+            return None;
+        }
+
+        match settings.esp_settings.get(&config_key) {
+            Some(EspConfig::Chicken(config)) => Some(config),
+            _ => None,
+        }
+    }
+}
+
+impl Enhancement for ChickenESP {
+    fn update(&mut self, ctx: &crate::UpdateContext) -> anyhow::Result<()> {
+        self.chickens.clear();
+
+        let settings = ctx.states.resolve::<AppSettings>(())?;
+        if Self::resolve_esp_config(&settings).is_none() {
+            return Ok(());
+        }
+
+        let entities = ctx.states.resolve::<EntitySystem>(())?;
+        let class_name_cache = ctx.states.resolve::<ClassNameCache>(())?;
+        for entity_identity in entities.all_identities() {
+            let entity_class = class_name_cache.lookup(&entity_identity.entity_class_info()?)?;
+            if !entity_class
+                .map(|name| name == "C_Chicken")
+                .unwrap_or(false)
+            {
+                continue;
+            }
+
+            let chicken = entity_identity
+                .entity_ptr::<C_BaseEntity>()?
+                .reference_schema()?;
+
+            self.chickens
+                .push(nalgebra::Vector3::<f32>::from_column_slice(
+                    &chicken
+                        .m_pGameSceneNode()?
+                        .reference_schema()?
+                        .m_vecAbsOrigin()?,
+                ));
+        }
+
+        Ok(())
+    }
+
+    fn render(&self, states: &utils_state::StateRegistry, ui: &imgui::Ui) -> anyhow::Result<()> {
+        if self.chickens.is_empty() {
+            return Ok(());
+        }
+
+        let settings = states.resolve::<AppSettings>(())?;
+        let esp_settings = match Self::resolve_esp_config(&settings) {
+            Some(esp_settings) => esp_settings,
+            None => return Ok(()),
+        };
+
+        let view = states.resolve::<ViewController>(())?;
+        let time = ui.time() as f32;
+        let view_world_position = match view.get_camera_world_position() {
+            Some(view_world_position) => view_world_position,
+            _ => return Ok(()),
+        };
+
+        let draw = ui.get_window_draw_list();
+        for chicken in self.chickens.iter() {
+            let distance = (chicken - view_world_position).norm() * UNITS_TO_METERS;
+            if distance > esp_settings.max_distance {
+                continue;
+            }
+
+            let position = match view.world_to_screen(
+                &(chicken + nalgebra::Vector3::new(0.0, 0.0, CHICKEN_MARKER_HEIGHT)),
+                false,
+            ) {
+                Some(position) => position,
+                None => continue,
+            };
+
+            if esp_settings.marker {
+                draw.add_circle(
+                    position,
+                    3.0,
+                    esp_settings.marker_color.calculate_color(
+                        &settings.color_palette,
+                        1.0,
+                        distance,
+                        time,
+                    ),
+                )
+                .filled(true)
+                .build();
+            }
+
+            if esp_settings.info_name {
+                let text = obfstr!("chicken").to_string();
+                let [text_width, text_height] = ui.calc_text_size(&text);
+                draw.add_text(
+                    [
+                        position.x - text_width / 2.0,
+                        position.y - text_height - 5.0,
+                    ],
+                    esp_settings.info_name_color.calculate_color(
+                        &settings.color_palette,
+                        1.0,
+                        distance,
+                        time,
+                    ),
+                    &text,
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
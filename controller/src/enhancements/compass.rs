@@ -0,0 +1,229 @@
+use cs2::{
+    EntitySystem,
+    PlayerPawnList,
+    PlayerPawnState,
+};
+
+use super::Enhancement;
+use crate::{
+    settings::{
+        AppSettings,
+        CompassColorMode,
+        CompassPosition,
+    },
+    view::ViewController,
+};
+
+/// Distance to the screen edge in pixels
+const SCREEN_MARGIN: f32 = 10.0;
+const STRIP_HEIGHT: f32 = 30.0;
+
+/// Bearing span (in degrees) visible on the strip
+const STRIP_SPAN: f32 = 180.0;
+
+/// Bearing difference (in degrees) between two minor ticks
+const TICK_STEP: usize = 15;
+
+const TICK_LENGTH_MAJOR: f32 = 8.0;
+const TICK_LENGTH_MINOR: f32 = 4.0;
+const MARKER_LENGTH: f32 = 10.0;
+
+const COLOR_ENEMY: [f32; 4] = [1.0, 0.2, 0.2, 1.0];
+const COLOR_TEAM: [f32; 4] = [0.3, 0.6, 1.0, 1.0];
+
+const CARDINAL_LABELS: [&'static str; 8] = ["N", "NE", "E", "SE", "S", "SW", "W", "NW"];
+
+/// Normalize an angle difference into [-180; 180] degrees
+fn normalize_angle(angle: f32) -> f32 {
+    (angle + 180.0).rem_euclid(360.0) - 180.0
+}
+
+/// Convert the game yaw (counter clockwise, 0 pointing to +X) into a
+/// compass bearing (clockwise, 0 pointing to north / +Y).
+fn yaw_to_bearing(yaw: f32) -> f32 {
+    (90.0 - yaw).rem_euclid(360.0)
+}
+
+struct CompassMarker {
+    position: nalgebra::Vector3<f32>,
+
+    /// Health in [0.0;1.0]
+    health: f32,
+    enemy: bool,
+}
+
+/// Horizontal compass strip with markers at the bearings of all alive players
+pub struct Compass {
+    markers: Vec<CompassMarker>,
+}
+
+impl Compass {
+    pub fn new() -> Self {
+        Self {
+            markers: Default::default(),
+        }
+    }
+}
+
+impl Enhancement for Compass {
+    fn update(&mut self, ctx: &crate::UpdateContext) -> anyhow::Result<()> {
+        self.markers.clear();
+
+        let settings = ctx.states.resolve::<AppSettings>(())?;
+        if !settings.compass {
+            return Ok(());
+        }
+
+        let entities = ctx.states.resolve::<EntitySystem>(())?;
+        let local_player_controller = entities.get_local_player_controller()?;
+        if local_player_controller.is_null()? {
+            return Ok(());
+        }
+
+        let local_player_controller = local_player_controller.reference_schema()?;
+        let local_team_id = local_player_controller.m_iPendingTeamNum()?;
+        let local_pawn_index = local_player_controller.m_hPlayerPawn()?.get_entity_index();
+
+        let pawn_list = ctx.states.resolve::<PlayerPawnList>(())?;
+        for pawn_entity_index in pawn_list.pawns.iter().copied() {
+            if pawn_entity_index == local_pawn_index {
+                continue;
+            }
+
+            let pawn_state = match ctx.states.resolve::<PlayerPawnState>(pawn_entity_index) {
+                Ok(state) => state,
+                Err(_) => continue,
+            };
+
+            let info = match &*pawn_state {
+                PlayerPawnState::Alive(info) => info,
+                _ => continue,
+            };
+
+            let enemy = info.team_id != local_team_id;
+            if !enemy && !settings.compass_teammates {
+                continue;
+            }
+
+            self.markers.push(CompassMarker {
+                position: info.position,
+                health: (info.player_health as f32 / 100.0).clamp(0.0, 1.0),
+                enemy,
+            });
+        }
+
+        Ok(())
+    }
+
+    fn render(&self, states: &utils_state::StateRegistry, ui: &imgui::Ui) -> anyhow::Result<()> {
+        let settings = states.resolve::<AppSettings>(())?;
+        if !settings.compass {
+            return Ok(());
+        }
+
+        let view = states.resolve::<ViewController>(())?;
+        let (camera_position, camera_yaw) =
+            match (view.get_camera_world_position(), view.get_camera_yaw()) {
+                (Some(position), Some(yaw)) => (position, yaw),
+                _ => return Ok(()),
+            };
+        let view_bearing = yaw_to_bearing(camera_yaw);
+
+        let [display_width, display_height] = ui.io().display_size;
+        let strip_width = settings
+            .compass_width
+            .min(display_width - SCREEN_MARGIN * 2.0);
+        let strip_min = [
+            (display_width - strip_width) / 2.0,
+            match settings.compass_position {
+                CompassPosition::Top => SCREEN_MARGIN,
+                CompassPosition::Bottom => display_height - STRIP_HEIGHT - SCREEN_MARGIN,
+            },
+        ];
+        let strip_max = [strip_min[0] + strip_width, strip_min[1] + STRIP_HEIGHT];
+        let strip_center_x = strip_min[0] + strip_width / 2.0;
+
+        /*
+         * Bearings are placed relative to the view bearing.
+         * Normalizing the difference wraps bearings around the 0/360 degree boundary
+         * and everything outside of the visible span is omitted.
+         */
+        let bearing_to_x = |bearing: f32| -> Option<f32> {
+            let offset = normalize_angle(bearing - view_bearing);
+            if offset.abs() > STRIP_SPAN / 2.0 {
+                return None;
+            }
+
+            Some(strip_center_x + offset / STRIP_SPAN * strip_width)
+        };
+
+        let color = settings.compass_color.as_f32();
+        let draw = ui.get_window_draw_list();
+        draw.add_rect(strip_min, strip_max, [0.0, 0.0, 0.0, 0.5])
+            .filled(true)
+            .rounding(3.0)
+            .build();
+
+        for bearing in (0..360).step_by(TICK_STEP) {
+            let x = match bearing_to_x(bearing as f32) {
+                Some(x) => x,
+                None => continue,
+            };
+
+            let major = bearing % 45 == 0;
+            let tick_length = if major {
+                TICK_LENGTH_MAJOR
+            } else {
+                TICK_LENGTH_MINOR
+            };
+            draw.add_line([x, strip_min[1]], [x, strip_min[1] + tick_length], color)
+                .thickness(1.0)
+                .build();
+
+            if major {
+                let label = CARDINAL_LABELS[(bearing / 45) as usize];
+                let [text_width, _] = ui.calc_text_size(label);
+                draw.add_text(
+                    [x - text_width / 2.0, strip_min[1] + TICK_LENGTH_MAJOR],
+                    color,
+                    label,
+                );
+            }
+        }
+
+        /* current view bearing */
+        draw.add_triangle(
+            [strip_center_x, strip_max[1] - 5.0],
+            [strip_center_x - 4.0, strip_max[1]],
+            [strip_center_x + 4.0, strip_max[1]],
+            color,
+        )
+        .filled(true)
+        .build();
+
+        for marker in self.markers.iter() {
+            let delta = marker.position - camera_position;
+            let bearing = yaw_to_bearing(delta.y.atan2(delta.x).to_degrees());
+            let x = match bearing_to_x(bearing) {
+                Some(x) => x,
+                None => continue,
+            };
+
+            let marker_color = match settings.compass_color_mode {
+                CompassColorMode::Team if marker.enemy => COLOR_ENEMY,
+                CompassColorMode::Team => COLOR_TEAM,
+                CompassColorMode::Health => [1.0 - marker.health, marker.health, 0.0, 1.0],
+            };
+
+            draw.add_line(
+                [x, strip_max[1] - MARKER_LENGTH],
+                [x, strip_max[1]],
+                marker_color,
+            )
+            .thickness(3.0)
+            .build();
+        }
+
+        Ok(())
+    }
+}
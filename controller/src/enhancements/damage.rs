@@ -0,0 +1,336 @@
+use std::{
+    collections::HashMap,
+    time::{
+        Duration,
+        Instant,
+    },
+};
+
+use cs2::{
+    CS2Model,
+    EntitySystem,
+    PlayerPawnList,
+    PlayerPawnState,
+};
+use utils_state::{
+    State,
+    StateCacheType,
+    StateRegistry,
+};
+
+use super::Enhancement;
+use crate::{
+    settings::AppSettings,
+    view::ViewController,
+};
+
+/// Time until a damage popup has been faded out completely
+const POPUP_LIFETIME: Duration = Duration::from_millis(1000);
+
+/// Distance in units a damage popup drifts upwards within its lifetime
+const POPUP_DRIFT: f32 = 20.0;
+
+/// Time the hitmarker will be shown after damage has been dealt
+const HITMARKER_LIFETIME: Duration = Duration::from_millis(250);
+
+/// Max time since the last local shot for damage to count as dealt by the local player
+const LOCAL_SHOT_WINDOW: Duration = Duration::from_millis(250);
+
+/// Fallback head offset for models without a head bone
+const HEAD_OFFSET: f32 = 72.0;
+
+/// Max time between two updates of the damage tracker.
+/// Health values older than this are outdated and will not be compared.
+const MAX_TRACKER_UPDATE_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Damage an enemy received since the last update
+#[derive(Debug, Clone)]
+pub struct DamageEvent {
+    pub controller_entity_id: u32,
+    pub player_name: String,
+
+    /// Head position of the pawn (last known position if the pawn died)
+    pub position: nalgebra::Vector3<f32>,
+    pub damage: i32,
+
+    /// The local player has been firing when the damage has been received
+    pub local_firing: bool,
+}
+
+/// Last known state of an enemy pawn
+struct TrackedPawn {
+    controller_entity_id: u32,
+    player_name: String,
+    health: i32,
+    position: nalgebra::Vector3<f32>,
+}
+
+/// Tracks the health of all enemy pawns and reports the damage they received since the last update.
+/// Damage is attributed to the local player if the local player has been firing shortly before.
+pub struct DamageTracker {
+    /// Enemy pawns of the last update by pawn entity index
+    pawns: HashMap<u32, TrackedPawn>,
+
+    local_shots_fired: i32,
+    local_last_shot: Option<Instant>,
+
+    last_update: Option<Instant>,
+
+    /// Damage events of the current update
+    pub events: Vec<DamageEvent>,
+}
+
+impl State for DamageTracker {
+    type Parameter = ();
+
+    fn create(_states: &StateRegistry, _param: Self::Parameter) -> anyhow::Result<Self> {
+        Ok(Self {
+            pawns: Default::default(),
+
+            local_shots_fired: 0,
+            local_last_shot: None,
+
+            last_update: None,
+            events: Default::default(),
+        })
+    }
+
+    fn cache_type() -> StateCacheType {
+        StateCacheType::Persistent
+    }
+
+    fn update(&mut self, states: &StateRegistry) -> anyhow::Result<()> {
+        self.events.clear();
+
+        /* the tracker is only updated while being used, older health values can not be compared */
+        let outdated = self
+            .last_update
+            .map(|timestamp| timestamp.elapsed() > MAX_TRACKER_UPDATE_INTERVAL)
+            .unwrap_or(true);
+        self.last_update = Some(Instant::now());
+        if outdated {
+            self.pawns.clear();
+            self.local_last_shot = None;
+        }
+
+        let entities = states.resolve::<EntitySystem>(())?;
+        let local_player_controller = entities.get_local_player_controller()?;
+        if local_player_controller.is_null()? {
+            self.pawns.clear();
+            return Ok(());
+        }
+
+        let local_player_controller = local_player_controller.reference_schema()?;
+        let local_team_id = local_player_controller.m_iPendingTeamNum()?;
+        let local_pawn_index = local_player_controller.m_hPlayerPawn()?.get_entity_index();
+
+        let local_shots_fired = match states.resolve::<PlayerPawnState>(local_pawn_index) {
+            Ok(state) => match &*state {
+                PlayerPawnState::Alive(info) => info.player_shots_fired,
+                _ => 0,
+            },
+            Err(_) => 0,
+        };
+        if !outdated && local_shots_fired > self.local_shots_fired {
+            self.local_last_shot = Some(Instant::now());
+        }
+        self.local_shots_fired = local_shots_fired;
+
+        let local_firing = self
+            .local_last_shot
+            .map(|timestamp| timestamp.elapsed() < LOCAL_SHOT_WINDOW)
+            .unwrap_or(false);
+
+        let pawn_list = states.resolve::<PlayerPawnList>(())?;
+        let mut pawns = HashMap::with_capacity(self.pawns.len());
+        for pawn_entity_index in pawn_list.pawns.iter().copied() {
+            if pawn_entity_index == local_pawn_index {
+                continue;
+            }
+
+            let pawn_state = match states.resolve::<PlayerPawnState>(pawn_entity_index) {
+                Ok(state) => state,
+                Err(_) => continue,
+            };
+
+            let info = match &*pawn_state {
+                PlayerPawnState::Alive(info) => info,
+                PlayerPawnState::Dead => {
+                    /* the pawn died since the last update, the remaining health has been the final damage */
+                    if let Some(pawn) = self.pawns.remove(&pawn_entity_index) {
+                        self.events.push(DamageEvent {
+                            controller_entity_id: pawn.controller_entity_id,
+                            player_name: pawn.player_name,
+                            position: pawn.position,
+                            damage: pawn.health,
+                            local_firing,
+                        });
+                    }
+                    continue;
+                }
+                PlayerPawnState::Dormant => continue,
+            };
+
+            if info.team_id == local_team_id {
+                continue;
+            }
+
+            let model = states.resolve::<CS2Model>(info.model_address)?;
+            let position = model
+                .find_bone("head_0")
+                .and_then(|index| info.bone_states.get(index))
+                .map(|bone| bone.position)
+                .unwrap_or_else(|| info.position + nalgebra::Vector3::new(0.0, 0.0, HEAD_OFFSET));
+
+            let pawn = TrackedPawn {
+                controller_entity_id: info.controller_entity_id,
+                player_name: info.player_name.clone(),
+                health: info.player_health,
+                position,
+            };
+
+            let damage = self
+                .pawns
+                .get(&pawn_entity_index)
+                .map(|previous| previous.health - pawn.health)
+                .unwrap_or(0);
+            if damage > 0 {
+                self.events.push(DamageEvent {
+                    controller_entity_id: pawn.controller_entity_id,
+                    player_name: pawn.player_name.clone(),
+                    position: pawn.position,
+                    damage,
+                    local_firing,
+                });
+            }
+
+            pawns.insert(pawn_entity_index, pawn);
+        }
+
+        /* pawns which died or disconnected will not be tracked any more */
+        self.pawns = pawns;
+        Ok(())
+    }
+}
+
+struct DamagePopup {
+    /// World position where the damage has been dealt
+    position: nalgebra::Vector3<f32>,
+    damage: i32,
+    created: Instant,
+}
+
+/// Show the damage enemies receive as floating numbers above their heads
+/// and optionally a hitmarker at the screen center.
+pub struct DamageIndicator {
+    popups: Vec<DamagePopup>,
+    last_hit: Option<Instant>,
+}
+
+impl DamageIndicator {
+    pub fn new() -> Self {
+        Self {
+            popups: Default::default(),
+            last_hit: None,
+        }
+    }
+}
+
+impl Enhancement for DamageIndicator {
+    fn update(&mut self, ctx: &crate::UpdateContext) -> anyhow::Result<()> {
+        self.popups
+            .retain(|popup| popup.created.elapsed() < POPUP_LIFETIME);
+
+        let settings = ctx.states.resolve::<AppSettings>(())?;
+        if !settings.damage_indicator {
+            self.popups.clear();
+            return Ok(());
+        }
+
+        let tracker = ctx.states.resolve::<DamageTracker>(())?;
+        for event in tracker.events.iter() {
+            if settings.damage_indicator_local_only && !event.local_firing {
+                continue;
+            }
+
+            self.last_hit = Some(Instant::now());
+            self.popups.push(DamagePopup {
+                position: event.position,
+                damage: event.damage,
+                created: Instant::now(),
+            });
+        }
+
+        Ok(())
+    }
+
+    fn render(&self, states: &utils_state::StateRegistry, ui: &imgui::Ui) -> anyhow::Result<()> {
+        let settings = states.resolve::<AppSettings>(())?;
+        if !settings.damage_indicator {
+            return Ok(());
+        }
+
+        let view = states.resolve::<ViewController>(())?;
+        let draw = ui.get_window_draw_list();
+
+        ui.set_window_font_scale(settings.damage_indicator_text_scale);
+        for popup in self.popups.iter() {
+            let progress =
+                (popup.created.elapsed().as_secs_f32() / POPUP_LIFETIME.as_secs_f32()).min(1.0);
+
+            let position =
+                popup.position + nalgebra::Vector3::new(0.0, 0.0, POPUP_DRIFT * progress);
+            let screen_position = match view.world_to_screen(&position, false) {
+                Some(position) => position,
+                None => continue,
+            };
+
+            let text = format!("-{}", popup.damage);
+            let text_size = ui.calc_text_size(&text);
+
+            let mut color = settings.damage_indicator_color.as_f32();
+            color[3] *= 1.0 - progress;
+
+            draw.add_text(
+                [
+                    screen_position.x - text_size[0] / 2.0,
+                    screen_position.y - text_size[1],
+                ],
+                color,
+                &text,
+            );
+        }
+        ui.set_window_font_scale(1.0);
+
+        if settings.damage_indicator_hitmarker {
+            let progress = self
+                .last_hit
+                .map(|timestamp| {
+                    timestamp.elapsed().as_secs_f32() / HITMARKER_LIFETIME.as_secs_f32()
+                })
+                .unwrap_or(1.0);
+
+            if progress < 1.0 {
+                let mut color = settings.damage_indicator_color.as_f32();
+                color[3] *= 1.0 - progress;
+
+                let center = view.screen_position(0.5, 0.5);
+                let (gap, length) = (4.0, 8.0);
+                for (dx, dy) in [(-1.0, -1.0), (1.0, -1.0), (-1.0, 1.0), (1.0, 1.0)] {
+                    draw.add_line(
+                        [center[0] + dx * gap, center[1] + dy * gap],
+                        [
+                            center[0] + dx * (gap + length),
+                            center[1] + dy * (gap + length),
+                        ],
+                        color,
+                    )
+                    .thickness(2.0)
+                    .build();
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
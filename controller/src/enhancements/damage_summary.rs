@@ -0,0 +1,180 @@
+use std::collections::HashMap;
+
+use cs2::GameRulesState;
+use imgui::{
+    Condition,
+    TableFlags,
+    WindowFlags,
+};
+use obfstr::obfstr;
+
+use super::{
+    DamageEvent,
+    DamageTracker,
+    Enhancement,
+};
+use crate::{
+    settings::AppSettings,
+    utils::DrawOpacity,
+};
+
+/// Damage dealt to a single enemy within the current round
+#[derive(Debug, Clone)]
+struct DamageGiven {
+    player_name: String,
+    damage: i32,
+    hits: u32,
+}
+
+/// Accumulate the damage the local player deals within the current round
+/// and show a summary (like the console "Damage Given" output) at round end or on demand.
+pub struct DamageSummary {
+    /// Damage given within the current round by enemy controller entity index
+    damage_given: HashMap<u32, DamageGiven>,
+
+    /// Round start counter of the game rules the damage has been accumulated for
+    round_start_count: Option<u8>,
+    round_end_count: Option<u8>,
+    round_ended: bool,
+
+    summary_key_down: bool,
+}
+
+impl DamageSummary {
+    pub fn new() -> Self {
+        Self {
+            damage_given: Default::default(),
+
+            round_start_count: None,
+            round_end_count: None,
+            round_ended: false,
+
+            summary_key_down: false,
+        }
+    }
+
+    fn add_damage(&mut self, event: &DamageEvent) {
+        let entry = self
+            .damage_given
+            .entry(event.controller_entity_id)
+            .or_insert_with(|| DamageGiven {
+                player_name: event.player_name.clone(),
+                damage: 0,
+                hits: 0,
+            });
+
+        entry.damage += event.damage;
+        entry.hits += 1;
+    }
+
+    fn update_round(&mut self, ctx: &crate::UpdateContext) -> anyhow::Result<()> {
+        let game_rules = ctx.states.resolve::<GameRulesState>(())?;
+        let game_rules = match &game_rules.game_rules {
+            Some(game_rules) => game_rules,
+            None => {
+                self.damage_given.clear();
+                self.round_start_count = None;
+                self.round_end_count = None;
+                self.round_ended = false;
+                return Ok(());
+            }
+        };
+
+        /* the round start counter changes when the round restarts and not when the freeze time ends */
+        if self.round_start_count != Some(game_rules.round_start_count) {
+            self.round_start_count = Some(game_rules.round_start_count);
+            self.round_end_count = Some(game_rules.round_end_count);
+            self.round_ended = false;
+            self.damage_given.clear();
+        }
+
+        if self.round_end_count != Some(game_rules.round_end_count) {
+            self.round_end_count = Some(game_rules.round_end_count);
+            self.round_ended = true;
+        }
+
+        Ok(())
+    }
+}
+
+impl Enhancement for DamageSummary {
+    fn update(&mut self, ctx: &crate::UpdateContext) -> anyhow::Result<()> {
+        let settings = ctx.states.resolve::<AppSettings>(())?;
+        if !settings.damage_summary {
+            self.damage_given.clear();
+            return Ok(());
+        }
+
+        self.summary_key_down = settings
+            .damage_summary_key
+            .as_ref()
+            .map(|key| key.is_down(ctx.input))
+            .unwrap_or(false);
+
+        self.update_round(ctx)?;
+
+        let tracker = ctx.states.resolve::<DamageTracker>(())?;
+        for event in tracker.events.iter() {
+            if event.local_firing {
+                self.add_damage(event);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn render(&self, states: &utils_state::StateRegistry, ui: &imgui::Ui) -> anyhow::Result<()> {
+        let settings = states.resolve::<AppSettings>(())?;
+        if !settings.damage_summary {
+            return Ok(());
+        }
+
+        if !self.summary_key_down && !(self.round_ended && settings.damage_summary_round_end) {
+            return Ok(());
+        }
+
+        let mut entries = self.damage_given.values().collect::<Vec<_>>();
+        entries.sort_by(|a, b| b.damage.cmp(&a.damage));
+
+        ui.window(obfstr!("Damage Given"))
+            .position([10.0, 500.0], Condition::FirstUseEver)
+            .flags(
+                WindowFlags::ALWAYS_AUTO_RESIZE
+                    | WindowFlags::NO_COLLAPSE
+                    | WindowFlags::NO_FOCUS_ON_APPEARING,
+            )
+            .build(|| {
+                let _opacity = DrawOpacity::window(ui, settings.overlay_opacity);
+                if entries.is_empty() {
+                    ui.text_disabled(obfstr!("No damage dealt this round"));
+                    return;
+                }
+
+                if let Some(_table) = ui.begin_table_with_flags(
+                    "damage_given",
+                    3,
+                    TableFlags::ROW_BG | TableFlags::BORDERS | TableFlags::SIZING_FIXED_FIT,
+                ) {
+                    ui.table_setup_column(obfstr!("Player"));
+                    ui.table_setup_column(obfstr!("Damage"));
+                    ui.table_setup_column(obfstr!("Hits"));
+                    ui.table_headers_row();
+
+                    for entry in entries.iter() {
+                        ui.table_next_row();
+                        ui.table_next_column();
+                        ui.text(&entry.player_name);
+                        ui.table_next_column();
+                        ui.text(format!("{}", entry.damage));
+                        ui.table_next_column();
+                        ui.text(format!("{}", entry.hits));
+                    }
+                }
+
+                let total_damage = entries.iter().map(|entry| entry.damage).sum::<i32>();
+                ui.text(format!("{} {}", obfstr!("Total damage:"), total_damage));
+            });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,169 @@
+use std::time::Instant;
+
+use cs2::{
+    EntitySystem,
+    GameRulesState,
+};
+
+use super::{
+    DeathTracker,
+    Enhancement,
+};
+use crate::{
+    settings::AppSettings,
+    view::{
+        KeyToggle,
+        ViewController,
+    },
+};
+
+const UNITS_TO_METERS: f32 = 0.01905;
+
+/// Size of the marker icon in pixels
+const MARKER_SIZE: f32 = 6.0;
+
+struct DeathMarker {
+    timestamp: Instant,
+    player_name: String,
+    position: nalgebra::Vector3<f32>,
+}
+
+/// Persistent markers at the death positions of enemies within the current round
+pub struct DeathMarkers {
+    /// Markers are only shown while the ESP is enabled
+    esp_toggle: KeyToggle,
+
+    markers: Vec<DeathMarker>,
+
+    /// Round start counter of the game rules the markers have been collected for
+    round_start_count: Option<u8>,
+}
+
+impl DeathMarkers {
+    pub fn new() -> Self {
+        Self {
+            esp_toggle: KeyToggle::new(),
+
+            markers: Default::default(),
+
+            round_start_count: None,
+        }
+    }
+
+    fn reset(&mut self) {
+        self.markers.clear();
+    }
+}
+
+impl Enhancement for DeathMarkers {
+    fn update(&mut self, ctx: &crate::UpdateContext) -> anyhow::Result<()> {
+        let settings = ctx.states.resolve::<AppSettings>(())?;
+        self.esp_toggle
+            .update(&settings.esp_mode, ctx.input, &settings.esp_toggle);
+
+        if !settings.death_markers || !self.esp_toggle.enabled {
+            self.reset();
+            return Ok(());
+        }
+
+        let game_rules = ctx.states.resolve::<GameRulesState>(())?;
+        let round_start_count = game_rules
+            .game_rules
+            .as_ref()
+            .map(|game_rules| game_rules.round_start_count);
+        if self.round_start_count != round_start_count {
+            self.round_start_count = round_start_count;
+            self.reset();
+        }
+
+        let entities = ctx.states.resolve::<EntitySystem>(())?;
+        let local_player_controller = entities.get_local_player_controller()?;
+        if local_player_controller.is_null()? {
+            self.reset();
+            return Ok(());
+        }
+
+        let local_team_id = local_player_controller
+            .reference_schema()?
+            .m_iPendingTeamNum()?;
+
+        let death_tracker = ctx.states.resolve::<DeathTracker>(())?;
+        for death in death_tracker.deaths.iter() {
+            if death.team_id == local_team_id {
+                continue;
+            }
+
+            self.markers.push(DeathMarker {
+                timestamp: Instant::now(),
+                player_name: death.player_name.clone(),
+                position: death.position,
+            });
+        }
+
+        let max_age = settings.death_markers_max_age.max(0.0);
+        self.markers
+            .retain(|marker| marker.timestamp.elapsed().as_secs_f32() < max_age);
+
+        Ok(())
+    }
+
+    fn render(&self, states: &utils_state::StateRegistry, ui: &imgui::Ui) -> anyhow::Result<()> {
+        let settings = states.resolve::<AppSettings>(())?;
+        if !settings.death_markers || self.markers.is_empty() {
+            return Ok(());
+        }
+
+        let view = states.resolve::<ViewController>(())?;
+        let camera_position = view.get_camera_world_position();
+        let draw = ui.get_window_draw_list();
+        let color = settings.death_markers_color.as_f32();
+
+        for marker in self.markers.iter() {
+            if let Some(camera_position) = &camera_position {
+                let distance = (marker.position - camera_position).norm() * UNITS_TO_METERS;
+                if distance > settings.death_markers_max_distance {
+                    continue;
+                }
+            }
+
+            let position = match view.world_to_screen(&marker.position, false) {
+                Some(position) => position,
+                None => continue,
+            };
+
+            /* skull replacement: crossed out circle */
+            draw.add_circle(position, MARKER_SIZE, color)
+                .thickness(1.5)
+                .build();
+            let offset = MARKER_SIZE * std::f32::consts::FRAC_1_SQRT_2;
+            draw.add_line(
+                [position.x - offset, position.y - offset],
+                [position.x + offset, position.y + offset],
+                color,
+            )
+            .thickness(1.5)
+            .build();
+            draw.add_line(
+                [position.x + offset, position.y - offset],
+                [position.x - offset, position.y + offset],
+                color,
+            )
+            .thickness(1.5)
+            .build();
+
+            if settings.death_markers_name {
+                let [text_width, _] = ui.calc_text_size(&marker.player_name);
+                draw.add_text(
+                    [
+                        position.x - text_width / 2.0,
+                        position.y + MARKER_SIZE + 2.0,
+                    ],
+                    color,
+                    &marker.player_name,
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
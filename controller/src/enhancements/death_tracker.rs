@@ -0,0 +1,167 @@
+use std::{
+    collections::HashMap,
+    time::{
+        Duration,
+        Instant,
+    },
+};
+
+use cs2::{
+    EntitySystem,
+    PlayerPawnList,
+    PlayerPawnState,
+};
+use utils_state::{
+    State,
+    StateCacheType,
+    StateRegistry,
+};
+
+/// Max time between two updates of the death tracker.
+/// Alive states older than this are outdated and will not be compared.
+const MAX_TRACKER_UPDATE_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Last known alive state of a player
+struct TrackedPlayer {
+    pawn_entity_index: u32,
+    player_name: String,
+    team_id: u8,
+    health: i32,
+    position: nalgebra::Vector3<f32>,
+}
+
+/// A player which died since the last update
+#[derive(Debug, Clone)]
+pub struct PlayerDeath {
+    pub controller_entity_id: u32,
+    pub player_name: String,
+    pub team_id: u8,
+
+    /// Last known health before the death
+    pub health: i32,
+
+    /// Last known position before the death
+    pub position: nalgebra::Vector3<f32>,
+}
+
+/// Detect player deaths by tracking the last alive state of every player.
+/// A player counts as dead when the pawn transitions from alive to dead or the health drops to zero.
+pub struct DeathTracker {
+    /// Alive players of the last update by controller entity index
+    tracked_players: HashMap<u32, TrackedPlayer>,
+
+    last_update: Option<Instant>,
+
+    /// Players which died since the last update
+    pub deaths: Vec<PlayerDeath>,
+}
+
+impl State for DeathTracker {
+    type Parameter = ();
+
+    fn create(_states: &StateRegistry, _param: Self::Parameter) -> anyhow::Result<Self> {
+        Ok(Self {
+            tracked_players: Default::default(),
+
+            last_update: None,
+            deaths: Default::default(),
+        })
+    }
+
+    fn cache_type() -> StateCacheType {
+        StateCacheType::Persistent
+    }
+
+    fn update(&mut self, states: &StateRegistry) -> anyhow::Result<()> {
+        self.deaths.clear();
+
+        /* the tracker is only updated while being used, older alive states can not be compared */
+        let outdated = self
+            .last_update
+            .map(|timestamp| timestamp.elapsed() > MAX_TRACKER_UPDATE_INTERVAL)
+            .unwrap_or(true);
+        self.last_update = Some(Instant::now());
+        if outdated {
+            self.tracked_players.clear();
+        }
+
+        let entities = states.resolve::<EntitySystem>(())?;
+        if entities.get_local_player_controller()?.is_null()? {
+            self.tracked_players.clear();
+            return Ok(());
+        }
+
+        self.deaths = self.collect_deaths(states)?;
+        Ok(())
+    }
+}
+
+impl DeathTracker {
+    fn take_death(&mut self, controller_entity_id: u32) -> Option<PlayerDeath> {
+        let player = self.tracked_players.remove(&controller_entity_id)?;
+        Some(PlayerDeath {
+            controller_entity_id,
+            player_name: player.player_name,
+            team_id: player.team_id,
+            health: player.health,
+            position: player.position,
+        })
+    }
+
+    /// Update the tracked players and return all players which died since the last update
+    fn collect_deaths(&mut self, states: &StateRegistry) -> anyhow::Result<Vec<PlayerDeath>> {
+        let pawn_list = states.resolve::<PlayerPawnList>(())?;
+
+        let mut deaths = Vec::new();
+        let mut pawn_controllers = self
+            .tracked_players
+            .iter()
+            .map(|(controller_entity_id, player)| (player.pawn_entity_index, *controller_entity_id))
+            .collect::<HashMap<_, _>>();
+
+        for pawn_entity_index in pawn_list.pawns.iter().copied() {
+            let pawn_state = match states.resolve::<PlayerPawnState>(pawn_entity_index) {
+                Ok(state) => state,
+                Err(_) => continue,
+            };
+
+            match &*pawn_state {
+                PlayerPawnState::Alive(info) if info.player_health > 0 => {
+                    pawn_controllers.remove(&pawn_entity_index);
+                    self.tracked_players.insert(
+                        info.controller_entity_id,
+                        TrackedPlayer {
+                            pawn_entity_index,
+                            player_name: info.player_name.clone(),
+                            team_id: info.team_id,
+                            health: info.player_health,
+                            position: info.position,
+                        },
+                    );
+                }
+                PlayerPawnState::Alive(info) => {
+                    /* health dropped to zero before the pawn state changed */
+                    pawn_controllers.remove(&pawn_entity_index);
+                    deaths.extend(self.take_death(info.controller_entity_id));
+                }
+                PlayerPawnState::Dead => {
+                    if let Some(controller_entity_id) = pawn_controllers.remove(&pawn_entity_index)
+                    {
+                        deaths.extend(self.take_death(controller_entity_id));
+                    }
+                }
+                PlayerPawnState::Dormant => {
+                    /* keep the last known state until the pawn becomes visible again */
+                    pawn_controllers.remove(&pawn_entity_index);
+                }
+            }
+        }
+
+        /* pawns which do no longer exist (e.g. player disconnected) */
+        for controller_entity_id in pawn_controllers.values() {
+            self.tracked_players.remove(controller_entity_id);
+        }
+
+        Ok(deaths)
+    }
+}
@@ -0,0 +1,143 @@
+use cs2::{
+    CEntityIdentityEx,
+    ClassNameCache,
+    EntitySystem,
+};
+use cs2_schema_generated::cs2::client::C_BaseEntity;
+use obfstr::obfstr;
+
+use super::Enhancement;
+use crate::{
+    settings::AppSettings,
+    view::ViewController,
+};
+
+/// Class names of the dropped defuse kit entity
+const DEFUSE_KIT_CLASS_NAMES: [&str; 2] = ["C_Item_Defuser", "C_ItemDefuser"];
+
+/// Team id of the counter terrorists
+const TEAM_CT: u8 = 3;
+
+const UNITS_TO_METERS: f32 = 0.01905;
+
+/// Marker for defuse kits lying on the ground.
+pub struct DefuseKitESP {
+    /// World positions of all dropped defuse kits
+    kits: Vec<nalgebra::Vector3<f32>>,
+}
+
+impl DefuseKitESP {
+    pub fn new() -> Self {
+        Self {
+            kits: Default::default(),
+        }
+    }
+}
+
+impl Enhancement for DefuseKitESP {
+    fn update(&mut self, ctx: &crate::UpdateContext) -> anyhow::Result<()> {
+        self.kits.clear();
+
+        let settings = ctx.states.resolve::<AppSettings>(())?;
+        if !settings.defuse_kit_esp {
+            return Ok(());
+        }
+
+        let entities = ctx.states.resolve::<EntitySystem>(())?;
+        let class_name_cache = ctx.states.resolve::<ClassNameCache>(())?;
+
+        if !settings.defuse_kit_esp_always {
+            let local_player_controller = entities.get_local_player_controller()?;
+            if local_player_controller.is_null()? {
+                return Ok(());
+            }
+
+            let local_team_id = local_player_controller
+                .reference_schema()?
+                .m_iPendingTeamNum()?;
+            if local_team_id != TEAM_CT {
+                return Ok(());
+            }
+        }
+
+        for entity_identity in entities.all_identities() {
+            let entity_class = class_name_cache.lookup(&entity_identity.entity_class_info()?)?;
+            if !entity_class
+                .map(|name| DEFUSE_KIT_CLASS_NAMES.contains(&name.as_str()))
+                .unwrap_or(false)
+            {
+                continue;
+            }
+
+            let kit = entity_identity
+                .entity_ptr::<C_BaseEntity>()?
+                .reference_schema()?;
+
+            let position = nalgebra::Vector3::<f32>::from_column_slice(
+                &kit.m_pGameSceneNode()?
+                    .reference_schema()?
+                    .m_vecAbsOrigin()?,
+            );
+            self.kits.push(position);
+        }
+
+        Ok(())
+    }
+
+    fn render(&self, states: &utils_state::StateRegistry, ui: &imgui::Ui) -> anyhow::Result<()> {
+        let settings = states.resolve::<AppSettings>(())?;
+        if !settings.defuse_kit_esp || self.kits.is_empty() {
+            return Ok(());
+        }
+
+        let view = states.resolve::<ViewController>(())?;
+        let view_world_position = match view.get_camera_world_position() {
+            Some(view_world_position) => view_world_position,
+            _ => return Ok(()),
+        };
+
+        let draw = ui.get_window_draw_list();
+        let color = settings.defuse_kit_esp_color.as_f32();
+        for kit in self.kits.iter() {
+            let distance = (kit - view_world_position).norm() * UNITS_TO_METERS;
+            if distance > settings.defuse_kit_esp_max_distance {
+                continue;
+            }
+
+            let position = match view.world_to_screen(kit, false) {
+                Some(position) => position,
+                None => continue,
+            };
+
+            let text = format!("{} [{:.0}m]", obfstr!("Kit"), distance);
+            let [text_width, text_height] = ui.calc_text_size(&text);
+
+            /* kit icon: a small box with a wire */
+            draw.add_rect(
+                [position.x - 4.0, position.y - 4.0],
+                [position.x + 4.0, position.y + 4.0],
+                color,
+            )
+            .filled(true)
+            .build();
+            draw.add_line(
+                [position.x, position.y + 4.0],
+                [position.x, position.y + 9.0],
+                color,
+            )
+            .thickness(2.0)
+            .build();
+
+            draw.add_text(
+                [
+                    position.x - text_width / 2.0,
+                    position.y - text_height - 6.0,
+                ],
+                color,
+                &text,
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,297 @@
+use std::cell::Cell;
+
+use cs2::{
+    CEntityIdentityEx,
+    ClassNameCache,
+    EntitySystem,
+    GameRulesState,
+    PlayerEconomy,
+};
+use imgui::{
+    Condition,
+    TableColumnFlags,
+    TableColumnSetup,
+    TableFlags,
+    TableSortDirection,
+    WindowFlags,
+};
+use obfstr::obfstr;
+
+use super::{
+    Enhancement,
+    EnhancementStatus,
+};
+use crate::{
+    settings::AppSettings,
+    utils::DrawOpacity,
+    view::KeyToggle,
+};
+
+const TEAM_ID_T: u8 = 2;
+const TEAM_ID_CT: u8 = 3;
+
+/// Estimated money a player receives for losing the current round
+/// with the given number of consecutive losses.
+fn loss_bonus(consecutive_losses: i32) -> i32 {
+    1400 + 500 * consecutive_losses.clamp(0, 4)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum BuyPrediction {
+    FullBuy,
+    Force,
+    Eco,
+}
+
+impl BuyPrediction {
+    fn from_money(money: i32, settings: &AppSettings) -> Self {
+        if money >= settings.economy_full_buy_threshold {
+            Self::FullBuy
+        } else if money >= settings.economy_force_buy_threshold {
+            Self::Force
+        } else {
+            Self::Eco
+        }
+    }
+
+    fn display_name(&self) -> &'static str {
+        match self {
+            Self::FullBuy => "full buy possible",
+            Self::Force => "force",
+            Self::Eco => "eco",
+        }
+    }
+
+    fn color(&self) -> [f32; 4] {
+        match self {
+            Self::FullBuy => [1.0, 0.3, 0.3, 1.0],
+            Self::Force => [1.0, 0.75, 0.2, 1.0],
+            Self::Eco => [0.4, 1.0, 0.4, 1.0],
+        }
+    }
+}
+
+struct EnemyEconomy {
+    player_name: String,
+    money: i32,
+    cash_spent_this_round: i32,
+}
+
+/// Window with the money of all enemies and a simple buy prediction for the enemy team
+pub struct EconomyPanel {
+    toggle: KeyToggle,
+
+    enemies: Vec<EnemyEconomy>,
+
+    /// Average money of the enemies at the beginning of the round
+    average_start_money: i32,
+
+    /// Estimated loss bonus of the enemy team
+    enemy_loss_bonus: Option<i32>,
+
+    /// Column index and ascending flag of the current table sorting
+    sort_order: Cell<(usize, bool)>,
+}
+
+impl EconomyPanel {
+    pub fn new() -> Self {
+        Self {
+            toggle: KeyToggle::new(),
+
+            enemies: Default::default(),
+            average_start_money: 0,
+            enemy_loss_bonus: None,
+
+            sort_order: Cell::new((1, false)),
+        }
+    }
+
+    fn sort_enemies<'a>(&self, enemies: &mut Vec<&'a EnemyEconomy>) {
+        let (column, ascending) = self.sort_order.get();
+        enemies.sort_by(|a, b| {
+            let ordering = match column {
+                0 => a
+                    .player_name
+                    .to_lowercase()
+                    .cmp(&b.player_name.to_lowercase()),
+                2 => a.cash_spent_this_round.cmp(&b.cash_spent_this_round),
+                _ => a.money.cmp(&b.money),
+            };
+
+            if ascending {
+                ordering
+            } else {
+                ordering.reverse()
+            }
+        });
+    }
+}
+
+impl Enhancement for EconomyPanel {
+    fn status(&self, settings: &AppSettings) -> Option<EnhancementStatus> {
+        Some(EnhancementStatus {
+            name: obfstr!("Economy").to_string(),
+            mode: settings.economy_panel_mode,
+            active: self.toggle.enabled,
+        })
+    }
+
+    fn update(&mut self, ctx: &crate::UpdateContext) -> anyhow::Result<()> {
+        let settings = ctx.states.resolve::<AppSettings>(())?;
+        self.toggle.update(
+            &settings.economy_panel_mode,
+            ctx.input,
+            &settings.economy_panel_toggle,
+        );
+
+        self.enemies.clear();
+        self.enemy_loss_bonus = None;
+        if !self.toggle.enabled {
+            return Ok(());
+        }
+
+        let entities = ctx.states.resolve::<EntitySystem>(())?;
+        let class_name_cache = ctx.states.resolve::<ClassNameCache>(())?;
+
+        let local_player_controller = entities.get_local_player_controller()?;
+        if local_player_controller.is_null()? {
+            return Ok(());
+        }
+
+        let local_team_id = local_player_controller.reference_schema()?.m_iTeamNum()?;
+
+        let mut total_start_money = 0;
+        for entity_identity in entities.all_identities() {
+            let entity_class = class_name_cache.lookup(&entity_identity.entity_class_info()?)?;
+            if !entity_class
+                .map(|name| *name == "CCSPlayerController")
+                .unwrap_or(false)
+            {
+                continue;
+            }
+
+            let controller_entity_index = entity_identity.handle::<()>()?.get_entity_index();
+            let economy = match ctx.states.resolve::<PlayerEconomy>(controller_entity_index) {
+                Ok(economy) => economy,
+                Err(_) => continue,
+            };
+
+            if economy.team_id == local_team_id
+                || !matches!(economy.team_id, TEAM_ID_T | TEAM_ID_CT)
+            {
+                continue;
+            }
+
+            total_start_money += economy.start_money;
+            self.enemies.push(EnemyEconomy {
+                player_name: economy.player_name.clone(),
+                money: economy.money,
+                cash_spent_this_round: economy.cash_spent_this_round,
+            });
+        }
+
+        self.average_start_money = if self.enemies.is_empty() {
+            0
+        } else {
+            total_start_money / self.enemies.len() as i32
+        };
+
+        let game_rules = ctx.states.resolve::<GameRulesState>(())?;
+        if let Some(game_rules) = &game_rules.game_rules {
+            self.enemy_loss_bonus = match local_team_id {
+                TEAM_ID_T => Some(loss_bonus(game_rules.consecutive_ct_losses)),
+                TEAM_ID_CT => Some(loss_bonus(game_rules.consecutive_t_losses)),
+                _ => None,
+            };
+        }
+
+        Ok(())
+    }
+
+    fn render(&self, states: &utils_state::StateRegistry, ui: &imgui::Ui) -> anyhow::Result<()> {
+        if !self.toggle.enabled {
+            return Ok(());
+        }
+
+        let settings = states.resolve::<AppSettings>(())?;
+        let prediction = BuyPrediction::from_money(self.average_start_money, &settings);
+
+        ui.window(obfstr!("Enemy Economy"))
+            .position([10.0, 600.0], Condition::FirstUseEver)
+            .flags(
+                WindowFlags::ALWAYS_AUTO_RESIZE
+                    | WindowFlags::NO_COLLAPSE
+                    | WindowFlags::NO_FOCUS_ON_APPEARING,
+            )
+            .build(|| {
+                let _opacity = DrawOpacity::window(ui, settings.overlay_opacity);
+                if self.enemies.is_empty() {
+                    ui.text_disabled(obfstr!("No enemies"));
+                    return;
+                }
+
+                let total_money = self.enemies.iter().map(|enemy| enemy.money).sum::<i32>();
+                ui.text(format!("{} ${}", obfstr!("Team money:"), total_money));
+                ui.text(obfstr!("Team prediction:"));
+                ui.same_line();
+                ui.text_colored(prediction.color(), prediction.display_name());
+                if let Some(loss_bonus) = self.enemy_loss_bonus {
+                    ui.text(format!("{} ${}", obfstr!("Loss bonus:"), loss_bonus));
+                }
+
+                if let Some(_table) = ui.begin_table_with_flags(
+                    "enemy_economy",
+                    4,
+                    TableFlags::ROW_BG
+                        | TableFlags::BORDERS
+                        | TableFlags::SIZING_FIXED_FIT
+                        | TableFlags::SORTABLE,
+                ) {
+                    ui.table_setup_column(obfstr!("Player"));
+                    ui.table_setup_column_with(TableColumnSetup {
+                        flags: TableColumnFlags::DEFAULT_SORT
+                            | TableColumnFlags::PREFER_SORT_DESCENDING,
+                        ..TableColumnSetup::new(obfstr!("Money"))
+                    });
+                    ui.table_setup_column_with(TableColumnSetup {
+                        flags: TableColumnFlags::PREFER_SORT_DESCENDING,
+                        ..TableColumnSetup::new(obfstr!("Spent"))
+                    });
+                    ui.table_setup_column_with(TableColumnSetup {
+                        flags: TableColumnFlags::NO_SORT,
+                        ..TableColumnSetup::new(obfstr!("Prediction"))
+                    });
+                    ui.table_headers_row();
+
+                    if let Some(sort_specs) = ui.table_sort_specs_mut() {
+                        sort_specs.conditional_sort(|specs| {
+                            if let Some(spec) = specs.iter().next() {
+                                self.sort_order.set((
+                                    spec.column_idx(),
+                                    spec.sort_direction() != Some(TableSortDirection::Descending),
+                                ));
+                            }
+                        });
+                    }
+
+                    let mut enemies = self.enemies.iter().collect::<Vec<_>>();
+                    self.sort_enemies(&mut enemies);
+
+                    for enemy in enemies {
+                        ui.table_next_row();
+                        ui.table_next_column();
+                        ui.text(&enemy.player_name);
+                        ui.table_next_column();
+                        ui.text(format!("${}", enemy.money));
+                        ui.table_next_column();
+                        ui.text(format!("${}", enemy.cash_spent_this_round));
+                        ui.table_next_column();
+                        let prediction = BuyPrediction::from_money(enemy.money, &settings);
+                        ui.text_colored(prediction.color(), prediction.display_name());
+                    }
+                }
+            });
+
+        Ok(())
+    }
+}
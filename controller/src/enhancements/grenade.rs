@@ -0,0 +1,172 @@
+use cs2::{
+    EntitySystem,
+    GrenadeProjectile,
+    GrenadeProjectileList,
+    GrenadeType,
+};
+
+use super::Enhancement;
+use crate::{
+    fonts::{
+        weapon_icon_glyph,
+        AppFonts,
+    },
+    settings::{
+        AppSettings,
+        Color,
+    },
+    view::ViewController,
+};
+
+/// Effective gravity (units per second²) applied to grenade projectiles
+/// (sv_gravity scaled by the grenade gravity factor)
+pub(super) const GRENADE_GRAVITY: f32 = 800.0 * 0.4;
+
+/// Time in seconds the predicted trajectory will be shown for
+const PREDICTION_DURATION: f32 = 0.5;
+const PREDICTION_STEPS: usize = 10;
+
+/// Predict the position of a projectile after the given time.
+/// Collisions and bounces are not taken into account.
+pub(super) fn predict_grenade_position(
+    position: &nalgebra::Vector3<f32>,
+    velocity: &nalgebra::Vector3<f32>,
+    time: f32,
+) -> nalgebra::Vector3<f32> {
+    position + velocity * time
+        - nalgebra::Vector3::new(0.0, 0.0, 0.5 * GRENADE_GRAVITY * time * time)
+}
+
+/// Label and optional trajectory for grenades which are currently in flight.
+pub struct GrenadeProjectileESP {
+    /// Projectiles of the last update.
+    /// Will be rebuild every update as projectiles despawn quickly.
+    projectiles: Vec<GrenadeProjectile>,
+}
+
+impl GrenadeProjectileESP {
+    pub fn new() -> Self {
+        Self {
+            projectiles: Default::default(),
+        }
+    }
+
+    fn grenade_color(settings: &AppSettings, grenade_type: GrenadeType) -> Color {
+        match grenade_type {
+            GrenadeType::HighExplosive => settings.grenade_esp_color_he,
+            GrenadeType::Flashbang => settings.grenade_esp_color_flash,
+            GrenadeType::Smoke => settings.grenade_esp_color_smoke,
+            GrenadeType::Molotov | GrenadeType::Incendiary => settings.grenade_esp_color_fire,
+            GrenadeType::Decoy => settings.grenade_esp_color_decoy,
+        }
+    }
+}
+
+impl Enhancement for GrenadeProjectileESP {
+    fn update(&mut self, ctx: &crate::UpdateContext) -> anyhow::Result<()> {
+        self.projectiles.clear();
+
+        let settings = ctx.states.resolve::<AppSettings>(())?;
+        if !settings.grenade_esp {
+            return Ok(());
+        }
+
+        let entities = ctx.states.resolve::<EntitySystem>(())?;
+        let local_player_controller = entities.get_local_player_controller()?;
+        let local_team_id = if local_player_controller.is_null()? {
+            None
+        } else {
+            Some(
+                local_player_controller
+                    .reference_schema()?
+                    .m_iPendingTeamNum()?,
+            )
+        };
+
+        let projectile_list = ctx.states.resolve::<GrenadeProjectileList>(())?;
+        for entity_index in projectile_list.projectiles.iter() {
+            /* the projectile might already be gone */
+            let projectile = match ctx.states.resolve::<GrenadeProjectile>(*entity_index) {
+                Ok(projectile) => projectile,
+                Err(_) => continue,
+            };
+
+            if projectile.detonated {
+                continue;
+            }
+
+            if settings.grenade_esp_enemy_only
+                && local_team_id.is_some()
+                && projectile.thrower_team_id == local_team_id
+            {
+                continue;
+            }
+
+            self.projectiles.push(projectile.clone());
+        }
+
+        Ok(())
+    }
+
+    fn render(&self, states: &utils_state::StateRegistry, ui: &imgui::Ui) -> anyhow::Result<()> {
+        let settings = states.resolve::<AppSettings>(())?;
+        if !settings.grenade_esp {
+            return Ok(());
+        }
+
+        let view = states.resolve::<ViewController>(())?;
+        let weapon_icon_font = states.resolve::<AppFonts>(())?.weapon_icons;
+        let draw = ui.get_window_draw_list();
+        for projectile in self.projectiles.iter() {
+            let color = Self::grenade_color(&settings, projectile.grenade_type).as_f32();
+
+            if settings.grenade_esp_prediction {
+                let points = (0..=PREDICTION_STEPS)
+                    .map(|step| step as f32 / PREDICTION_STEPS as f32 * PREDICTION_DURATION)
+                    .map(|time| {
+                        predict_grenade_position(&projectile.position, &projectile.velocity, time)
+                    })
+                    .map(|point| view.world_to_screen(&point, true))
+                    .collect::<Vec<_>>();
+
+                for segment in points.windows(2) {
+                    if let [Some(start), Some(end)] = segment {
+                        draw.add_line(*start, *end, color).thickness(1.5).build();
+                    }
+                }
+            }
+
+            let position = match view.world_to_screen(&projectile.position, false) {
+                Some(position) => position,
+                None => continue,
+            };
+
+            draw.add_circle(position, 3.0, color).filled(true).build();
+
+            let label = projectile.grenade_type.display_name();
+            let [label_width, _] = ui.calc_text_size(label);
+            draw.add_text(
+                [position.x - label_width / 2.0, position.y + 6.0],
+                color,
+                label,
+            );
+
+            let icon = weapon_icon_font.zip(weapon_icon_glyph(projectile.grenade_type.weapon_id()));
+            if let Some((font, glyph)) = icon {
+                let _font = ui.push_font(font);
+                let glyph = glyph.to_string();
+                let [icon_width, icon_height] = ui.calc_text_size(&glyph);
+                draw.add_text(
+                    [
+                        position.x - icon_width / 2.0,
+                        position.y - icon_height - 4.0,
+                    ],
+                    color,
+                    &glyph,
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
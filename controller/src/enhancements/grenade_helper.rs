@@ -0,0 +1,243 @@
+use cs2::{
+    CurrentMapState,
+    EntitySystem,
+    PlayerPawnInfo,
+    PlayerPawnState,
+};
+use obfstr::obfstr;
+use utils_state::StateRegistry;
+
+use super::Enhancement;
+use crate::{
+    settings::{
+        AppSettings,
+        GrenadeSpot,
+        GrenadeSpotType,
+    },
+    view::ViewController,
+};
+
+const UNITS_TO_METERS: f32 = 0.01905;
+
+/// Radius (in units) around the stand position in which the spot counts as reached
+const STAND_RADIUS: f32 = 12.0;
+
+/// Max view angle difference (in degrees) for the lineup to count as aligned
+const ALIGN_THRESHOLD: f32 = 0.3;
+
+/// Distance (in units) of the projected aim target in front of the camera
+const TARGET_DISTANCE: f32 = 1000.0;
+
+const COLOR_ALIGNED: [f32; 4] = [0.0, 1.0, 0.0, 1.0];
+
+/// Resolve the pawn info of the local player (None if the local player is not alive)
+pub fn get_local_pawn_info(states: &StateRegistry) -> anyhow::Result<Option<PlayerPawnInfo>> {
+    let entities = states.resolve::<EntitySystem>(())?;
+    let local_player_controller = entities.get_local_player_controller()?;
+    if local_player_controller.is_null()? {
+        return Ok(None);
+    }
+
+    let local_pawn_index = local_player_controller
+        .reference_schema()?
+        .m_hPlayerPawn()?
+        .get_entity_index();
+
+    let local_pawn = states.resolve::<PlayerPawnState>(local_pawn_index)?;
+    Ok(match &*local_pawn {
+        PlayerPawnState::Alive(info) => Some(info.clone()),
+        _ => None,
+    })
+}
+
+/// Unit vector of the view direction for the given angles (pitch, yaw) in degrees
+fn angles_to_direction(pitch: f32, yaw: f32) -> nalgebra::Vector3<f32> {
+    let (pitch, yaw) = (pitch.to_radians(), yaw.to_radians());
+    nalgebra::Vector3::new(
+        pitch.cos() * yaw.cos(),
+        pitch.cos() * yaw.sin(),
+        -pitch.sin(),
+    )
+}
+
+/// Normalize an angle difference into [-180; 180] degrees
+fn normalize_angle(angle: f32) -> f32 {
+    (angle + 180.0).rem_euclid(360.0) - 180.0
+}
+
+struct NearbySpot {
+    spot: GrenadeSpot,
+
+    /// The local player is standing at the spot
+    reached: bool,
+}
+
+/// Show saved grenade lineups of the current map around the local player.
+pub struct GrenadeHelper {
+    spots: Vec<NearbySpot>,
+
+    /// View angles (pitch, yaw) of the local player
+    local_eye_angles: [f32; 2],
+}
+
+impl GrenadeHelper {
+    pub fn new() -> Self {
+        Self {
+            spots: Default::default(),
+            local_eye_angles: [0.0, 0.0],
+        }
+    }
+}
+
+impl Enhancement for GrenadeHelper {
+    fn update(&mut self, ctx: &crate::UpdateContext) -> anyhow::Result<()> {
+        self.spots.clear();
+
+        let settings = ctx.states.resolve::<AppSettings>(())?;
+        if !settings.grenade_helper {
+            return Ok(());
+        }
+
+        let current_map = ctx.states.resolve::<CurrentMapState>(())?;
+        let map_spots = match current_map
+            .current_map
+            .as_ref()
+            .and_then(|map_name| settings.grenade_spots.get(map_name))
+        {
+            Some(spots) => spots,
+            None => return Ok(()),
+        };
+
+        let local_pawn = match get_local_pawn_info(ctx.states)? {
+            Some(local_pawn) => local_pawn,
+            None => return Ok(()),
+        };
+
+        let held_grenade = GrenadeSpotType::from_weapon(local_pawn.weapon);
+        if settings.grenade_helper_holding_only && held_grenade.is_none() {
+            return Ok(());
+        }
+
+        self.local_eye_angles = [local_pawn.eye_angles.x, local_pawn.eye_angles.y];
+        for spot in map_spots.iter() {
+            if held_grenade.is_some() && held_grenade != Some(spot.grenade_type) {
+                continue;
+            }
+
+            let position = nalgebra::Vector3::from_row_slice(&spot.position);
+            let distance = (position - local_pawn.position).norm();
+            if distance * UNITS_TO_METERS > settings.grenade_helper_max_distance {
+                continue;
+            }
+
+            self.spots.push(NearbySpot {
+                spot: spot.clone(),
+                reached: (position - local_pawn.position).xy().norm() <= STAND_RADIUS,
+            });
+        }
+
+        Ok(())
+    }
+
+    fn render(&self, states: &StateRegistry, ui: &imgui::Ui) -> anyhow::Result<()> {
+        let settings = states.resolve::<AppSettings>(())?;
+        if !settings.grenade_helper || self.spots.is_empty() {
+            return Ok(());
+        }
+
+        let view = states.resolve::<ViewController>(())?;
+        let draw = ui.get_window_draw_list();
+        let color = settings.grenade_helper_color.as_f32();
+
+        for nearby in self.spots.iter() {
+            let spot = &nearby.spot;
+            let position = nalgebra::Vector3::from_row_slice(&spot.position);
+            let spot_color = if nearby.reached { COLOR_ALIGNED } else { color };
+
+            /* stand position marker */
+            let outline = (0..16)
+                .map(|index| {
+                    let angle = index as f32 / 16.0 * std::f32::consts::TAU;
+                    position
+                        + nalgebra::Vector3::new(
+                            angle.cos() * STAND_RADIUS,
+                            angle.sin() * STAND_RADIUS,
+                            0.0,
+                        )
+                })
+                .map(|point| view.world_to_screen(&point, true))
+                .collect::<Vec<_>>();
+
+            for index in 0..outline.len() {
+                let start = &outline[index];
+                let end = &outline[(index + 1) % outline.len()];
+                if let (Some(start), Some(end)) = (start, end) {
+                    draw.add_line(*start, *end, spot_color)
+                        .thickness(2.0)
+                        .build();
+                }
+            }
+
+            if let Some(label_position) =
+                view.world_to_screen(&(position + nalgebra::Vector3::new(0.0, 0.0, 10.0)), false)
+            {
+                let text = format!("{} ({})", spot.name, spot.grenade_type.display_name());
+                let [text_width, _] = ui.calc_text_size(&text);
+                draw.add_text(
+                    [label_position.x - text_width / 2.0, label_position.y],
+                    spot_color,
+                    &text,
+                );
+            }
+
+            if !nearby.reached {
+                continue;
+            }
+
+            /* aim target for the required view angles */
+            let camera_position = match view.get_camera_world_position() {
+                Some(position) => position,
+                None => continue,
+            };
+
+            let [pitch, yaw] = spot.eye_angles;
+            let target = camera_position + angles_to_direction(pitch, yaw) * TARGET_DISTANCE;
+            let target = match view.world_to_screen(&target, false) {
+                Some(target) => target,
+                None => continue,
+            };
+
+            let aligned = normalize_angle(pitch - self.local_eye_angles[0]).abs()
+                <= ALIGN_THRESHOLD
+                && normalize_angle(yaw - self.local_eye_angles[1]).abs() <= ALIGN_THRESHOLD;
+            let target_color = if aligned { COLOR_ALIGNED } else { color };
+
+            draw.add_circle(target, 6.0, target_color)
+                .thickness(2.0)
+                .build();
+            draw.add_circle(target, 1.5, target_color)
+                .filled(true)
+                .build();
+
+            let mut text_y = target.y + 10.0;
+            for text in [
+                if aligned {
+                    format!("{} - {}", obfstr!("THROW"), spot.throw_type.display_name())
+                } else {
+                    spot.throw_type.display_name().to_string()
+                },
+                spot.description.clone(),
+            ] {
+                if text.is_empty() {
+                    continue;
+                }
+
+                let [text_width, text_height] = ui.calc_text_size(&text);
+                draw.add_text([target.x - text_width / 2.0, text_y], target_color, &text);
+                text_y += text_height;
+            }
+        }
+
+        Ok(())
+    }
+}
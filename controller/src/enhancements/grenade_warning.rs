@@ -0,0 +1,166 @@
+use cs2::{
+    GrenadeProjectile,
+    GrenadeProjectileList,
+    GrenadeType,
+};
+use obfstr::obfstr;
+
+use super::{
+    grenade::predict_grenade_position,
+    Enhancement,
+};
+use crate::{
+    settings::AppSettings,
+    view::ViewController,
+};
+
+const UNITS_TO_METERS: f32 = 0.01905;
+
+struct IncomingGrenade {
+    grenade_type: GrenadeType,
+
+    /// Predicted detonation position
+    detonation_position: nalgebra::Vector3<f32>,
+
+    /// Time remaining (in seconds) until detonation
+    time_detonation: f32,
+}
+
+/// Warn when a grenade is predicted to detonate close to the local camera.
+pub struct GrenadeWarning {
+    /// Incoming grenades of the last update
+    incoming: Vec<IncomingGrenade>,
+}
+
+impl GrenadeWarning {
+    pub fn new() -> Self {
+        Self {
+            incoming: Default::default(),
+        }
+    }
+
+    fn warning_enabled(settings: &AppSettings, grenade_type: GrenadeType) -> bool {
+        match grenade_type {
+            GrenadeType::Flashbang => settings.grenade_warning_flash,
+            GrenadeType::HighExplosive => settings.grenade_warning_he,
+            GrenadeType::Molotov | GrenadeType::Incendiary => settings.grenade_warning_molotov,
+            GrenadeType::Smoke | GrenadeType::Decoy => false,
+        }
+    }
+
+    fn warning_text(grenade_type: GrenadeType) -> String {
+        match grenade_type {
+            GrenadeType::Flashbang => obfstr!("FLASH INCOMING").to_string(),
+            GrenadeType::HighExplosive => obfstr!("HE INCOMING").to_string(),
+            GrenadeType::Molotov | GrenadeType::Incendiary => {
+                obfstr!("MOLOTOV INCOMING").to_string()
+            }
+            GrenadeType::Smoke | GrenadeType::Decoy => obfstr!("GRENADE INCOMING").to_string(),
+        }
+    }
+}
+
+impl Enhancement for GrenadeWarning {
+    fn update(&mut self, ctx: &crate::UpdateContext) -> anyhow::Result<()> {
+        /* the warning must disappear as soon as the projectile detonates or despawns */
+        self.incoming.clear();
+
+        let settings = ctx.states.resolve::<AppSettings>(())?;
+        if !settings.grenade_warning {
+            return Ok(());
+        }
+
+        let projectile_list = ctx.states.resolve::<GrenadeProjectileList>(())?;
+        for entity_index in projectile_list.projectiles.iter() {
+            let projectile = match ctx.states.resolve::<GrenadeProjectile>(*entity_index) {
+                Ok(projectile) => projectile,
+                Err(_) => continue,
+            };
+
+            if projectile.detonated || !Self::warning_enabled(&settings, projectile.grenade_type) {
+                continue;
+            }
+
+            let time_detonation = match projectile.time_detonation {
+                Some(time) if time > 0.0 => time,
+                _ => continue,
+            };
+
+            self.incoming.push(IncomingGrenade {
+                grenade_type: projectile.grenade_type,
+                detonation_position: predict_grenade_position(
+                    &projectile.position,
+                    &projectile.velocity,
+                    time_detonation,
+                ),
+                time_detonation,
+            });
+        }
+
+        Ok(())
+    }
+
+    fn render(&self, states: &utils_state::StateRegistry, ui: &imgui::Ui) -> anyhow::Result<()> {
+        let settings = states.resolve::<AppSettings>(())?;
+        if !settings.grenade_warning || self.incoming.is_empty() {
+            return Ok(());
+        }
+
+        let view = states.resolve::<ViewController>(())?;
+        let view_world_position = match view.get_camera_world_position() {
+            Some(view_world_position) => view_world_position,
+            _ => return Ok(()),
+        };
+
+        /* warn about the grenade which detonates first */
+        let incoming = self
+            .incoming
+            .iter()
+            .filter(|grenade| {
+                let distance =
+                    (grenade.detonation_position - view_world_position).norm() * UNITS_TO_METERS;
+                distance <= settings.grenade_warning_radius
+            })
+            .filter(|grenade| {
+                /* flashbangs behind the camera do not blind us */
+                grenade.grenade_type != GrenadeType::Flashbang
+                    || view
+                        .world_to_screen(&grenade.detonation_position, true)
+                        .is_some()
+            })
+            .min_by(|a, b| a.time_detonation.total_cmp(&b.time_detonation));
+
+        let incoming = match incoming {
+            Some(incoming) => incoming,
+            None => return Ok(()),
+        };
+
+        let text = format!(
+            "{} {:.1}s",
+            Self::warning_text(incoming.grenade_type),
+            incoming.time_detonation
+        );
+
+        ui.set_window_font_scale(1.5);
+        let text_size = ui.calc_text_size(&text);
+        let [center_x, top] = view.screen_position(0.5, 0.2);
+        let position = [center_x - text_size[0] / 2.0, top];
+
+        let draw = ui.get_window_draw_list();
+        draw.add_rect(
+            [position[0] - 10.0, position[1] - 5.0],
+            [
+                position[0] + text_size[0] + 10.0,
+                position[1] + text_size[1] + 5.0,
+            ],
+            [0.0, 0.0, 0.0, 0.6],
+        )
+        .filled(true)
+        .rounding(4.0)
+        .build();
+        draw.add_text(position, settings.grenade_warning_color.as_f32(), &text);
+        ui.set_window_font_scale(1.0);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,204 @@
+use cs2::{
+    Hostage,
+    HostageList,
+    PlayerPawnState,
+};
+use obfstr::obfstr;
+
+use super::Enhancement;
+use crate::{
+    settings::{
+        AppSettings,
+        EspBoxType,
+        EspConfig,
+        EspHostageSettings,
+        EspSelector,
+    },
+    view::ViewController,
+};
+
+const UNITS_TO_METERS: f32 = 0.01905;
+
+/// Approximate hull of a hostage relative to its origin
+const HOSTAGE_HULL_MIN: [f32; 3] = [-16.0, -16.0, 0.0];
+const HOSTAGE_HULL_MAX: [f32; 3] = [16.0, 16.0, 72.0];
+
+const HOSTAGE_MAX_HEALTH: f32 = 100.0;
+
+struct HostageInfo {
+    position: nalgebra::Vector3<f32>,
+    health: i32,
+
+    /// Name of the player who is currently carrying the hostage
+    carrier_name: Option<String>,
+}
+
+pub struct HostageESP {
+    hostages: Vec<HostageInfo>,
+}
+
+impl HostageESP {
+    pub fn new() -> Self {
+        Self {
+            hostages: Default::default(),
+        }
+    }
+
+    fn resolve_esp_config(settings: &AppSettings) -> Option<&EspHostageSettings> {
+        let config_key = EspSelector::Hostage.config_key();
+        if !settings
+            .esp_settings_enabled
+            .get(&config_key)
+            .cloned()
+            .unwrap_or_default()
+        {
+            return None;
+        }
+
+        match settings.esp_settings.get(&config_key) {
+            Some(EspConfig::Hostage(config)) => Some(config),
+            _ => None,
+        }
+    }
+}
+
+impl Enhancement for HostageESP {
+    fn update(&mut self, ctx: &crate::UpdateContext) -> anyhow::Result<()> {
+        self.hostages.clear();
+
+        let settings = ctx.states.resolve::<AppSettings>(())?;
+        if Self::resolve_esp_config(&settings).is_none() {
+            return Ok(());
+        }
+
+        let hostage_list = ctx.states.resolve::<HostageList>(())?;
+        for entity_index in hostage_list.hostages.iter() {
+            let hostage = match ctx.states.resolve::<Hostage>(*entity_index) {
+                Ok(hostage) => hostage,
+                Err(_) => continue,
+            };
+
+            if hostage.rescued {
+                continue;
+            }
+
+            /* carried hostages move together with their carrier */
+            let carrier = hostage
+                .carrier_entity_index
+                .and_then(|entity_index| ctx.states.resolve::<PlayerPawnState>(entity_index).ok())
+                .and_then(|carrier| match &*carrier {
+                    PlayerPawnState::Alive(carrier) => {
+                        Some((carrier.position, carrier.player_name.clone()))
+                    }
+                    _ => None,
+                });
+
+            let (position, carrier_name) = match carrier {
+                Some((position, name)) => (position, Some(name)),
+                None => (hostage.position, None),
+            };
+
+            self.hostages.push(HostageInfo {
+                position,
+                health: hostage.health,
+                carrier_name,
+            });
+        }
+
+        Ok(())
+    }
+
+    fn render(&self, states: &utils_state::StateRegistry, ui: &imgui::Ui) -> anyhow::Result<()> {
+        let settings = states.resolve::<AppSettings>(())?;
+        let esp_settings = match Self::resolve_esp_config(&settings) {
+            Some(esp_settings) => esp_settings,
+            None => return Ok(()),
+        };
+
+        let view = states.resolve::<ViewController>(())?;
+        let time = ui.time() as f32;
+        let box_width = esp_settings.box_width * view.ui_scale(settings.ui_scale_override);
+        let view_world_position = match view.get_camera_world_position() {
+            Some(view_world_position) => view_world_position,
+            _ => return Ok(()),
+        };
+
+        let draw = ui.get_window_draw_list();
+        for hostage in self.hostages.iter() {
+            let distance = (hostage.position - view_world_position).norm() * UNITS_TO_METERS;
+            let relative_health = (hostage.health as f32 / HOSTAGE_MAX_HEALTH).clamp(0.0, 1.0);
+
+            let vmin = hostage.position + nalgebra::Vector3::from(HOSTAGE_HULL_MIN);
+            let vmax = hostage.position + nalgebra::Vector3::from(HOSTAGE_HULL_MAX);
+
+            let box_color = esp_settings.box_color.calculate_color(
+                &settings.color_palette,
+                relative_health,
+                distance,
+                time,
+            );
+            match esp_settings.box_type {
+                EspBoxType::Box2D | EspBoxType::Box2DCorners => {
+                    if let Some((vmin, vmax)) = view.calculate_box_2d(&vmin, &vmax) {
+                        draw.add_rect([vmin.x, vmin.y], [vmax.x, vmax.y], box_color)
+                            .thickness(box_width)
+                            .build();
+                    }
+                }
+                EspBoxType::Box3D => {
+                    view.draw_box_3d(&draw, &vmin, &vmax, box_color.into(), box_width);
+                }
+                EspBoxType::None => {}
+            }
+
+            let label_position = view.world_to_screen(
+                &(hostage.position + nalgebra::Vector3::new(0.0, 0.0, HOSTAGE_HULL_MAX[2])),
+                false,
+            );
+            let mut label_position = match label_position {
+                Some(position) => position,
+                None => continue,
+            };
+
+            let mut lines = Vec::with_capacity(2);
+            if esp_settings.info_health {
+                lines.push((
+                    esp_settings.info_health_color.calculate_color(
+                        &settings.color_palette,
+                        relative_health,
+                        distance,
+                        time,
+                    ),
+                    format!("{} {} HP", obfstr!("Hostage"), hostage.health),
+                ));
+            }
+
+            if esp_settings.info_carrier {
+                if let Some(carrier_name) = &hostage.carrier_name {
+                    lines.push((
+                        esp_settings.info_carrier_color.calculate_color(
+                            &settings.color_palette,
+                            relative_health,
+                            distance,
+                            time,
+                        ),
+                        format!("{} {}", obfstr!("Carried by"), carrier_name),
+                    ));
+                }
+            }
+
+            /* lines are stacked upwards from the top of the hostage */
+            for (color, text) in lines.iter().rev() {
+                let [text_width, text_height] = ui.calc_text_size(text);
+                label_position.y -= text_height + 2.0;
+                draw.add_text(
+                    [label_position.x - text_width / 2.0, label_position.y],
+                    *color,
+                    text,
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
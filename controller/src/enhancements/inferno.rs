@@ -0,0 +1,186 @@
+use cs2::{
+    Inferno,
+    InfernoList,
+};
+
+use super::Enhancement;
+use crate::{
+    settings::AppSettings,
+    view::ViewController,
+};
+
+/// Number of points used to approximate the ground circle of a single fire
+const FIRE_OUTLINE_SEGMENTS: usize = 8;
+
+/// Fire radius (in units) used when the entity does not provide one
+const DEFAULT_FIRE_RADIUS: f32 = 30.0;
+
+/// Compute the convex hull of the given points on the ground (x/y plane).
+/// The height of every hull point is kept as is.
+fn ground_convex_hull(mut points: Vec<nalgebra::Vector3<f32>>) -> Vec<nalgebra::Vector3<f32>> {
+    if points.len() < 3 {
+        return points;
+    }
+
+    points.sort_by(|a, b| a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y)));
+
+    let cross =
+        |o: &nalgebra::Vector3<f32>, a: &nalgebra::Vector3<f32>, b: &nalgebra::Vector3<f32>| {
+            (a.x - o.x) * (b.y - o.y) - (a.y - o.y) * (b.x - o.x)
+        };
+
+    /* Andrew's monotone chain */
+    let mut hull: Vec<nalgebra::Vector3<f32>> = Vec::with_capacity(points.len() * 2);
+    for point in points.iter() {
+        while hull.len() >= 2 && cross(&hull[hull.len() - 2], &hull[hull.len() - 1], point) <= 0.0 {
+            hull.pop();
+        }
+        hull.push(*point);
+    }
+
+    let lower_length = hull.len() + 1;
+    for point in points.iter().rev().skip(1) {
+        while hull.len() >= lower_length
+            && cross(&hull[hull.len() - 2], &hull[hull.len() - 1], point) <= 0.0
+        {
+            hull.pop();
+        }
+        hull.push(*point);
+    }
+
+    hull.pop();
+    hull
+}
+
+struct InfernoArea {
+    /// Outline of the burning area on the ground
+    outline: Vec<nalgebra::Vector3<f32>>,
+
+    /// Center of the burning fires
+    center: nalgebra::Vector3<f32>,
+
+    time_remaining: f32,
+}
+
+/// Outline of the area covered by molotov and incendiary fires.
+pub struct InfernoESP {
+    /// Fire areas of the last update
+    areas: Vec<InfernoArea>,
+}
+
+impl InfernoESP {
+    pub fn new() -> Self {
+        Self {
+            areas: Default::default(),
+        }
+    }
+}
+
+impl Enhancement for InfernoESP {
+    fn update(&mut self, ctx: &crate::UpdateContext) -> anyhow::Result<()> {
+        self.areas.clear();
+
+        let settings = ctx.states.resolve::<AppSettings>(())?;
+        if !settings.inferno_esp {
+            return Ok(());
+        }
+
+        let inferno_list = ctx.states.resolve::<InfernoList>(())?;
+        for entity_index in inferno_list.infernos.iter() {
+            let inferno = match ctx.states.resolve::<Inferno>(*entity_index) {
+                Ok(inferno) => inferno,
+                Err(_) => continue,
+            };
+
+            if inferno.fire_positions.is_empty() {
+                continue;
+            }
+
+            let fire_radius = if inferno.fire_radius > 0.0 {
+                inferno.fire_radius
+            } else {
+                DEFAULT_FIRE_RADIUS
+            };
+
+            /* every fire covers a small circle around its position */
+            let outline_points = inferno
+                .fire_positions
+                .iter()
+                .flat_map(|position| {
+                    (0..FIRE_OUTLINE_SEGMENTS).map(move |segment| {
+                        let angle =
+                            segment as f32 / FIRE_OUTLINE_SEGMENTS as f32 * std::f32::consts::TAU;
+                        position
+                            + nalgebra::Vector3::new(
+                                angle.cos() * fire_radius,
+                                angle.sin() * fire_radius,
+                                0.0,
+                            )
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            let center = inferno
+                .fire_positions
+                .iter()
+                .fold(nalgebra::Vector3::zeros(), |sum, position| sum + position)
+                / inferno.fire_positions.len() as f32;
+
+            self.areas.push(InfernoArea {
+                outline: ground_convex_hull(outline_points),
+                center,
+                time_remaining: inferno.time_remaining,
+            });
+        }
+
+        Ok(())
+    }
+
+    fn render(&self, states: &utils_state::StateRegistry, ui: &imgui::Ui) -> anyhow::Result<()> {
+        let settings = states.resolve::<AppSettings>(())?;
+        if !settings.inferno_esp {
+            return Ok(());
+        }
+
+        let view = states.resolve::<ViewController>(())?;
+        let draw = ui.get_window_draw_list();
+        let color = settings.inferno_esp_color.as_f32();
+        for area in self.areas.iter() {
+            let outline = area
+                .outline
+                .iter()
+                .map(|point| view.world_to_screen(point, true))
+                .collect::<Vec<_>>();
+
+            for index in 0..outline.len() {
+                let start = &outline[index];
+                let end = &outline[(index + 1) % outline.len()];
+                if let (Some(start), Some(end)) = (start, end) {
+                    draw.add_line(*start, *end, color).thickness(2.0).build();
+                }
+            }
+
+            if !settings.inferno_esp_timer {
+                continue;
+            }
+
+            let position = match view.world_to_screen(&area.center, false) {
+                Some(position) => position,
+                None => continue,
+            };
+
+            let text = format!("{:.1}s", area.time_remaining);
+            let [text_width, text_height] = ui.calc_text_size(&text);
+            draw.add_text(
+                [
+                    position.x - text_width / 2.0,
+                    position.y - text_height / 2.0,
+                ],
+                color,
+                &text,
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,223 @@
+use std::{
+    collections::VecDeque,
+    time::{
+        Duration,
+        Instant,
+    },
+};
+
+use cs2::EntitySystem;
+use obfstr::obfstr;
+
+use super::{
+    get_local_pawn_info,
+    DeathTracker,
+    Enhancement,
+    PlayerDeath,
+};
+use crate::settings::{
+    AppSettings,
+    OverlayCorner,
+};
+
+const UNITS_TO_METERS: f32 = 0.01905;
+
+/// Distance to the screen edges in pixels
+const SCREEN_MARGIN: f32 = 10.0;
+const TEXT_PADDING: [f32; 2] = [8.0, 4.0];
+const ENTRY_SPACING: f32 = 4.0;
+
+/// Duration of the fade out at the end of the display duration of an entry
+const FADE_DURATION: Duration = Duration::from_millis(750);
+
+const COLOR_ENEMY: [f32; 4] = [1.0, 0.35, 0.35, 1.0];
+const COLOR_TEAM: [f32; 4] = [0.35, 0.65, 1.0, 1.0];
+
+struct KillFeedEntry {
+    timestamp: Instant,
+    time: String,
+
+    victim_name: String,
+    victim_enemy: bool,
+
+    /// Last known health before the death
+    victim_health: i32,
+
+    /// Distance to the local player in meters
+    distance: Option<f32>,
+}
+
+/// Custom kill feed listing all player deaths with additional information
+pub struct KillFeed {
+    entries: VecDeque<KillFeedEntry>,
+
+    local_position: Option<nalgebra::Vector3<f32>>,
+}
+
+impl KillFeed {
+    pub fn new() -> Self {
+        Self {
+            entries: Default::default(),
+            local_position: None,
+        }
+    }
+
+    fn push_entry(&mut self, death: PlayerDeath, local_team_id: u8, max_entries: usize) {
+        let distance = self
+            .local_position
+            .map(|local_position| (death.position - local_position).norm() * UNITS_TO_METERS);
+
+        self.entries.push_back(KillFeedEntry {
+            timestamp: Instant::now(),
+            time: chrono::Local::now().format("%H:%M:%S").to_string(),
+
+            victim_name: death.player_name,
+            victim_enemy: death.team_id != local_team_id,
+            victim_health: death.health,
+            distance,
+        });
+
+        while self.entries.len() > max_entries {
+            self.entries.pop_front();
+        }
+    }
+}
+
+impl Enhancement for KillFeed {
+    fn update(&mut self, ctx: &crate::UpdateContext) -> anyhow::Result<()> {
+        let settings = ctx.states.resolve::<AppSettings>(())?;
+        if !settings.kill_feed {
+            self.entries.clear();
+            return Ok(());
+        }
+
+        let display_duration = Duration::from_secs_f32(settings.kill_feed_duration.max(0.0));
+        self.entries
+            .retain(|entry| entry.timestamp.elapsed() < display_duration);
+
+        let entities = ctx.states.resolve::<EntitySystem>(())?;
+        let local_player_controller = entities.get_local_player_controller()?;
+        if local_player_controller.is_null()? {
+            self.local_position = None;
+            return Ok(());
+        }
+
+        let local_team_id = local_player_controller
+            .reference_schema()?
+            .m_iPendingTeamNum()?;
+        if let Some(local_pawn) = get_local_pawn_info(ctx.states)? {
+            self.local_position = Some(local_pawn.position);
+        }
+
+        let max_entries = settings.kill_feed_max_entries as usize;
+        let death_tracker = ctx.states.resolve::<DeathTracker>(())?;
+        for death in death_tracker.deaths.iter() {
+            self.push_entry(death.clone(), local_team_id, max_entries);
+        }
+
+        Ok(())
+    }
+
+    fn render(&self, states: &utils_state::StateRegistry, ui: &imgui::Ui) -> anyhow::Result<()> {
+        let settings = states.resolve::<AppSettings>(())?;
+        if !settings.kill_feed || self.entries.is_empty() {
+            return Ok(());
+        }
+
+        let display_duration = Duration::from_secs_f32(settings.kill_feed_duration.max(0.0));
+        let [display_width, display_height] = ui.io().display_size;
+        let draw = ui.get_window_draw_list();
+
+        let mut offset_y = SCREEN_MARGIN;
+        for entry in self.entries.iter().rev() {
+            if settings.kill_feed_enemies_only && !entry.victim_enemy {
+                continue;
+            }
+
+            let remaining = display_duration.saturating_sub(entry.timestamp.elapsed());
+            let alpha = (remaining.as_secs_f32() / FADE_DURATION.as_secs_f32()).clamp(0.0, 1.0);
+            if alpha <= 0.0 {
+                continue;
+            }
+
+            let mut details = vec![
+                entry.time.clone(),
+                format!("{} {}", entry.victim_health, obfstr!("HP")),
+            ];
+            if let Some(distance) = entry.distance {
+                details.push(format!("{:.0}m", distance));
+            }
+            let details = format!("  {}", details.join(" | "));
+
+            let [name_width, text_height] = ui.calc_text_size(&entry.victim_name);
+            let [details_width, _] = ui.calc_text_size(&details);
+
+            /* skull replacement drawn as a cross in front of the details */
+            let icon_size = text_height * 0.6;
+            let icon_spacing = text_height * 0.4;
+
+            let bar_size = [
+                name_width + icon_spacing + icon_size + details_width + TEXT_PADDING[0] * 2.0,
+                text_height + TEXT_PADDING[1] * 2.0,
+            ];
+
+            let bar_position = match settings.kill_feed_position {
+                OverlayCorner::TopLeft => [SCREEN_MARGIN, offset_y],
+                OverlayCorner::TopRight => [display_width - bar_size[0] - SCREEN_MARGIN, offset_y],
+                OverlayCorner::BottomLeft => {
+                    [SCREEN_MARGIN, display_height - bar_size[1] - offset_y]
+                }
+                OverlayCorner::BottomRight => [
+                    display_width - bar_size[0] - SCREEN_MARGIN,
+                    display_height - bar_size[1] - offset_y,
+                ],
+            };
+            offset_y += bar_size[1] + ENTRY_SPACING;
+
+            let mut name_color = if entry.victim_enemy {
+                COLOR_ENEMY
+            } else {
+                COLOR_TEAM
+            };
+            name_color[3] *= alpha;
+
+            let mut text_color = settings.kill_feed_color.as_f32();
+            text_color[3] *= alpha;
+
+            draw.add_rect(
+                bar_position,
+                [bar_position[0] + bar_size[0], bar_position[1] + bar_size[1]],
+                [0.0, 0.0, 0.0, 0.5 * alpha],
+            )
+            .filled(true)
+            .rounding(3.0)
+            .build();
+
+            let mut cursor_x = bar_position[0] + TEXT_PADDING[0];
+            let text_y = bar_position[1] + TEXT_PADDING[1];
+            draw.add_text([cursor_x, text_y], name_color, &entry.victim_name);
+            cursor_x += name_width + icon_spacing;
+
+            let icon_y = text_y + (text_height - icon_size) / 2.0;
+            draw.add_line(
+                [cursor_x, icon_y],
+                [cursor_x + icon_size, icon_y + icon_size],
+                text_color,
+            )
+            .thickness(2.0)
+            .build();
+            draw.add_line(
+                [cursor_x + icon_size, icon_y],
+                [cursor_x, icon_y + icon_size],
+                text_color,
+            )
+            .thickness(2.0)
+            .build();
+            cursor_x += icon_size;
+
+            draw.add_text([cursor_x, text_y], text_color, &details);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,278 @@
+use std::collections::HashMap;
+
+use cs2::{
+    CurrentMapState,
+    DroppedC4,
+    EntitySystem,
+    PlantedC4,
+    PlantedC4State,
+    PlayerPawnList,
+    PlayerPawnState,
+};
+
+use super::Enhancement;
+use crate::{
+    map_overview::MapOverview,
+    settings::{
+        AppSettings,
+        OverlayCorner,
+    },
+};
+
+const UNITS_TO_METERS: f32 = 0.01905;
+
+/// Distance to the screen edges in pixels
+const SCREEN_MARGIN: f32 = 10.0;
+
+const PLAYER_RADIUS: f32 = 4.0;
+const LOCAL_PLAYER_SIZE: f32 = 7.0;
+const BOMB_SIZE: f32 = 4.0;
+
+struct MinimapPlayer {
+    position: nalgebra::Vector3<f32>,
+    enemy: bool,
+    has_bomb: bool,
+}
+
+/// On screen minimap with all players relative to the local player
+pub struct Minimap {
+    /// Radar images by map name
+    map_overviews: HashMap<String, MapOverview>,
+    current_map: Option<String>,
+
+    players: Vec<MinimapPlayer>,
+    bomb_position: Option<nalgebra::Vector3<f32>>,
+
+    local_position: Option<nalgebra::Vector3<f32>>,
+
+    /// View yaw of the local player in degrees
+    local_yaw: f32,
+}
+
+impl Minimap {
+    pub fn new(map_overviews: HashMap<String, MapOverview>) -> Self {
+        Self {
+            map_overviews,
+            current_map: None,
+
+            players: Default::default(),
+            bomb_position: None,
+
+            local_position: None,
+            local_yaw: 0.0,
+        }
+    }
+
+    /// Transform a world position into a pixel offset from the minimap center
+    fn world_to_minimap(
+        &self,
+        settings: &AppSettings,
+        local_position: &nalgebra::Vector3<f32>,
+        position: &nalgebra::Vector3<f32>,
+    ) -> [f32; 2] {
+        let pixels_per_unit = UNITS_TO_METERS / settings.minimap_zoom.max(0.001);
+        let delta = (position - local_position).xy() * pixels_per_unit;
+
+        if settings.minimap_rotate {
+            /* view direction points upwards */
+            let (sin, cos) = self.local_yaw.to_radians().sin_cos();
+            [
+                delta.x * sin - delta.y * cos,
+                -(delta.x * cos + delta.y * sin),
+            ]
+        } else {
+            [delta.x, -delta.y]
+        }
+    }
+}
+
+impl Enhancement for Minimap {
+    fn update(&mut self, ctx: &crate::UpdateContext) -> anyhow::Result<()> {
+        self.players.clear();
+        self.bomb_position = None;
+        self.local_position = None;
+
+        let settings = ctx.states.resolve::<AppSettings>(())?;
+        if !settings.minimap {
+            return Ok(());
+        }
+
+        let current_map = ctx.states.resolve::<CurrentMapState>(())?;
+        self.current_map = current_map.current_map.clone();
+
+        let entities = ctx.states.resolve::<EntitySystem>(())?;
+        let local_player_controller = entities.get_local_player_controller()?;
+        if local_player_controller.is_null()? {
+            return Ok(());
+        }
+
+        let local_player_controller = local_player_controller.reference_schema()?;
+        let local_team_id = local_player_controller.m_iPendingTeamNum()?;
+        let local_pawn_index = local_player_controller.m_hPlayerPawn()?.get_entity_index();
+
+        let pawn_list = ctx.states.resolve::<PlayerPawnList>(())?;
+        for pawn_entity_index in pawn_list.pawns.iter().copied() {
+            let pawn_state = match ctx.states.resolve::<PlayerPawnState>(pawn_entity_index) {
+                Ok(state) => state,
+                Err(_) => continue,
+            };
+
+            let info = match &*pawn_state {
+                PlayerPawnState::Alive(info) => info,
+                _ => continue,
+            };
+
+            if pawn_entity_index == local_pawn_index {
+                self.local_position = Some(info.position);
+                self.local_yaw = info.eye_angles.y;
+                continue;
+            }
+
+            let enemy = info.team_id != local_team_id;
+            if !enemy && !settings.minimap_teammates {
+                continue;
+            }
+
+            self.players.push(MinimapPlayer {
+                position: info.position,
+                enemy,
+                has_bomb: info.player_has_bomb,
+            });
+        }
+
+        let planted_c4 = ctx.states.resolve::<PlantedC4>(())?;
+        if matches!(planted_c4.state, PlantedC4State::Active { .. }) {
+            self.bomb_position = Some(planted_c4.position);
+        } else {
+            let dropped_c4 = ctx.states.resolve::<DroppedC4>(())?;
+            self.bomb_position = dropped_c4.position;
+        }
+
+        Ok(())
+    }
+
+    fn render(&self, states: &utils_state::StateRegistry, ui: &imgui::Ui) -> anyhow::Result<()> {
+        let settings = states.resolve::<AppSettings>(())?;
+        if !settings.minimap {
+            return Ok(());
+        }
+
+        let local_position = match &self.local_position {
+            Some(position) => position,
+            None => return Ok(()),
+        };
+
+        let [display_width, display_height] = ui.io().display_size;
+        let size = settings.minimap_size;
+        let map_min = match settings.minimap_position {
+            OverlayCorner::TopLeft => [SCREEN_MARGIN, SCREEN_MARGIN],
+            OverlayCorner::TopRight => [display_width - size - SCREEN_MARGIN, SCREEN_MARGIN],
+            OverlayCorner::BottomLeft => [SCREEN_MARGIN, display_height - size - SCREEN_MARGIN],
+            OverlayCorner::BottomRight => [
+                display_width - size - SCREEN_MARGIN,
+                display_height - size - SCREEN_MARGIN,
+            ],
+        };
+        let map_max = [map_min[0] + size, map_min[1] + size];
+        let map_center = [map_min[0] + size / 2.0, map_min[1] + size / 2.0];
+
+        /* positions outside of the minimap will be clamped to the minimap edge */
+        let to_screen = |position: &nalgebra::Vector3<f32>, inset: f32| -> [f32; 2] {
+            let [x, y] = self.world_to_minimap(&settings, local_position, position);
+            let half_size = (size / 2.0 - inset).max(0.0);
+            [
+                map_center[0] + x.clamp(-half_size, half_size),
+                map_center[1] + y.clamp(-half_size, half_size),
+            ]
+        };
+
+        let draw = ui.get_window_draw_list();
+        draw.add_rect(map_min, map_max, [0.0, 0.0, 0.0, 0.5])
+            .filled(true)
+            .build();
+
+        /* maps without an overview fall back to the plain relative positions */
+        let map_overview = self
+            .current_map
+            .as_ref()
+            .filter(|_| settings.minimap_map_overview)
+            .and_then(|map_name| self.map_overviews.get(map_name));
+        if let Some(map_overview) = map_overview {
+            let [top_left, top_right, bottom_right, bottom_left] =
+                [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]].map(|uv| {
+                    let world_position = map_overview.calibration.uv_to_world(uv);
+                    let [x, y] = self.world_to_minimap(&settings, local_position, &world_position);
+                    [map_center[0] + x, map_center[1] + y]
+                });
+
+            draw.with_clip_rect_intersect(map_min, map_max, || {
+                draw.add_image_quad(
+                    map_overview.texture_id,
+                    top_left,
+                    top_right,
+                    bottom_right,
+                    bottom_left,
+                )
+                .col([1.0, 1.0, 1.0, settings.minimap_map_overview_alpha])
+                .build();
+            });
+        }
+        draw.add_rect(map_min, map_max, [1.0, 1.0, 1.0, 0.3])
+            .thickness(1.0)
+            .build();
+
+        let bomb_color = settings.minimap_bomb_color.as_f32();
+        if let Some(bomb_position) = &self.bomb_position {
+            let [x, y] = to_screen(bomb_position, BOMB_SIZE);
+            draw.add_rect(
+                [x - BOMB_SIZE, y - BOMB_SIZE],
+                [x + BOMB_SIZE, y + BOMB_SIZE],
+                bomb_color,
+            )
+            .filled(true)
+            .build();
+        }
+
+        for player in self.players.iter() {
+            let position = to_screen(&player.position, PLAYER_RADIUS);
+            let color = if player.enemy {
+                settings.minimap_enemy_color.as_f32()
+            } else {
+                settings.minimap_team_color.as_f32()
+            };
+
+            draw.add_circle(position, PLAYER_RADIUS, color)
+                .filled(true)
+                .build();
+            if player.has_bomb {
+                draw.add_circle(position, PLAYER_RADIUS + 2.0, bomb_color)
+                    .thickness(2.0)
+                    .build();
+            }
+        }
+
+        /* local player in the center pointing towards the view direction */
+        let view_angle = if settings.minimap_rotate {
+            0.0
+        } else {
+            (90.0 - self.local_yaw).to_radians()
+        };
+        let (sin, cos) = view_angle.sin_cos();
+        let rotate = |x: f32, y: f32| -> [f32; 2] {
+            [
+                map_center[0] + x * cos - y * sin,
+                map_center[1] + x * sin + y * cos,
+            ]
+        };
+        draw.add_triangle(
+            rotate(0.0, -LOCAL_PLAYER_SIZE),
+            rotate(LOCAL_PLAYER_SIZE * 0.6, LOCAL_PLAYER_SIZE * 0.6),
+            rotate(-LOCAL_PLAYER_SIZE * 0.6, LOCAL_PLAYER_SIZE * 0.6),
+            settings.minimap_team_color.as_f32(),
+        )
+        .filled(true)
+        .build();
+
+        Ok(())
+    }
+}
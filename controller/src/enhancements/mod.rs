@@ -1,4 +1,15 @@
-use crate::settings::AppSettings;
+use crate::settings::{
+    ActivationMode,
+    AppSettings,
+};
+
+/// Current state of a feature which can be toggled by the user
+#[derive(Debug, Clone)]
+pub struct EnhancementStatus {
+    pub name: String,
+    pub mode: ActivationMode,
+    pub active: bool,
+}
 
 pub trait Enhancement {
     /* FIXME: Remove the update method! */
@@ -11,22 +22,103 @@ pub trait Enhancement {
         Ok(false)
     }
 
+    /// Report the current state of the feature (e.g. for the bind list).
+    /// Enhancements without a toggle return None.
+    fn status(&self, _settings: &AppSettings) -> Option<EnhancementStatus> {
+        None
+    }
+
     fn render(&self, states: &StateRegistry, ui: &imgui::Ui) -> anyhow::Result<()>;
     fn render_debug_window(&mut self, _states: &StateRegistry, _ui: &imgui::Ui) {}
 }
 
+mod bind_list;
+pub use bind_list::*;
+
 mod bomb;
 pub use bomb::*;
 
+mod bomb_esp;
+pub use bomb_esp::*;
+
+mod bomb_timer_hud;
+pub use bomb_timer_hud::*;
+
+mod chicken;
+pub use chicken::*;
+
+mod compass;
+pub use compass::*;
+
+mod defuse_kit;
+pub use defuse_kit::*;
+
+mod grenade;
+pub use grenade::*;
+
+mod grenade_helper;
+pub use grenade_helper::*;
+
+mod grenade_warning;
+pub use grenade_warning::*;
+
+mod hostage;
+pub use hostage::*;
+
+mod inferno;
+pub use inferno::*;
+
+mod kill_feed;
+pub use kill_feed::*;
+
+mod minimap;
+pub use minimap::*;
+
 mod player;
 pub use player::*;
 
+mod proximity_alert;
+pub use proximity_alert::*;
+
+mod round_hud;
+pub use round_hud::*;
+
+mod recoil_crosshair;
+pub use recoil_crosshair::*;
+
+mod smoke;
+pub use smoke::*;
+
+mod sniper_crosshair;
+pub use sniper_crosshair::*;
+
 mod trigger;
 pub use trigger::*;
 
 mod spectators_list;
 pub use spectators_list::*;
 
+mod sound;
+pub use sound::*;
+
+mod damage;
+pub use damage::*;
+
+mod damage_summary;
+pub use damage_summary::*;
+
+mod death_markers;
+pub use death_markers::*;
+
+mod death_tracker;
+pub use death_tracker::*;
+
+mod economy;
+pub use economy::*;
+
+mod watermark;
+pub use watermark::*;
+
 mod aim;
 pub use aim::*;
 use utils_state::StateRegistry;
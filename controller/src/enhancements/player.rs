@@ -1,27 +1,71 @@
+use std::{
+    borrow::Cow,
+    cell::RefCell,
+    collections::{
+        hash_map::DefaultHasher,
+        BTreeMap,
+        HashMap,
+        VecDeque,
+    },
+    hash::{
+        Hash,
+        Hasher,
+    },
+    time::{
+        Duration,
+        Instant,
+    },
+};
+
 use cs2::{
     BoneFlags,
-    CEntityIdentityEx,
     CS2Model,
-    ClassNameCache,
+    CurrentMapState,
     EntitySystem,
     LocalCameraControllerTarget,
+    PlayerControllerInfo,
+    PlayerMatchStats,
     PlayerPawnInfo,
+    PlayerPawnList,
     PlayerPawnState,
+    PlayerPawnVisibility,
+    PLAYER_FLAG_DUCKING,
+    PLAYER_FLAG_ON_GROUND,
+};
+use imgui::{
+    FontId,
+    ImColor32,
 };
-use cs2_schema_generated::cs2::client::C_CSPlayerPawn;
-use imgui::ImColor32;
 use obfstr::obfstr;
 
-use super::Enhancement;
+use super::{
+    Enhancement,
+    EnhancementStatus,
+};
 use crate::{
+    fonts::{
+        weapon_icon_glyph,
+        AppFonts,
+        FontGlyphs,
+    },
     settings::{
         AppSettings,
+        EspArmorBar,
+        EspBotFilter,
+        EspBoxFit,
         EspBoxType,
+        EspColor,
         EspConfig,
+        EspHeadDisplay,
         EspHealthBar,
+        EspInfoAnchor,
+        EspInfoLine,
         EspPlayerSettings,
         EspSelector,
+        EspSkeletonMode,
+        EspSpeedUnit,
         EspTracePosition,
+        EspWeaponStyle,
     },
     view::{
         KeyToggle,
@@ -29,10 +73,189 @@ use crate::{
     },
 };
 
+struct PlayerESPInfo {
+    pawn: PlayerPawnInfo,
+
+    /// Whether the player is visible for the pawn the local camera is following
+    visible: bool,
+
+    /// Info of the owning player controller.
+    /// None if the controller could not be resolved.
+    controller: Option<PlayerControllerInfo>,
+
+    /// Match stats of the owning player controller.
+    /// None if the stats could not be read.
+    match_stats: Option<PlayerMatchStats>,
+
+    /// Timestamp when the pawn info has been updated the last time
+    last_seen: Instant,
+
+    /// The pawn is no longer updated and only the last known position will be shown
+    ghost: bool,
+
+    /// Flash time of the player when the current flash started
+    flash_duration: f32,
+
+    /// Timestamp when the player fired the last shot
+    last_shot: Option<Instant>,
+
+    /// Recorded positions of the player (oldest first)
+    trail: VecDeque<(Instant, nalgebra::Vector3<f32>)>,
+}
+
+/// Interval in which the player positions will be recorded for the trail
+const TRAIL_RECORD_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Max age of recorded trail positions
+const TRAIL_MAX_AGE: Duration = Duration::from_secs(10);
+
+/// Bones used by the simplified skeleton
+const SKELETON_SIMPLIFIED_BONES: [&'static str; 19] = [
+    "pelvis",
+    "spine_1",
+    "spine_2",
+    "neck_0",
+    "head_0",
+    "arm_upper_l",
+    "arm_lower_l",
+    "hand_l",
+    "arm_upper_r",
+    "arm_lower_r",
+    "hand_r",
+    "leg_upper_l",
+    "leg_lower_l",
+    "ankle_l",
+    "leg_upper_r",
+    "leg_lower_r",
+    "ankle_r",
+    "clavicle_l",
+    "clavicle_r",
+];
+
+/// Radius of the head dot in world units, used if the model has no head hitbox
+const HEAD_DOT_RADIUS: f32 = 4.0;
+
+/// Distance in meters at which the skeleton joints will be rendered with their base radius
+const SKELETON_JOINT_REFERENCE_DISTANCE: f32 = 10.0;
+
+/// Max number of cached skeleton segments.
+/// Every edit of the custom bone list creates a new entry, therefore the cache is reset once exceeded.
+const SKELETON_SEGMENTS_CACHE_LIMIT: usize = 256;
+
+/// Resolved skeleton segments of a model for a specific skeleton mode
+struct SkeletonSegments {
+    /// Bone index pairs (bone, parent) which should be connected
+    segments: Vec<(usize, usize)>,
+
+    /// Indices of all bones which are part of the segments
+    joints: Vec<usize>,
+}
+
+impl SkeletonSegments {
+    /// Hash of the bones selected by the skeleton mode.
+    /// Different custom bone lists on the same model must not share their segments.
+    fn selection_hash(mode: EspSkeletonMode, custom_bones: &str) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        if mode == EspSkeletonMode::Custom {
+            for name in custom_bones
+                .split(',')
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+            {
+                name.to_ascii_lowercase().hash(&mut hasher);
+            }
+        }
+        hasher.finish()
+    }
+
+    fn resolve(model: &CS2Model, mode: EspSkeletonMode, custom_bones: &str) -> Self {
+        let segments = match mode {
+            EspSkeletonMode::Full => model
+                .bones
+                .iter()
+                .enumerate()
+                .filter(|(_, bone)| (bone.flags & BoneFlags::FlagHitbox as u32) != 0)
+                .filter_map(|(index, bone)| Some((index, bone.parent?)))
+                .collect(),
+            EspSkeletonMode::Simplified | EspSkeletonMode::Custom => {
+                let bone_names = if mode == EspSkeletonMode::Simplified {
+                    SKELETON_SIMPLIFIED_BONES
+                        .iter()
+                        .map(|name| name.to_string())
+                        .collect::<Vec<_>>()
+                } else {
+                    custom_bones
+                        .split(',')
+                        .map(|name| name.trim().to_string())
+                        .filter(|name| !name.is_empty())
+                        .collect::<Vec<_>>()
+                };
+
+                let included = model
+                    .bones
+                    .iter()
+                    .map(|bone| {
+                        bone_names
+                            .iter()
+                            .any(|name| name.eq_ignore_ascii_case(&bone.name))
+                    })
+                    .collect::<Vec<_>>();
+
+                /* connect every included bone to its closest included ancestor */
+                let mut segments = Vec::new();
+                for (index, bone) in model.bones.iter().enumerate() {
+                    if !included[index] {
+                        continue;
+                    }
+
+                    let mut parent = bone.parent;
+                    while let Some(parent_index) = parent {
+                        if included[parent_index] {
+                            segments.push((index, parent_index));
+                            break;
+                        }
+
+                        parent = model.bones[parent_index].parent;
+                    }
+                }
+                segments
+            }
+        };
+
+        let mut joints = segments
+            .iter()
+            .flat_map(|(bone, parent)| [*bone, *parent])
+            .collect::<Vec<_>>();
+        joints.sort_unstable();
+        joints.dedup();
+
+        Self { segments, joints }
+    }
+}
+
 pub struct PlayerESP {
     toggle: KeyToggle,
-    players: Vec<PlayerPawnInfo>,
+
+    /// Players mapped by their pawn entity index
+    players: BTreeMap<u32, PlayerESPInfo>,
     local_team_id: u8,
+
+    /// Name of the current map for the map specific ESP overrides
+    current_map: Option<String>,
+
+    /// Skeleton segments by model address, skeleton mode and bone selection hash
+    skeleton_segments: RefCell<HashMap<(u64, EspSkeletonMode, u64), SkeletonSegments>>,
+}
+
+/// Unit vector of the view direction for the given eye angles (pitch, yaw, roll) in degrees
+pub(crate) fn eye_direction(eye_angles: &nalgebra::Vector3<f32>) -> nalgebra::Vector3<f32> {
+    let pitch = eye_angles.x.to_radians();
+    let yaw = eye_angles.y.to_radians();
+    nalgebra::Vector3::new(
+        pitch.cos() * yaw.cos(),
+        pitch.cos() * yaw.sin(),
+        -pitch.sin(),
+    )
 }
 
 impl PlayerESP {
@@ -40,18 +263,21 @@ impl PlayerESP {
         PlayerESP {
             toggle: KeyToggle::new(),
             players: Default::default(),
+            skeleton_segments: Default::default(),
             local_team_id: 0,
+            current_map: None,
         }
     }
 
+    /// Resolve the ESP config for the target including the overrides for the current map
     fn resolve_esp_player_config<'a>(
         &self,
         settings: &'a AppSettings,
-        target: &PlayerPawnInfo,
-    ) -> Option<&'a EspPlayerSettings> {
+        target: &PlayerESPInfo,
+    ) -> Option<Cow<'a, EspPlayerSettings>> {
         let mut esp_target = Some(EspSelector::PlayerTeamVisibility {
-            enemy: target.team_id != self.local_team_id,
-            visible: true, // TODO: Implement visibility, maybe rename it to spottet!
+            enemy: target.pawn.team_id != self.local_team_id,
+            visible: target.visible,
         });
 
         while let Some(target) = esp_target.take() {
@@ -65,7 +291,15 @@ impl PlayerESP {
             {
                 if let Some(settings) = settings.esp_settings.get(&config_key) {
                     if let EspConfig::Player(settings) = settings {
-                        return Some(settings);
+                        let map_settings = self
+                            .current_map
+                            .as_ref()
+                            .and_then(|map_name| settings.for_map(map_name));
+
+                        return Some(match map_settings {
+                            Some(map_settings) => Cow::Owned(map_settings),
+                            None => Cow::Borrowed(settings),
+                        });
                     }
                 }
             }
@@ -75,85 +309,376 @@ impl PlayerESP {
 
         None
     }
+
+    /// Check if the player has been added to the ignore list.
+    /// Names are compared case-insensitive, SteamIDs must match exactly.
+    fn is_blacklisted(settings: &AppSettings, player: &PlayerESPInfo) -> bool {
+        let steam_id = player
+            .controller
+            .as_ref()
+            .map(|controller| controller.steam_id)
+            .filter(|steam_id| *steam_id != 0)
+            .map(|steam_id| steam_id.to_string());
+
+        settings.player_blacklist.iter().any(|entry| {
+            let entry = entry.trim();
+            entry.to_lowercase() == player.pawn.player_name.to_lowercase()
+                || steam_id.as_deref() == Some(entry)
+        })
+    }
+
+    /// Returns the opacity in [0.0;1.0] of the player ESP based on the distance fade settings
+    fn distance_fade_alpha(esp_settings: &EspPlayerSettings, distance: f32) -> f32 {
+        if !esp_settings.distance_fade {
+            return 1.0;
+        }
+
+        let fade_start = esp_settings.fade_distance_start;
+        let fade_end = esp_settings.fade_distance_end;
+        if fade_end <= fade_start {
+            return if distance < fade_end { 1.0 } else { 0.0 };
+        }
+
+        1.0 - ((distance - fade_start) / (fade_end - fade_start)).clamp(0.0, 1.0)
+    }
+
+    /// Returns the ghost opacity in [0.0;1.0] or None if the ghost should not be shown (anymore)
+    fn ghost_alpha(esp_settings: &EspPlayerSettings, target: &PlayerESPInfo) -> Option<f32> {
+        if !esp_settings.ghost || esp_settings.ghost_timeout <= 0.0 {
+            return None;
+        }
+
+        let elapsed = target.last_seen.elapsed().as_secs_f32();
+        if elapsed >= esp_settings.ghost_timeout {
+            return None;
+        }
+
+        Some(1.0 - elapsed / esp_settings.ghost_timeout)
+    }
 }
 
+struct PlayerInfoLine {
+    color: ImColor32,
+    text: String,
+    width: f32,
+
+    /// Font to render the line with instead of the current font
+    font: Option<FontId>,
+}
+
+/// Collects the player info lines and renders them
+/// relative to the player box when the layout gets dropped.
 struct PlayerInfoLayout<'a> {
     ui: &'a imgui::Ui,
     draw: &'a imgui::DrawListMut<'a>,
+    screen_offset: mint::Vector2<f32>,
+    screen_bounds: mint::Vector2<f32>,
 
     vmin: nalgebra::Vector2<f32>,
     vmax: nalgebra::Vector2<f32>,
 
-    line_count: usize,
+    lines: Vec<PlayerInfoLine>,
     font_scale: f32,
+    ui_scale: f32,
 
+    anchor: EspInfoAnchor,
     has_2d_box: bool,
+    text_outline: bool,
 }
 
 impl<'a> PlayerInfoLayout<'a> {
     pub fn new(
         ui: &'a imgui::Ui,
         draw: &'a imgui::DrawListMut<'a>,
+        screen_offset: mint::Vector2<f32>,
         screen_bounds: mint::Vector2<f32>,
         vmin: nalgebra::Vector2<f32>,
         vmax: nalgebra::Vector2<f32>,
+        anchor: EspInfoAnchor,
         has_2d_box: bool,
+        text_outline: bool,
+        text_scale: f32,
+        ui_scale: f32,
     ) -> Self {
         let target_scale_raw = (vmax.y - vmin.y) / screen_bounds.y * 8.0;
-        let target_scale = target_scale_raw.clamp(0.5, 1.25);
+        let target_scale =
+            target_scale_raw.clamp(0.5, 1.25) * text_scale.clamp(0.5, 2.0) * ui_scale;
         ui.set_window_font_scale(target_scale);
 
         Self {
             ui,
             draw,
+            screen_offset,
+            screen_bounds,
 
             vmin,
             vmax,
 
-            line_count: 0,
+            lines: Vec::with_capacity(8),
             font_scale: target_scale,
+            ui_scale,
 
+            anchor,
             has_2d_box,
+            text_outline,
         }
     }
 
     pub fn add_line(&mut self, color: impl Into<ImColor32>, text: &str) {
-        let [text_width, _] = self.ui.calc_text_size(text);
+        self.add_line_with_font(None, color, text);
+    }
 
-        let mut pos = if self.has_2d_box {
-            let mut pos = self.vmin;
-            pos.x = self.vmax.x + 5.0;
-            pos
-        } else {
-            let mut pos = self.vmax.clone();
-            pos.x -= (self.vmax.x - self.vmin.x) / 2.0;
-            pos.x -= text_width / 2.0;
-            pos
-        };
-        pos.y += self.line_count as f32 * self.font_scale * (self.ui.text_line_height())
-            + 4.0 * self.line_count as f32;
+    pub fn add_line_with_font(
+        &mut self,
+        font: Option<FontId>,
+        color: impl Into<ImColor32>,
+        text: &str,
+    ) {
+        let _font = font.map(|font| self.ui.push_font(font));
+        let [width, _] = self.ui.calc_text_size(text);
+        self.lines.push(PlayerInfoLine {
+            color: color.into(),
+            text: text.to_string(),
+            width,
+            font,
+        });
+    }
+
+    /// Resolve the Auto anchor into the actual side the lines will be rendered at
+    fn resolve_anchor(&self, text_width: f32, text_height: f32) -> EspInfoAnchor {
+        let spacing = self.box_spacing();
+        match self.anchor {
+            EspInfoAnchor::Auto if self.has_2d_box => {
+                if self.vmax.x + spacing + text_width > self.screen_offset.x + self.screen_bounds.x
+                    && self.vmin.x - spacing - text_width >= self.screen_offset.x
+                {
+                    EspInfoAnchor::Left
+                } else {
+                    EspInfoAnchor::Right
+                }
+            }
+            EspInfoAnchor::Auto => {
+                if self.vmax.y + text_height > self.screen_offset.y + self.screen_bounds.y
+                    && self.vmin.y - text_height >= self.screen_offset.y
+                {
+                    EspInfoAnchor::Top
+                } else {
+                    EspInfoAnchor::Bottom
+                }
+            }
+            anchor => anchor,
+        }
+    }
 
-        self.draw.add_text([pos.x, pos.y], color, text);
-        self.line_count += 1;
+    /// Horizontal distance between the box and the lines
+    fn box_spacing(&self) -> f32 {
+        5.0 * self.ui_scale
+    }
+
+    fn render_lines(&self) {
+        let spacing = self.box_spacing();
+        let line_height = self.font_scale * self.ui.text_line_height() + 4.0 * self.ui_scale;
+        let text_width = self.lines.iter().map(|line| line.width).fold(0.0, f32::max);
+        let text_height = self.lines.len() as f32 * line_height;
+
+        let box_center_x = (self.vmin.x + self.vmax.x) / 2.0;
+        let anchor = self.resolve_anchor(text_width, text_height);
+        for (index, line) in self.lines.iter().enumerate() {
+            let offset_y = index as f32 * line_height;
+            let width = line.width;
+            let pos = match anchor {
+                EspInfoAnchor::Right => [self.vmax.x + spacing, self.vmin.y + offset_y],
+                EspInfoAnchor::Left => [self.vmin.x - spacing - width, self.vmin.y + offset_y],
+                EspInfoAnchor::Top => [
+                    box_center_x - width / 2.0,
+                    self.vmin.y - text_height + offset_y,
+                ],
+                EspInfoAnchor::Bottom | EspInfoAnchor::Auto => {
+                    [box_center_x - width / 2.0, self.vmax.y + offset_y]
+                }
+            };
+
+            let _font = line.font.map(|font| self.ui.push_font(font));
+            if self.text_outline {
+                let shadow_color = ImColor32::from_rgba(0, 0, 0, line.color.a);
+                self.draw
+                    .add_text([pos[0] + 1.0, pos[1] + 1.0], shadow_color, &line.text);
+            }
+
+            self.draw.add_text(pos, line.color, &line.text);
+        }
     }
 }
 
 impl Drop for PlayerInfoLayout<'_> {
     fn drop(&mut self) {
+        self.render_lines();
         self.ui.set_window_font_scale(1.0);
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum BarSide {
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
+impl BarSide {
+    fn from_health_bar(value: EspHealthBar) -> Option<Self> {
+        match value {
+            EspHealthBar::None => None,
+            EspHealthBar::Top => Some(Self::Top),
+            EspHealthBar::Bottom => Some(Self::Bottom),
+            EspHealthBar::Left => Some(Self::Left),
+            EspHealthBar::Right => Some(Self::Right),
+        }
+    }
+
+    fn from_armor_bar(value: EspArmorBar) -> Option<Self> {
+        match value {
+            EspArmorBar::None => None,
+            EspArmorBar::Top => Some(Self::Top),
+            EspArmorBar::Bottom => Some(Self::Bottom),
+            EspArmorBar::Left => Some(Self::Left),
+            EspArmorBar::Right => Some(Self::Right),
+        }
+    }
+
+    /// Calculate the bar bounds ([x, y, width, height]) next to the players 2D box.
+    /// The offset will move the bar further away from the box.
+    fn calculate_bounds(
+        &self,
+        vmin: &nalgebra::Vector2<f32>,
+        vmax: &nalgebra::Vector2<f32>,
+        box_width: f32,
+        bar_width: f32,
+        offset: f32,
+    ) -> [f32; 4] {
+        match self {
+            Self::Left => {
+                let xoffset = vmin.x - box_width / 2.0 - bar_width - offset;
+
+                [
+                    xoffset,
+                    vmin.y - box_width / 2.0,
+                    bar_width,
+                    vmax.y - vmin.y + box_width,
+                ]
+            }
+            Self::Right => {
+                let xoffset = vmax.x + box_width / 2.0 + offset;
+
+                [
+                    xoffset,
+                    vmin.y - box_width / 2.0,
+                    bar_width,
+                    vmax.y - vmin.y + box_width,
+                ]
+            }
+            Self::Top => {
+                let yoffset = vmin.y - box_width / 2.0 - bar_width - offset;
+
+                [
+                    vmin.x - box_width / 2.0,
+                    yoffset,
+                    vmax.x - vmin.x + box_width,
+                    bar_width,
+                ]
+            }
+            Self::Bottom => {
+                let yoffset = vmax.y + box_width / 2.0 + offset;
+
+                [
+                    vmin.x - box_width / 2.0,
+                    yoffset,
+                    vmax.x - vmin.x + box_width,
+                    bar_width,
+                ]
+            }
+        }
+    }
+}
+
+/// Draw a bordered bar filled by value in [0.0;1.0].
+/// The bar orientation is determined by the bounds.
+fn draw_bar(
+    draw: &imgui::DrawListMut,
+    bounds: [f32; 4],
+    value: f32,
+    fill_color: [f32; 4],
+    empty_color: [f32; 4],
+) {
+    let [mut box_x, mut box_y, mut box_width, mut box_height] = bounds;
+
+    const BORDER_WIDTH: f32 = 1.0;
+    draw.add_rect(
+        [box_x + BORDER_WIDTH / 2.0, box_y + BORDER_WIDTH / 2.0],
+        [
+            box_x + box_width - BORDER_WIDTH / 2.0,
+            box_y + box_height - BORDER_WIDTH / 2.0,
+        ],
+        [0.0, 0.0, 0.0, 1.0],
+    )
+    .filled(false)
+    .thickness(BORDER_WIDTH)
+    .build();
+
+    box_x += BORDER_WIDTH / 2.0 + 1.0;
+    box_y += BORDER_WIDTH / 2.0 + 1.0;
+
+    box_width -= BORDER_WIDTH + 2.0;
+    box_height -= BORDER_WIDTH + 2.0;
+
+    if box_width < box_height {
+        /* vertical */
+        let yoffset = box_y + (1.0 - value) * box_height;
+        draw.add_rect([box_x, box_y], [box_x + box_width, yoffset], empty_color)
+            .filled(true)
+            .build();
+
+        draw.add_rect(
+            [box_x, yoffset],
+            [box_x + box_width, box_y + box_height],
+            fill_color,
+        )
+        .filled(true)
+        .build();
+    } else {
+        /* horizontal */
+        let xoffset = box_x + (1.0 - value) * box_width;
+        draw.add_rect([box_x, box_y], [xoffset, box_y + box_height], empty_color)
+            .filled(true)
+            .build();
+
+        draw.add_rect(
+            [xoffset, box_y],
+            [box_x + box_width, box_y + box_height],
+            fill_color,
+        )
+        .filled(true)
+        .build();
+    }
+}
+
 const HEALTH_BAR_MAX_HEALTH: f32 = 100.0;
 const HEALTH_BAR_BORDER_WIDTH: f32 = 1.0;
 impl Enhancement for PlayerESP {
+    fn status(&self, settings: &AppSettings) -> Option<EnhancementStatus> {
+        Some(EnhancementStatus {
+            name: obfstr!("ESP").to_string(),
+            mode: settings.esp_mode,
+            active: self.toggle.enabled,
+        })
+    }
+
     fn update(&mut self, ctx: &crate::UpdateContext) -> anyhow::Result<()> {
         let entities = ctx.states.resolve::<EntitySystem>(())?;
-        let class_name_cache = ctx.states.resolve::<ClassNameCache>(())?;
         let settings = ctx.states.resolve::<AppSettings>(())?;
         if self
             .toggle
-            .update(&settings.esp_mode, ctx.input, &settings.esp_toogle)
+            .update(&settings.esp_mode, ctx.input, &settings.esp_toggle)
         {
             ctx.cs2.add_metrics_record(
                 obfstr!("feature-esp-toggle"),
@@ -164,68 +689,191 @@ impl Enhancement for PlayerESP {
             );
         }
 
-        self.players.clear();
         if !self.toggle.enabled {
+            self.players.clear();
             return Ok(());
         }
 
-        self.players.reserve(16);
+        self.current_map = ctx
+            .states
+            .resolve::<CurrentMapState>(())?
+            .current_map
+            .clone();
 
         let local_player_controller = entities.get_local_player_controller()?;
         if local_player_controller.is_null()? {
+            self.players.clear();
             return Ok(());
         }
 
         let local_player_controller = local_player_controller.reference_schema()?;
         self.local_team_id = local_player_controller.m_iPendingTeamNum()?;
 
+        /*
+         * Only the pawn we're looking through will be hidden.
+         * In chase or free-look mode (and within GOTV) all players are rendered.
+         */
         let view_target = ctx.states.resolve::<LocalCameraControllerTarget>(())?;
-        let target_entity_id = match &view_target.target_entity_id {
-            Some(value) => *value,
-            None => return Ok(()),
-        };
+        let target_entity_id = view_target.first_person_entity_id();
 
-        for entity_identity in entities.all_identities() {
-            if entity_identity.handle::<()>()?.get_entity_index() == target_entity_id {
-                continue;
-            }
+        /* every player which does not get updated this tick will become a ghost */
+        for player in self.players.values_mut() {
+            player.ghost = true;
+            player.visible = false;
+        }
 
-            let entity_class = class_name_cache.lookup(&entity_identity.entity_class_info()?)?;
-            if !entity_class
-                .map(|name| *name == "C_CSPlayerPawn")
-                .unwrap_or(false)
-            {
-                /* entity is not a player pawn */
+        let pawn_list = ctx.states.resolve::<PlayerPawnList>(())?;
+        for pawn_entity_index in pawn_list.pawns.iter().copied() {
+            if Some(pawn_entity_index) == target_entity_id {
+                self.players.remove(&pawn_entity_index);
                 continue;
             }
 
-            let player_pawn = entity_identity.entity_ptr::<C_CSPlayerPawn>()?;
-            match ctx
-                .states
-                .resolve::<PlayerPawnState>(entity_identity.handle::<()>()?.get_entity_index())
-            {
+            match ctx.states.resolve::<PlayerPawnState>(pawn_entity_index) {
                 Ok(info) => match &*info {
-                    PlayerPawnState::Alive(info) => self.players.push(info.clone()),
-                    PlayerPawnState::Dead => continue,
+                    PlayerPawnState::Alive(info) => {
+                        if let Ok(mut glyphs) = ctx.states.resolve_mut::<FontGlyphs>(()) {
+                            glyphs.register_text(&info.player_name);
+                            glyphs.register_text(&info.player_clan_tag);
+                        }
+
+                        let visible = ctx
+                            .states
+                            .resolve::<PlayerPawnVisibility>(pawn_entity_index)
+                            .map(|visibility| visibility.visible)
+                            .unwrap_or(false);
+
+                        let controller = ctx
+                            .states
+                            .resolve::<PlayerControllerInfo>(pawn_entity_index)
+                            .ok()
+                            .map(|controller| controller.clone());
+
+                        let match_stats = ctx
+                            .states
+                            .resolve::<PlayerMatchStats>(info.controller_entity_id)
+                            .ok()
+                            .map(|stats| stats.clone());
+
+                        /* keep the initial flash time until the player gets flashed again */
+                        let flash_duration = match self.players.get(&pawn_entity_index) {
+                            Some(previous)
+                                if info.player_flashtime > 0.0
+                                    && info.player_flashtime <= previous.pawn.player_flashtime =>
+                            {
+                                previous.flash_duration
+                            }
+                            _ => info.player_flashtime,
+                        };
+
+                        let last_shot = match self.players.get(&pawn_entity_index) {
+                            Some(previous)
+                                if info.player_shots_fired > previous.pawn.player_shots_fired =>
+                            {
+                                Some(Instant::now())
+                            }
+                            Some(previous) => previous.last_shot,
+                            None => None,
+                        };
+
+                        let mut trail = self
+                            .players
+                            .get_mut(&pawn_entity_index)
+                            .map(|previous| std::mem::take(&mut previous.trail))
+                            .unwrap_or_default();
+                        while trail
+                            .front()
+                            .map(|(timestamp, _)| timestamp.elapsed() > TRAIL_MAX_AGE)
+                            .unwrap_or(false)
+                        {
+                            trail.pop_front();
+                        }
+
+                        let record_position = trail
+                            .back()
+                            .map(|(timestamp, _)| timestamp.elapsed() >= TRAIL_RECORD_INTERVAL)
+                            .unwrap_or(true);
+                        if record_position {
+                            trail.push_back((Instant::now(), info.position));
+                        }
+
+                        let player = PlayerESPInfo {
+                            pawn: info.clone(),
+                            visible,
+                            controller,
+                            match_stats,
+                            last_seen: Instant::now(),
+                            ghost: false,
+                            flash_duration,
+                            last_shot,
+                            trail,
+                        };
+
+                        if Self::is_blacklisted(&settings, &player) {
+                            self.players.remove(&pawn_entity_index);
+                            continue;
+                        }
+
+                        let is_bot = player
+                            .controller
+                            .as_ref()
+                            .map(|controller| controller.is_bot)
+                            .unwrap_or(false);
+                        let hide_bot = is_bot
+                            && self
+                                .resolve_esp_player_config(&settings, &player)
+                                .map(|config| config.filter_bots == EspBotFilter::Hide)
+                                .unwrap_or(false);
+                        if hide_bot {
+                            self.players.remove(&pawn_entity_index);
+                            continue;
+                        }
+
+                        self.players.insert(pawn_entity_index, player);
+                    }
+                    PlayerPawnState::Dormant => continue,
+                    PlayerPawnState::Dead => {
+                        self.players.remove(&pawn_entity_index);
+                    }
                 },
                 Err(error) => {
                     log::warn!(
-                        "Failed to generate player pawn ESP info for {:X}: {:#}",
-                        player_pawn.address()?,
+                        "Failed to generate player pawn ESP info for {}: {:#}",
+                        pawn_entity_index,
                         error
                     );
                 }
             }
         }
 
+        let expired_ghosts = self
+            .players
+            .iter()
+            .filter(|(_, player)| player.ghost)
+            .filter(|(_, player)| {
+                self.resolve_esp_player_config(&settings, player)
+                    .and_then(|esp_settings| Self::ghost_alpha(&esp_settings, player))
+                    .is_none()
+            })
+            .map(|(pawn_entity_index, _)| *pawn_entity_index)
+            .collect::<Vec<_>>();
+
+        for pawn_entity_index in expired_ghosts {
+            self.players.remove(&pawn_entity_index);
+        }
+
         Ok(())
     }
 
     fn render(&self, states: &utils_state::StateRegistry, ui: &imgui::Ui) -> anyhow::Result<()> {
         let settings = states.resolve::<AppSettings>(())?;
         let view = states.resolve::<ViewController>(())?;
+        let fonts = states.resolve::<AppFonts>(())?;
+        let _esp_font = ui.push_font(fonts.esp);
 
         let draw = ui.get_window_draw_list();
+        let time = ui.time() as f32;
+        let ui_scale = view.ui_scale(settings.ui_scale_override);
         const UNITS_TO_METERS: f32 = 0.01905;
 
         let view_world_position = match view.get_camera_world_position() {
@@ -233,9 +881,10 @@ impl Enhancement for PlayerESP {
             _ => return Ok(()),
         };
 
-        for entry in self.players.iter() {
-            let distance = (entry.position - view_world_position).norm() * UNITS_TO_METERS;
-            let esp_settings = match self.resolve_esp_player_config(&settings, entry) {
+        let mut players = Vec::with_capacity(self.players.len());
+        for (pawn_entity_index, player) in self.players.iter() {
+            let distance = (player.pawn.position - view_world_position).norm() * UNITS_TO_METERS;
+            let esp_settings = match self.resolve_esp_player_config(&settings, player) {
                 Some(settings) => settings,
                 None => continue,
             };
@@ -245,184 +894,542 @@ impl Enhancement for PlayerESP {
                 }
             }
 
+            /* invisible players must not count towards the rendered player limit */
+            let ghost_alpha = if player.ghost {
+                match Self::ghost_alpha(&esp_settings, player) {
+                    Some(alpha) => Some(alpha),
+                    None => continue,
+                }
+            } else {
+                None
+            };
+            let fade_alpha = Self::distance_fade_alpha(&esp_settings, distance);
+            if fade_alpha <= 0.0 {
+                continue;
+            }
+
+            players.push((
+                *pawn_entity_index,
+                player,
+                esp_settings,
+                distance,
+                ghost_alpha,
+                fade_alpha,
+            ));
+        }
+
+        /* only render the closest players if the amount of rendered players is limited */
+        players.sort_by(|(_, _, _, a, _, _), (_, _, _, b, _, _)| a.total_cmp(b));
+        let mut rendered_enemies = 0;
+        let mut rendered_teammates = 0;
+        players.retain(|(_, player, _, _, _, _)| {
+            let (rendered, limit) = if player.pawn.team_id == self.local_team_id {
+                (&mut rendered_teammates, settings.esp_max_teammates)
+            } else {
+                (&mut rendered_enemies, settings.esp_max_enemies)
+            };
+
+            if limit > 0 && *rendered >= limit {
+                return false;
+            }
+
+            *rendered += 1;
+            true
+        });
+
+        if settings.esp_render_far_first {
+            /* draw the nearest players last so they are on top */
+            players.reverse();
+        } else {
+            players.sort_by_key(|(pawn_entity_index, _, _, _, _, _)| *pawn_entity_index);
+        }
+
+        let mut aiming_at_you_banner = false;
+        for (_, player, esp_settings, distance, ghost_alpha, fade_alpha) in players {
+            let esp_settings = &*esp_settings;
+            let entry = &player.pawn;
+
             let player_rel_health = (entry.player_health as f32 / 100.0).clamp(0.0, 1.0);
+            let player_rel_armor = (entry.player_armor as f32 / 100.0).clamp(0.0, 1.0);
 
-            let entry_model = states.resolve::<CS2Model>(entry.model_address)?;
-            let player_2d_box = view.calculate_box_2d(
-                &(entry_model.vhull_min + entry.position),
-                &(entry_model.vhull_max + entry.position),
-            );
+            /* configured sizes are relative to 1080p */
+            let box_width = esp_settings.box_width * ui_scale;
+            let health_bar_width = esp_settings.health_bar_width * ui_scale;
+            let armor_bar_width = esp_settings.armor_bar_width * ui_scale;
 
-            if esp_settings.skeleton {
-                let bones = entry_model.bones.iter().zip(entry.bone_states.iter());
+            let ghost_alpha = if player.ghost {
+                match Self::ghost_alpha(esp_settings, player) {
+                    Some(alpha) => Some(alpha),
+                    None => continue,
+                }
+            } else {
+                None
+            };
+            let fade_alpha = Self::distance_fade_alpha(esp_settings, distance);
+            if fade_alpha <= 0.0 {
+                continue;
+            }
 
-                for (bone, state) in bones {
-                    if (bone.flags & BoneFlags::FlagHitbox as u32) == 0 {
-                        continue;
+            let resolve_color = |color: &EspColor| -> [f32; 4] {
+                let mut color = match ghost_alpha {
+                    Some(alpha) => {
+                        let mut color = esp_settings.ghost_color.calculate_color(
+                            &settings.color_palette,
+                            player_rel_health,
+                            distance,
+                            time,
+                        );
+                        color[3] *= alpha;
+                        color
                     }
+                    None => color.calculate_color(
+                        &settings.color_palette,
+                        player_rel_health,
+                        distance,
+                        time,
+                    ),
+                };
+                color[3] *= fade_alpha;
+                color
+            };
 
-                    let parent_index = if let Some(parent) = bone.parent {
-                        parent
-                    } else {
-                        continue;
-                    };
+            let shooting = esp_settings.shot_indicator
+                && player
+                    .last_shot
+                    .map(|timestamp| {
+                        timestamp.elapsed().as_secs_f32() < esp_settings.shot_indicator_duration
+                    })
+                    .unwrap_or(false);
+            let low_health = esp_settings.low_health_threshold > 0
+                && entry.player_health <= esp_settings.low_health_threshold;
 
-                    let parent_position = match view
-                        .world_to_screen(&entry.bone_states[parent_index].position, true)
-                    {
-                        Some(position) => position,
-                        None => continue,
-                    };
-                    let bone_position = match view.world_to_screen(&state.position, true) {
-                        Some(position) => position,
-                        None => continue,
-                    };
+            let entry_model = states.resolve::<CS2Model>(entry.model_address)?;
+            let head_position = entry_model
+                .find_bone("head_0")
+                .and_then(|index| entry.bone_states.get(index))
+                .map(|bone| bone.position);
+
+            let aiming_at_you = esp_settings.aiming_at_you
+                && ghost_alpha.is_none()
+                && entry.team_id != self.local_team_id
+                && distance <= esp_settings.aiming_at_you_max_distance
+                && head_position
+                    .map(|head_position| {
+                        let target_direction = view_world_position - head_position;
+                        let angle = eye_direction(&entry.eye_angles)
+                            .angle(&target_direction)
+                            .to_degrees();
+
+                        angle <= esp_settings.aiming_at_you_fov
+                    })
+                    .unwrap_or(false);
+
+            let box_color = if shooting {
+                &esp_settings.shot_indicator_color
+            } else if aiming_at_you {
+                &esp_settings.aiming_at_you_color
+            } else if esp_settings.bomb_carrier_highlight && entry.player_has_bomb {
+                &esp_settings.bomb_carrier_box_color
+            } else if low_health {
+                &esp_settings.low_health_color
+            } else {
+                &esp_settings.box_color
+            };
+            let skeleton_color = if aiming_at_you {
+                &esp_settings.aiming_at_you_color
+            } else if low_health {
+                &esp_settings.low_health_color
+            } else {
+                &esp_settings.skeleton_color
+            };
+
+            let player_2d_box = match esp_settings.box_fit {
+                EspBoxFit::Hull => view.calculate_box_2d(
+                    &(entry_model.vhull_min + entry.position),
+                    &(entry_model.vhull_max + entry.position),
+                ),
+                EspBoxFit::Bones => {
+                    let bone_positions = entry_model
+                        .bones
+                        .iter()
+                        .zip(entry.bone_states.iter())
+                        .filter(|(bone, _)| (bone.flags & BoneFlags::FlagHitbox as u32) != 0)
+                        .map(|(_, state)| &state.position);
+
+                    view.calculate_points_box_2d(bone_positions)
+                        .map(|(vmin, vmax)| {
+                            let margin = nalgebra::Vector2::new(
+                                esp_settings.box_fit_margin,
+                                esp_settings.box_fit_margin,
+                            );
+                            (vmin - margin, vmax + margin)
+                        })
+                }
+            };
+
+            if esp_settings.box_fill
+                && matches!(
+                    esp_settings.box_type,
+                    EspBoxType::Box2D | EspBoxType::Box2DCorners
+                )
+            {
+                if let Some((vmin, vmax)) = &player_2d_box {
+                    let mut color = resolve_color(&esp_settings.box_fill_color);
+                    color[3] *= esp_settings.box_fill_alpha.clamp(0.0, 1.0);
+
+                    draw.add_rect([vmin.x, vmin.y], [vmax.x, vmax.y], color)
+                        .filled(true)
+                        .build();
+                }
+            }
+
+            if esp_settings.skeleton || esp_settings.skeleton_joints {
+                let mut skeleton_segments = self.skeleton_segments.borrow_mut();
+                if skeleton_segments.len() > SKELETON_SEGMENTS_CACHE_LIMIT {
+                    skeleton_segments.clear();
+                }
+
+                let selection_hash = SkeletonSegments::selection_hash(
+                    esp_settings.skeleton_mode,
+                    &esp_settings.skeleton_custom_bones,
+                );
+                let segments = skeleton_segments
+                    .entry((
+                        entry.model_address,
+                        esp_settings.skeleton_mode,
+                        selection_hash,
+                    ))
+                    .or_insert_with(|| {
+                        SkeletonSegments::resolve(
+                            &entry_model,
+                            esp_settings.skeleton_mode,
+                            &esp_settings.skeleton_custom_bones,
+                        )
+                    });
+
+                /* bones are shared between segments and joints, project each of them only once */
+                let bone_positions = entry
+                    .bone_states
+                    .iter()
+                    .map(|state| state.position)
+                    .collect::<Vec<_>>();
+                let screen_positions = view.project_batch(&bone_positions, true);
+                let screen_position = |index: usize| screen_positions.get(index).copied().flatten();
+
+                let lines = if esp_settings.skeleton {
+                    segments.segments.as_slice()
+                } else {
+                    &[]
+                };
+                for (bone_index, parent_index) in lines.iter().cloned() {
+                    let (bone_position, parent_position) =
+                        match (screen_position(bone_index), screen_position(parent_index)) {
+                            (Some(bone_position), Some(parent_position)) => {
+                                (bone_position, parent_position)
+                            }
+                            _ => continue,
+                        };
 
                     draw.add_line(
                         parent_position,
                         bone_position,
-                        esp_settings
-                            .skeleton_color
-                            .calculate_color(player_rel_health, distance),
+                        resolve_color(skeleton_color),
                     )
-                    .thickness(esp_settings.skeleton_width)
+                    .thickness(esp_settings.skeleton_width * ui_scale)
                     .build();
                 }
+
+                if esp_settings.skeleton_joints {
+                    let radius = esp_settings.skeleton_joints_radius
+                        * ui_scale
+                        * (SKELETON_JOINT_REFERENCE_DISTANCE / distance.max(0.1)).clamp(0.5, 2.0);
+                    let color = resolve_color(&esp_settings.skeleton_joints_color);
+
+                    for bone_index in segments.joints.iter() {
+                        let position = match screen_position(*bone_index) {
+                            Some(position) => position,
+                            None => continue,
+                        };
+
+                        draw.add_circle(position, radius, color)
+                            .filled(true)
+                            .build();
+                    }
+                }
+            }
+
+            if esp_settings.head_display == EspHeadDisplay::Hitbox3D {
+                let head_bone = entry_model.find_bone("head_0").and_then(|index| {
+                    entry
+                        .bone_states
+                        .get(index)
+                        .map(|state| (state, entry_model.find_hitbox(index)))
+                });
+
+                let color = resolve_color(&esp_settings.head_display_color);
+                match head_bone {
+                    Some((head_bone, Some(hitbox))) => {
+                        /* capsule hitboxes extend by their radius around the min/max points */
+                        let radius = nalgebra::Vector3::repeat(hitbox.radius.max(0.0));
+                        view.draw_box_3d_oriented(
+                            &draw,
+                            &head_bone.transform(),
+                            &(hitbox.min - radius),
+                            &(hitbox.max + radius),
+                            color.into(),
+                            esp_settings.head_display_width * ui_scale,
+                        );
+                    }
+                    Some((head_bone, None)) => {
+                        let center = view.world_to_screen(&head_bone.position, true);
+                        let edge = view.world_to_screen(
+                            &(head_bone.position + nalgebra::Vector3::z() * HEAD_DOT_RADIUS),
+                            true,
+                        );
+
+                        if let (Some(center), Some(edge)) = (center, edge) {
+                            let radius = (center.x - edge.x).hypot(center.y - edge.y).max(1.0);
+                            draw.add_circle([center.x, center.y], radius, color)
+                                .thickness(esp_settings.head_display_width * ui_scale)
+                                .build();
+                        }
+                    }
+                    None => {}
+                }
             }
 
             match esp_settings.box_type {
                 EspBoxType::Box2D => {
                     if let Some((vmin, vmax)) = &player_2d_box {
-                        draw.add_rect(
-                            [vmin.x, vmin.y],
-                            [vmax.x, vmax.y],
-                            esp_settings
-                                .box_color
-                                .calculate_color(player_rel_health, distance),
-                        )
-                        .thickness(esp_settings.box_width)
-                        .build();
+                        let color = resolve_color(box_color);
+                        if esp_settings.outline {
+                            draw.add_rect(
+                                [vmin.x, vmin.y],
+                                [vmax.x, vmax.y],
+                                [0.0, 0.0, 0.0, color[3]],
+                            )
+                            .thickness(box_width + 2.0)
+                            .build();
+                        }
+
+                        draw.add_rect([vmin.x, vmin.y], [vmax.x, vmax.y], color)
+                            .thickness(box_width)
+                            .build();
+                    }
+                }
+                EspBoxType::Box2DCorners => {
+                    if let Some((vmin, vmax)) = &player_2d_box {
+                        let ratio = esp_settings.box_corner_length_ratio.clamp(0.05, 0.5);
+                        let corner_width = (vmax.x - vmin.x) * ratio;
+                        let corner_height = (vmax.y - vmin.y) * ratio;
+                        let color = resolve_color(box_color);
+
+                        let corners = [
+                            ([vmin.x, vmin.y], 1.0, 1.0),
+                            ([vmax.x, vmin.y], -1.0, 1.0),
+                            ([vmin.x, vmax.y], 1.0, -1.0),
+                            ([vmax.x, vmax.y], -1.0, -1.0),
+                        ];
+
+                        let mut passes = Vec::with_capacity(2);
+                        if esp_settings.outline {
+                            passes.push(([0.0, 0.0, 0.0, color[3]], box_width + 2.0));
+                        }
+                        passes.push((color, box_width));
+
+                        for (color, thickness) in passes {
+                            for (corner, dir_x, dir_y) in corners {
+                                draw.add_line(
+                                    corner,
+                                    [corner[0] + corner_width * dir_x, corner[1]],
+                                    color,
+                                )
+                                .thickness(thickness)
+                                .build();
+                                draw.add_line(
+                                    corner,
+                                    [corner[0], corner[1] + corner_height * dir_y],
+                                    color,
+                                )
+                                .thickness(thickness)
+                                .build();
+                            }
+                        }
                     }
                 }
                 EspBoxType::Box3D => {
+                    if esp_settings.box_fill {
+                        let mut color = resolve_color(&esp_settings.box_fill_color);
+                        color[3] *= esp_settings.box_fill_alpha.clamp(0.0, 1.0);
+
+                        view.fill_box_3d(
+                            &draw,
+                            &(entry_model.vhull_min + entry.position),
+                            &(entry_model.vhull_max + entry.position),
+                            color.into(),
+                        );
+                    }
+
                     view.draw_box_3d(
                         &draw,
                         &(entry_model.vhull_min + entry.position),
                         &(entry_model.vhull_max + entry.position),
-                        esp_settings
-                            .box_color
-                            .calculate_color(player_rel_health, distance)
-                            .into(),
-                        esp_settings.box_width,
+                        resolve_color(box_color).into(),
+                        box_width,
                     );
                 }
                 EspBoxType::None => {}
             }
 
-            if let Some((vmin, vmax)) = &player_2d_box {
-                let box_bounds = match esp_settings.health_bar {
-                    EspHealthBar::None => None,
-                    EspHealthBar::Left => {
-                        let xoffset =
-                            vmin.x - esp_settings.box_width / 2.0 - esp_settings.health_bar_width;
-
-                        Some([
-                            xoffset,
-                            vmin.y - esp_settings.box_width / 2.0,
-                            esp_settings.health_bar_width,
-                            vmax.y - vmin.y + esp_settings.box_width,
-                        ])
-                    }
-                    EspHealthBar::Right => {
-                        let xoffset = vmax.x + esp_settings.box_width / 2.0;
-
-                        Some([
-                            xoffset,
-                            vmin.y - esp_settings.box_width / 2.0,
-                            esp_settings.health_bar_width,
-                            vmax.y - vmin.y + esp_settings.box_width,
-                        ])
-                    }
-                    EspHealthBar::Top => {
-                        let yoffset =
-                            vmin.y - esp_settings.box_width / 2.0 - esp_settings.health_bar_width;
-
-                        Some([
-                            vmin.x - esp_settings.box_width / 2.0,
-                            yoffset,
-                            vmax.x - vmin.x + esp_settings.box_width,
-                            esp_settings.health_bar_width,
-                        ])
-                    }
-                    EspHealthBar::Bottom => {
-                        let yoffset = vmax.y + esp_settings.box_width / 2.0;
-
-                        Some([
-                            vmin.x - esp_settings.box_width / 2.0,
-                            yoffset,
-                            vmax.x - vmin.x + esp_settings.box_width,
-                            esp_settings.health_bar_width,
-                        ])
-                    }
-                };
+            if player.ghost {
+                /* ghosts only show the box and skeleton at the last known position */
+                continue;
+            }
 
-                if let Some([mut box_x, mut box_y, mut box_width, mut box_height]) = box_bounds {
-                    const BORDER_WIDTH: f32 = 1.0;
-                    draw.add_rect(
-                        [box_x + BORDER_WIDTH / 2.0, box_y + BORDER_WIDTH / 2.0],
-                        [
-                            box_x + box_width - BORDER_WIDTH / 2.0,
-                            box_y + box_height - BORDER_WIDTH / 2.0,
-                        ],
-                        [0.0, 0.0, 0.0, 1.0],
+            if esp_settings.offscreen_arrows
+                && distance <= esp_settings.offscreen_arrows_max_distance
+                && view.world_to_screen(&entry.position, false).is_none()
+            {
+                if let Some((position, angle)) = view.world_to_screen_clamped(
+                    &entry.position,
+                    esp_settings.offscreen_arrows_edge_distance * ui_scale,
+                ) {
+                    let size = esp_settings.offscreen_arrows_size * ui_scale;
+                    let direction = [angle.cos(), angle.sin()];
+                    let normal = [-direction[1], direction[0]];
+
+                    let tip = [
+                        position.x + direction[0] * size,
+                        position.y + direction[1] * size,
+                    ];
+                    let base_left = [
+                        position.x - direction[0] * size * 0.5 + normal[0] * size * 0.6,
+                        position.y - direction[1] * size * 0.5 + normal[1] * size * 0.6,
+                    ];
+                    let base_right = [
+                        position.x - direction[0] * size * 0.5 - normal[0] * size * 0.6,
+                        position.y - direction[1] * size * 0.5 - normal[1] * size * 0.6,
+                    ];
+
+                    draw.add_triangle(
+                        tip,
+                        base_left,
+                        base_right,
+                        resolve_color(&esp_settings.offscreen_arrows_color),
                     )
-                    .filled(false)
-                    .thickness(BORDER_WIDTH)
+                    .filled(true)
                     .build();
+                }
+            }
 
-                    box_x += BORDER_WIDTH / 2.0 + 1.0;
-                    box_y += BORDER_WIDTH / 2.0 + 1.0;
+            if esp_settings.trail {
+                let trail_points = player
+                    .trail
+                    .iter()
+                    .filter_map(|(timestamp, position)| {
+                        let age = timestamp.elapsed().as_secs_f32();
+                        if age > esp_settings.trail_duration {
+                            return None;
+                        }
+
+                        Some((age, view.world_to_screen(position, true)?))
+                    })
+                    .collect::<Vec<_>>();
+
+                for segment in trail_points.windows(2) {
+                    let [(age, start), (_, end)] = segment else {
+                        continue;
+                    };
 
-                    box_width -= BORDER_WIDTH + 2.0;
-                    box_height -= BORDER_WIDTH + 2.0;
+                    let mut color = resolve_color(&esp_settings.trail_color);
+                    color[3] *= 1.0 - (age / esp_settings.trail_duration).clamp(0.0, 1.0);
 
-                    if box_width < box_height {
-                        /* vertical */
-                        let yoffset = box_y + (1.0 - player_rel_health) * box_height;
-                        draw.add_rect(
-                            [box_x, box_y],
-                            [box_x + box_width, yoffset],
-                            [1.0, 0.0, 0.0, 1.0],
-                        )
-                        .filled(true)
+                    draw.add_line(*start, *end, color)
+                        .thickness(esp_settings.trail_width * ui_scale)
                         .build();
+                }
+            }
 
-                        draw.add_rect(
-                            [box_x, yoffset],
-                            [box_x + box_width, box_y + box_height],
-                            [0.0, 1.0, 0.0, 1.0],
+            if esp_settings.view_direction_line {
+                if let Some(head_position) = head_position {
+                    let line_end = head_position
+                        + eye_direction(&entry.eye_angles)
+                            * (esp_settings.view_direction_line_length / UNITS_TO_METERS);
+                    if let (Some(start), Some(end)) = (
+                        view.world_to_screen(&head_position, true),
+                        view.world_to_screen(&line_end, true),
+                    ) {
+                        draw.add_line(
+                            start,
+                            end,
+                            resolve_color(&esp_settings.view_direction_line_color),
                         )
-                        .filled(true)
+                        .thickness(esp_settings.view_direction_line_width * ui_scale)
                         .build();
+                    }
+                }
+            }
+
+            if let Some((vmin, vmax)) = &player_2d_box {
+                let health_bar_side = BarSide::from_health_bar(esp_settings.health_bar);
+                if let Some(side) = health_bar_side {
+                    let bounds =
+                        side.calculate_bounds(vmin, vmax, box_width, health_bar_width, 0.0);
+                    draw_bar(
+                        &draw,
+                        bounds,
+                        player_rel_health,
+                        [0.0, 1.0, 0.0, fade_alpha],
+                        [1.0, 0.0, 0.0, fade_alpha],
+                    );
+                }
+
+                if let Some(side) = BarSide::from_armor_bar(esp_settings.armor_bar) {
+                    /* stack the armor bar on top of the health bar if both are on the same side */
+                    let offset = if health_bar_side == Some(side) {
+                        health_bar_width
                     } else {
-                        /* horizontal */
-                        let xoffset = box_x + (1.0 - player_rel_health) * box_width;
-                        draw.add_rect(
-                            [box_x, box_y],
-                            [xoffset, box_y + box_height],
-                            [1.0, 0.0, 0.0, 1.0],
-                        )
-                        .filled(true)
-                        .build();
+                        0.0
+                    };
 
-                        draw.add_rect(
-                            [xoffset, box_y],
-                            [box_x + box_width, box_y + box_height],
-                            [0.0, 1.0, 0.0, 1.0],
-                        )
-                        .filled(true)
-                        .build();
+                    let bounds =
+                        side.calculate_bounds(vmin, vmax, box_width, armor_bar_width, offset);
+                    draw_bar(
+                        &draw,
+                        bounds,
+                        player_rel_armor,
+                        [0.0, 0.5, 1.0, fade_alpha],
+                        [0.2, 0.2, 0.2, fade_alpha],
+                    );
+                }
+
+                if esp_settings.flash_bar
+                    && entry.player_flashtime > 0.0
+                    && player.flash_duration > 0.0
+                {
+                    /* place the flash bar above all other bars at the top */
+                    let mut offset = 0.0;
+                    if health_bar_side == Some(BarSide::Top) {
+                        offset += health_bar_width;
+                    }
+                    if BarSide::from_armor_bar(esp_settings.armor_bar) == Some(BarSide::Top) {
+                        offset += armor_bar_width;
                     }
+
+                    let bounds = BarSide::Top.calculate_bounds(
+                        vmin,
+                        vmax,
+                        box_width,
+                        esp_settings.flash_bar_height * ui_scale,
+                        offset,
+                    );
+                    draw_bar(
+                        &draw,
+                        bounds,
+                        (entry.player_flashtime / player.flash_duration).clamp(0.0, 1.0),
+                        resolve_color(&esp_settings.flash_bar_color),
+                        [0.2, 0.2, 0.2, fade_alpha],
+                    );
                 }
             }
 
@@ -430,99 +1437,268 @@ impl Enhancement for PlayerESP {
                 let mut player_info = PlayerInfoLayout::new(
                     ui,
                     &draw,
+                    view.screen_offset,
                     view.screen_bounds,
                     vmin,
                     vmax,
-                    esp_settings.box_type == EspBoxType::Box2D,
+                    esp_settings.info_anchor,
+                    matches!(
+                        esp_settings.box_type,
+                        EspBoxType::Box2D | EspBoxType::Box2DCorners
+                    ),
+                    esp_settings.outline,
+                    esp_settings.info_text_scale,
+                    ui_scale,
                 );
 
-                if esp_settings.info_name {
-                    player_info.add_line(
-                        esp_settings
-                            .info_name_color
-                            .calculate_color(player_rel_health, distance),
-                        &entry.player_name,
-                    );
-                }
-
-                if esp_settings.info_weapon {
-                    let text = entry.weapon.display_name();
-                    player_info.add_line(
-                        esp_settings
-                            .info_weapon_color
-                            .calculate_color(player_rel_health, distance),
-                        &text,
-                    );
-                }
-
-                if esp_settings.info_hp_text {
-                    let text = format!("{} HP", entry.player_health);
-                    player_info.add_line(
-                        esp_settings
-                            .info_hp_text_color
-                            .calculate_color(player_rel_health, distance),
-                        &text,
-                    );
-                }
-
-                let mut player_flags = Vec::new();
-                if esp_settings.info_flag_kit && entry.player_has_defuser {
-                    player_flags.push("Kit");
-                }
-
-                if esp_settings.info_flag_flashed && entry.player_flashtime > 0.0 {
-                    player_flags.push("flashed");
-                }
-
-                if !player_flags.is_empty() {
-                    player_info.add_line(
-                        esp_settings
-                            .info_flags_color
-                            .calculate_color(player_rel_health, distance),
-                        &player_flags.join(", "),
-                    );
-                }
-                if esp_settings.info_distance {
-                    let text = format!("{:.0}m", distance);
-                    player_info.add_line(
-                        esp_settings
-                            .info_distance_color
-                            .calculate_color(player_rel_health, distance),
-                        &text,
-                    );
+                for info_line in esp_settings.info_line_order.iter() {
+                    match info_line {
+                        EspInfoLine::Name => {
+                            if esp_settings.info_name {
+                                let text = if esp_settings.info_show_clan_tag
+                                    && !entry.player_clan_tag.is_empty()
+                                {
+                                    format!("[{}] {}", entry.player_clan_tag, entry.player_name)
+                                } else {
+                                    entry.player_name.clone()
+                                };
+
+                                player_info
+                                    .add_line(resolve_color(&esp_settings.info_name_color), &text);
+                            }
+                        }
+                        EspInfoLine::Rank => {
+                            if esp_settings.info_rank {
+                                let rank = player
+                                    .controller
+                                    .as_ref()
+                                    .and_then(|controller| controller.player_rank.display_name());
+
+                                if let Some(rank) = rank {
+                                    player_info.add_line(
+                                        resolve_color(&esp_settings.info_rank_color),
+                                        &rank,
+                                    );
+                                }
+                            }
+                        }
+                        EspInfoLine::KillsDeaths => {
+                            if esp_settings.info_kd {
+                                if let Some(stats) = &player.match_stats {
+                                    let text = format!("{} / {}", stats.kills, stats.deaths);
+                                    player_info.add_line(
+                                        resolve_color(&esp_settings.info_kd_color),
+                                        &text,
+                                    );
+                                }
+                            }
+                        }
+                        EspInfoLine::Weapon => {
+                            if esp_settings.info_weapon {
+                                let weapon_icon = match esp_settings.info_weapon_style {
+                                    EspWeaponStyle::Icon => {
+                                        fonts.weapon_icons.zip(weapon_icon_glyph(entry.weapon))
+                                    }
+                                    EspWeaponStyle::Text => None,
+                                };
+
+                                if let Some((font, glyph)) = weapon_icon {
+                                    player_info.add_line_with_font(
+                                        Some(font),
+                                        resolve_color(&esp_settings.info_weapon_color),
+                                        &glyph.to_string(),
+                                    );
+                                } else {
+                                    let text = entry.weapon.display_name();
+                                    player_info.add_line(
+                                        resolve_color(&esp_settings.info_weapon_color),
+                                        &text,
+                                    );
+                                }
+                            }
+                        }
+                        EspInfoLine::Ammo => {
+                            if esp_settings.info_ammo {
+                                if let Some(ammo) = &entry.weapon_ammo {
+                                    let text = format!("{} / {}", ammo.clip, ammo.reserve);
+                                    player_info.add_line(
+                                        resolve_color(&esp_settings.info_ammo_color),
+                                        &text,
+                                    );
+                                }
+                            }
+                        }
+                        EspInfoLine::Health => {
+                            if esp_settings.info_hp_text {
+                                let text = format!("{} HP", entry.player_health);
+                                let mut color = resolve_color(&esp_settings.info_hp_text_color);
+                                if low_health && esp_settings.low_health_blink {
+                                    let phase = (time * std::f32::consts::TAU * 2.0).sin();
+                                    color[3] *= 0.6 + phase * 0.4;
+                                }
+
+                                player_info.add_line(color, &text);
+                            }
+                        }
+                        EspInfoLine::Money => {
+                            if esp_settings.info_money {
+                                let player_money = player
+                                    .controller
+                                    .as_ref()
+                                    .and_then(|controller| controller.player_money);
+
+                                if let Some(player_money) = player_money {
+                                    let text = format!("${}", player_money);
+                                    player_info.add_line(
+                                        resolve_color(&esp_settings.info_money_color),
+                                        &text,
+                                    );
+                                }
+                            }
+                        }
+                        EspInfoLine::Speed => {
+                            if esp_settings.info_speed {
+                                let speed = entry.velocity.xy().norm();
+                                let text = match esp_settings.info_speed_unit {
+                                    EspSpeedUnit::UnitsPerSecond => format!("{:.0} u/s", speed),
+                                    EspSpeedUnit::KilometersPerHour => {
+                                        format!("{:.0} km/h", speed * UNITS_TO_METERS * 3.6)
+                                    }
+                                };
+
+                                player_info
+                                    .add_line(resolve_color(&esp_settings.info_speed_color), &text);
+                            }
+                        }
+                        EspInfoLine::Ping => {
+                            if esp_settings.info_ping {
+                                let player_ping = player
+                                    .controller
+                                    .as_ref()
+                                    .map(|controller| controller.player_ping)
+                                    .unwrap_or(0);
+
+                                /* bots do not have any ping */
+                                if player_ping > 0 {
+                                    let text = format!("{}ms", player_ping);
+                                    player_info.add_line(
+                                        resolve_color(&esp_settings.info_ping_color),
+                                        &text,
+                                    );
+                                }
+                            }
+                        }
+                        EspInfoLine::Flags => {
+                            let mut player_flags = Vec::new();
+                            if esp_settings.info_flag_kit && entry.player_has_defuser {
+                                player_flags.push("Kit");
+                            }
+
+                            if esp_settings.info_flag_flashed && entry.player_flashtime > 0.0 {
+                                player_flags.push("flashed");
+                            }
+
+                            if esp_settings.info_flag_scoped && entry.player_scoped {
+                                player_flags.push("scoped");
+                            }
+
+                            if esp_settings.info_flag_defusing && entry.player_defusing {
+                                player_flags.push("defusing");
+                            }
+
+                            if esp_settings.info_flag_planting && entry.player_planting {
+                                player_flags.push("planting");
+                            }
+
+                            if esp_settings.info_flag_reloading && entry.weapon_reloading {
+                                player_flags.push("reloading");
+                            }
+
+                            if esp_settings.info_flag_airborne
+                                && (entry.player_flags_raw & PLAYER_FLAG_ON_GROUND) == 0
+                            {
+                                player_flags.push("airborne");
+                            }
+
+                            if esp_settings.info_flag_crouched
+                                && (entry.player_flags_raw & PLAYER_FLAG_DUCKING) != 0
+                            {
+                                player_flags.push("crouched");
+                            }
+
+                            if esp_settings.info_flag_bomb && entry.player_has_bomb {
+                                player_flags.push("C4");
+                            }
+
+                            let is_bot = player
+                                .controller
+                                .as_ref()
+                                .map(|controller| controller.is_bot)
+                                .unwrap_or(false);
+                            if esp_settings.filter_bots == EspBotFilter::TagOnly && is_bot {
+                                player_flags.push("BOT");
+                            }
+
+                            if !player_flags.is_empty() {
+                                player_info.add_line(
+                                    resolve_color(&esp_settings.info_flags_color),
+                                    &player_flags.join(", "),
+                                );
+                            }
+                        }
+                        EspInfoLine::Distance => {
+                            if esp_settings.info_distance {
+                                let text = format!("{:.0}m", distance);
+                                player_info.add_line(
+                                    resolve_color(&esp_settings.info_distance_color),
+                                    &text,
+                                );
+                            }
+                        }
+                    }
                 }
             }
 
             if let Some(pos) = view.world_to_screen(&entry.position, false) {
                 let tracer_origin = match esp_settings.tracer_lines {
-                    EspTracePosition::TopLeft => Some([0.0, 0.0]),
-                    EspTracePosition::TopCenter => Some([view.screen_bounds.x / 2.0, 0.0]),
-                    EspTracePosition::TopRight => Some([view.screen_bounds.x, 0.0]),
-                    EspTracePosition::Center => {
-                        Some([view.screen_bounds.x / 2.0, view.screen_bounds.y / 2.0])
-                    }
-                    EspTracePosition::BottomLeft => Some([0.0, view.screen_bounds.y]),
-                    EspTracePosition::BottomCenter => {
-                        Some([view.screen_bounds.x / 2.0, view.screen_bounds.y])
-                    }
-                    EspTracePosition::BottomRight => {
-                        Some([view.screen_bounds.x, view.screen_bounds.y])
-                    }
+                    EspTracePosition::TopLeft => Some(view.screen_position(0.0, 0.0)),
+                    EspTracePosition::TopCenter => Some(view.screen_position(0.5, 0.0)),
+                    EspTracePosition::TopRight => Some(view.screen_position(1.0, 0.0)),
+                    EspTracePosition::Center => Some(view.screen_position(0.5, 0.5)),
+                    EspTracePosition::BottomLeft => Some(view.screen_position(0.0, 1.0)),
+                    EspTracePosition::BottomCenter => Some(view.screen_position(0.5, 1.0)),
+                    EspTracePosition::BottomRight => Some(view.screen_position(1.0, 1.0)),
                     EspTracePosition::None => None,
                 };
 
                 if let Some(origin) = tracer_origin {
-                    draw.add_line(
-                        origin,
-                        pos,
-                        esp_settings
-                            .tracer_lines_color
-                            .calculate_color(player_rel_health, distance),
-                    )
-                    .thickness(esp_settings.tracer_lines_width)
-                    .build();
+                    draw.add_line(origin, pos, resolve_color(&esp_settings.tracer_lines_color))
+                        .thickness(esp_settings.tracer_lines_width * ui_scale)
+                        .build();
                 }
             }
+
+            aiming_at_you_banner |= aiming_at_you && esp_settings.aiming_at_you_banner;
+        }
+
+        if aiming_at_you_banner {
+            let text = obfstr!("Enemy is aiming at you").to_string();
+            let text_size = ui.calc_text_size(&text);
+            let [center_x, top] = view.screen_position(0.5, 0.1);
+            let position = [center_x - text_size[0] / 2.0, top];
+
+            draw.add_rect(
+                [position[0] - 8.0, position[1] - 4.0],
+                [
+                    position[0] + text_size[0] + 8.0,
+                    position[1] + text_size[1] + 4.0,
+                ],
+                [0.8, 0.0, 0.0, 0.6],
+            )
+            .filled(true)
+            .rounding(4.0)
+            .build();
+            draw.add_text(position, [1.0, 1.0, 1.0, 1.0], &text);
         }
 
         Ok(())
@@ -0,0 +1,190 @@
+use cs2::{
+    EntitySystem,
+    PlayerPawnList,
+    PlayerPawnState,
+};
+
+use super::{
+    get_local_pawn_info,
+    Enhancement,
+};
+use crate::{
+    settings::AppSettings,
+    view::ViewController,
+};
+
+const UNITS_TO_METERS: f32 = 0.01905;
+
+/// Offset of the HUD element below the screen center in pixels
+const HUD_OFFSET_Y: f32 = 120.0;
+
+const ARROW_LENGTH: f32 = 14.0;
+const ARROW_WIDTH: f32 = 10.0;
+
+/// Pulses per second while an enemy is within the alert radius
+const PULSE_FREQUENCY: f32 = 2.0;
+const PULSE_RADIUS: f32 = 24.0;
+
+const COLOR_ALERT: [f32; 4] = [1.0, 0.2, 0.2, 1.0];
+
+struct NearestEnemy {
+    position: nalgebra::Vector3<f32>,
+
+    /// Distance to the local player in meters
+    distance: f32,
+}
+
+/// HUD element with the distance and direction to the nearest alive enemy
+pub struct ProximityAlert {
+    nearest_enemy: Option<NearestEnemy>,
+    local_position: nalgebra::Vector3<f32>,
+}
+
+impl ProximityAlert {
+    pub fn new() -> Self {
+        Self {
+            nearest_enemy: None,
+            local_position: Default::default(),
+        }
+    }
+}
+
+impl Enhancement for ProximityAlert {
+    fn update(&mut self, ctx: &crate::UpdateContext) -> anyhow::Result<()> {
+        self.nearest_enemy = None;
+
+        let settings = ctx.states.resolve::<AppSettings>(())?;
+        if !settings.proximity_alert {
+            return Ok(());
+        }
+
+        let entities = ctx.states.resolve::<EntitySystem>(())?;
+        let local_player_controller = entities.get_local_player_controller()?;
+        if local_player_controller.is_null()? {
+            return Ok(());
+        }
+
+        let local_team_id = local_player_controller
+            .reference_schema()?
+            .m_iPendingTeamNum()?;
+        let local_pawn = match get_local_pawn_info(ctx.states)? {
+            Some(local_pawn) => local_pawn,
+            None => return Ok(()),
+        };
+        self.local_position = local_pawn.position;
+
+        let pawn_list = ctx.states.resolve::<PlayerPawnList>(())?;
+        for pawn_entity_index in pawn_list.pawns.iter().copied() {
+            let pawn_state = match ctx.states.resolve::<PlayerPawnState>(pawn_entity_index) {
+                Ok(state) => state,
+                Err(_) => continue,
+            };
+
+            let info = match &*pawn_state {
+                PlayerPawnState::Alive(info) => info,
+                _ => continue,
+            };
+
+            if info.team_id == local_team_id || info.player_health <= 0 {
+                continue;
+            }
+
+            let distance = (info.position - local_pawn.position).norm() * UNITS_TO_METERS;
+            if self
+                .nearest_enemy
+                .as_ref()
+                .map(|nearest| nearest.distance <= distance)
+                .unwrap_or(false)
+            {
+                continue;
+            }
+
+            self.nearest_enemy = Some(NearestEnemy {
+                position: info.position,
+                distance,
+            });
+        }
+
+        Ok(())
+    }
+
+    fn render(&self, states: &utils_state::StateRegistry, ui: &imgui::Ui) -> anyhow::Result<()> {
+        let settings = states.resolve::<AppSettings>(())?;
+        if !settings.proximity_alert {
+            return Ok(());
+        }
+
+        let nearest_enemy = match &self.nearest_enemy {
+            Some(nearest_enemy) => nearest_enemy,
+            None => return Ok(()),
+        };
+
+        let view = states.resolve::<ViewController>(())?;
+        let camera_yaw = match view.get_camera_yaw() {
+            Some(yaw) => yaw,
+            None => return Ok(()),
+        };
+
+        let delta = nearest_enemy.position - self.local_position;
+        let target_yaw = delta.y.atan2(delta.x).to_degrees();
+
+        /* positive yaw turns to the left while the screen angle rotates clockwise */
+        let screen_angle = -(target_yaw - camera_yaw).to_radians();
+        let direction = [screen_angle.sin(), -screen_angle.cos()];
+        let normal = [-direction[1], direction[0]];
+
+        let [display_width, display_height] = ui.io().display_size;
+        let center = [display_width / 2.0, display_height / 2.0 + HUD_OFFSET_Y];
+
+        let within_radius = nearest_enemy.distance <= settings.proximity_alert_radius;
+        let color = if within_radius {
+            COLOR_ALERT
+        } else {
+            settings.proximity_alert_color.as_f32()
+        };
+
+        let draw = ui.get_window_draw_list();
+        if within_radius && settings.proximity_alert_pulse {
+            let phase = (ui.time() as f32 * PULSE_FREQUENCY).fract();
+            let mut pulse_color = COLOR_ALERT;
+            pulse_color[3] *= 1.0 - phase;
+
+            draw.add_circle(center, ARROW_LENGTH + phase * PULSE_RADIUS, pulse_color)
+                .thickness(2.0)
+                .build();
+        }
+
+        let tip = [
+            center[0] + direction[0] * ARROW_LENGTH,
+            center[1] + direction[1] * ARROW_LENGTH,
+        ];
+        let base = [
+            center[0] - direction[0] * ARROW_LENGTH * 0.5,
+            center[1] - direction[1] * ARROW_LENGTH * 0.5,
+        ];
+        draw.add_triangle(
+            tip,
+            [
+                base[0] + normal[0] * ARROW_WIDTH,
+                base[1] + normal[1] * ARROW_WIDTH,
+            ],
+            [
+                base[0] - normal[0] * ARROW_WIDTH,
+                base[1] - normal[1] * ARROW_WIDTH,
+            ],
+            color,
+        )
+        .filled(true)
+        .build();
+
+        let text = format!("{:.0}m", nearest_enemy.distance);
+        let [text_width, _] = ui.calc_text_size(&text);
+        draw.add_text(
+            [center[0] - text_width / 2.0, center[1] + ARROW_LENGTH + 4.0],
+            color,
+            &text,
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,93 @@
+use cs2::EntitySystem;
+
+use super::Enhancement;
+use crate::{
+    settings::AppSettings,
+    view::ViewController,
+};
+
+/// Factor between the aim punch and the actual bullet deviation (weapon_recoil_scale)
+const RECOIL_SCALE: f32 = 2.0;
+
+/// Dot at the position where the bullets will land, taking the aim punch into account.
+pub struct RecoilCrosshair {
+    /// Aim punch (pitch, yaw) of the local pawn in degrees
+    punch_angle: Option<[f32; 2]>,
+    shots_fired: i32,
+}
+
+impl RecoilCrosshair {
+    pub fn new() -> Self {
+        Self {
+            punch_angle: None,
+            shots_fired: 0,
+        }
+    }
+}
+
+impl Enhancement for RecoilCrosshair {
+    fn update(&mut self, ctx: &crate::UpdateContext) -> anyhow::Result<()> {
+        self.punch_angle = None;
+
+        let settings = ctx.states.resolve::<AppSettings>(())?;
+        if !settings.recoil_crosshair {
+            return Ok(());
+        }
+
+        let entities = ctx.states.resolve::<EntitySystem>(())?;
+        let local_controller = entities.get_local_player_controller()?;
+        if local_controller.is_null()? {
+            return Ok(());
+        }
+
+        let local_controller = local_controller.reference_schema()?;
+        if !local_controller.m_bPawnIsAlive()? {
+            return Ok(());
+        }
+
+        let local_pawn = match entities.get_by_handle(&local_controller.m_hPlayerPawn()?)? {
+            Some(pawn) => pawn.entity()?.read_schema()?,
+            None => return Ok(()),
+        };
+
+        let [pitch, yaw, ..] = local_pawn.m_aimPunchAngle()?;
+        self.punch_angle = Some([pitch, yaw]);
+        self.shots_fired = local_pawn.m_iShotsFired()?;
+
+        Ok(())
+    }
+
+    fn render(&self, states: &utils_state::StateRegistry, ui: &imgui::Ui) -> anyhow::Result<()> {
+        let settings = states.resolve::<AppSettings>(())?;
+        if !settings.recoil_crosshair {
+            return Ok(());
+        }
+
+        let [pitch, yaw] = match self.punch_angle {
+            Some(punch_angle) => punch_angle,
+            None => return Ok(()),
+        };
+
+        if settings.recoil_crosshair_firing_only && self.shots_fired <= 0 {
+            return Ok(());
+        }
+
+        let view = states.resolve::<ViewController>(())?;
+        let delta = match view.angles_to_screen_delta(pitch * RECOIL_SCALE, yaw * RECOIL_SCALE) {
+            Some(delta) => delta,
+            None => return Ok(()),
+        };
+
+        let [center_x, center_y] = view.screen_position(0.5, 0.5);
+        ui.get_window_draw_list()
+            .add_circle(
+                [center_x + delta.x, center_y + delta.y],
+                settings.recoil_crosshair_size,
+                settings.recoil_crosshair_color.as_f32(),
+            )
+            .filled(true)
+            .build();
+
+        Ok(())
+    }
+}
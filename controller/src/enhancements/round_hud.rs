@@ -0,0 +1,122 @@
+use cs2::{
+    GameRules,
+    GameRulesState,
+};
+use obfstr::obfstr;
+
+use super::Enhancement;
+use crate::settings::{
+    AppSettings,
+    OverlayCorner,
+};
+
+/// Distance to the screen edges in pixels
+const SCREEN_MARGIN: f32 = 10.0;
+const TEXT_PADDING: [f32; 2] = [8.0, 4.0];
+
+fn format_time(seconds: f32) -> String {
+    let seconds = seconds.max(0.0).ceil() as u32;
+    format!("{}:{:0>2}", seconds / 60, seconds % 60)
+}
+
+/// Small HUD with the remaining round, freeze and buy time
+pub struct RoundHud {
+    game_rules: Option<GameRules>,
+}
+
+impl RoundHud {
+    pub fn new() -> Self {
+        Self { game_rules: None }
+    }
+}
+
+impl Enhancement for RoundHud {
+    fn update(&mut self, ctx: &crate::UpdateContext) -> anyhow::Result<()> {
+        self.game_rules = None;
+
+        let settings = ctx.states.resolve::<AppSettings>(())?;
+        if !settings.round_hud {
+            return Ok(());
+        }
+
+        let game_rules = ctx.states.resolve::<GameRulesState>(())?;
+        self.game_rules = game_rules.game_rules.clone();
+        Ok(())
+    }
+
+    fn render(&self, states: &utils_state::StateRegistry, ui: &imgui::Ui) -> anyhow::Result<()> {
+        let settings = states.resolve::<AppSettings>(())?;
+        if !settings.round_hud {
+            return Ok(());
+        }
+
+        let game_rules = match &self.game_rules {
+            Some(game_rules) => game_rules,
+            None => return Ok(()),
+        };
+
+        let mut elements = Vec::with_capacity(2);
+        if game_rules.warmup_period {
+            elements.push(obfstr!("WARMUP").to_string());
+        } else if game_rules.freeze_period {
+            elements.push(format!(
+                "{} {}",
+                obfstr!("FREEZE"),
+                format_time(game_rules.freeze_time_remaining())
+            ));
+        } else if game_rules.bomb_planted {
+            elements.push(obfstr!("BOMB PLANTED").to_string());
+        } else {
+            elements.push(format_time(game_rules.round_time_remaining()));
+        }
+
+        let buy_time_remaining = game_rules.buy_time_remaining();
+        if settings.round_hud_buy_time && !game_rules.warmup_period && buy_time_remaining > 0.0 {
+            elements.push(format!(
+                "{} {}",
+                obfstr!("BUY"),
+                format_time(buy_time_remaining)
+            ));
+        }
+
+        let text = elements.join(" | ");
+        let [text_width, text_height] = ui.calc_text_size(&text);
+        let bar_size = [
+            text_width + TEXT_PADDING[0] * 2.0,
+            text_height + TEXT_PADDING[1] * 2.0,
+        ];
+
+        let [display_width, display_height] = ui.io().display_size;
+        let bar_position = match settings.round_hud_position {
+            OverlayCorner::TopLeft => [SCREEN_MARGIN, SCREEN_MARGIN],
+            OverlayCorner::TopRight => [display_width - bar_size[0] - SCREEN_MARGIN, SCREEN_MARGIN],
+            OverlayCorner::BottomLeft => {
+                [SCREEN_MARGIN, display_height - bar_size[1] - SCREEN_MARGIN]
+            }
+            OverlayCorner::BottomRight => [
+                display_width - bar_size[0] - SCREEN_MARGIN,
+                display_height - bar_size[1] - SCREEN_MARGIN,
+            ],
+        };
+
+        let draw = ui.get_window_draw_list();
+        draw.add_rect(
+            bar_position,
+            [bar_position[0] + bar_size[0], bar_position[1] + bar_size[1]],
+            [0.0, 0.0, 0.0, 0.5],
+        )
+        .filled(true)
+        .rounding(3.0)
+        .build();
+        draw.add_text(
+            [
+                bar_position[0] + TEXT_PADDING[0],
+                bar_position[1] + TEXT_PADDING[1],
+            ],
+            settings.round_hud_color.as_f32(),
+            &text,
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,152 @@
+use cs2::{
+    GrenadeProjectileList,
+    SmokeVolume,
+};
+
+use super::Enhancement;
+use crate::{
+    settings::AppSettings,
+    view::ViewController,
+};
+
+/// Approximate radius of a smoke cloud in units
+const SMOKE_RADIUS: f32 = 144.0;
+
+const SPHERE_SEGMENTS: usize = 24;
+
+/// Elevation angles (in radians) of the horizontal rings of the smoke dome
+const SPHERE_RINGS: [f32; 3] = [0.0, 0.5, 1.0];
+
+/// Number of vertical half circles of the smoke dome
+const SPHERE_MERIDIANS: usize = 4;
+
+/// Wireframe dome and remaining time of active smoke clouds.
+pub struct SmokeESP {
+    /// Active smokes of the last update
+    smokes: Vec<SmokeVolume>,
+}
+
+impl SmokeESP {
+    pub fn new() -> Self {
+        Self {
+            smokes: Default::default(),
+        }
+    }
+
+    fn render_polyline(
+        view: &ViewController,
+        draw: &imgui::DrawListMut,
+        points: impl Iterator<Item = nalgebra::Vector3<f32>>,
+        color: [f32; 4],
+    ) {
+        let points = points
+            .map(|point| view.world_to_screen(&point, true))
+            .collect::<Vec<_>>();
+
+        for segment in points.windows(2) {
+            if let [Some(start), Some(end)] = segment {
+                draw.add_line(*start, *end, color).thickness(1.0).build();
+            }
+        }
+    }
+}
+
+impl Enhancement for SmokeESP {
+    fn update(&mut self, ctx: &crate::UpdateContext) -> anyhow::Result<()> {
+        /* the smoke entity will be removed once the smoke dissipated */
+        self.smokes.clear();
+
+        let settings = ctx.states.resolve::<AppSettings>(())?;
+        if !settings.smoke_esp {
+            return Ok(());
+        }
+
+        let projectile_list = ctx.states.resolve::<GrenadeProjectileList>(())?;
+        for entity_index in projectile_list.projectiles.iter() {
+            /* not a smoke grenade or the smoke has not been deployed yet */
+            let smoke = match ctx.states.resolve::<SmokeVolume>(*entity_index) {
+                Ok(smoke) => smoke,
+                Err(_) => continue,
+            };
+
+            if smoke.time_remaining <= 0.0 {
+                continue;
+            }
+
+            self.smokes.push(smoke.clone());
+        }
+
+        Ok(())
+    }
+
+    fn render(&self, states: &utils_state::StateRegistry, ui: &imgui::Ui) -> anyhow::Result<()> {
+        let settings = states.resolve::<AppSettings>(())?;
+        if !settings.smoke_esp {
+            return Ok(());
+        }
+
+        let view = states.resolve::<ViewController>(())?;
+        let draw = ui.get_window_draw_list();
+        let color = settings.smoke_esp_color.as_f32();
+        for smoke in self.smokes.iter() {
+            for elevation in SPHERE_RINGS {
+                let ring_radius = SMOKE_RADIUS * elevation.cos();
+                let ring_height = SMOKE_RADIUS * elevation.sin();
+                Self::render_polyline(
+                    &view,
+                    &draw,
+                    (0..=SPHERE_SEGMENTS).map(|segment| {
+                        let angle = segment as f32 / SPHERE_SEGMENTS as f32 * std::f32::consts::TAU;
+                        smoke.position
+                            + nalgebra::Vector3::new(
+                                angle.cos() * ring_radius,
+                                angle.sin() * ring_radius,
+                                ring_height,
+                            )
+                    }),
+                    color,
+                );
+            }
+
+            for meridian in 0..SPHERE_MERIDIANS {
+                let azimuth = meridian as f32 / SPHERE_MERIDIANS as f32 * std::f32::consts::PI;
+                Self::render_polyline(
+                    &view,
+                    &draw,
+                    (0..=SPHERE_SEGMENTS / 2).map(|segment| {
+                        let angle = segment as f32 / SPHERE_SEGMENTS as f32 * std::f32::consts::TAU;
+                        smoke.position
+                            + nalgebra::Vector3::new(
+                                angle.cos() * azimuth.cos() * SMOKE_RADIUS,
+                                angle.cos() * azimuth.sin() * SMOKE_RADIUS,
+                                angle.sin() * SMOKE_RADIUS,
+                            )
+                    }),
+                    color,
+                );
+            }
+
+            if !settings.smoke_esp_timer {
+                continue;
+            }
+
+            let position = match view.world_to_screen(&smoke.position, false) {
+                Some(position) => position,
+                None => continue,
+            };
+
+            let text = format!("{:.1}s", smoke.time_remaining);
+            let [text_width, text_height] = ui.calc_text_size(&text);
+            draw.add_text(
+                [
+                    position.x - text_width / 2.0,
+                    position.y - text_height / 2.0,
+                ],
+                color,
+                &text,
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,102 @@
+use cs2::{
+    EntitySystem,
+    PlayerPawnState,
+    WEAPON_FLAG_TYPE_SNIPER_RIFLE,
+};
+
+use super::Enhancement;
+use crate::{
+    settings::{
+        AppSettings,
+        SniperCrosshairStyle,
+    },
+    view::ViewController,
+};
+
+/// Crosshair for sniper rifles while not being scoped.
+/// The game does not render a crosshair for those weapons.
+pub struct SniperCrosshair;
+
+impl SniperCrosshair {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn show_crosshair(states: &utils_state::StateRegistry) -> anyhow::Result<bool> {
+        let entities = states.resolve::<EntitySystem>(())?;
+        let local_player_controller = entities.get_local_player_controller()?;
+        if local_player_controller.is_null()? {
+            return Ok(false);
+        }
+
+        let local_pawn_index = local_player_controller
+            .reference_schema()?
+            .m_hPlayerPawn()?
+            .get_entity_index();
+
+        let local_pawn = states.resolve::<PlayerPawnState>(local_pawn_index)?;
+        Ok(match &*local_pawn {
+            PlayerPawnState::Alive(info) => {
+                (info.weapon.flags() & WEAPON_FLAG_TYPE_SNIPER_RIFLE) > 0 && !info.player_scoped
+            }
+            _ => false,
+        })
+    }
+}
+
+impl Enhancement for SniperCrosshair {
+    fn update(&mut self, _ctx: &crate::UpdateContext) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    fn render(&self, states: &utils_state::StateRegistry, ui: &imgui::Ui) -> anyhow::Result<()> {
+        let settings = states.resolve::<AppSettings>(())?;
+        if !settings.sniper_crosshair {
+            return Ok(());
+        }
+
+        /* resolved every frame so the crosshair disappears instantly when scoping or switching weapons */
+        if !Self::show_crosshair(states)? {
+            return Ok(());
+        }
+
+        let view = states.resolve::<ViewController>(())?;
+        let center = view.screen_position(0.5, 0.5);
+
+        let draw = ui.get_window_draw_list();
+        let color = settings.sniper_crosshair_color.as_f32();
+        let size = settings.sniper_crosshair_size;
+        let gap = settings.sniper_crosshair_gap;
+        let thickness = settings.sniper_crosshair_thickness;
+
+        match settings.sniper_crosshair_style {
+            SniperCrosshairStyle::Cross => {
+                for [direction_x, direction_y] in [[1.0, 0.0], [-1.0, 0.0], [0.0, 1.0], [0.0, -1.0]]
+                {
+                    draw.add_line(
+                        [center[0] + direction_x * gap, center[1] + direction_y * gap],
+                        [
+                            center[0] + direction_x * (gap + size),
+                            center[1] + direction_y * (gap + size),
+                        ],
+                        color,
+                    )
+                    .thickness(thickness)
+                    .build();
+                }
+            }
+            SniperCrosshairStyle::Dot => {
+                draw.add_circle(center, size / 2.0, color)
+                    .filled(true)
+                    .build();
+            }
+            SniperCrosshairStyle::Circle => {
+                draw.add_circle(center, gap + size, color)
+                    .thickness(thickness)
+                    .build();
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,182 @@
+use std::{
+    collections::HashMap,
+    time::{
+        Duration,
+        Instant,
+    },
+};
+
+use cs2::{
+    CEntityIdentityEx,
+    ClassNameCache,
+    EntitySystem,
+    PlayerPawnState,
+    PLAYER_FLAG_ON_GROUND,
+};
+
+use super::Enhancement;
+use crate::{
+    settings::AppSettings,
+    view::ViewController,
+};
+
+/// Players moving faster then this (units per second) are audible
+const AUDIBLE_SPEED: f32 = 135.0;
+
+/// Interval in which a moving player emits a new sound ring
+const EMIT_INTERVAL: Duration = Duration::from_millis(400);
+
+/// Time until a sound ring has been faded out completely
+const RING_LIFETIME: Duration = Duration::from_millis(1000);
+
+/// Base radius of a sound ring in units
+const RING_RADIUS: f32 = 20.0;
+
+const UNITS_TO_METERS: f32 = 0.01905;
+
+struct SoundRing {
+    position: nalgebra::Vector3<f32>,
+    created: Instant,
+}
+
+/// Approximate the footstep sounds of enemies by their movement state
+/// and visualize them as expanding rings at their feet.
+pub struct SoundESP {
+    rings: Vec<SoundRing>,
+
+    /// Timestamp of the last emitted ring by pawn entity index
+    last_emitted: HashMap<u32, Instant>,
+}
+
+impl SoundESP {
+    pub fn new() -> Self {
+        Self {
+            rings: Default::default(),
+            last_emitted: Default::default(),
+        }
+    }
+}
+
+impl Enhancement for SoundESP {
+    fn update(&mut self, ctx: &crate::UpdateContext) -> anyhow::Result<()> {
+        self.rings
+            .retain(|ring| ring.created.elapsed() < RING_LIFETIME);
+
+        let settings = ctx.states.resolve::<AppSettings>(())?;
+        if !settings.sound_esp {
+            self.rings.clear();
+            self.last_emitted.clear();
+            return Ok(());
+        }
+
+        let entities = ctx.states.resolve::<EntitySystem>(())?;
+        let class_name_cache = ctx.states.resolve::<ClassNameCache>(())?;
+
+        let local_player_controller = entities.get_local_player_controller()?;
+        if local_player_controller.is_null()? {
+            self.last_emitted.clear();
+            return Ok(());
+        }
+        let local_team_id = local_player_controller
+            .reference_schema()?
+            .m_iPendingTeamNum()?;
+
+        let mut audible_pawns = Vec::new();
+        for entity_identity in entities.all_identities() {
+            let entity_class = class_name_cache.lookup(&entity_identity.entity_class_info()?)?;
+            if !entity_class
+                .map(|name| *name == "C_CSPlayerPawn")
+                .unwrap_or(false)
+            {
+                continue;
+            }
+
+            let pawn_entity_index = entity_identity.handle::<()>()?.get_entity_index();
+            let pawn_state = match ctx.states.resolve::<PlayerPawnState>(pawn_entity_index) {
+                Ok(state) => state,
+                Err(_) => continue,
+            };
+
+            let info = match &*pawn_state {
+                PlayerPawnState::Alive(info) => info,
+                _ => continue,
+            };
+
+            if info.team_id == local_team_id {
+                continue;
+            }
+
+            let on_ground = (info.player_flags_raw & PLAYER_FLAG_ON_GROUND) != 0;
+            if !on_ground || info.velocity.xy().norm() < AUDIBLE_SPEED {
+                continue;
+            }
+
+            audible_pawns.push(pawn_entity_index);
+            let emit = self
+                .last_emitted
+                .get(&pawn_entity_index)
+                .map(|timestamp| timestamp.elapsed() >= EMIT_INTERVAL)
+                .unwrap_or(true);
+
+            if emit {
+                self.last_emitted.insert(pawn_entity_index, Instant::now());
+                self.rings.push(SoundRing {
+                    position: info.position,
+                    created: Instant::now(),
+                });
+            }
+        }
+
+        /* forget pawns which are no longer moving, dead or disconnected */
+        self.last_emitted
+            .retain(|pawn_entity_index, _| audible_pawns.contains(pawn_entity_index));
+
+        Ok(())
+    }
+
+    fn render(&self, states: &utils_state::StateRegistry, ui: &imgui::Ui) -> anyhow::Result<()> {
+        let settings = states.resolve::<AppSettings>(())?;
+        if !settings.sound_esp {
+            return Ok(());
+        }
+
+        let view = states.resolve::<ViewController>(())?;
+        let view_world_position = match view.get_camera_world_position() {
+            Some(view_world_position) => view_world_position,
+            _ => return Ok(()),
+        };
+
+        let draw = ui.get_window_draw_list();
+        for ring in self.rings.iter() {
+            let distance = (ring.position - view_world_position).norm() * UNITS_TO_METERS;
+            if distance > settings.sound_esp_max_distance {
+                continue;
+            }
+
+            let progress =
+                (ring.created.elapsed().as_secs_f32() / RING_LIFETIME.as_secs_f32()).min(1.0);
+            let radius = RING_RADIUS * settings.sound_esp_radius_scale * (0.25 + progress * 0.75);
+
+            let mut color = settings.sound_esp_color.as_f32();
+            color[3] *= 1.0 - progress;
+
+            const SEGMENTS: usize = 24;
+            let points = (0..=SEGMENTS)
+                .map(|segment| {
+                    let angle = segment as f32 / SEGMENTS as f32 * std::f32::consts::TAU;
+                    ring.position
+                        + nalgebra::Vector3::new(angle.cos() * radius, angle.sin() * radius, 0.0)
+                })
+                .map(|point| view.world_to_screen(&point, true))
+                .collect::<Vec<_>>();
+
+            for segment in points.windows(2) {
+                if let [Some(start), Some(end)] = segment {
+                    draw.add_line(*start, *end, color).thickness(2.0).build();
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -1,15 +1,73 @@
+use std::cell::Cell;
+
 use cs2::{
     LocalCameraControllerTarget,
     SpectatorList,
 };
+use imgui::{
+    Condition,
+    MouseButton,
+    WindowFlags,
+};
+use obfstr::obfstr;
 
 use super::Enhancement;
-use crate::settings::AppSettings;
+use crate::{
+    settings::{
+        AppSettings,
+        SpectatorsListPosition,
+    },
+    utils::DrawOpacity,
+};
+
+/// Distance to the screen edges in percent of the screen size
+const SCREEN_MARGIN: f32 = 0.01;
+
+/// Initial window position for the custom position
+const CUSTOM_INITIAL_POSITION: [f32; 2] = [10.0, 300.0];
+
+pub struct SpectatorsListIndicator {
+    /// Window position of the last frame (only tracked for the custom position)
+    window_position: Cell<Option<[f32; 2]>>,
+
+    /// Window position which has been saved to the settings
+    saved_window_position: Option<[f32; 2]>,
+}
 
-pub struct SpectatorsListIndicator;
 impl SpectatorsListIndicator {
     pub fn new() -> Self {
-        Self
+        Self {
+            window_position: Default::default(),
+            saved_window_position: None,
+        }
+    }
+
+    /// Window position and pivot for the configured screen position.
+    /// Returns None for the custom position.
+    fn window_anchor(
+        position: SpectatorsListPosition,
+        display_size: [f32; 2],
+    ) -> Option<([f32; 2], [f32; 2])> {
+        let margin_x = display_size[0] * SCREEN_MARGIN;
+        let margin_y = display_size[1] * SCREEN_MARGIN;
+
+        let anchor = match position {
+            SpectatorsListPosition::Left => ([margin_x, display_size[1] * 0.5], [0.0, 0.5]),
+            SpectatorsListPosition::TopLeft => ([margin_x, margin_y], [0.0, 0.0]),
+            SpectatorsListPosition::TopRight => {
+                ([display_size[0] - margin_x, margin_y], [1.0, 0.0])
+            }
+            SpectatorsListPosition::BottomLeft => {
+                ([margin_x, display_size[1] - margin_y], [0.0, 1.0])
+            }
+            SpectatorsListPosition::BottomRight => (
+                [display_size[0] - margin_x, display_size[1] - margin_y],
+                [1.0, 1.0],
+            ),
+            SpectatorsListPosition::Custom => return None,
+        };
+
+        Some(anchor)
     }
 }
 
@@ -18,8 +76,41 @@ impl Enhancement for SpectatorsListIndicator {
         Ok(())
     }
 
+    fn update_settings(
+        &mut self,
+        ui: &imgui::Ui,
+        _settings: &mut AppSettings,
+    ) -> anyhow::Result<bool> {
+        let window_position = match self.window_position.get() {
+            Some(position) => position,
+            None => return Ok(false),
+        };
+
+        if self.saved_window_position.is_none() {
+            /* initial position, nothing has been moved */
+            self.saved_window_position = Some(window_position);
+            return Ok(false);
+        }
+
+        if self.saved_window_position == Some(window_position)
+            || ui.is_mouse_down(MouseButton::Left)
+        {
+            return Ok(false);
+        }
+
+        /* window has been moved, save the settings (including the imgui window positions) */
+        self.saved_window_position = Some(window_position);
+        Ok(true)
+    }
+
     fn render(&self, states: &utils_state::StateRegistry, ui: &imgui::Ui) -> anyhow::Result<()> {
         let settings = states.resolve::<AppSettings>(())?;
+        if !settings.spectators_list
+            || settings.spectators_list_position != SpectatorsListPosition::Custom
+        {
+            self.window_position.set(None);
+        }
+
         if !settings.spectators_list {
             return Ok(());
         }
@@ -30,23 +121,57 @@ impl Enhancement for SpectatorsListIndicator {
             None => return Ok(()),
         };
         let spectators = states.resolve::<SpectatorList>(target_entity_id)?;
+        if spectators.spectators.is_empty() {
+            return Ok(());
+        }
 
-        let group = ui.begin_group();
+        let anchor = Self::window_anchor(settings.spectators_list_position, ui.io().display_size);
+        let window = ui
+            .window(obfstr!("Spectators List"))
+            .bg_alpha(settings.spectators_list_background_alpha.clamp(0.0, 1.0));
 
-        let line_count = spectators.spectators.iter().count();
-        let text_height = ui.text_line_height_with_spacing() * line_count as f32;
+        let window = match anchor {
+            Some((position, pivot)) => window
+                .position(position, Condition::Always)
+                .position_pivot(pivot)
+                .flags(
+                    WindowFlags::NO_DECORATION
+                        | WindowFlags::NO_INPUTS
+                        | WindowFlags::ALWAYS_AUTO_RESIZE
+                        | WindowFlags::NO_SAVED_SETTINGS
+                        | WindowFlags::NO_FOCUS_ON_APPEARING
+                        | WindowFlags::NO_NAV,
+                ),
+            /* the window can be moved and its position is saved within the imgui settings */
+            None => window
+                .position(CUSTOM_INITIAL_POSITION, Condition::FirstUseEver)
+                .flags(
+                    WindowFlags::NO_DECORATION
+                        | WindowFlags::ALWAYS_AUTO_RESIZE
+                        | WindowFlags::NO_FOCUS_ON_APPEARING
+                        | WindowFlags::NO_NAV,
+                ),
+        };
 
-        let offset_x = ui.io().display_size[0] * 0.01;
-        let offset_y = (ui.io().display_size[1] - text_height) * 0.5;
-        let mut offset_y = offset_y;
+        window.build(|| {
+            let _opacity = DrawOpacity::window(ui, settings.overlay_opacity);
+            ui.text_disabled(format!(
+                "{} ({})",
+                obfstr!("Spectators"),
+                spectators.spectators.len()
+            ));
 
-        for spectator in &spectators.spectators {
-            ui.set_cursor_pos([offset_x, offset_y]);
-            ui.text(&spectator.spectator_name);
-            offset_y += ui.text_line_height_with_spacing();
-        }
+            for spectator in &spectators.spectators {
+                ui.text(&spectator.spectator_name);
+                ui.same_line();
+                ui.text_disabled(spectator.observer_mode.display_name());
+            }
+
+            if anchor.is_none() {
+                self.window_position.set(Some(ui.window_pos()));
+            }
+        });
 
-        group.end();
         Ok(())
     }
 }
@@ -1,10 +1,8 @@
 use std::time::Instant;
 
-use anyhow::Context;
-use cs2::EntitySystem;
-use cs2_schema_generated::{
-    cs2::client::C_CSPlayerPawn,
-    EntityHandle,
+use cs2::{
+    EntitySystem,
+    PlayerPawnState,
 };
 use obfstr::obfstr;
 use rand::{
@@ -14,26 +12,177 @@ use rand::{
 use utils_state::StateRegistry;
 use valthrun_kernel_interface::MouseState;
 
-use super::Enhancement;
+use super::{
+    get_local_pawn_info,
+    Enhancement,
+    EnhancementStatus,
+};
 use crate::{
-    settings::AppSettings,
+    settings::{
+        AppSettings,
+        TriggerBotHitboxFilter,
+    },
     view::{
+        HitboxGroup,
         KeyToggle,
         LocalCrosshair,
     },
     UpdateContext,
 };
 
+/// Check if the targeted hitbox group passes the hitbox filter.
+/// Unknown hitbox groups only pass if any hitbox is allowed.
+fn hitbox_filter_matches(filter: TriggerBotHitboxFilter, group: Option<HitboxGroup>) -> bool {
+    match filter {
+        TriggerBotHitboxFilter::Any => true,
+        TriggerBotHitboxFilter::Head => group == Some(HitboxGroup::Head),
+        TriggerBotHitboxFilter::HeadChest => {
+            matches!(group, Some(HitboxGroup::Head | HitboxGroup::Chest))
+        }
+    }
+}
+
+/// Velocity (in units per second) below the max velocity at which the trigger bot resumes
+const VELOCITY_HYSTERESIS: f32 = 10.0;
+
+/// Factor between the aim punch and the actual bullet deviation (weapon_recoil_scale)
+const RECOIL_SCALE: f32 = 2.0;
+
+/// Sample a random value within the given range (in any order)
+fn sample_range(a: u32, b: u32) -> u32 {
+    let (min, max) = (a.min(b), a.max(b));
+    if min == max {
+        min
+    } else {
+        Uniform::new_inclusive(min, max).sample(&mut rand::thread_rng())
+    }
+}
+
+/// Timing settings of the trigger bot in milliseconds
+#[derive(Debug, Clone, Copy)]
+struct TriggerTimings {
+    delay_min: u32,
+    delay_max: u32,
+
+    /// Max additional random delay for every shot
+    jitter: u32,
+
+    /// Duration of a single shot. Zero means holding until the target is lost.
+    shot_duration_min: u32,
+    shot_duration_max: u32,
+}
+
+impl TriggerTimings {
+    fn from_settings(settings: &AppSettings) -> Self {
+        Self {
+            delay_min: settings.trigger_delay_min_ms,
+            delay_max: settings.trigger_delay_max_ms,
+            jitter: settings.trigger_bot_jitter,
+            shot_duration_min: settings.trigger_bot_shot_duration_min,
+            shot_duration_max: settings.trigger_bot_shot_duration_max,
+        }
+    }
+
+    fn sample_reaction_delay(&self) -> u32 {
+        sample_range(self.delay_min, self.delay_max) + sample_range(0, self.jitter)
+    }
+
+    fn sample_shot_delay(&self) -> u32 {
+        sample_range(0, self.jitter)
+    }
+
+    fn sample_shot_duration(&self) -> u32 {
+        sample_range(self.shot_duration_min, self.shot_duration_max)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 enum TriggerState {
     Idle,
-    Pending { delay: u32, timestamp: Instant },
-    Active,
+    Pending {
+        target: u32,
+        delay: u32,
+        timestamp: Instant,
+    },
+    Active {
+        target: u32,
+        duration: u32,
+        timestamp: Instant,
+    },
+}
+
+impl TriggerState {
+    /// Calculate the next state for the current crosshair target.
+    /// Returns None if the state does not change.
+    fn transition(
+        &self,
+        target: Option<u32>,
+        now: Instant,
+        timings: &TriggerTimings,
+    ) -> Option<TriggerState> {
+        let pending = |target: u32, delay: u32| TriggerState::Pending {
+            target,
+            delay,
+            timestamp: now,
+        };
+
+        match self {
+            TriggerState::Idle => {
+                target.map(|target| pending(target, timings.sample_reaction_delay()))
+            }
+            TriggerState::Pending {
+                target: pending_target,
+                delay,
+                timestamp,
+            } => {
+                if target != Some(*pending_target) {
+                    /* target left the crosshair (or died) before we fired */
+                    return Some(match target {
+                        Some(target) => pending(target, timings.sample_reaction_delay()),
+                        None => TriggerState::Idle,
+                    });
+                }
+
+                if now.duration_since(*timestamp).as_millis() < *delay as u128 {
+                    /* still waiting to be activated */
+                    return None;
+                }
+
+                Some(TriggerState::Active {
+                    target: *pending_target,
+                    duration: timings.sample_shot_duration(),
+                    timestamp: now,
+                })
+            }
+            TriggerState::Active {
+                target: active_target,
+                duration,
+                timestamp,
+            } => {
+                if target != Some(*active_target) {
+                    return Some(TriggerState::Idle);
+                }
+
+                if *duration == 0 || now.duration_since(*timestamp).as_millis() < *duration as u128
+                {
+                    /* keep on shooting */
+                    return None;
+                }
+
+                /* shot finished, release and fire again at the same target */
+                Some(pending(*active_target, timings.sample_shot_delay()))
+            }
+        }
+    }
 }
 
 pub struct TriggerBot {
     toggle: KeyToggle,
     state: TriggerState,
     trigger_active: bool,
+
+    /// The local player exceeded the max velocity and has not yet slowed down enough
+    velocity_exceeded: bool,
 }
 
 impl TriggerBot {
@@ -42,17 +191,72 @@ impl TriggerBot {
             toggle: KeyToggle::new(),
             state: TriggerState::Idle,
             trigger_active: false,
+            velocity_exceeded: false,
         }
     }
 
-    fn should_be_active(&self, ctx: &UpdateContext) -> anyhow::Result<bool> {
+    /// Check the scope, weapon, velocity and aim punch conditions of the local player
+    fn local_conditions_met(&mut self, ctx: &UpdateContext) -> anyhow::Result<bool> {
+        let settings = ctx.states.resolve::<AppSettings>(())?;
+        if !settings.trigger_bot_scoped_only
+            && !settings.trigger_bot_weapon_whitelist
+            && !settings.trigger_bot_velocity_gate
+            && !settings.trigger_bot_punch_gate
+        {
+            return Ok(true);
+        }
+
+        let local_pawn = match get_local_pawn_info(ctx.states)? {
+            Some(local_pawn) => local_pawn,
+            None => return Ok(false),
+        };
+
+        if settings.trigger_bot_scoped_only && !local_pawn.player_scoped {
+            return Ok(false);
+        }
+
+        if settings.trigger_bot_weapon_whitelist
+            && !settings
+                .trigger_bot_weapons
+                .iter()
+                .any(|weapon| weapon == local_pawn.weapon.name())
+        {
+            return Ok(false);
+        }
+
+        if settings.trigger_bot_velocity_gate {
+            let velocity = local_pawn.velocity.norm();
+            if velocity > settings.trigger_bot_max_velocity {
+                self.velocity_exceeded = true;
+            } else if velocity < settings.trigger_bot_max_velocity - VELOCITY_HYSTERESIS {
+                self.velocity_exceeded = false;
+            }
+
+            if self.velocity_exceeded {
+                return Ok(false);
+            }
+        }
+
+        if settings.trigger_bot_punch_gate {
+            /* the bullets would not land where the crosshair target is */
+            let punch = local_pawn.aim_punch_angle.xy().norm() * RECOIL_SCALE;
+            if punch > settings.trigger_bot_max_punch {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Entity id of the crosshair target if it's a valid target to shoot at
+    fn current_target(&self, ctx: &UpdateContext) -> anyhow::Result<Option<u32>> {
         let settings = ctx.states.resolve::<AppSettings>(())?;
         let crosshair = ctx.states.resolve::<LocalCrosshair>(())?;
         let entities = ctx.states.resolve::<EntitySystem>(())?;
 
         let target = match crosshair.current_target() {
             Some(target) => target,
-            None => return Ok(false),
+            None => return Ok(None),
         };
 
         if !target
@@ -61,36 +265,53 @@ impl TriggerBot {
             .map(|t| t == "C_CSPlayerPawn")
             .unwrap_or(false)
         {
-            return Ok(false);
+            return Ok(None);
         }
 
-        if settings.trigger_bot_team_check {
-            let crosshair_entity = entities
-                .get_by_handle(&EntityHandle::<C_CSPlayerPawn>::from_index(
-                    target.entity_id,
-                ))?
-                .context("missing crosshair player pawn")?
-                .entity()?
-                .read_schema()?;
+        if !hitbox_filter_matches(settings.trigger_bot_hitbox_filter, target.hitbox_group) {
+            return Ok(None);
+        }
+
+        /* dead targets must not be shot at */
+        let target_team_id = match &*ctx.states.resolve::<PlayerPawnState>(target.entity_id)? {
+            PlayerPawnState::Alive(info) => info.team_id,
+            _ => return Ok(None),
+        };
 
+        if settings.trigger_bot_team_check {
             let local_player_controller = entities.get_local_player_controller()?;
             if local_player_controller.is_null()? {
-                return Ok(false);
+                return Ok(None);
             }
 
-            let local_player_controller = local_player_controller.reference_schema()?;
+            let local_pawn_index = local_player_controller
+                .reference_schema()?
+                .m_hPlayerPawn()?
+                .get_entity_index();
 
-            let target_player = crosshair_entity.as_schema::<C_CSPlayerPawn>()?;
-            if target_player.m_iTeamNum()? == local_player_controller.m_iTeamNum()? {
-                return Ok(false);
+            let local_team_id = match &*ctx.states.resolve::<PlayerPawnState>(local_pawn_index)? {
+                PlayerPawnState::Alive(info) => info.team_id,
+                _ => return Ok(None),
+            };
+
+            if target_team_id == local_team_id {
+                return Ok(None);
             }
         }
 
-        Ok(true)
+        Ok(Some(target.entity_id))
     }
 }
 
 impl Enhancement for TriggerBot {
+    fn status(&self, settings: &AppSettings) -> Option<EnhancementStatus> {
+        Some(EnhancementStatus {
+            name: obfstr!("Trigger").to_string(),
+            mode: settings.trigger_bot_mode,
+            active: self.toggle.enabled,
+        })
+    }
+
     fn update(&mut self, ctx: &UpdateContext) -> anyhow::Result<()> {
         let settings = ctx.states.resolve::<AppSettings>(())?;
         if self.toggle.update(
@@ -107,69 +328,26 @@ impl Enhancement for TriggerBot {
             );
         }
 
-        let should_shoot: bool = if self.toggle.enabled {
-            self.should_be_active(ctx)?
+        let target = if self.toggle.enabled && self.local_conditions_met(ctx)? {
+            self.current_target(ctx)?
         } else {
-            false
+            None
         };
 
-        loop {
-            match &self.state {
-                TriggerState::Idle => {
-                    if !should_shoot {
-                        /* nothing changed */
-                        break;
-                    }
-
-                    let delay_min = settings
-                        .trigger_bot_delay_min
-                        .min(settings.trigger_bot_delay_max);
-                    let delay_max = settings
-                        .trigger_bot_delay_min
-                        .max(settings.trigger_bot_delay_max);
-                    let selected_delay = if delay_max == delay_min {
-                        delay_min
-                    } else {
-                        let dist = Uniform::new_inclusive(delay_min, delay_max);
-                        dist.sample(&mut rand::thread_rng())
-                    };
-
-                    log::trace!(
-                        "Setting trigger bot into pending mode with a delay of {}ms",
-                        selected_delay
-                    );
-                    self.state = TriggerState::Pending {
-                        delay: selected_delay,
-                        timestamp: Instant::now(),
-                    };
-                }
-                TriggerState::Pending { delay, timestamp } => {
-                    let time_elapsed = timestamp.elapsed().as_millis();
-                    if time_elapsed < *delay as u128 {
-                        /* still waiting to be activated */
-                        break;
-                    }
-
-                    if settings.trigger_bot_check_target_after_delay && !should_shoot {
-                        self.state = TriggerState::Idle;
-                    } else {
-                        self.state = TriggerState::Active;
-                    }
-                    /* regardsless of the next state, we always need to execute the current action */
-                    break;
-                }
-                TriggerState::Active => {
-                    if should_shoot {
-                        /* nothing changed */
-                        break;
-                    }
+        let timings = TriggerTimings::from_settings(&settings);
+        let now = Instant::now();
+        while let Some(next_state) = self.state.transition(target, now, &timings) {
+            log::trace!("Trigger bot state {:?} -> {:?}", self.state, next_state);
 
-                    self.state = TriggerState::Idle;
-                }
+            let shot_finished = matches!(self.state, TriggerState::Active { .. });
+            self.state = next_state;
+            if shot_finished {
+                /* release the mouse for at least one update before the next shot */
+                break;
             }
         }
 
-        let should_be_active = matches!(self.state, TriggerState::Active);
+        let should_be_active = matches!(self.state, TriggerState::Active { .. });
         if should_be_active != self.trigger_active {
             self.trigger_active = should_be_active;
 
@@ -188,3 +366,179 @@ impl Enhancement for TriggerBot {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::{
+        Duration,
+        Instant,
+    };
+
+    use super::{
+        hitbox_filter_matches,
+        TriggerState,
+        TriggerTimings,
+    };
+    use crate::{
+        settings::TriggerBotHitboxFilter,
+        view::HitboxGroup,
+    };
+
+    #[test]
+    fn hitbox_filter_any() {
+        assert!(hitbox_filter_matches(TriggerBotHitboxFilter::Any, None));
+        assert!(hitbox_filter_matches(
+            TriggerBotHitboxFilter::Any,
+            Some(HitboxGroup::Legs)
+        ));
+    }
+
+    #[test]
+    fn hitbox_filter_head() {
+        assert!(hitbox_filter_matches(
+            TriggerBotHitboxFilter::Head,
+            Some(HitboxGroup::Head)
+        ));
+        assert!(!hitbox_filter_matches(
+            TriggerBotHitboxFilter::Head,
+            Some(HitboxGroup::Chest)
+        ));
+        assert!(!hitbox_filter_matches(TriggerBotHitboxFilter::Head, None));
+    }
+
+    #[test]
+    fn hitbox_filter_head_chest() {
+        assert!(hitbox_filter_matches(
+            TriggerBotHitboxFilter::HeadChest,
+            Some(HitboxGroup::Head)
+        ));
+        assert!(hitbox_filter_matches(
+            TriggerBotHitboxFilter::HeadChest,
+            Some(HitboxGroup::Chest)
+        ));
+        assert!(!hitbox_filter_matches(
+            TriggerBotHitboxFilter::HeadChest,
+            Some(HitboxGroup::Arms)
+        ));
+        assert!(!hitbox_filter_matches(
+            TriggerBotHitboxFilter::HeadChest,
+            None
+        ));
+    }
+
+    const TIMINGS: TriggerTimings = TriggerTimings {
+        delay_min: 50,
+        delay_max: 50,
+        jitter: 0,
+        shot_duration_min: 100,
+        shot_duration_max: 100,
+    };
+
+    fn pending(target: u32, delay: u32, timestamp: Instant) -> TriggerState {
+        TriggerState::Pending {
+            target,
+            delay,
+            timestamp,
+        }
+    }
+
+    fn active(target: u32, duration: u32, timestamp: Instant) -> TriggerState {
+        TriggerState::Active {
+            target,
+            duration,
+            timestamp,
+        }
+    }
+
+    #[test]
+    fn idle_without_target() {
+        let now = Instant::now();
+        assert_eq!(TriggerState::Idle.transition(None, now, &TIMINGS), None);
+    }
+
+    #[test]
+    fn idle_to_pending() {
+        let now = Instant::now();
+        assert_eq!(
+            TriggerState::Idle.transition(Some(1), now, &TIMINGS),
+            Some(pending(1, 50, now))
+        );
+    }
+
+    #[test]
+    fn pending_keeps_sampled_delay() {
+        let start = Instant::now();
+        let state = pending(1, 50, start);
+        assert_eq!(
+            state.transition(Some(1), start + Duration::from_millis(20), &TIMINGS),
+            None
+        );
+    }
+
+    #[test]
+    fn pending_to_active() {
+        let start = Instant::now();
+        let now = start + Duration::from_millis(50);
+        assert_eq!(
+            pending(1, 50, start).transition(Some(1), now, &TIMINGS),
+            Some(active(1, 100, now))
+        );
+    }
+
+    #[test]
+    fn pending_cancelled_when_target_lost() {
+        let start = Instant::now();
+        let now = start + Duration::from_millis(10);
+        assert_eq!(
+            pending(1, 50, start).transition(None, now, &TIMINGS),
+            Some(TriggerState::Idle)
+        );
+    }
+
+    #[test]
+    fn pending_restarted_on_new_target() {
+        let start = Instant::now();
+        let now = start + Duration::from_millis(40);
+        assert_eq!(
+            pending(1, 50, start).transition(Some(2), now, &TIMINGS),
+            Some(pending(2, 50, now))
+        );
+    }
+
+    #[test]
+    fn active_until_shot_finished() {
+        let start = Instant::now();
+        assert_eq!(
+            active(1, 100, start).transition(Some(1), start + Duration::from_millis(50), &TIMINGS),
+            None
+        );
+
+        let now = start + Duration::from_millis(100);
+        assert_eq!(
+            active(1, 100, start).transition(Some(1), now, &TIMINGS),
+            Some(pending(1, 0, now))
+        );
+    }
+
+    #[test]
+    fn active_holds_without_shot_duration() {
+        let start = Instant::now();
+        assert_eq!(
+            active(1, 0, start).transition(Some(1), start + Duration::from_secs(10), &TIMINGS),
+            None
+        );
+    }
+
+    #[test]
+    fn active_to_idle_when_target_lost() {
+        let start = Instant::now();
+        assert_eq!(
+            active(1, 100, start).transition(None, start, &TIMINGS),
+            Some(TriggerState::Idle)
+        );
+        assert_eq!(
+            active(1, 100, start).transition(Some(2), start, &TIMINGS),
+            Some(TriggerState::Idle)
+        );
+    }
+}
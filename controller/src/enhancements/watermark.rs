@@ -0,0 +1,145 @@
+use cs2::EntitySystem;
+use obfstr::obfstr;
+use overlay::CaptureProtection;
+
+use super::Enhancement;
+use crate::{
+    settings::{
+        AppSettings,
+        OverlayCorner,
+    },
+    view::KeyToggle,
+    ScreenCaptureState,
+};
+
+const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Distance to the screen edges in pixels
+const SCREEN_MARGIN: f32 = 10.0;
+const TEXT_PADDING: [f32; 2] = [8.0, 4.0];
+
+/// Single line stats bar with the overlay FPS, current time, ping, version and capture protection.
+/// The bar has its own toggle and is therefore independent of the ESP toggle.
+pub struct Watermark {
+    toggle: KeyToggle,
+
+    /// Ping of the local player controller in milliseconds
+    local_ping: Option<u32>,
+}
+
+impl Watermark {
+    pub fn new() -> Self {
+        Self {
+            toggle: KeyToggle::new(),
+            local_ping: None,
+        }
+    }
+}
+
+impl Enhancement for Watermark {
+    fn update(&mut self, ctx: &crate::UpdateContext) -> anyhow::Result<()> {
+        let settings = ctx.states.resolve::<AppSettings>(())?;
+        self.toggle.update(
+            &settings.watermark_mode,
+            ctx.input,
+            &settings.watermark_toggle,
+        );
+
+        self.local_ping = None;
+        if !self.toggle.enabled || !settings.watermark_ping {
+            return Ok(());
+        }
+
+        let entities = ctx.states.resolve::<EntitySystem>(())?;
+        let local_controller = entities.get_local_player_controller()?;
+        if !local_controller.is_null()? {
+            self.local_ping = Some(local_controller.reference_schema()?.m_iPing()?);
+        }
+
+        Ok(())
+    }
+
+    fn render(&self, states: &utils_state::StateRegistry, ui: &imgui::Ui) -> anyhow::Result<()> {
+        if !self.toggle.enabled {
+            return Ok(());
+        }
+
+        let settings = states.resolve::<AppSettings>(())?;
+
+        let mut elements = Vec::with_capacity(5);
+        if settings.watermark_version {
+            elements.push(format!("{} {}", obfstr!("Valthrun"), VERSION));
+        }
+        if settings.watermark_fps {
+            elements.push(format!("{:.0} FPS", ui.io().framerate));
+        }
+        if settings.watermark_ping {
+            if let Some(ping) = self.local_ping {
+                elements.push(format!("{} ms", ping));
+            }
+        }
+        if settings.watermark_time {
+            elements.push(chrono::Local::now().format("%H:%M:%S").to_string());
+        }
+        if settings.watermark_capture {
+            if let Some(state) = states.get::<ScreenCaptureState>(()) {
+                let label = match state.protection {
+                    CaptureProtection::Disabled => obfstr!("Capture: visible").to_string(),
+                    CaptureProtection::Excluded => obfstr!("Capture: hidden").to_string(),
+                    CaptureProtection::Masked => obfstr!("Capture: masked").to_string(),
+                    CaptureProtection::Failed => obfstr!("Capture: failed").to_string(),
+                };
+                elements.push(label);
+            }
+        }
+
+        if elements.is_empty() {
+            return Ok(());
+        }
+
+        let text = elements.join(" | ");
+        let [text_width, text_height] = ui.calc_text_size(&text);
+        let bar_size = [
+            text_width + TEXT_PADDING[0] * 2.0,
+            text_height + TEXT_PADDING[1] * 2.0,
+        ];
+
+        let [display_width, display_height] = ui.io().display_size;
+        let bar_position = match settings.watermark_position {
+            OverlayCorner::TopLeft => [SCREEN_MARGIN, SCREEN_MARGIN],
+            OverlayCorner::TopRight => [display_width - bar_size[0] - SCREEN_MARGIN, SCREEN_MARGIN],
+            OverlayCorner::BottomLeft => {
+                [SCREEN_MARGIN, display_height - bar_size[1] - SCREEN_MARGIN]
+            }
+            OverlayCorner::BottomRight => [
+                display_width - bar_size[0] - SCREEN_MARGIN,
+                display_height - bar_size[1] - SCREEN_MARGIN,
+            ],
+        };
+
+        let draw = ui.get_window_draw_list();
+        draw.add_rect(
+            bar_position,
+            [bar_position[0] + bar_size[0], bar_position[1] + bar_size[1]],
+            [
+                0.0,
+                0.0,
+                0.0,
+                settings.watermark_background_alpha.clamp(0.0, 1.0),
+            ],
+        )
+        .filled(true)
+        .rounding(3.0)
+        .build();
+        draw.add_text(
+            [
+                bar_position[0] + TEXT_PADDING[0],
+                bar_position[1] + TEXT_PADDING[1],
+            ],
+            settings.watermark_color.as_f32(),
+            &text,
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,624 @@
+use std::{
+    collections::{
+        BTreeSet,
+        HashMap,
+    },
+    path::{
+        Path,
+        PathBuf,
+    },
+    sync::OnceLock,
+};
+
+use cs2::WeaponId;
+use imgui::{
+    FontAtlas,
+    FontConfig,
+    FontGlyphRanges,
+    FontId,
+    FontSource,
+};
+use obfstr::obfstr;
+use utils_state::{
+    State,
+    StateCacheType,
+    StateRegistry,
+};
+
+use crate::settings::AppSettings;
+
+/// Directory (next to the executable) containing custom fonts (`.ttf` / `.otf`)
+/// which can be selected within the settings.
+const FONTS_DIRECTORY: &'static str = "fonts";
+
+/// File name of the weapon icon font which will be loaded from the directory of the executable.
+/// The font is expected to provide the icons at the codepoints of weapon_icon_glyph.
+const WEAPON_ICON_FONT_FILE: &'static str = "weapon-icons.ttf";
+
+/// Size of the Valthrun title and weapon icon font
+const ICON_FONT_SIZE: f32 = 18.0;
+
+/// Fonts of the operating system (within `%WINDIR%\Fonts`) which provide glyphs
+/// missing in the text font (e.g. Cyrillic, Arabic, CJK).
+/// Glyphs will be taken from the first font providing them.
+const SYSTEM_FALLBACK_FONTS: [&'static str; 4] =
+    ["segoeui.ttf", "msyh.ttc", "YuGothM.ttc", "malgun.ttf"];
+
+/// Glyphs which are always part of the font atlas (Basic Latin + Latin-1 Supplement)
+const DEFAULT_GLYPH_RANGE: [u32; 2] = [0x0020, 0x00FF];
+
+/// Highest codepoint which can be added to the font atlas.
+/// ImGui uses 16 bit characters, glyphs outside of the BMP (e.g. emojis) can not be rendered.
+const MAX_GLYPH_CODEPOINT: u32 = 0xFFFF;
+
+/// Fonts of the overlay.
+/// The font ids change whenever the font atlas gets rebuild.
+pub struct AppFonts {
+    pub valthrun: FontId,
+
+    /// Optional weapon icon font (see WEAPON_ICON_FONT_FILE)
+    pub weapon_icons: Option<FontId>,
+
+    pub menu: FontId,
+    pub esp: FontId,
+    pub hud: FontId,
+}
+
+/*
+ * The font atlas gets built on a worker thread of the overlay.
+ * Font ids are only compared against the fonts of the active font atlas.
+ */
+unsafe impl Send for AppFonts {}
+
+impl State for AppFonts {
+    type Parameter = ();
+
+    fn cache_type() -> StateCacheType {
+        StateCacheType::Persistent
+    }
+}
+
+/// Codepoints of dynamic text (e.g. player names) which are not covered by the default glyph range.
+/// Rendering text with unseen codepoints will request a font atlas rebuild containing these glyphs.
+#[derive(Debug, Default)]
+pub struct FontGlyphs {
+    codepoints: BTreeSet<u32>,
+    changed: bool,
+}
+
+impl State for FontGlyphs {
+    type Parameter = ();
+
+    fn create(_states: &StateRegistry, _param: Self::Parameter) -> anyhow::Result<Self> {
+        Ok(Default::default())
+    }
+
+    fn cache_type() -> StateCacheType {
+        StateCacheType::Persistent
+    }
+}
+
+impl FontGlyphs {
+    /// Register all codepoints of the text which should be rendered
+    pub fn register_text(&mut self, text: &str) {
+        for codepoint in text.chars().map(|char| char as u32) {
+            if codepoint <= DEFAULT_GLYPH_RANGE[1] || codepoint > MAX_GLYPH_CODEPOINT {
+                continue;
+            }
+
+            if self.codepoints.insert(codepoint) {
+                self.changed = true;
+            }
+        }
+    }
+
+    /// Returns true if new codepoints have been registered since the last call
+    pub fn take_changed(&mut self) -> bool {
+        std::mem::replace(&mut self.changed, false)
+    }
+
+    /// Zero terminated list of inclusive ranges covering the default
+    /// glyphs and all registered codepoints.
+    pub fn glyph_ranges(&self) -> Vec<u32> {
+        let mut ranges = DEFAULT_GLYPH_RANGE.to_vec();
+        for codepoint in self.codepoints.iter().copied() {
+            let range_end = ranges.last_mut().expect("at least one range");
+            if *range_end + 1 == codepoint {
+                *range_end = codepoint;
+            } else {
+                ranges.extend([codepoint, codepoint]);
+            }
+        }
+
+        ranges.push(0);
+        ranges
+    }
+}
+
+/// Get the glyph of the weapon icon within the weapon icon font.
+/// Returns None if the font contains no icon for the weapon.
+pub fn weapon_icon_glyph(weapon: WeaponId) -> Option<char> {
+    let glyph = match weapon {
+        WeaponId::Deagle => '\u{E001}',
+        WeaponId::Elite => '\u{E002}',
+        WeaponId::FiveSeven => '\u{E003}',
+        WeaponId::Glock => '\u{E004}',
+        WeaponId::Ak47 => '\u{E007}',
+        WeaponId::Aug => '\u{E008}',
+        WeaponId::AWP => '\u{E009}',
+        WeaponId::Famas => '\u{E00A}',
+        WeaponId::G3SG1 => '\u{E00B}',
+        WeaponId::Galilar => '\u{E00D}',
+        WeaponId::M249 => '\u{E00E}',
+        WeaponId::M4A4 => '\u{E010}',
+        WeaponId::Mac10 => '\u{E011}',
+        WeaponId::P90 => '\u{E013}',
+        WeaponId::MP5SD => '\u{E017}',
+        WeaponId::Ump45 => '\u{E018}',
+        WeaponId::XM1014 => '\u{E019}',
+        WeaponId::Bizon => '\u{E01A}',
+        WeaponId::Mag7 => '\u{E01B}',
+        WeaponId::Negev => '\u{E01C}',
+        WeaponId::SawedOff => '\u{E01D}',
+        WeaponId::Tec9 => '\u{E01E}',
+        WeaponId::Taser => '\u{E01F}',
+        WeaponId::HKP200 => '\u{E020}',
+        WeaponId::MP7 => '\u{E021}',
+        WeaponId::MP9 => '\u{E022}',
+        WeaponId::Nova => '\u{E023}',
+        WeaponId::P250 => '\u{E024}',
+        WeaponId::Scar20 => '\u{E026}',
+        WeaponId::Sg553 => '\u{E027}',
+        WeaponId::Ssg08 => '\u{E028}',
+        WeaponId::Knife => '\u{E02A}',
+        WeaponId::Flashbang => '\u{E02B}',
+        WeaponId::HZGranade => '\u{E02C}',
+        WeaponId::SmokeGranade => '\u{E02D}',
+        WeaponId::Molotov => '\u{E02E}',
+        WeaponId::Decoy => '\u{E02F}',
+        WeaponId::Incendiary => '\u{E030}',
+        WeaponId::C4 => '\u{E031}',
+        WeaponId::Healthshot => '\u{E039}',
+        WeaponId::KnifeT => '\u{E03B}',
+        WeaponId::M4A1Silencer => '\u{E03C}',
+        WeaponId::USPS => '\u{E03D}',
+        WeaponId::CZ75a => '\u{E03F}',
+        WeaponId::Revolver => '\u{E040}',
+        WeaponId::KnifeBayonet => '\u{E1F4}',
+        WeaponId::KnifeFlip => '\u{E1F9}',
+        WeaponId::KnifeGut => '\u{E1FA}',
+        WeaponId::KnifeKarambit => '\u{E1FB}',
+        WeaponId::KnifeM9Bayonet => '\u{E1FC}',
+        WeaponId::KnifeTactical => '\u{E1FD}',
+        WeaponId::KnifeFalchion => '\u{E200}',
+        WeaponId::KnifeSurvivalBowie => '\u{E202}',
+        WeaponId::KnifeButterfly => '\u{E203}',
+        WeaponId::KnifePush => '\u{E204}',
+        WeaponId::KnifeUrsus => '\u{E207}',
+        WeaponId::KnifesNavaja => '\u{E208}',
+        WeaponId::KnifesStiletto => '\u{E20A}',
+        WeaponId::KnifesTalon => '\u{E20B}',
+        _ => return None,
+    };
+
+    Some(glyph)
+}
+
+/// Zero terminated glyph ranges covering all weapon icons (see weapon_icon_glyph)
+fn weapon_icon_glyph_ranges() -> &'static [u32] {
+    static RANGES: OnceLock<Vec<u32>> = OnceLock::new();
+    RANGES.get_or_init(|| {
+        let mut codepoints = WeaponId::all_weapons()
+            .into_iter()
+            .filter_map(weapon_icon_glyph)
+            .map(|glyph| glyph as u32)
+            .collect::<Vec<_>>();
+        codepoints.sort_unstable();
+        codepoints.dedup();
+
+        let mut ranges = Vec::<u32>::with_capacity(codepoints.len() * 2 + 1);
+        for codepoint in codepoints {
+            match ranges.last_mut() {
+                Some(range_end) if *range_end + 1 == codepoint => *range_end = codepoint,
+                _ => ranges.extend([codepoint, codepoint]),
+            }
+        }
+
+        ranges.push(0);
+        ranges
+    })
+}
+
+/// Font selection and sizes of the font atlas
+#[derive(Debug, Clone, PartialEq)]
+pub struct FontOptions {
+    /// File name within the fonts directory (empty = default font)
+    pub font_file: String,
+
+    pub menu_size: f32,
+    pub esp_size: f32,
+    pub hud_size: f32,
+}
+
+impl FontOptions {
+    pub fn from_settings(settings: &AppSettings) -> Self {
+        Self {
+            font_file: settings.font_file.clone(),
+            menu_size: settings.font_size_menu.clamp(8.0, 48.0),
+            esp_size: settings.font_size_esp.clamp(8.0, 48.0),
+            hud_size: settings.font_size_hud.clamp(8.0, 48.0),
+        }
+    }
+}
+
+fn exe_directory() -> Option<PathBuf> {
+    Some(std::env::current_exe().ok()?.parent()?.to_path_buf())
+}
+
+fn system_fonts_directory() -> PathBuf {
+    let windows_directory = std::env::var("WINDIR").unwrap_or_else(|_| "C:\\Windows".to_string());
+    PathBuf::from(windows_directory).join("Fonts")
+}
+
+/// List the file names of all fonts within the fonts directory
+pub fn list_custom_fonts() -> Vec<String> {
+    let directory = match exe_directory() {
+        Some(directory) => directory.join(FONTS_DIRECTORY),
+        None => return Vec::new(),
+    };
+
+    let entries = match std::fs::read_dir(&directory) {
+        Ok(entries) => entries,
+        Err(_) => return Vec::new(),
+    };
+
+    let mut fonts = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension()
+                .and_then(|extension| extension.to_str())
+                .map(|extension| {
+                    extension.eq_ignore_ascii_case("ttf") || extension.eq_ignore_ascii_case("otf")
+                })
+                .unwrap_or(false)
+        })
+        .filter_map(|path| Some(path.file_name()?.to_str()?.to_string()))
+        .collect::<Vec<_>>();
+
+    fonts.sort();
+    fonts
+}
+
+/// ImGui aborts on invalid font data, therefore at least the header is checked upfront
+fn is_font_data(data: &[u8]) -> bool {
+    matches!(
+        data.get(0..4),
+        Some([0x00, 0x01, 0x00, 0x00]) | Some(b"OTTO") | Some(b"true") | Some(b"ttcf")
+    )
+}
+
+/// Builds the application fonts every time the overlay (re)creates the font atlas.
+pub struct FontLoader {
+    options: FontOptions,
+
+    /// The font atlas requires the font data to live as long as the imgui context.
+    /// Every font file will be read (and leaked) only once.
+    font_data: HashMap<PathBuf, Option<&'static [u8]>>,
+
+    /// Glyph ranges of the text fonts.
+    /// None if only the default glyphs are required.
+    glyph_ranges: Option<&'static [u32]>,
+
+    /// Fonts which have been built but not yet been picked up by the application
+    fonts: Option<AppFonts>,
+}
+
+impl FontLoader {
+    pub fn new(options: FontOptions) -> Self {
+        Self {
+            options,
+            font_data: Default::default(),
+            glyph_ranges: None,
+            fonts: None,
+        }
+    }
+
+    pub fn options(&self) -> &FontOptions {
+        &self.options
+    }
+
+    /// Options for the next font atlas build
+    pub fn set_options(&mut self, options: FontOptions) {
+        self.options = options;
+    }
+
+    /// Glyph ranges (see FontGlyphs::glyph_ranges) for the next font atlas build.
+    /// The ranges must live as long as the font atlas and are therefore leaked.
+    /// This is acceptable as new ranges are only set when unseen glyphs are encountered.
+    pub fn set_glyph_ranges(&mut self, glyph_ranges: Vec<u32>) {
+        self.glyph_ranges = Some(Box::leak(glyph_ranges.into_boxed_slice()));
+    }
+
+    /// Take the fonts of the last font atlas build.
+    /// Must only be called once the overlay activated the new font atlas.
+    pub fn take_fonts(&mut self) -> Option<AppFonts> {
+        self.fonts.take()
+    }
+
+    fn read_font(&mut self, path: &Path) -> Option<&'static [u8]> {
+        *self.font_data.entry(path.to_path_buf()).or_insert_with(|| {
+            let data = match std::fs::read(path) {
+                Ok(data) => data,
+                Err(error) => {
+                    log::debug!(
+                        "{} {}: {}",
+                        obfstr!("Failed to read font"),
+                        path.display(),
+                        error
+                    );
+                    return None;
+                }
+            };
+
+            if !is_font_data(&data) {
+                log::warn!(
+                    "{} {}",
+                    path.display(),
+                    obfstr!("is not a valid TTF/OTF font")
+                );
+                return None;
+            }
+
+            Some(Box::leak(data.into_boxed_slice()))
+        })
+    }
+
+    fn load_weapon_icon_font(&mut self, atlas: &mut FontAtlas) -> Option<FontId> {
+        let font_path = exe_directory()?.join(WEAPON_ICON_FONT_FILE);
+        let font_data = match self.read_font(&font_path) {
+            Some(data) => data,
+            None => {
+                log::debug!(
+                    "{} {}. {}",
+                    obfstr!("Weapon icon font not found at"),
+                    font_path.display(),
+                    obfstr!("Using weapon names instead.")
+                );
+                return None;
+            }
+        };
+
+        Some(atlas.add_font(&[FontSource::TtfData {
+            data: font_data,
+            size_pixels: ICON_FONT_SIZE,
+            config: Some(FontConfig {
+                glyph_ranges: FontGlyphRanges::from_slice(weapon_icon_glyph_ranges()),
+                oversample_h: 4,
+                oversample_v: 4,
+                ..FontConfig::default()
+            }),
+        }]))
+    }
+
+    fn load_custom_font(&mut self) -> Option<&'static [u8]> {
+        if self.options.font_file.is_empty() {
+            return None;
+        }
+
+        let font_path = exe_directory()?
+            .join(FONTS_DIRECTORY)
+            .join(&self.options.font_file);
+
+        let data = self.read_font(&font_path);
+        if data.is_none() {
+            log::warn!(
+                "{} {}. {}",
+                obfstr!("Failed to load font"),
+                font_path.display(),
+                obfstr!("Using the default font instead.")
+            );
+        }
+
+        data
+    }
+
+    /// System fallback fonts are only required if glyphs outside of the default range are used
+    fn load_fallback_fonts(&mut self) -> Vec<&'static [u8]> {
+        if self.glyph_ranges.is_none() {
+            return Vec::new();
+        }
+
+        let directory = system_fonts_directory();
+        SYSTEM_FALLBACK_FONTS
+            .iter()
+            .filter_map(|font_file| self.read_font(&directory.join(font_file)))
+            .collect()
+    }
+
+    /// Add a text font with the given size.
+    /// Glyphs missing in the custom font will be taken from the default font
+    /// and afterwards from the system fallback fonts.
+    fn add_text_font(
+        atlas: &mut FontAtlas,
+        custom_font: Option<&'static [u8]>,
+        fallback_fonts: &[&'static [u8]],
+        glyph_ranges: Option<&'static [u32]>,
+        size: f32,
+    ) -> FontId {
+        let config = FontConfig {
+            rasterizer_multiply: 1.5,
+            oversample_h: 4,
+            oversample_v: 4,
+            glyph_ranges: glyph_ranges
+                .map(FontGlyphRanges::from_slice)
+                .unwrap_or_else(FontGlyphRanges::default),
+            ..FontConfig::default()
+        };
+
+        /* additional sources will be merged, already existing glyphs will not be replaced */
+        let sources = custom_font
+            .into_iter()
+            .chain(Some(overlay::DEFAULT_FONT_DATA))
+            .chain(fallback_fonts.iter().copied())
+            .map(|data| FontSource::TtfData {
+                data,
+                size_pixels: size,
+                config: Some(config.clone()),
+            })
+            .collect::<Vec<_>>();
+
+        atlas.add_font(&sources)
+    }
+
+    /// Add all application fonts to the font atlas
+    pub fn build(&mut self, atlas: &mut FontAtlas) {
+        let valthrun = atlas.add_font(&[FontSource::TtfData {
+            data: include_bytes!("../resources/Valthrun-Regular.ttf"),
+            size_pixels: ICON_FONT_SIZE,
+            config: Some(FontConfig {
+                rasterizer_multiply: 1.5,
+                oversample_h: 4,
+                oversample_v: 4,
+                ..FontConfig::default()
+            }),
+        }]);
+
+        let weapon_icons = self.load_weapon_icon_font(atlas);
+        let custom_font = self.load_custom_font();
+        let fallback_fonts = self.load_fallback_fonts();
+        let glyph_ranges = self.glyph_ranges;
+
+        /* elements sharing the same size share the same font */
+        let mut text_fonts = Vec::<(f32, FontId)>::with_capacity(3);
+        let mut text_font = |atlas: &mut FontAtlas, size: f32| {
+            if let Some((_, font)) = text_fonts.iter().find(|(font_size, _)| *font_size == size) {
+                return *font;
+            }
+
+            let font = Self::add_text_font(atlas, custom_font, &fallback_fonts, glyph_ranges, size);
+            text_fonts.push((size, font));
+            font
+        };
+
+        let menu = text_font(atlas, self.options.menu_size);
+        let esp = text_font(atlas, self.options.esp_size);
+        let hud = text_font(atlas, self.options.hud_size);
+
+        self.fonts = Some(AppFonts {
+            valthrun,
+            weapon_icons,
+
+            menu,
+            esp,
+            hud,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cs2::WeaponId;
+
+    use super::{
+        weapon_icon_glyph,
+        weapon_icon_glyph_ranges,
+        FontGlyphs,
+    };
+
+    fn covers(ranges: &[u32], codepoint: u32) -> bool {
+        ranges
+            .chunks_exact(2)
+            .any(|range| range[0] <= codepoint && codepoint <= range[1])
+    }
+
+    #[test]
+    fn default_glyphs_only() {
+        let mut glyphs = FontGlyphs::default();
+        glyphs.register_text("Player [VT] äöü");
+
+        assert!(!glyphs.take_changed());
+        assert_eq!(glyphs.glyph_ranges(), vec![0x20, 0xFF, 0]);
+    }
+
+    #[test]
+    fn mixed_script_names() {
+        let names = [
+            "Игрок",
+            "玩家一号",
+            "プレイヤー",
+            "لاعب",
+            "플레이어",
+            "Mixed Имя 名前",
+        ];
+
+        let mut glyphs = FontGlyphs::default();
+        for name in names {
+            glyphs.register_text(name);
+        }
+
+        assert!(glyphs.take_changed());
+        let ranges = glyphs.glyph_ranges();
+        assert_eq!(ranges.last(), Some(&0));
+
+        for name in names {
+            for char in name.chars() {
+                assert!(
+                    covers(&ranges, char as u32),
+                    "{:?} ({:X}) is not covered",
+                    char,
+                    char as u32
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn emoji_outside_bmp() {
+        let mut glyphs = FontGlyphs::default();
+        glyphs.register_text("🔥 Player ♥");
+
+        let ranges = glyphs.glyph_ranges();
+        assert!(covers(&ranges, '♥' as u32));
+        assert!(!covers(&ranges, '🔥' as u32));
+    }
+
+    #[test]
+    fn changes_only_once() {
+        let mut glyphs = FontGlyphs::default();
+        glyphs.register_text("Игрок");
+        assert!(glyphs.take_changed());
+
+        glyphs.register_text("Игрок");
+        assert!(!glyphs.take_changed());
+
+        glyphs.register_text("Иг");
+        assert!(!glyphs.take_changed());
+    }
+
+    #[test]
+    fn weapon_icons_are_covered() {
+        let ranges = weapon_icon_glyph_ranges();
+        assert_eq!(ranges.last(), Some(&0));
+        assert_eq!(weapon_icon_glyph(WeaponId::Unknown), None);
+
+        for weapon in WeaponId::all_weapons() {
+            if let Some(glyph) = weapon_icon_glyph(weapon) {
+                assert!(
+                    covers(ranges, glyph as u32),
+                    "{:?} ({:X}) is not covered",
+                    weapon,
+                    glyph as u32
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn consecutive_codepoints_are_merged() {
+        let mut glyphs = FontGlyphs::default();
+        glyphs.register_text("\u{100}\u{101}\u{102}\u{400}");
+
+        assert_eq!(glyphs.glyph_ranges(), vec![0x20, 0x102, 0x400, 0x400, 0]);
+    }
+}
@@ -3,6 +3,7 @@
 
 use std::{
     cell::{
+        Cell,
         Ref,
         RefCell,
         RefMut,
@@ -42,16 +43,21 @@ use cs2::{
     CS2Offsets,
 };
 use enhancements::Enhancement;
+use fonts::{
+    AppFonts,
+    FontGlyphs,
+    FontLoader,
+    FontOptions,
+};
 use imgui::{
     Condition,
-    FontConfig,
-    FontId,
-    FontSource,
+    StyleVar,
     Ui,
 };
 use libloading::Library;
 use obfstr::obfstr;
 use overlay::{
+    CaptureProtection,
     LoadingError,
     OverlayError,
     OverlayOptions,
@@ -60,12 +66,33 @@ use overlay::{
 };
 use radar::WebRadar;
 use settings::{
+    apply_settings_overrides,
+    create_config_backup,
+    create_profile,
+    delete_profile,
+    get_profile_path,
+    list_profiles,
+    load_active_profile,
     load_app_settings,
+    rename_profile,
+    save_active_profile,
+    serialize_app_settings,
+    set_settings_path,
+    write_config_file,
     AppSettings,
+    ConfigBackupOptions,
+    ProfileAction,
+    SettingsAutosave,
+    SettingsOverride,
     SettingsUI,
+    DEFAULT_PROFILE,
 };
 use tokio::runtime;
-use utils_state::StateRegistry;
+use utils_state::{
+    State,
+    StateCacheType,
+    StateRegistry,
+};
 use valthrun_kernel_interface::KInterfaceError;
 use view::ViewController;
 use windows::{
@@ -87,17 +114,44 @@ use windows::{
 use crate::{
     enhancements::{
         AntiAimPunsh,
+        BindList,
+        BombESP,
         BombInfoIndicator,
+        BombTimerHud,
+        ChickenESP,
+        Compass,
+        DamageIndicator,
+        DamageSummary,
+        DeathMarkers,
+        DefuseKitESP,
+        EconomyPanel,
+        EnhancementStatusList,
+        GrenadeHelper,
+        GrenadeProjectileESP,
+        GrenadeWarning,
+        HostageESP,
+        InfernoESP,
+        KillFeed,
+        Minimap,
         PlayerESP,
+        ProximityAlert,
+        RecoilCrosshair,
+        RoundHud,
+        SmokeESP,
+        SniperCrosshair,
+        SoundESP,
         SpectatorsListIndicator,
         TriggerBot,
+        Watermark,
     },
-    settings::save_app_settings,
+    utils::DrawOpacity,
     winver::version_info,
 };
 
 mod cache;
 mod enhancements;
+mod fonts;
+mod map_overview;
 mod radar;
 mod settings;
 mod utils;
@@ -114,9 +168,34 @@ impl MetricsClient for CS2Handle {
     }
 }
 
+/// Default max delay between the two presses of a double tap
+const DEFAULT_DOUBLE_TAP_WINDOW: Duration = Duration::from_millis(300);
+
+/// Default min hold duration of a long press
+const DEFAULT_LONG_PRESS_DURATION: Duration = Duration::from_millis(500);
+
+/// Time the warnings about an invalid config will be shown
+const SETTINGS_WARNINGS_DURATION: Duration = Duration::from_secs(10);
+
+/// Duration of the settings menu fade in / out
+const MENU_ANIMATION_DURATION: Duration = Duration::from_millis(150);
+
 pub trait KeyboardInput {
     fn is_key_down(&self, key: imgui::Key) -> bool;
     fn is_key_pressed(&self, key: imgui::Key, repeating: bool) -> bool;
+
+    /// Timestamp of the current input state
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn double_tap_window(&self) -> Duration {
+        DEFAULT_DOUBLE_TAP_WINDOW
+    }
+
+    fn long_press_duration(&self) -> Duration {
+        DEFAULT_LONG_PRESS_DURATION
+    }
 }
 
 impl KeyboardInput for imgui::Ui {
@@ -133,6 +212,54 @@ impl KeyboardInput for imgui::Ui {
     }
 }
 
+/// Keyboard input using the bind timings configured by the user
+pub struct BindInput<'a> {
+    ui: &'a imgui::Ui,
+    double_tap_window: Duration,
+    long_press_duration: Duration,
+}
+
+impl<'a> BindInput<'a> {
+    pub fn new(ui: &'a imgui::Ui, settings: &AppSettings) -> Self {
+        Self {
+            ui,
+            double_tap_window: Duration::from_millis(settings.key_double_tap_window as u64),
+            long_press_duration: Duration::from_millis(settings.key_long_press_duration as u64),
+        }
+    }
+}
+
+impl KeyboardInput for BindInput<'_> {
+    fn is_key_down(&self, key: imgui::Key) -> bool {
+        KeyboardInput::is_key_down(self.ui, key)
+    }
+
+    fn is_key_pressed(&self, key: imgui::Key, repeating: bool) -> bool {
+        KeyboardInput::is_key_pressed(self.ui, key, repeating)
+    }
+
+    fn double_tap_window(&self) -> Duration {
+        self.double_tap_window
+    }
+
+    fn long_press_duration(&self) -> Duration {
+        self.long_press_duration
+    }
+}
+
+/// Display capture protection of the overlay window as applied by the overlay
+pub struct ScreenCaptureState {
+    pub protection: CaptureProtection,
+}
+
+impl State for ScreenCaptureState {
+    type Parameter = ();
+
+    fn cache_type() -> StateCacheType {
+        StateCacheType::Persistent
+    }
+}
+
 pub struct UpdateContext<'a> {
     pub input: &'a dyn KeyboardInput,
     pub states: &'a StateRegistry,
@@ -140,12 +267,8 @@ pub struct UpdateContext<'a> {
     pub cs2: &'a Arc<CS2Handle>,
 }
 
-pub struct AppFonts {
-    valthrun: FontId,
-}
-
 pub struct Application {
-    pub fonts: AppFonts,
+    pub font_loader: Arc<Mutex<FontLoader>>,
     pub app_state: StateRegistry,
 
     pub cs2: Arc<CS2Handle>,
@@ -155,10 +278,37 @@ pub struct Application {
     pub last_total_read_calls: usize,
 
     pub settings_visible: bool,
+    /// Time the settings menu has been opened or closed
+    pub settings_toggled: Option<Instant>,
     pub settings_dirty: bool,
     pub settings_ui: RefCell<SettingsUI>,
     pub settings_screen_capture_changed: AtomicBool,
     pub settings_render_debug_window_changed: AtomicBool,
+    pub settings_overlay_monitor_changed: AtomicBool,
+    pub settings_fonts_changed: AtomicBool,
+
+    /// Client area of the CS2 window within the overlay ([x, y, width, height])
+    pub game_viewport: [f32; 4],
+
+    /// Set by the settings UI if the settings have been changed and should be saved
+    pub settings_changed: AtomicBool,
+    pub settings_autosave: SettingsAutosave,
+
+    /// Settings overridden via the command line which should not be saved
+    pub settings_overrides: Vec<SettingsOverride>,
+
+    /// Invalid or unknown values of the loaded config.
+    /// The warnings will be shown once after loading the config.
+    pub settings_warnings: Vec<String>,
+    pub settings_warnings_shown: Cell<Option<Instant>>,
+
+    /// Name of the profile the current settings belong to
+    pub active_profile: String,
+    pub profile_action: RefCell<Option<ProfileAction>>,
+
+    /// All enhancements are paused and nothing gets rendered while the panic mode is active.
+    /// The panic mode is intentionally not persisted.
+    pub panic_mode: bool,
 
     pub web_radar: RefCell<Option<Arc<Mutex<WebRadar>>>>,
 }
@@ -176,35 +326,105 @@ impl Application {
             .expect("app settings to be present")
     }
 
-    pub fn pre_update(&mut self, controller: &mut SystemRuntimeController) -> anyhow::Result<()> {
+    /// Forward all settings changes to the autosave
+    fn collect_settings_changes(&mut self) {
+        if self.settings_changed.swap(false, Ordering::Relaxed) {
+            self.settings_dirty = true;
+        }
+
         if self.settings_dirty {
             self.settings_dirty = false;
-            let mut settings = self.settings_mut();
+            self.settings_autosave.mark_dirty();
+        }
+    }
 
-            settings.imgui = None;
-            if let Ok(value) = serde_json::to_string(&*settings) {
-                self.cs2.add_metrics_record("settings-updated", &value);
+    fn serialize_settings(
+        app_state: &StateRegistry,
+        cs2: &CS2Handle,
+        settings_overrides: &[SettingsOverride],
+        controller: &mut SystemRuntimeController,
+    ) -> Option<(String, Option<ConfigBackupOptions>)> {
+        let mut settings = app_state
+            .get_mut::<AppSettings>(())
+            .expect("app settings to be present");
+
+        settings.imgui = None;
+        if let Ok(value) = serde_json::to_string(&*settings) {
+            cs2.add_metrics_record("settings-updated", &value);
+        }
+
+        let mut imgui_settings = String::new();
+        controller.imgui.save_ini_settings(&mut imgui_settings);
+        settings.imgui = Some(imgui_settings);
+
+        let backup = match ConfigBackupOptions::from_settings(&settings) {
+            Ok(backup) => Some(backup),
+            Err(error) => {
+                log::warn!("Failed to resolve the config backup options: {:#}", error);
+                None
+            }
+        };
+
+        match serialize_app_settings(&*settings, settings_overrides) {
+            Ok(content) => Some((content, backup)),
+            Err(error) => {
+                log::warn!("Failed to serialize user settings: {:#}", error);
+                None
             }
+        }
+    }
 
-            let mut imgui_settings = String::new();
-            controller.imgui.save_ini_settings(&mut imgui_settings);
-            settings.imgui = Some(imgui_settings);
+    /// Save all pending settings changes and wait until they have been written.
+    /// Must be called before the process exits, as pending changes are only saved periodically.
+    pub fn flush_settings(&mut self, controller: &mut SystemRuntimeController) {
+        self.collect_settings_changes();
+        self.settings_autosave.flush(&self.active_profile, || {
+            Self::serialize_settings(
+                &self.app_state,
+                &self.cs2,
+                &self.settings_overrides,
+                controller,
+            )
+        });
+    }
 
-            if let Err(error) = save_app_settings(&*settings) {
-                log::warn!("Failed to save user settings: {}", error);
-            };
+    pub fn pre_update(&mut self, controller: &mut SystemRuntimeController) -> anyhow::Result<()> {
+        /* tracked every frame so the ESP follows the CS2 window while it's being moved */
+        self.game_viewport = controller.game_viewport();
+
+        /* the font atlas has been rebuild, all previous font ids are invalid */
+        if controller.fonts_rebuilt() {
+            if let Some(fonts) = self.font_loader.lock().unwrap().take_fonts() {
+                self.app_state.set(fonts, ())?;
+            }
+        }
+
+        self.collect_settings_changes();
+        if self.settings_autosave.should_save() {
+            /* the file IO happens in the background to not stall the overlay */
+            if let Some((content, backup)) = Self::serialize_settings(
+                &self.app_state,
+                &self.cs2,
+                &self.settings_overrides,
+                controller,
+            ) {
+                self.settings_autosave
+                    .save(&self.active_profile, content, backup);
+            }
         }
 
         if self
             .settings_screen_capture_changed
             .swap(false, Ordering::Relaxed)
         {
-            let settings = self.settings();
-            controller.toggle_screen_capture_visibility(!settings.hide_overlay_from_screen_capture);
+            let hide_overlay = self.settings().hide_overlay_from_screen_capture;
+            let protection = controller.toggle_screen_capture_visibility(!hide_overlay);
             log::debug!(
-                "Updating screen capture visibility to {}",
-                !settings.hide_overlay_from_screen_capture
+                "Updating screen capture visibility to {} ({:?})",
+                !hide_overlay,
+                protection
             );
+            self.app_state.set(ScreenCaptureState { protection }, ())?;
         }
 
         if self
@@ -215,11 +435,213 @@ impl Application {
             controller.toggle_debug_overlay(settings.render_debug_window);
         }
 
+        if self
+            .settings_overlay_monitor_changed
+            .swap(false, Ordering::Relaxed)
+        {
+            let settings = self.settings();
+            let monitor = settings
+                .overlay_monitor
+                .checked_sub(1)
+                .map(|index| index as usize);
+            controller.set_overlay_monitor(monitor);
+            log::debug!("Updating overlay monitor to {:?}", monitor);
+        }
+
+        if self.settings_fonts_changed.swap(false, Ordering::Relaxed) {
+            let options = FontOptions::from_settings(&*self.settings());
+            let mut font_loader = self.font_loader.lock().unwrap();
+            if *font_loader.options() != options {
+                log::debug!("Rebuilding fonts ({:?})", options);
+                font_loader.set_options(options);
+                controller.rebuild_fonts();
+            }
+        }
+
+        /* the overlay state changes every frame, therefore the limit is evaluated every frame */
+        {
+            let settings = self.settings();
+            let frame_rate = if self.settings_visible {
+                settings.fps_limit_menu
+            } else if settings.fps_idle_throttling && controller.last_frame_empty() {
+                settings.fps_limit_idle
+            } else {
+                settings.fps_limit_esp
+            };
+            controller.set_frame_rate_limit(Some(frame_rate));
+
+            /* the key / gamepad state is global, navigation must not move the focus while in game */
+            controller.set_keyboard_navigation(
+                self.settings_visible && settings.menu_keyboard_navigation,
+            );
+            controller
+                .set_gamepad_navigation(self.settings_visible && settings.menu_gamepad_navigation);
+        }
+
+        /* the atlas will be rebuild in the background */
+        if let Ok(mut glyphs) = self.app_state.resolve_mut::<FontGlyphs>(()) {
+            if glyphs.take_changed() {
+                log::debug!("Rebuilding fonts for new glyphs");
+                self.font_loader
+                    .lock()
+                    .unwrap()
+                    .set_glyph_ranges(glyphs.glyph_ranges());
+                controller.rebuild_fonts();
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Load the given profile and replace the current settings.
+    /// All enhancements will pick up the new settings with the next update.
+    fn activate_profile(&mut self, profile: &str) -> anyhow::Result<()> {
+        let (settings, warnings) = load_app_settings(profile)?;
+        self.app_state.set::<AppSettings>(settings, ())?;
+
+        self.settings_warnings = warnings;
+        self.settings_warnings_shown.set(None);
+
+        /* command line overrides only apply to the initial profile */
+        self.settings_overrides.clear();
+        self.active_profile = profile.to_string();
+        if let Err(error) = save_active_profile(profile) {
+            log::warn!("Failed to save active profile: {:#}", error);
+        }
+
+        self.settings_screen_capture_changed
+            .store(true, Ordering::Relaxed);
+        self.settings_render_debug_window_changed
+            .store(true, Ordering::Relaxed);
+        self.settings_overlay_monitor_changed
+            .store(true, Ordering::Relaxed);
+
+        log::info!("Switched to profile {}", profile);
+        Ok(())
+    }
+
+    fn switch_profile(&mut self, profile: &str) -> anyhow::Result<()> {
+        if self.active_profile == profile {
+            return Ok(());
+        }
+
+        /* save any pending changes of the current profile before leaving it */
+        self.settings_autosave.wait();
+        let content = serialize_app_settings(&*self.settings(), &self.settings_overrides)?;
+        let config_path = get_profile_path(&self.active_profile)?;
+        let backup = ConfigBackupOptions::from_settings(&*self.settings())?;
+        if let Err(error) = create_config_backup(&backup, &self.active_profile, &config_path) {
+            log::warn!("Failed to back up the config: {:#}", error);
+        }
+        write_config_file(&config_path, &content)?;
+        self.settings_dirty = false;
+        self.settings_autosave.mark_saved();
+
+        self.activate_profile(profile)
+    }
+
+    fn apply_profile_action(&mut self, action: ProfileAction) -> anyhow::Result<()> {
+        match action {
+            ProfileAction::Switch(profile) => self.switch_profile(&profile)?,
+            ProfileAction::Create(profile) => {
+                create_profile(&profile, &*self.settings())?;
+                self.switch_profile(&profile)?;
+            }
+            ProfileAction::Rename(profile) => {
+                rename_profile(&self.active_profile, &profile)?;
+                self.active_profile = profile;
+                save_active_profile(&self.active_profile)?;
+            }
+            ProfileAction::Delete => {
+                delete_profile(&self.active_profile)?;
+                self.activate_profile(DEFAULT_PROFILE)?;
+            }
+        }
+
         Ok(())
     }
 
+    fn cycle_profile(&mut self) -> anyhow::Result<()> {
+        let profiles = list_profiles()?;
+        let next_profile = profiles
+            .iter()
+            .position(|profile| *profile == self.active_profile)
+            .map(|index| (index + 1) % profiles.len())
+            .unwrap_or(0);
+
+        let next_profile = profiles[next_profile].clone();
+        self.switch_profile(&next_profile)
+    }
+
     pub fn update(&mut self, ui: &imgui::Ui) -> anyhow::Result<()> {
-        {
+        let panic_key = self.settings().panic_key.clone();
+        if let Some(panic_key) = panic_key {
+            if panic_key.is_pressed(ui, false) {
+                self.panic_mode = !self.panic_mode;
+                log::info!(
+                    "Panic mode {}",
+                    if self.panic_mode {
+                        "activated"
+                    } else {
+                        "deactivated"
+                    }
+                );
+                self.cs2.add_metrics_record(
+                    "panic-mode-toggled",
+                    &format!("active: {}", self.panic_mode),
+                );
+
+                if self.panic_mode && self.settings_visible {
+                    self.settings_visible = false;
+                    /* hide the menu instantly */
+                    self.settings_toggled = None;
+                    self.settings_dirty = true;
+                }
+            }
+        }
+
+        let profile_action = self.profile_action.get_mut().take();
+        if let Some(action) = profile_action {
+            let result = self.apply_profile_action(action);
+            let mut settings_ui = self.settings_ui.borrow_mut();
+            settings_ui.profiles_changed();
+            if let Err(error) = result {
+                log::warn!("Failed to apply profile action: {:#}", error);
+                settings_ui.profile_status = Some(format!("{:#}", error));
+            }
+        }
+
+        let key_profile_cycle = self.settings().key_profile_cycle.clone();
+        if let Some(key_profile_cycle) = key_profile_cycle {
+            if key_profile_cycle.is_pressed(ui, false) {
+                if let Err(error) = self.cycle_profile() {
+                    log::warn!("Failed to switch profile: {:#}", error);
+                }
+                self.settings_ui.borrow_mut().profiles_changed();
+            }
+        }
+
+        let key_capture_protection = self.settings().key_capture_protection.clone();
+        if let Some(key_capture_protection) = key_capture_protection {
+            if key_capture_protection.is_pressed(ui, false) {
+                let hide_overlay = {
+                    let mut settings = self.settings_mut();
+                    settings.hide_overlay_from_screen_capture =
+                        !settings.hide_overlay_from_screen_capture;
+                    settings.hide_overlay_from_screen_capture
+                };
+                log::info!(
+                    "Screen capture protection {}",
+                    if hide_overlay { "enabled" } else { "disabled" }
+                );
+
+                self.settings_dirty = true;
+                self.settings_screen_capture_changed
+                    .store(true, Ordering::Relaxed);
+            }
+        }
+
+        if !self.panic_mode {
             for enhancement in self.enhancements.iter() {
                 let mut hack = enhancement.borrow_mut();
                 if hack.update_settings(ui, &mut *self.settings_mut())? {
@@ -228,9 +650,10 @@ impl Application {
             }
         }
 
-        if ui.is_key_pressed_no_repeat(self.settings().key_settings.0) {
+        if self.settings().key_settings.is_pressed(ui, false) {
             log::debug!("Toogle settings");
             self.settings_visible = !self.settings_visible;
+            self.settings_toggled = Some(Instant::now());
             self.cs2.add_metrics_record(
                 "settings-toggled",
                 &format!("visible: {}", self.settings_visible),
@@ -242,16 +665,36 @@ impl Application {
             }
         }
 
+        if self.panic_mode {
+            /* the settings can still be opened, but no enhancement will be updated */
+            return Ok(());
+        }
+
         self.app_state.invalidate_states();
         if let Ok(mut view_controller) = self.app_state.resolve_mut::<ViewController>(()) {
-            view_controller.update_screen_bounds(mint::Vector2::from_slice(&ui.io().display_size));
+            let [x, y, width, height] = self.game_viewport;
+            if width > 0.0 && height > 0.0 {
+                view_controller.update_screen_viewport(
+                    mint::Vector2 { x, y },
+                    mint::Vector2 {
+                        x: width,
+                        y: height,
+                    },
+                );
+            } else {
+                view_controller.update_screen_viewport(
+                    mint::Vector2 { x: 0.0, y: 0.0 },
+                    mint::Vector2::from_slice(&ui.io().display_size),
+                );
+            }
         }
 
+        let input = BindInput::new(ui, &*self.settings());
         let update_context = UpdateContext {
             cs2: &self.cs2,
 
             states: &self.app_state,
-            input: ui,
+            input: &input,
         };
 
         for enhancement in self.enhancements.iter() {
@@ -259,6 +702,19 @@ impl Application {
             hack.update(&update_context)?;
         }
 
+        {
+            let settings = self.settings();
+            let entries = self
+                .enhancements
+                .iter()
+                .filter_map(|enhancement| enhancement.borrow().status(&settings))
+                .collect();
+
+            self.app_state
+                .resolve_mut::<EnhancementStatusList>(())?
+                .entries = entries;
+        }
+
         let read_calls = self.cs2.ke_interface.total_read_calls();
         self.frame_read_calls = read_calls - self.last_total_read_calls;
         self.last_total_read_calls = read_calls;
@@ -266,31 +722,130 @@ impl Application {
         Ok(())
     }
 
+    /// Visibility of the settings menu (0.0 = hidden, 1.0 = fully shown) including the fade animation
+    fn menu_visibility(&self) -> f32 {
+        let target = if self.settings_visible { 1.0 } else { 0.0 };
+        if !self.settings().menu_animation {
+            return target;
+        }
+
+        let progress = match self.settings_toggled {
+            Some(toggled) => {
+                (toggled.elapsed().as_secs_f32() / MENU_ANIMATION_DURATION.as_secs_f32()).min(1.0)
+            }
+            None => 1.0,
+        };
+
+        if self.settings_visible {
+            progress
+        } else {
+            1.0 - progress
+        }
+    }
+
     pub fn render(&self, ui: &imgui::Ui) {
-        ui.window("overlay")
-            .draw_background(false)
-            .no_decoration()
-            .no_inputs()
-            .size(ui.io().display_size, Condition::Always)
-            .position([0.0, 0.0], Condition::Always)
-            .build(|| self.render_overlay(ui));
+        let menu_visibility = self.menu_visibility();
+        if !self.panic_mode {
+            let (dim_background, hide_esp) = {
+                let settings = self.settings();
+                (
+                    settings
+                        .menu_dim_background
+                        .then_some(settings.menu_dim_alpha),
+                    settings.menu_hide_esp,
+                )
+            };
+
+            /* the background draw list will be rendered before any window including the ESP */
+            if let Some(dim_alpha) = dim_background {
+                if menu_visibility > 0.0 {
+                    ui.get_background_draw_list()
+                        .add_rect(
+                            [0.0, 0.0],
+                            ui.io().display_size,
+                            [0.0, 0.0, 0.0, dim_alpha.clamp(0.0, 1.0) * menu_visibility],
+                        )
+                        .filled(true)
+                        .build();
+                }
+            }
+
+            if !hide_esp || !self.settings_visible {
+                ui.window("overlay")
+                    .draw_background(false)
+                    .no_decoration()
+                    .no_inputs()
+                    .size(ui.io().display_size, Condition::Always)
+                    .position([0.0, 0.0], Condition::Always)
+                    .build(|| self.render_overlay(ui));
+            }
 
-        {
             for enhancement in self.enhancements.iter() {
                 let mut enhancement = enhancement.borrow_mut();
                 enhancement.render_debug_window(&self.app_state, ui);
             }
+
+            self.render_settings_warnings(ui);
         }
 
-        if self.settings_visible {
+        if menu_visibility > 0.0 {
+            let _alpha = ui.push_style_var(StyleVar::Alpha(menu_visibility));
             let mut settings_ui = self.settings_ui.borrow_mut();
             settings_ui.render(self, ui)
         }
     }
 
+    fn render_settings_warnings(&self, ui: &imgui::Ui) {
+        if self.settings_warnings.is_empty() {
+            return;
+        }
+
+        let shown = match self.settings_warnings_shown.get() {
+            Some(shown) => shown,
+            None => {
+                let now = Instant::now();
+                self.settings_warnings_shown.set(Some(now));
+                now
+            }
+        };
+        if shown.elapsed() > SETTINGS_WARNINGS_DURATION {
+            return;
+        }
+
+        let display_size = ui.io().display_size;
+        ui.window(obfstr!("Config warnings"))
+            .no_decoration()
+            .no_inputs()
+            .always_auto_resize(true)
+            .bg_alpha(0.8)
+            .position([display_size[0] / 2.0, 20.0], Condition::Always)
+            .position_pivot([0.5, 0.0])
+            .build(|| {
+                ui.text_colored(
+                    [1.0, 0.76, 0.03, 1.0],
+                    obfstr!("The config contains invalid values:"),
+                );
+
+                for warning in self.settings_warnings.iter().take(5) {
+                    ui.text(warning);
+                }
+
+                if self.settings_warnings.len() > 5 {
+                    ui.text_disabled(format!(
+                        "{} {}",
+                        self.settings_warnings.len() - 5,
+                        obfstr!("more, see the log for details")
+                    ));
+                }
+            });
+    }
+
     fn render_overlay(&self, ui: &imgui::Ui) {
         let settings = self.settings();
 
+        /* the settings menu is a separate window and therefore not affected */
+        let _opacity = DrawOpacity::begin(ui, settings.overlay_opacity);
+
         if settings.valthrun_watermark {
             {
                 let text_buf;
@@ -320,6 +875,12 @@ impl Application {
             }
         }
 
+        let _hud_font = self
+            .app_state
+            .resolve::<AppFonts>(())
+            .ok()
+            .map(|fonts| ui.push_font(fonts.hud));
+
         for hack in self.enhancements.iter() {
             let hack = hack.borrow();
             if let Err(err) = hack.render(&self.app_state, ui) {
@@ -368,7 +929,7 @@ fn main() {
     let command = args.command.as_ref().unwrap_or(&AppCommand::Overlay);
     let result = match command {
         AppCommand::DumpSchema(args) => main_schema_dump(args),
-        AppCommand::Overlay => main_overlay(),
+        AppCommand::Overlay => main_overlay(&args),
     };
 
     if let Err(error) = result {
@@ -383,6 +944,19 @@ struct AppArgs {
     #[clap(short, long)]
     verbose: bool,
 
+    /// Config file to use instead of the config.yaml next to the executable
+    #[clap(long, global = true)]
+    config: Option<PathBuf>,
+
+    /// Profile to use for this session
+    #[clap(long, global = true)]
+    profile: Option<String>,
+
+    /// Override a setting for this session, e.g. --set esp.player.enemy.box_type=Box3D.
+    /// Overridden values will not be saved.
+    #[clap(long = "set", value_name = "PATH=VALUE", global = true)]
+    overrides: Vec<String>,
+
     #[clap(subcommand)]
     command: Option<AppCommand>,
 }
@@ -447,7 +1021,7 @@ fn preload_vulkan_with_act_ctx() -> anyhow::Result<()> {
     Ok(())
 }
 
-fn main_overlay() -> anyhow::Result<()> {
+fn main_overlay(args: &AppArgs) -> anyhow::Result<()> {
     let build_info = version_info()?;
     log::info!(
         "{} v{} ({}). Windows build {}.",
@@ -471,7 +1045,23 @@ fn main_overlay() -> anyhow::Result<()> {
         log::warn!("Act CTX preload failed: {:#}", err);
     }
 
-    let settings = load_app_settings()?;
+    if let Some(config) = &args.config {
+        set_settings_path(config.clone())?;
+    }
+
+    let active_profile = match &args.profile {
+        Some(profile) => {
+            if !list_profiles()?.contains(profile) {
+                anyhow::bail!("profile {} does not exist", profile);
+            }
+
+            profile.clone()
+        }
+        None => load_active_profile(),
+    };
+
+    let (mut settings, settings_warnings) = load_app_settings(&active_profile)?;
+    let settings_overrides = apply_settings_overrides(&mut settings, &args.overrides)?;
     let cs2 = match CS2Handle::create(settings.metrics) {
         Ok(handle) => handle,
         Err(err) => {
@@ -527,6 +1117,12 @@ fn main_overlay() -> anyhow::Result<()> {
     let mut app_state = StateRegistry::new(1024 * 8);
     app_state.set(CS2HandleState::new(cs2.clone()), ())?;
     app_state.set(settings, ())?;
+    app_state.set(
+        ScreenCaptureState {
+            protection: CaptureProtection::Disabled,
+        },
+        (),
+    )?;
 
     {
         let cs2_build_info = app_state.resolve::<BuildInfo>(()).with_context(|| {
@@ -554,32 +1150,18 @@ fn main_overlay() -> anyhow::Result<()> {
         .with_context(|| obfstr!("failed to load CS2 offsets").to_string())?;
 
     log::debug!("Initialize overlay");
-    let app_fonts: Rc<RefCell<Option<AppFonts>>> = Default::default();
+    let font_loader = {
+        let settings = app_state.resolve::<AppSettings>(())?;
+        Arc::new(Mutex::new(FontLoader::new(FontOptions::from_settings(
+            &settings,
+        ))))
+    };
     let overlay_options = OverlayOptions {
         title: obfstr!("CS2 Overlay").to_string(),
         target: OverlayTarget::WindowOfProcess(cs2.process_id() as u32),
-        font_init: Some(Box::new({
-            let app_fonts = app_fonts.clone();
-
-            move |imgui| {
-                let mut app_fonts = app_fonts.borrow_mut();
-
-                let font_size = 18.0;
-                let valthrun_font = imgui.fonts().add_font(&[FontSource::TtfData {
-                    data: include_bytes!("../resources/Valthrun-Regular.ttf"),
-                    size_pixels: font_size,
-                    config: Some(FontConfig {
-                        rasterizer_multiply: 1.5,
-                        oversample_h: 4,
-                        oversample_v: 4,
-                        ..FontConfig::default()
-                    }),
-                }]);
-
-                *app_fonts = Some(AppFonts {
-                    valthrun: valthrun_font,
-                });
-            }
+        font_init: Some(Arc::new({
+            let font_loader = font_loader.clone();
+            move |atlas| font_loader.lock().unwrap().build(atlas)
         })),
     };
 
@@ -611,11 +1193,16 @@ fn main_overlay() -> anyhow::Result<()> {
         }
     }
 
+    let fonts = font_loader
+        .lock()
+        .unwrap()
+        .take_fonts()
+        .context("failed to initialize app fonts")?;
+    app_state.set(fonts, ())?;
+    let map_overviews = map_overview::load_map_overviews(&mut overlay);
+
     let app = Application {
-        fonts: app_fonts
-            .borrow_mut()
-            .take()
-            .context("failed to initialize app fonts")?,
+        font_loader,
 
         app_state,
 
@@ -625,20 +1212,58 @@ fn main_overlay() -> anyhow::Result<()> {
         enhancements: vec![
             Rc::new(RefCell::new(PlayerESP::new())),
             Rc::new(RefCell::new(SpectatorsListIndicator::new())),
+            Rc::new(RefCell::new(SoundESP::new())),
+            Rc::new(RefCell::new(DamageIndicator::new())),
             Rc::new(RefCell::new(BombInfoIndicator::new())),
+            Rc::new(RefCell::new(BombESP::new())),
+            Rc::new(RefCell::new(DefuseKitESP::new())),
+            Rc::new(RefCell::new(GrenadeProjectileESP::new())),
+            Rc::new(RefCell::new(GrenadeWarning::new())),
+            Rc::new(RefCell::new(InfernoESP::new())),
+            Rc::new(RefCell::new(SmokeESP::new())),
+            Rc::new(RefCell::new(HostageESP::new())),
+            Rc::new(RefCell::new(ChickenESP::new())),
+            Rc::new(RefCell::new(BombTimerHud::new())),
+            Rc::new(RefCell::new(SniperCrosshair::new())),
+            Rc::new(RefCell::new(RecoilCrosshair::new())),
+            Rc::new(RefCell::new(Watermark::new())),
+            Rc::new(RefCell::new(BindList::new())),
             Rc::new(RefCell::new(TriggerBot::new())),
             Rc::new(RefCell::new(AntiAimPunsh::new())),
+            Rc::new(RefCell::new(GrenadeHelper::new())),
+            Rc::new(RefCell::new(DamageSummary::new())),
+            Rc::new(RefCell::new(RoundHud::new())),
+            Rc::new(RefCell::new(EconomyPanel::new())),
+            Rc::new(RefCell::new(KillFeed::new())),
+            Rc::new(RefCell::new(DeathMarkers::new())),
+            Rc::new(RefCell::new(ProximityAlert::new())),
+            Rc::new(RefCell::new(Compass::new())),
+            Rc::new(RefCell::new(Minimap::new(map_overviews))),
         ],
 
         last_total_read_calls: 0,
         frame_read_calls: 0,
 
         settings_visible: false,
+        settings_toggled: None,
         settings_dirty: false,
         settings_ui: RefCell::new(SettingsUI::new()),
         /* set the screen capture visibility at the beginning of the first update */
         settings_screen_capture_changed: AtomicBool::new(true),
         settings_render_debug_window_changed: AtomicBool::new(true),
+        settings_overlay_monitor_changed: AtomicBool::new(true),
+        settings_fonts_changed: AtomicBool::new(false),
+        game_viewport: [0.0; 4],
+        settings_changed: AtomicBool::new(false),
+        settings_autosave: SettingsAutosave::new(),
+        settings_overrides,
+        settings_warnings,
+        settings_warnings_shown: Default::default(),
+
+        active_profile,
+        profile_action: Default::default(),
+
+        panic_mode: false,
     };
     let app = Rc::new(RefCell::new(app));
 
@@ -653,6 +1278,11 @@ fn main_overlay() -> anyhow::Result<()> {
     );
 
     log::info!("{}", obfstr!("App initialized. Spawning overlay."));
+    overlay.on_exit({
+        let app = app.clone();
+        move |controller| app.borrow_mut().flush_settings(controller)
+    });
+
     let mut update_fail_count = 0;
     let mut update_timeout: Option<(Instant, Duration)> = None;
     overlay.main_loop(
@@ -662,6 +1292,7 @@ fn main_overlay() -> anyhow::Result<()> {
                 let mut app = app.borrow_mut();
                 if let Err(err) = app.pre_update(controller) {
                     show_critical_error(&format!("{:#}", err));
+                    app.flush_settings(controller);
                     false
                 } else {
                     true
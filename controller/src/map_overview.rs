@@ -0,0 +1,173 @@
+use std::{
+    collections::HashMap,
+    path::{
+        Path,
+        PathBuf,
+    },
+};
+
+use anyhow::Context;
+use imgui::TextureId;
+use obfstr::obfstr;
+
+/// Directory (next to the executable) containing the map overview images and calibration files.
+/// Every map requires a `<map name>.png` radar image and a `<map name>.txt` calibration file
+/// using the same format as the overview files of the game.
+const MAPS_DIRECTORY: &'static str = "maps";
+
+/// Image resolution the overview scale of the game refers to
+const OVERVIEW_RESOLUTION: f32 = 1024.0;
+
+/// Transformation between world coordinates and the overview image
+#[derive(Debug, Clone, Copy)]
+pub struct MapOverviewCalibration {
+    /// World position of the upper left image corner
+    pub pos_x: f32,
+    pub pos_y: f32,
+
+    /// World units per overview pixel
+    pub scale: f32,
+}
+
+impl MapOverviewCalibration {
+    /// Parse the calibration from the game overview (KeyValues) format
+    pub fn parse(content: &str) -> anyhow::Result<Self> {
+        let mut values = HashMap::new();
+        for line in content.lines() {
+            let tokens = line.split('"').skip(1).step_by(2).collect::<Vec<_>>();
+            if let [key, value] = tokens[..] {
+                values.entry(key.to_lowercase()).or_insert(value);
+            }
+        }
+
+        let read_value = |key: &str| -> anyhow::Result<f32> {
+            values
+                .get(key)
+                .with_context(|| format!("missing {}", key))?
+                .trim()
+                .parse::<f32>()
+                .with_context(|| format!("invalid {}", key))
+        };
+
+        let scale = read_value("scale")?;
+        if scale <= 0.0 {
+            anyhow::bail!("invalid scale");
+        }
+
+        Ok(Self {
+            pos_x: read_value("pos_x")?,
+            pos_y: read_value("pos_y")?,
+            scale,
+        })
+    }
+
+    /// Transform image UV coordinates into a world position (at height zero)
+    pub fn uv_to_world(&self, uv: [f32; 2]) -> nalgebra::Vector3<f32> {
+        let image_size = self.scale * OVERVIEW_RESOLUTION;
+        nalgebra::Vector3::new(
+            self.pos_x + uv[0] * image_size,
+            self.pos_y - uv[1] * image_size,
+            0.0,
+        )
+    }
+}
+
+/// Radar image of a map which has been uploaded as texture
+pub struct MapOverview {
+    pub texture_id: TextureId,
+    pub calibration: MapOverviewCalibration,
+}
+
+fn get_maps_directory() -> anyhow::Result<PathBuf> {
+    let exe_file = std::env::current_exe().context("missing current exe path")?;
+    let base_dir = exe_file.parent().context("could not get exe directory")?;
+
+    Ok(base_dir.join(MAPS_DIRECTORY))
+}
+
+fn load_map_overview(
+    overlay: &mut overlay::System,
+    image_path: &Path,
+    calibration_path: &Path,
+) -> anyhow::Result<MapOverview> {
+    let calibration =
+        std::fs::read_to_string(calibration_path).context("failed to read calibration file")?;
+    let calibration = MapOverviewCalibration::parse(&calibration)?;
+
+    let image = image::open(image_path)
+        .context("failed to load image")?
+        .to_rgba8();
+
+    let texture_id = overlay
+        .register_texture(image.width(), image.height(), image.as_raw())
+        .context("failed to upload texture")?;
+
+    Ok(MapOverview {
+        texture_id,
+        calibration,
+    })
+}
+
+/// Load all map overviews from the maps directory.
+/// Maps which fail to load will be skipped.
+pub fn load_map_overviews(overlay: &mut overlay::System) -> HashMap<String, MapOverview> {
+    let mut overviews = HashMap::new();
+
+    let maps_directory = match get_maps_directory() {
+        Ok(directory) => directory,
+        Err(error) => {
+            log::warn!(
+                "{}: {:#}",
+                obfstr!("Failed to resolve maps directory"),
+                error
+            );
+            return overviews;
+        }
+    };
+
+    let entries = match std::fs::read_dir(&maps_directory) {
+        Ok(entries) => entries,
+        Err(_) => {
+            log::debug!(
+                "{} {}",
+                obfstr!("No map overviews found at"),
+                maps_directory.display()
+            );
+            return overviews;
+        }
+    };
+
+    for entry in entries.flatten() {
+        let image_path = entry.path();
+        if image_path
+            .extension()
+            .map(|ext| ext != "png")
+            .unwrap_or(true)
+        {
+            continue;
+        }
+
+        let map_name = match image_path.file_stem().and_then(|name| name.to_str()) {
+            Some(name) => name.to_string(),
+            None => continue,
+        };
+
+        let calibration_path = image_path.with_extension("txt");
+        match load_map_overview(overlay, &image_path, &calibration_path) {
+            Ok(overview) => {
+                overviews.insert(map_name, overview);
+            }
+            Err(error) => {
+                log::warn!(
+                    "{} {}: {:#}",
+                    obfstr!("Failed to load map overview for"),
+                    map_name,
+                    error
+                );
+            }
+        }
+    }
+
+    log::debug!("{} {} map overviews", obfstr!("Loaded"), overviews.len());
+    overviews
+}
@@ -0,0 +1,123 @@
+use std::{
+    thread::JoinHandle,
+    time::{
+        Duration,
+        Instant,
+    },
+};
+
+use super::{
+    create_config_backup,
+    get_profile_path,
+    write_config_file,
+    ConfigBackupOptions,
+};
+
+/// Min time between two automatic saves
+const AUTOSAVE_INTERVAL: Duration = Duration::from_secs(3);
+
+/// Saves changed settings in the background.
+/// Changes are collected and written at most once every [AUTOSAVE_INTERVAL].
+pub struct SettingsAutosave {
+    pending: bool,
+    last_save: Option<Instant>,
+    worker: Option<JoinHandle<()>>,
+}
+
+impl SettingsAutosave {
+    pub fn new() -> Self {
+        Self {
+            pending: false,
+            last_save: None,
+            worker: None,
+        }
+    }
+
+    /// Schedule a save of the current settings
+    pub fn mark_dirty(&mut self) {
+        self.pending = true;
+    }
+
+    pub fn is_dirty(&self) -> bool {
+        self.pending
+    }
+
+    /// Returns true if the pending changes should be saved now.
+    pub fn should_save(&self) -> bool {
+        if !self.pending {
+            return false;
+        }
+
+        if self
+            .worker
+            .as_ref()
+            .map(|worker| !worker.is_finished())
+            .unwrap_or(false)
+        {
+            /* wait for the previous save so writes never overtake each other */
+            return false;
+        }
+
+        self.last_save
+            .map(|last_save| last_save.elapsed() >= AUTOSAVE_INTERVAL)
+            .unwrap_or(true)
+    }
+
+    /// Write the serialized settings of the given profile on a background thread.
+    /// The previous config will be backed up before.
+    pub fn save(&mut self, profile: &str, content: String, backup: Option<ConfigBackupOptions>) {
+        self.pending = false;
+        self.last_save = Some(Instant::now());
+
+        let profile = profile.to_string();
+        self.worker = Some(std::thread::spawn(move || {
+            let result = get_profile_path(&profile).and_then(|config_path| {
+                if let Some(backup) = &backup {
+                    if let Err(error) = create_config_backup(backup, &profile, &config_path) {
+                        log::warn!("Failed to back up the config: {:#}", error);
+                    }
+                }
+
+                write_config_file(&config_path, &content)
+            });
+
+            match result {
+                Ok(_) => log::debug!("Saved app config ({}).", profile),
+                Err(error) => log::warn!("Failed to save user settings: {:#}", error),
+            }
+        }));
+    }
+
+    /// Wait until the current background save has been finished.
+    /// Pending changes are not affected and will be saved by the next save.
+    pub fn wait(&mut self) {
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+
+    /// The settings have been saved synchronously (outside of the autosave)
+    pub fn mark_saved(&mut self) {
+        self.pending = false;
+        self.last_save = Some(Instant::now());
+    }
+
+    /// Save the pending changes immediately and wait until all saves have been finished.
+    /// `serialize` will only be invoked if there are pending changes.
+    pub fn flush(
+        &mut self,
+        profile: &str,
+        serialize: impl FnOnce() -> Option<(String, Option<ConfigBackupOptions>)>,
+    ) {
+        if self.is_dirty() {
+            /* writes must never overtake each other */
+            self.wait();
+            match serialize() {
+                Some((content, backup)) => self.save(profile, content, backup),
+                None => self.pending = false,
+            }
+        }
+
+        self.wait();
+    }
+}
@@ -0,0 +1,201 @@
+use std::{
+    path::{
+        Path,
+        PathBuf,
+    },
+    time::SystemTime,
+};
+
+use anyhow::Context;
+use chrono::{
+    DateTime,
+    Local,
+    NaiveDateTime,
+};
+
+use super::{
+    parse_app_config,
+    AppSettings,
+};
+
+/// Default directory (next to the executable) containing the config backups
+const BACKUP_DIRECTORY: &'static str = "backups";
+
+/// Timestamp format within the backup file names.
+/// The format sorts chronologically.
+const BACKUP_TIMESTAMP_FORMAT: &'static str = "%Y%m%d-%H%M%S-%3f";
+
+/// Where and how many config backups should be kept
+#[derive(Debug, Clone)]
+pub struct ConfigBackupOptions {
+    pub directory: PathBuf,
+    pub keep: usize,
+}
+
+impl ConfigBackupOptions {
+    pub fn from_settings(settings: &AppSettings) -> anyhow::Result<Self> {
+        let directory = if settings.config_backup_directory.trim().is_empty() {
+            let exe_file = std::env::current_exe().context("missing current exe path")?;
+            exe_file
+                .parent()
+                .context("could not get exe directory")?
+                .join(BACKUP_DIRECTORY)
+        } else {
+            PathBuf::from(settings.config_backup_directory.trim())
+        };
+
+        Ok(Self {
+            directory,
+            keep: settings.config_backup_count as usize,
+        })
+    }
+}
+
+/// Previous version of a profile config
+#[derive(Debug, Clone)]
+pub struct ConfigBackup {
+    pub path: PathBuf,
+    pub profile: String,
+    pub modified: DateTime<Local>,
+}
+
+fn parse_backup_profile(file_name: &str) -> Option<String> {
+    let name = file_name.strip_suffix(".yaml")?;
+    let (profile, timestamp) = name.rsplit_once('_')?;
+    NaiveDateTime::parse_from_str(timestamp, BACKUP_TIMESTAMP_FORMAT).ok()?;
+    Some(profile.to_string())
+}
+
+/// List all backups of the given profile, newest first
+pub fn list_config_backups(
+    options: &ConfigBackupOptions,
+    profile: &str,
+) -> anyhow::Result<Vec<ConfigBackup>> {
+    if !options.directory.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let entries = std::fs::read_dir(&options.directory).with_context(|| {
+        format!(
+            "failed to read backup directory {}",
+            options.directory.to_string_lossy()
+        )
+    })?;
+
+    let mut backups = Vec::new();
+    for entry in entries {
+        let entry = entry.context("failed to read backup directory")?;
+        let path = entry.path();
+        let file_name = match path.file_name().and_then(|name| name.to_str()) {
+            Some(name) => name,
+            None => continue,
+        };
+
+        if parse_backup_profile(file_name).as_deref() != Some(profile) {
+            continue;
+        }
+
+        let modified = entry
+            .metadata()
+            .and_then(|metadata| metadata.modified())
+            .unwrap_or(SystemTime::UNIX_EPOCH);
+
+        backups.push(ConfigBackup {
+            path,
+            profile: profile.to_string(),
+            modified: modified.into(),
+        });
+    }
+
+    /* the timestamp in the file name is more reliable than the modification time */
+    backups.sort_by(|a, b| b.path.cmp(&a.path));
+    Ok(backups)
+}
+
+/// Copy the current config of the profile into the backup directory and
+/// remove the oldest backups exceeding the backup limit.
+pub fn create_config_backup(
+    options: &ConfigBackupOptions,
+    profile: &str,
+    config_path: &Path,
+) -> anyhow::Result<()> {
+    if options.keep == 0 || !config_path.is_file() {
+        return Ok(());
+    }
+
+    std::fs::create_dir_all(&options.directory).with_context(|| {
+        format!(
+            "failed to create backup directory {}",
+            options.directory.to_string_lossy()
+        )
+    })?;
+
+    let backup_path = options.directory.join(format!(
+        "{}_{}.yaml",
+        profile,
+        Local::now().format(BACKUP_TIMESTAMP_FORMAT)
+    ));
+    std::fs::copy(config_path, &backup_path).with_context(|| {
+        format!(
+            "failed to create config backup at {}",
+            backup_path.to_string_lossy()
+        )
+    })?;
+
+    for backup in list_config_backups(options, profile)?
+        .into_iter()
+        .skip(options.keep)
+    {
+        if let Err(error) = std::fs::remove_file(&backup.path) {
+            log::warn!(
+                "Failed to remove old config backup {}: {}",
+                backup.path.to_string_lossy(),
+                error
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Load a backup like a regular config including migrations and validation.
+/// Returns the settings and warnings about invalid values.
+pub fn load_config_backup(backup: &ConfigBackup) -> anyhow::Result<(AppSettings, Vec<String>)> {
+    let content = std::fs::read_to_string(&backup.path).with_context(|| {
+        format!(
+            "failed to read config backup {}",
+            backup.path.to_string_lossy()
+        )
+    })?;
+
+    let (settings, warnings, _version) = parse_app_config(&content)?;
+    Ok((settings, warnings))
+}
+
+/// Names of all top level settings which differ between the two settings
+pub fn changed_config_sections(
+    current: &AppSettings,
+    other: &AppSettings,
+) -> anyhow::Result<Vec<String>> {
+    let current = serde_yaml::to_value(current).context("failed to serialize settings")?;
+    let other = serde_yaml::to_value(other).context("failed to serialize settings")?;
+
+    let (current, other) = match (current.as_mapping(), other.as_mapping()) {
+        (Some(current), Some(other)) => (current, other),
+        _ => anyhow::bail!("settings are not a mapping"),
+    };
+
+    let mut sections = current
+        .iter()
+        .chain(other.iter())
+        .filter_map(|(key, _)| key.as_str())
+        /* the window layout is not a setting */
+        .filter(|key| *key != "imgui")
+        .filter(|key| current.get(*key) != other.get(*key))
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+
+    sections.sort();
+    sections.dedup();
+    Ok(sections)
+}
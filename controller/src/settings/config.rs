@@ -1,11 +1,12 @@
 use std::{
     collections::BTreeMap,
     fs::File,
-    io::{
-        BufReader,
-        BufWriter,
+    io::Write,
+    path::{
+        Path,
+        PathBuf,
     },
-    path::PathBuf,
+    sync::OnceLock,
 };
 
 use anyhow::Context;
@@ -20,10 +21,17 @@ use utils_state::{
 };
 
 use super::{
+    get_profile_path,
+    migrate_config,
+    parse_app_settings,
+    Color,
+    ColorPalette,
     EspConfig,
     EspPlayerSettings,
     EspSelector,
+    GrenadeSpots,
     HotKey,
+    CONFIG_VERSION,
 };
 
 fn bool_true() -> bool {
@@ -39,6 +47,10 @@ fn default_i32<const V: i32>() -> i32 {
     V
 }
 
+fn default_config_version() -> u32 {
+    CONFIG_VERSION
+}
+
 fn default_key_settings() -> HotKey {
     Key::Pause.into()
 }
@@ -49,12 +61,210 @@ fn default_key_none() -> Option<HotKey> {
     None
 }
 
-fn default_esp_mode() -> KeyToggleMode {
-    KeyToggleMode::AlwaysOn
+fn default_color_palette() -> ColorPalette {
+    ColorPalette::classic()
+}
+
+fn default_sound_esp_color() -> Color {
+    Color::from_f32([1.0, 0.85, 0.0, 0.8])
+}
+fn default_sound_esp_radius_scale() -> f32 {
+    1.0
+}
+fn default_sound_esp_max_distance() -> f32 {
+    30.0
+}
+
+fn default_damage_indicator_color() -> Color {
+    Color::from_f32([1.0, 0.3, 0.3, 1.0])
+}
+fn default_font_size() -> f32 {
+    18.0
+}
+fn default_damage_indicator_text_scale() -> f32 {
+    1.25
+}
+
+fn default_defuse_kit_esp_color() -> Color {
+    Color::from_f32([0.3, 0.6, 1.0, 1.0])
+}
+fn default_defuse_kit_esp_max_distance() -> f32 {
+    50.0
+}
+
+fn default_sniper_crosshair_size() -> f32 {
+    6.0
+}
+fn default_sniper_crosshair_gap() -> f32 {
+    2.0
+}
+fn default_sniper_crosshair_thickness() -> f32 {
+    1.5
+}
+fn default_sniper_crosshair_color() -> Color {
+    Color::from_f32([0.0, 1.0, 0.0, 1.0])
+}
+
+fn default_recoil_crosshair_size() -> f32 {
+    3.0
+}
+fn default_recoil_crosshair_color() -> Color {
+    Color::from_f32([1.0, 0.0, 0.0, 1.0])
+}
+
+fn default_watermark_mode() -> ActivationMode {
+    ActivationMode::Off
+}
+fn default_watermark_color() -> Color {
+    Color::from_f32([1.0, 1.0, 1.0, 1.0])
+}
+fn default_watermark_background_alpha() -> f32 {
+    0.5
+}
+
+fn default_overlay_opacity() -> f32 {
+    1.0
+}
+
+fn default_menu_dim_alpha() -> f32 {
+    0.5
+}
+
+fn default_bind_list_position() -> OverlayCorner {
+    OverlayCorner::BottomLeft
+}
+fn default_bind_list_color() -> Color {
+    Color::from_f32([1.0, 1.0, 1.0, 1.0])
+}
+fn default_bind_list_background_alpha() -> f32 {
+    0.5
+}
+
+fn default_round_hud_position() -> OverlayCorner {
+    OverlayCorner::TopRight
+}
+fn default_round_hud_color() -> Color {
+    Color::from_f32([1.0, 1.0, 1.0, 1.0])
+}
+
+fn default_kill_feed_position() -> OverlayCorner {
+    OverlayCorner::BottomRight
+}
+fn default_kill_feed_color() -> Color {
+    Color::from_f32([1.0, 1.0, 1.0, 1.0])
+}
+fn default_kill_feed_duration() -> f32 {
+    6.0
+}
+
+fn default_death_markers_color() -> Color {
+    Color::from_f32([1.0, 0.35, 0.35, 0.8])
+}
+fn default_death_markers_max_distance() -> f32 {
+    50.0
+}
+fn default_death_markers_max_age() -> f32 {
+    120.0
 }
 
-fn default_trigger_bot_mode() -> KeyToggleMode {
-    KeyToggleMode::Trigger
+fn default_proximity_alert_color() -> Color {
+    Color::from_f32([1.0, 1.0, 1.0, 1.0])
+}
+fn default_proximity_alert_radius() -> f32 {
+    10.0
+}
+
+fn default_compass_width() -> f32 {
+    600.0
+}
+fn default_compass_color() -> Color {
+    Color::from_f32([1.0, 1.0, 1.0, 1.0])
+}
+
+fn default_minimap_size() -> f32 {
+    200.0
+}
+fn default_minimap_zoom() -> f32 {
+    0.15
+}
+fn default_minimap_map_overview_alpha() -> f32 {
+    0.8
+}
+fn default_minimap_team_color() -> Color {
+    Color::from_f32([0.3, 0.6, 1.0, 1.0])
+}
+fn default_minimap_enemy_color() -> Color {
+    Color::from_f32([1.0, 0.2, 0.2, 1.0])
+}
+fn default_minimap_bomb_color() -> Color {
+    Color::from_f32([1.0, 0.8, 0.0, 1.0])
+}
+
+fn default_economy_panel_mode() -> ActivationMode {
+    ActivationMode::Off
+}
+fn default_economy_full_buy_threshold() -> i32 {
+    4500
+}
+fn default_economy_force_buy_threshold() -> i32 {
+    2000
+}
+
+fn default_spectators_list_background_alpha() -> f32 {
+    0.5
+}
+
+fn default_grenade_warning_radius() -> f32 {
+    10.0
+}
+fn default_grenade_warning_color() -> Color {
+    Color::from_f32([1.0, 0.85, 0.2, 1.0])
+}
+
+fn default_grenade_helper_max_distance() -> f32 {
+    15.0
+}
+fn default_grenade_helper_color() -> Color {
+    Color::from_f32([0.0, 0.75, 1.0, 1.0])
+}
+
+fn default_inferno_esp_color() -> Color {
+    Color::from_f32([1.0, 0.45, 0.0, 1.0])
+}
+
+fn default_smoke_esp_color() -> Color {
+    Color::from_f32([0.75, 0.75, 0.75, 0.5])
+}
+
+fn default_grenade_esp_color_he() -> Color {
+    Color::from_f32([1.0, 0.25, 0.25, 1.0])
+}
+fn default_grenade_esp_color_flash() -> Color {
+    Color::from_f32([1.0, 1.0, 0.6, 1.0])
+}
+fn default_grenade_esp_color_smoke() -> Color {
+    Color::from_f32([0.75, 0.75, 0.75, 1.0])
+}
+fn default_grenade_esp_color_fire() -> Color {
+    Color::from_f32([1.0, 0.55, 0.0, 1.0])
+}
+fn default_grenade_esp_color_decoy() -> Color {
+    Color::from_f32([0.5, 0.8, 0.5, 1.0])
+}
+
+fn default_trigger_bot_max_velocity() -> f32 {
+    40.0
+}
+fn default_trigger_bot_max_punch() -> f32 {
+    0.5
+}
+
+fn default_esp_mode() -> ActivationMode {
+    ActivationMode::Always
+}
+
+fn default_trigger_bot_mode() -> ActivationMode {
+    ActivationMode::Hold
 }
 
 fn default_esp_configs() -> BTreeMap<String, EspConfig> {
@@ -74,25 +284,161 @@ fn default_esp_configs_enabled() -> BTreeMap<String, bool> {
     result
 }
 
+/// How a feature is activated by its keybind
 #[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, PartialOrd)]
-pub enum KeyToggleMode {
-    AlwaysOn,
+pub enum ActivationMode {
+    /// Always active, the keybind is ignored
+    Always,
+
+    /// Pressing the keybind toggles the feature
     Toggle,
-    Trigger,
-    TriggerInverted,
+
+    /// Active while the keybind is held down
+    Hold,
+
+    /// Active unless the keybind is held down
+    HoldInverted,
+
+    /// Pressing the keybind twice within the double tap window toggles the feature
+    DoubleTap,
+
+    /// Holding the keybind for the long press duration toggles the feature
+    LongPress,
+
     Off,
 }
 
+impl ActivationMode {
+    /// Returns true if the mode depends on a keybind
+    pub fn uses_key(&self) -> bool {
+        matches!(
+            self,
+            Self::Toggle | Self::Hold | Self::HoldInverted | Self::DoubleTap | Self::LongPress
+        )
+    }
+}
+
+/// Body parts the trigger bot is allowed to shoot at
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, PartialOrd)]
+pub enum TriggerBotHitboxFilter {
+    Head,
+    HeadChest,
+    Any,
+}
+
+impl Default for TriggerBotHitboxFilter {
+    fn default() -> Self {
+        Self::Any
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, PartialOrd)]
+pub enum SniperCrosshairStyle {
+    Cross,
+    Dot,
+    Circle,
+}
+
+impl Default for SniperCrosshairStyle {
+    fn default() -> Self {
+        Self::Cross
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, PartialOrd)]
+pub enum OverlayCorner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+impl Default for OverlayCorner {
+    fn default() -> Self {
+        Self::TopLeft
+    }
+}
+
+/// Vertical screen position of the compass strip
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, PartialOrd)]
+pub enum CompassPosition {
+    Top,
+    Bottom,
+}
+
+impl Default for CompassPosition {
+    fn default() -> Self {
+        Self::Top
+    }
+}
+
+/// Color source of the player markers on the compass strip
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, PartialOrd)]
+pub enum CompassColorMode {
+    Team,
+    Health,
+}
+
+impl Default for CompassColorMode {
+    fn default() -> Self {
+        Self::Health
+    }
+}
+
+/// Screen position of the spectators list
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, PartialOrd)]
+pub enum SpectatorsListPosition {
+    Left,
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+
+    /// Freely movable window.
+    /// The window position is stored within the imgui settings of the app config.
+    Custom,
+}
+
+impl Default for SpectatorsListPosition {
+    fn default() -> Self {
+        Self::Left
+    }
+}
+
 #[derive(Clone, Deserialize, Serialize)]
 pub struct AppSettings {
+    /// Version of the config format (see CONFIG_VERSION)
+    #[serde(default = "default_config_version")]
+    pub config_version: u32,
+
     #[serde(default = "default_key_settings")]
     pub key_settings: HotKey,
 
+    /// Switch to the next settings profile
+    #[serde(default = "default_key_none")]
+    pub key_profile_cycle: Option<HotKey>,
+
+    /// Instantly stop all enhancements and hide the overlay until pressed again
+    #[serde(default = "default_key_none")]
+    pub panic_key: Option<HotKey>,
+
+    /// Toggle hide_overlay_from_screen_capture
+    #[serde(default = "default_key_none")]
+    pub key_capture_protection: Option<HotKey>,
+
+    /// Max delay in milliseconds between the two presses of a double tap bind
+    #[serde(default = "default_u32::<300>")]
+    pub key_double_tap_window: u32,
+
+    /// Min hold duration in milliseconds of a long press bind
+    #[serde(default = "default_u32::<500>")]
+    pub key_long_press_duration: u32,
+
     #[serde(default = "default_esp_mode")]
-    pub esp_mode: KeyToggleMode,
+    pub esp_mode: ActivationMode,
 
     #[serde(default = "default_key_none")]
-    pub esp_toogle: Option<HotKey>,
+    pub esp_toggle: Option<HotKey>,
 
     #[serde(default = "default_esp_configs")]
     pub esp_settings: BTreeMap<String, EspConfig>,
@@ -100,20 +446,426 @@ pub struct AppSettings {
     #[serde(default = "default_esp_configs_enabled")]
     pub esp_settings_enabled: BTreeMap<String, bool>,
 
+    /// Named colors referenced by palette based ESP colors
+    #[serde(default = "default_color_palette")]
+    pub color_palette: ColorPalette,
+
+    /// Scale of the ESP line widths and text (0 = derived from the screen resolution)
+    #[serde(default)]
+    pub ui_scale_override: f32,
+
+    /// Opacity multiplier for everything drawn by the enhancements (0.1 - 1.0).
+    /// The settings menu is not affected.
+    #[serde(default = "default_overlay_opacity")]
+    pub overlay_opacity: f32,
+
+    /// Max amount of enemies to render the ESP for (0 = unlimited)
+    #[serde(default = "default_u32::<0>")]
+    pub esp_max_enemies: u32,
+
+    /// Max amount of teammates to render the ESP for (0 = unlimited)
+    #[serde(default = "default_u32::<0>")]
+    pub esp_max_teammates: u32,
+
+    /// Render farther players first so nearer players are drawn on top
+    #[serde(default = "bool_true")]
+    pub esp_render_far_first: bool,
+
+    /// Players (by name or SteamID64) which will be ignored by the ESP
+    #[serde(default)]
+    pub player_blacklist: Vec<String>,
+
     #[serde(default = "bool_true")]
     pub bomb_timer: bool,
 
+    /// Draggable widget with the C4 time and defuse windows
+    #[serde(default = "bool_false")]
+    pub bomb_timer_hud: bool,
+
+    /// Marker at the planted or dropped C4
+    #[serde(default = "bool_false")]
+    pub bomb_esp: bool,
+
+    /// Panel with the C4 timer and defuse feasibility
+    #[serde(default = "bool_true")]
+    pub bomb_esp_panel: bool,
+
+    /// Label thrown grenades while they're in flight
+    #[serde(default = "bool_false")]
+    pub grenade_esp: bool,
+
+    /// Only show grenades which have been thrown by enemies
+    #[serde(default = "bool_true")]
+    pub grenade_esp_enemy_only: bool,
+
+    /// Show the predicted trajectory of the grenades
+    #[serde(default = "bool_true")]
+    pub grenade_esp_prediction: bool,
+
+    #[serde(default = "default_grenade_esp_color_he")]
+    pub grenade_esp_color_he: Color,
+
+    #[serde(default = "default_grenade_esp_color_flash")]
+    pub grenade_esp_color_flash: Color,
+
+    #[serde(default = "default_grenade_esp_color_smoke")]
+    pub grenade_esp_color_smoke: Color,
+
+    #[serde(default = "default_grenade_esp_color_fire")]
+    pub grenade_esp_color_fire: Color,
+
+    #[serde(default = "default_grenade_esp_color_decoy")]
+    pub grenade_esp_color_decoy: Color,
+
+    /// Warning banner when a grenade is about to detonate next to the local player
+    #[serde(default = "bool_false")]
+    pub grenade_warning: bool,
+
+    /// Max distance in meters between the predicted detonation point and the local player
+    #[serde(default = "default_grenade_warning_radius")]
+    pub grenade_warning_radius: f32,
+
+    #[serde(default = "default_grenade_warning_color")]
+    pub grenade_warning_color: Color,
+
+    #[serde(default = "bool_true")]
+    pub grenade_warning_flash: bool,
+
+    #[serde(default = "bool_true")]
+    pub grenade_warning_he: bool,
+
+    #[serde(default = "bool_true")]
+    pub grenade_warning_molotov: bool,
+
+    /// Show saved grenade lineups of the current map
+    #[serde(default = "bool_false")]
+    pub grenade_helper: bool,
+
+    /// Only show the lineups while holding a grenade
+    #[serde(default = "bool_true")]
+    pub grenade_helper_holding_only: bool,
+
+    /// Max distance in meters to the stand position
+    #[serde(default = "default_grenade_helper_max_distance")]
+    pub grenade_helper_max_distance: f32,
+
+    #[serde(default = "default_grenade_helper_color")]
+    pub grenade_helper_color: Color,
+
+    #[serde(default)]
+    pub grenade_spots: GrenadeSpots,
+
+    /// Outline of the area covered by molotov and incendiary fires
+    #[serde(default = "bool_false")]
+    pub inferno_esp: bool,
+
+    #[serde(default = "default_inferno_esp_color")]
+    pub inferno_esp_color: Color,
+
+    /// Show the remaining burn time
+    #[serde(default = "bool_true")]
+    pub inferno_esp_timer: bool,
+
+    /// Wireframe of active smoke clouds
+    #[serde(default = "bool_false")]
+    pub smoke_esp: bool,
+
+    #[serde(default = "default_smoke_esp_color")]
+    pub smoke_esp_color: Color,
+
+    /// Show the time until the smoke dissipates
+    #[serde(default = "bool_true")]
+    pub smoke_esp_timer: bool,
+
+    /// Marker for dropped defuse kits
+    #[serde(default = "bool_false")]
+    pub defuse_kit_esp: bool,
+
+    /// Show the defuse kits even when not playing as counter terrorist
+    #[serde(default = "bool_false")]
+    pub defuse_kit_esp_always: bool,
+
+    #[serde(default = "default_defuse_kit_esp_color")]
+    pub defuse_kit_esp_color: Color,
+
+    /// Max distance in meters
+    #[serde(default = "default_defuse_kit_esp_max_distance")]
+    pub defuse_kit_esp_max_distance: f32,
+
+    /// Crosshair for sniper rifles while not scoped
+    #[serde(default = "bool_false")]
+    pub sniper_crosshair: bool,
+
+    #[serde(default)]
+    pub sniper_crosshair_style: SniperCrosshairStyle,
+
+    #[serde(default = "default_sniper_crosshair_size")]
+    pub sniper_crosshair_size: f32,
+
+    #[serde(default = "default_sniper_crosshair_gap")]
+    pub sniper_crosshair_gap: f32,
+
+    #[serde(default = "default_sniper_crosshair_thickness")]
+    pub sniper_crosshair_thickness: f32,
+
+    #[serde(default = "default_sniper_crosshair_color")]
+    pub sniper_crosshair_color: Color,
+
+    /// Dot where the bullets will land according to the current recoil
+    #[serde(default = "bool_false")]
+    pub recoil_crosshair: bool,
+
+    #[serde(default = "default_recoil_crosshair_size")]
+    pub recoil_crosshair_size: f32,
+
+    #[serde(default = "default_recoil_crosshair_color")]
+    pub recoil_crosshair_color: Color,
+
+    /// Only show the recoil crosshair while shooting
+    #[serde(default = "bool_true")]
+    pub recoil_crosshair_firing_only: bool,
+
     #[serde(default = "bool_false")]
     pub spectators_list: bool,
 
+    #[serde(default)]
+    pub spectators_list_position: SpectatorsListPosition,
+
+    #[serde(default = "default_spectators_list_background_alpha")]
+    pub spectators_list_background_alpha: f32,
+
+    #[serde(default = "bool_false")]
+    pub sound_esp: bool,
+
+    #[serde(default = "default_sound_esp_color")]
+    pub sound_esp_color: Color,
+
+    #[serde(default = "default_sound_esp_radius_scale")]
+    pub sound_esp_radius_scale: f32,
+
+    /// Max distance in meters
+    #[serde(default = "default_sound_esp_max_distance")]
+    pub sound_esp_max_distance: f32,
+
+    #[serde(default = "bool_false")]
+    pub damage_indicator: bool,
+
+    #[serde(default = "default_damage_indicator_color")]
+    pub damage_indicator_color: Color,
+
+    #[serde(default = "default_damage_indicator_text_scale")]
+    pub damage_indicator_text_scale: f32,
+
+    #[serde(default = "bool_false")]
+    pub damage_indicator_hitmarker: bool,
+
+    /// Only count damage which has been dealt while the local player is shooting
+    #[serde(default = "bool_true")]
+    pub damage_indicator_local_only: bool,
+
+    /// Summary of the damage dealt within the current round
+    #[serde(default = "bool_false")]
+    pub damage_summary: bool,
+
+    /// Show the damage summary when the round ends
+    #[serde(default = "bool_true")]
+    pub damage_summary_round_end: bool,
+
+    /// Show the damage summary while this key is pressed
+    #[serde(default = "default_key_none")]
+    pub damage_summary_key: Option<HotKey>,
+
     #[serde(default = "bool_true")]
     pub valthrun_watermark: bool,
 
+    /// List of all currently active features
+    #[serde(default = "bool_false")]
+    pub bind_list: bool,
+
+    #[serde(default = "default_bind_list_position")]
+    pub bind_list_position: OverlayCorner,
+
+    #[serde(default = "default_bind_list_color")]
+    pub bind_list_color: Color,
+
+    #[serde(default = "default_bind_list_background_alpha")]
+    pub bind_list_background_alpha: f32,
+
+    /// HUD with the remaining round, freeze and buy time
+    #[serde(default = "bool_false")]
+    pub round_hud: bool,
+
+    #[serde(default = "default_round_hud_position")]
+    pub round_hud_position: OverlayCorner,
+
+    #[serde(default = "default_round_hud_color")]
+    pub round_hud_color: Color,
+
+    #[serde(default = "bool_true")]
+    pub round_hud_buy_time: bool,
+
+    /// Custom kill feed with the health and distance of killed players
+    #[serde(default = "bool_false")]
+    pub kill_feed: bool,
+
+    #[serde(default = "default_kill_feed_position")]
+    pub kill_feed_position: OverlayCorner,
+
+    #[serde(default = "default_kill_feed_color")]
+    pub kill_feed_color: Color,
+
+    #[serde(default = "default_u32::<5>")]
+    pub kill_feed_max_entries: u32,
+
+    /// Display duration of an entry in seconds
+    #[serde(default = "default_kill_feed_duration")]
+    pub kill_feed_duration: f32,
+
+    #[serde(default = "bool_false")]
+    pub kill_feed_enemies_only: bool,
+
+    /// Markers at the death positions of enemies within the current round (requires the ESP to be enabled)
+    #[serde(default = "bool_false")]
+    pub death_markers: bool,
+
+    #[serde(default = "default_death_markers_color")]
+    pub death_markers_color: Color,
+
+    #[serde(default = "bool_false")]
+    pub death_markers_name: bool,
+
+    /// Max distance to the marker in meters
+    #[serde(default = "default_death_markers_max_distance")]
+    pub death_markers_max_distance: f32,
+
+    /// Max age of a marker in seconds
+    #[serde(default = "default_death_markers_max_age")]
+    pub death_markers_max_age: f32,
+
+    /// Distance and direction to the nearest alive enemy
+    #[serde(default = "bool_false")]
+    pub proximity_alert: bool,
+
+    #[serde(default = "default_proximity_alert_color")]
+    pub proximity_alert_color: Color,
+
+    /// Radius in meters in which the alert becomes active
+    #[serde(default = "default_proximity_alert_radius")]
+    pub proximity_alert_radius: f32,
+
+    #[serde(default = "bool_true")]
+    pub proximity_alert_pulse: bool,
+
+    /// Compass strip with the bearings of all alive enemies
+    #[serde(default = "bool_false")]
+    pub compass: bool,
+
+    /// Width of the compass strip in pixels
+    #[serde(default = "default_compass_width")]
+    pub compass_width: f32,
+
+    #[serde(default)]
+    pub compass_position: CompassPosition,
+
+    #[serde(default = "default_compass_color")]
+    pub compass_color: Color,
+
+    #[serde(default = "bool_false")]
+    pub compass_teammates: bool,
+
+    #[serde(default)]
+    pub compass_color_mode: CompassColorMode,
+
+    /// On screen minimap with all players relative to the local player
+    #[serde(default = "bool_false")]
+    pub minimap: bool,
+
+    /// Width and height of the minimap in pixels
+    #[serde(default = "default_minimap_size")]
+    pub minimap_size: f32,
+
+    #[serde(default)]
+    pub minimap_position: OverlayCorner,
+
+    /// Zoom level in meters per pixel
+    #[serde(default = "default_minimap_zoom")]
+    pub minimap_zoom: f32,
+
+    /// Rotate the minimap so the view direction of the local player always points upwards
+    #[serde(default = "bool_true")]
+    pub minimap_rotate: bool,
+
+    #[serde(default = "bool_true")]
+    pub minimap_teammates: bool,
+
+    /// Render the radar image of the current map (if available in the maps directory)
+    #[serde(default = "bool_true")]
+    pub minimap_map_overview: bool,
+
+    #[serde(default = "default_minimap_map_overview_alpha")]
+    pub minimap_map_overview_alpha: f32,
+
+    #[serde(default = "default_minimap_team_color")]
+    pub minimap_team_color: Color,
+
+    #[serde(default = "default_minimap_enemy_color")]
+    pub minimap_enemy_color: Color,
+
+    #[serde(default = "default_minimap_bomb_color")]
+    pub minimap_bomb_color: Color,
+
+    /// Window with the money of all enemies
+    #[serde(default = "default_economy_panel_mode")]
+    pub economy_panel_mode: ActivationMode,
+
+    #[serde(default = "default_key_none")]
+    pub economy_panel_toggle: Option<HotKey>,
+
+    /// Min average money at round start for the enemy team to be able to full buy
+    #[serde(default = "default_economy_full_buy_threshold")]
+    pub economy_full_buy_threshold: i32,
+
+    /// Min average money at round start for the enemy team to be able to force buy
+    #[serde(default = "default_economy_force_buy_threshold")]
+    pub economy_force_buy_threshold: i32,
+
+    /// Stats bar with FPS, time, ping and version
+    #[serde(default = "default_watermark_mode")]
+    pub watermark_mode: ActivationMode,
+
+    #[serde(default = "default_key_none")]
+    pub watermark_toggle: Option<HotKey>,
+
+    #[serde(default)]
+    pub watermark_position: OverlayCorner,
+
+    #[serde(default = "default_watermark_color")]
+    pub watermark_color: Color,
+
+    #[serde(default = "default_watermark_background_alpha")]
+    pub watermark_background_alpha: f32,
+
+    #[serde(default = "bool_true")]
+    pub watermark_fps: bool,
+
+    #[serde(default = "bool_true")]
+    pub watermark_time: bool,
+
+    #[serde(default = "bool_true")]
+    pub watermark_ping: bool,
+
+    #[serde(default = "bool_true")]
+    pub watermark_version: bool,
+
+    /// Show whether the overlay is hidden from screen capture
+    #[serde(default = "bool_true")]
+    pub watermark_capture: bool,
+
     #[serde(default = "default_i32::<16364>")]
     pub mouse_x_360: i32,
 
     #[serde(default = "default_trigger_bot_mode")]
-    pub trigger_bot_mode: KeyToggleMode,
+    pub trigger_bot_mode: ActivationMode,
 
     #[serde(default = "default_key_trigger_bot")]
     pub key_trigger_bot: Option<HotKey>,
@@ -121,14 +873,54 @@ pub struct AppSettings {
     #[serde(default = "bool_true")]
     pub trigger_bot_team_check: bool,
 
-    #[serde(default = "default_u32::<10>")]
-    pub trigger_bot_delay_min: u32,
+    #[serde(default)]
+    pub trigger_bot_hitbox_filter: TriggerBotHitboxFilter,
+
+    /// Do not shoot while the local player is moving faster than the max velocity
+    #[serde(default = "bool_false")]
+    pub trigger_bot_velocity_gate: bool,
+
+    /// Max velocity in units per second
+    #[serde(default = "default_trigger_bot_max_velocity")]
+    pub trigger_bot_max_velocity: f32,
+
+    /// Do not shoot while the aim punch exceeds the max punch
+    #[serde(default = "bool_false")]
+    pub trigger_bot_punch_gate: bool,
 
-    #[serde(default = "default_u32::<20>")]
-    pub trigger_bot_delay_max: u32,
+    /// Max aim punch in degrees
+    #[serde(default = "default_trigger_bot_max_punch")]
+    pub trigger_bot_max_punch: f32,
 
+    /// Only shoot while the local player is scoped
     #[serde(default = "bool_false")]
-    pub trigger_bot_check_target_after_delay: bool,
+    pub trigger_bot_scoped_only: bool,
+
+    /// Only shoot while holding one of the whitelisted weapons
+    #[serde(default = "bool_false")]
+    pub trigger_bot_weapon_whitelist: bool,
+
+    /// Whitelisted weapons (see WeaponId::name)
+    #[serde(default)]
+    pub trigger_bot_weapons: Vec<String>,
+
+    /// Delay (in ms) between a target entering the crosshair and the first shot
+    #[serde(default = "default_u32::<10>", alias = "trigger_bot_delay_min")]
+    pub trigger_delay_min_ms: u32,
+
+    #[serde(default = "default_u32::<20>", alias = "trigger_bot_delay_max")]
+    pub trigger_delay_max_ms: u32,
+
+    /// Max additional random delay (in ms) for every shot
+    #[serde(default = "default_u32::<0>")]
+    pub trigger_bot_jitter: u32,
+
+    /// Duration (in ms) of a single shot (0 = hold while the target is in the crosshair)
+    #[serde(default = "default_u32::<0>")]
+    pub trigger_bot_shot_duration_min: u32,
+
+    #[serde(default = "default_u32::<0>")]
+    pub trigger_bot_shot_duration_max: u32,
 
     #[serde(default = "bool_false")]
     pub aim_assist_recoil: bool,
@@ -139,6 +931,62 @@ pub struct AppSettings {
     #[serde(default = "bool_false")]
     pub render_debug_window: bool,
 
+    /// Monitor the overlay covers (0 = follow the CS2 window, n = n-th monitor)
+    #[serde(default = "default_u32::<0>")]
+    pub overlay_monitor: u32,
+
+    /// Dim the whole screen behind the settings menu
+    #[serde(default = "bool_false")]
+    pub menu_dim_background: bool,
+
+    #[serde(default = "default_menu_dim_alpha")]
+    pub menu_dim_alpha: f32,
+
+    /// Fade the settings menu in and out
+    #[serde(default = "bool_true")]
+    pub menu_animation: bool,
+
+    /// Hide all enhancements while the settings menu is shown
+    #[serde(default = "bool_false")]
+    pub menu_hide_esp: bool,
+
+    /// Navigate the settings menu with tab / arrow keys
+    #[serde(default = "bool_true")]
+    pub menu_keyboard_navigation: bool,
+
+    /// Navigate the settings menu with a XInput gamepad
+    #[serde(default = "bool_false")]
+    pub menu_gamepad_navigation: bool,
+
+    /// Overlay frame rate limit while the settings are shown (0 = unlimited)
+    #[serde(default = "default_u32::<60>")]
+    pub fps_limit_menu: u32,
+
+    /// Overlay frame rate limit while anything is drawn (0 = unlimited)
+    #[serde(default = "default_u32::<0>")]
+    pub fps_limit_esp: u32,
+
+    /// Overlay frame rate limit while nothing has been drawn (0 = unlimited)
+    #[serde(default = "default_u32::<30>")]
+    pub fps_limit_idle: u32,
+
+    /// Use the idle frame rate limit when nothing has been drawn within the last frame
+    #[serde(default = "bool_true")]
+    pub fps_idle_throttling: bool,
+
+    /// Font file within the fonts directory (empty = default font)
+    #[serde(default)]
+    pub font_file: String,
+
+    #[serde(default = "default_font_size")]
+    pub font_size_menu: f32,
+
+    #[serde(default = "default_font_size")]
+    pub font_size_esp: f32,
+
+    #[serde(default = "default_font_size")]
+    pub font_size_hud: f32,
+
     #[serde(default = "bool_true")]
     pub metrics: bool,
 
@@ -148,10 +996,213 @@ pub struct AppSettings {
     #[serde(default = "bool_false")]
     pub web_radar_advanced_settings: bool,
 
+    /// Amount of config backups to keep per profile (0 = disabled)
+    #[serde(default = "default_u32::<10>")]
+    pub config_backup_count: u32,
+
+    /// Directory of the config backups (empty = next to the executable)
+    #[serde(default)]
+    pub config_backup_directory: String,
+
     #[serde(default)]
     pub imgui: Option<String>,
 }
 
+impl AppSettings {
+    /// Reset the ESP config of the given target to its defaults.
+    /// The enabled state of the target and all keybinds are kept.
+    pub fn reset_esp_config(&mut self, target: &EspSelector) {
+        let config_key = target.config_key();
+        match EspConfig::default_for(target) {
+            Some(config) => {
+                self.esp_settings.insert(config_key, config);
+            }
+            None => {
+                self.esp_settings.remove(&config_key);
+            }
+        }
+    }
+
+    /// Reset all keybinds to their defaults
+    pub fn reset_hotkeys(&mut self) {
+        self.key_settings = default_key_settings();
+        self.key_profile_cycle = default_key_none();
+        self.panic_key = default_key_none();
+        self.key_capture_protection = default_key_none();
+        self.esp_toggle = default_key_none();
+        self.key_trigger_bot = default_key_trigger_bot();
+        self.damage_summary_key = default_key_none();
+        self.economy_panel_toggle = default_key_none();
+        self.watermark_toggle = default_key_none();
+        self.key_double_tap_window = default_u32::<300>();
+        self.key_long_press_duration = default_u32::<500>();
+    }
+
+    /// Reset the trigger bot and aim assist settings to their defaults.
+    /// The trigger bot keybind is kept.
+    pub fn reset_trigger_bot(&mut self) {
+        self.trigger_bot_mode = default_trigger_bot_mode();
+        self.trigger_bot_team_check = bool_true();
+        self.trigger_bot_hitbox_filter = Default::default();
+        self.trigger_bot_velocity_gate = bool_false();
+        self.trigger_bot_max_velocity = default_trigger_bot_max_velocity();
+        self.trigger_bot_punch_gate = bool_false();
+        self.trigger_bot_max_punch = default_trigger_bot_max_punch();
+        self.trigger_bot_scoped_only = bool_false();
+        self.trigger_bot_weapon_whitelist = bool_false();
+        self.trigger_bot_weapons = Default::default();
+        self.trigger_delay_min_ms = default_u32::<10>();
+        self.trigger_delay_max_ms = default_u32::<20>();
+        self.trigger_bot_jitter = default_u32::<0>();
+        self.trigger_bot_shot_duration_min = default_u32::<0>();
+        self.trigger_bot_shot_duration_max = default_u32::<0>();
+        self.aim_assist_recoil = bool_false();
+    }
+
+    /// Reset the misc settings to their defaults.
+    /// The keybinds of the economy panel and the watermark are kept.
+    pub fn reset_misc(&mut self) {
+        self.valthrun_watermark = bool_true();
+        self.bind_list = bool_false();
+        self.bind_list_position = default_bind_list_position();
+        self.bind_list_color = default_bind_list_color();
+        self.bind_list_background_alpha = default_bind_list_background_alpha();
+        self.round_hud = bool_false();
+        self.round_hud_position = default_round_hud_position();
+        self.round_hud_color = default_round_hud_color();
+        self.round_hud_buy_time = bool_true();
+        self.kill_feed = bool_false();
+        self.kill_feed_position = default_kill_feed_position();
+        self.kill_feed_color = default_kill_feed_color();
+        self.kill_feed_max_entries = default_u32::<5>();
+        self.kill_feed_duration = default_kill_feed_duration();
+        self.kill_feed_enemies_only = bool_false();
+        self.death_markers = bool_false();
+        self.death_markers_color = default_death_markers_color();
+        self.death_markers_name = bool_false();
+        self.death_markers_max_distance = default_death_markers_max_distance();
+        self.death_markers_max_age = default_death_markers_max_age();
+        self.proximity_alert = bool_false();
+        self.proximity_alert_color = default_proximity_alert_color();
+        self.proximity_alert_radius = default_proximity_alert_radius();
+        self.proximity_alert_pulse = bool_true();
+        self.compass = bool_false();
+        self.compass_width = default_compass_width();
+        self.compass_position = Default::default();
+        self.compass_color = default_compass_color();
+        self.compass_teammates = bool_false();
+        self.compass_color_mode = Default::default();
+        self.minimap = bool_false();
+        self.minimap_size = default_minimap_size();
+        self.minimap_position = Default::default();
+        self.minimap_zoom = default_minimap_zoom();
+        self.minimap_rotate = bool_true();
+        self.minimap_teammates = bool_true();
+        self.minimap_map_overview = bool_true();
+        self.minimap_map_overview_alpha = default_minimap_map_overview_alpha();
+        self.minimap_team_color = default_minimap_team_color();
+        self.minimap_enemy_color = default_minimap_enemy_color();
+        self.minimap_bomb_color = default_minimap_bomb_color();
+        self.economy_panel_mode = default_economy_panel_mode();
+        self.economy_full_buy_threshold = default_economy_full_buy_threshold();
+        self.economy_force_buy_threshold = default_economy_force_buy_threshold();
+        self.watermark_mode = default_watermark_mode();
+        self.watermark_position = Default::default();
+        self.watermark_color = default_watermark_color();
+        self.watermark_background_alpha = default_watermark_background_alpha();
+        self.watermark_fps = bool_true();
+        self.watermark_time = bool_true();
+        self.watermark_ping = bool_true();
+        self.watermark_version = bool_true();
+        self.watermark_capture = bool_true();
+        self.hide_overlay_from_screen_capture = bool_true();
+        self.render_debug_window = bool_false();
+        self.overlay_monitor = default_u32::<0>();
+        self.menu_dim_background = bool_false();
+        self.menu_dim_alpha = default_menu_dim_alpha();
+        self.menu_animation = bool_true();
+        self.menu_hide_esp = bool_false();
+        self.menu_keyboard_navigation = bool_true();
+        self.menu_gamepad_navigation = bool_false();
+        self.fps_limit_menu = default_u32::<60>();
+        self.fps_limit_esp = default_u32::<0>();
+        self.fps_limit_idle = default_u32::<30>();
+        self.fps_idle_throttling = bool_true();
+        self.font_file = Default::default();
+        self.font_size_menu = default_font_size();
+        self.font_size_esp = default_font_size();
+        self.font_size_hud = default_font_size();
+    }
+
+    /// Reset the visuals settings to their defaults.
+    /// The ESP and damage summary keybinds as well as the grenade spots are kept.
+    pub fn reset_visuals(&mut self) {
+        self.ui_scale_override = Default::default();
+        self.overlay_opacity = default_overlay_opacity();
+        self.color_palette = default_color_palette();
+        self.esp_max_enemies = default_u32::<0>();
+        self.esp_max_teammates = default_u32::<0>();
+        self.esp_render_far_first = bool_true();
+        self.player_blacklist = Default::default();
+        self.bomb_timer = bool_true();
+        self.bomb_timer_hud = bool_false();
+        self.bomb_esp = bool_false();
+        self.bomb_esp_panel = bool_true();
+        self.grenade_esp = bool_false();
+        self.grenade_esp_enemy_only = bool_true();
+        self.grenade_esp_prediction = bool_true();
+        self.grenade_esp_color_he = default_grenade_esp_color_he();
+        self.grenade_esp_color_flash = default_grenade_esp_color_flash();
+        self.grenade_esp_color_smoke = default_grenade_esp_color_smoke();
+        self.grenade_esp_color_fire = default_grenade_esp_color_fire();
+        self.grenade_esp_color_decoy = default_grenade_esp_color_decoy();
+        self.grenade_warning = bool_false();
+        self.grenade_warning_radius = default_grenade_warning_radius();
+        self.grenade_warning_color = default_grenade_warning_color();
+        self.grenade_warning_flash = bool_true();
+        self.grenade_warning_he = bool_true();
+        self.grenade_warning_molotov = bool_true();
+        self.grenade_helper = bool_false();
+        self.grenade_helper_holding_only = bool_true();
+        self.grenade_helper_max_distance = default_grenade_helper_max_distance();
+        self.grenade_helper_color = default_grenade_helper_color();
+        self.inferno_esp = bool_false();
+        self.inferno_esp_color = default_inferno_esp_color();
+        self.inferno_esp_timer = bool_true();
+        self.smoke_esp = bool_false();
+        self.smoke_esp_color = default_smoke_esp_color();
+        self.smoke_esp_timer = bool_true();
+        self.defuse_kit_esp = bool_false();
+        self.defuse_kit_esp_always = bool_false();
+        self.defuse_kit_esp_color = default_defuse_kit_esp_color();
+        self.defuse_kit_esp_max_distance = default_defuse_kit_esp_max_distance();
+        self.sniper_crosshair = bool_false();
+        self.sniper_crosshair_style = Default::default();
+        self.sniper_crosshair_size = default_sniper_crosshair_size();
+        self.sniper_crosshair_gap = default_sniper_crosshair_gap();
+        self.sniper_crosshair_thickness = default_sniper_crosshair_thickness();
+        self.sniper_crosshair_color = default_sniper_crosshair_color();
+        self.recoil_crosshair = bool_false();
+        self.recoil_crosshair_size = default_recoil_crosshair_size();
+        self.recoil_crosshair_color = default_recoil_crosshair_color();
+        self.recoil_crosshair_firing_only = bool_true();
+        self.spectators_list = bool_false();
+        self.spectators_list_position = Default::default();
+        self.spectators_list_background_alpha = default_spectators_list_background_alpha();
+        self.sound_esp = bool_false();
+        self.sound_esp_color = default_sound_esp_color();
+        self.sound_esp_radius_scale = default_sound_esp_radius_scale();
+        self.sound_esp_max_distance = default_sound_esp_max_distance();
+        self.damage_indicator = bool_false();
+        self.damage_indicator_color = default_damage_indicator_color();
+        self.damage_indicator_text_scale = default_damage_indicator_text_scale();
+        self.damage_indicator_hitmarker = bool_false();
+        self.damage_indicator_local_only = bool_true();
+        self.damage_summary = bool_false();
+        self.damage_summary_round_end = bool_true();
+    }
+}
+
 impl State for AppSettings {
     type Parameter = ();
 
@@ -160,15 +1211,47 @@ impl State for AppSettings {
     }
 }
 
+/// Config file set via the command line
+static SETTINGS_PATH: OnceLock<PathBuf> = OnceLock::new();
+
+/// Use the given file instead of the config next to the executable.
+/// Must be called before any settings are loaded.
+pub fn set_settings_path(path: PathBuf) -> anyhow::Result<()> {
+    SETTINGS_PATH
+        .set(path)
+        .map_err(|_| anyhow::anyhow!("config path has already been set"))
+}
+
 pub fn get_settings_path() -> anyhow::Result<PathBuf> {
+    if let Some(path) = SETTINGS_PATH.get() {
+        return Ok(path.clone());
+    }
+
     let exe_file = std::env::current_exe().context("missing current exe path")?;
     let base_dir = exe_file.parent().context("could not get exe directory")?;
 
     Ok(base_dir.join("config.yaml"))
 }
 
-pub fn load_app_settings() -> anyhow::Result<AppSettings> {
-    let config_path = get_settings_path()?;
+/// Parse and migrate the given config file content.
+/// Returns the settings, warnings about invalid values and the original config version.
+pub fn parse_app_config(content: &str) -> anyhow::Result<(AppSettings, Vec<String>, u32)> {
+    let mut config: serde_yaml::Value =
+        serde_yaml::from_str(content).context("failed to parse app config")?;
+    let config_version = migrate_config(&mut config)?;
+
+    let (config, warnings) = parse_app_settings(config).context("failed to parse app config")?;
+    for warning in warnings.iter() {
+        log::warn!("Invalid app config value {}", warning);
+    }
+
+    Ok((config, warnings, config_version))
+}
+
+/// Load the settings of the given profile.
+/// Returns the settings and warnings about values which have been ignored or reset.
+pub fn load_app_settings(profile: &str) -> anyhow::Result<(AppSettings, Vec<String>)> {
+    let config_path = get_profile_path(profile)?;
     if !config_path.is_file() {
         log::info!(
             "App config file {} does not exist.",
@@ -178,41 +1261,127 @@ pub fn load_app_settings() -> anyhow::Result<AppSettings> {
         let config: AppSettings =
             serde_yaml::from_str("").context("failed to parse empty config")?;
 
-        return Ok(config);
+        return Ok((config, Vec::new()));
     }
 
-    let config = File::open(&config_path).with_context(|| {
+    let content = std::fs::read_to_string(&config_path).with_context(|| {
         format!(
             "failed to open app config at {}",
             config_path.to_string_lossy()
         )
     })?;
-    let mut config = BufReader::new(config);
 
-    let config: AppSettings =
-        serde_yaml::from_reader(&mut config).context("failed to parse app config")?;
+    let (config, warnings, config_version) = parse_app_config(&content)?;
 
     log::info!("Loaded app config from {}", config_path.to_string_lossy());
-    Ok(config)
+    if config_version < CONFIG_VERSION {
+        /* keep the original config in case something went wrong while migrating */
+        let backup_path = config_path.with_extension(format!("v{}.yaml.bak", config_version));
+        std::fs::write(&backup_path, &content).with_context(|| {
+            format!(
+                "failed to write config backup to {}",
+                backup_path.to_string_lossy()
+            )
+        })?;
+
+        save_app_settings(profile, &config)?;
+        log::info!(
+            "Migrated app config from version {} to {}. The original config has been saved to {}",
+            config_version,
+            CONFIG_VERSION,
+            backup_path.to_string_lossy()
+        );
+    }
+
+    Ok((config, warnings))
 }
 
-pub fn save_app_settings(settings: &AppSettings) -> anyhow::Result<()> {
-    let config_path = get_settings_path()?;
-    let config = File::options()
-        .create(true)
-        .truncate(true)
-        .write(true)
-        .open(&config_path)
-        .with_context(|| {
+/// Replace the config file with the given content.
+/// The content is written to a temporary file first, so a crash while writing never corrupts the config.
+pub fn write_config_file(config_path: &Path, content: &str) -> anyhow::Result<()> {
+    let temp_path = config_path.with_extension("yaml.tmp");
+    {
+        let mut file = File::create(&temp_path).with_context(|| {
             format!(
-                "failed to open app config at {}",
-                config_path.to_string_lossy()
+                "failed to create temporary config at {}",
+                temp_path.to_string_lossy()
             )
         })?;
-    let mut config = BufWriter::new(config);
 
-    serde_yaml::to_writer(&mut config, settings).context("failed to serialize config")?;
+        file.write_all(content.as_bytes())
+            .context("failed to write config")?;
+        file.sync_all().context("failed to write config")?;
+    }
+
+    std::fs::rename(&temp_path, config_path).with_context(|| {
+        format!(
+            "failed to replace app config at {}",
+            config_path.to_string_lossy()
+        )
+    })?;
 
-    log::debug!("Saved app config.");
     Ok(())
 }
+
+pub fn save_app_settings(profile: &str, settings: &AppSettings) -> anyhow::Result<()> {
+    let content = serde_yaml::to_string(settings).context("failed to serialize config")?;
+    write_config_file(&get_profile_path(profile)?, &content)?;
+
+    log::debug!("Saved app config ({}).", profile);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use imgui::Key;
+
+    use super::{
+        AppSettings,
+        Color,
+    };
+
+    #[test]
+    fn reset_visuals() {
+        let defaults: AppSettings = serde_yaml::from_str("").unwrap();
+
+        let mut settings = defaults.clone();
+        settings.ui_scale_override = 1.5;
+        settings.overlay_opacity = 0.5;
+        settings.color_palette.colors.clear();
+        settings.esp_max_enemies = 3;
+        settings.esp_render_far_first = false;
+        settings.player_blacklist.push("player".to_string());
+        settings.bomb_timer = false;
+        settings.grenade_esp = true;
+        settings.grenade_esp_color_he = Color::from_f32([0.1, 0.2, 0.3, 0.4]);
+        settings.grenade_warning_radius = 1.0;
+        settings.grenade_helper = true;
+        settings.inferno_esp_timer = false;
+        settings.smoke_esp = true;
+        settings.defuse_kit_esp_max_distance = 1.0;
+        settings.sniper_crosshair_gap = 1.0;
+        settings.recoil_crosshair = true;
+        settings.spectators_list = true;
+        settings.sound_esp_max_distance = 1.0;
+        settings.damage_indicator_text_scale = 3.0;
+        settings.damage_summary = true;
+
+        /* neither keybinds nor settings of other sections are reset */
+        settings.damage_summary_key = Some(Key::F5.into());
+        settings.trigger_bot_jitter = 15;
+
+        settings.reset_visuals();
+        assert_eq!(
+            settings.damage_summary_key.map(|key| key.key),
+            Some(Key::F5)
+        );
+        assert_eq!(settings.trigger_bot_jitter, 15);
+
+        settings.damage_summary_key = defaults.damage_summary_key.clone();
+        settings.trigger_bot_jitter = defaults.trigger_bot_jitter;
+        assert_eq!(
+            serde_yaml::to_value(&settings).unwrap(),
+            serde_yaml::to_value(&defaults).unwrap()
+        );
+    }
+}
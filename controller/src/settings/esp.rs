@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use cs2::{
     WeaponId,
     WEAPON_FLAG_TYPE_GRANADE,
@@ -11,9 +13,15 @@ use cs2::{
 use obfstr::obfstr;
 use serde::{
     Deserialize,
+    Deserializer,
     Serialize,
 };
 
+use super::{
+    ColorPalette,
+    EspPlayerSettingsPatch,
+};
+
 #[derive(Clone, Copy, Deserialize, Serialize, PartialEq, PartialOrd)]
 pub struct Color(u32);
 impl Color {
@@ -52,15 +60,161 @@ impl From<[f32; 4]> for Color {
     }
 }
 
-#[derive(Clone, Copy, Deserialize, Serialize, PartialEq, PartialOrd)]
+fn default_palette_fallback() -> Color {
+    Color::from_f32([1.0, 1.0, 1.0, 1.0])
+}
+
+fn default_distance_near_color() -> Color {
+    Color::from_f32([1.0, 0.0, 0.0, 0.75])
+}
+
+fn default_distance_far_color() -> Color {
+    Color::from_f32([0.0, 1.0, 0.0, 0.75])
+}
+
+fn default_distance_near() -> f32 {
+    0.0
+}
+
+fn default_distance_far() -> f32 {
+    80.0
+}
+
+fn default_animation_speed() -> f32 {
+    0.5
+}
+
+fn default_animation_from() -> Color {
+    Color::from_f32([1.0, 0.0, 0.0, 1.0])
+}
+
+fn default_animation_to() -> Color {
+    Color::from_f32([1.0, 1.0, 0.0, 1.0])
+}
+
+/// Convert a hue in [0.0;1.0] with full saturation and value into RGB
+fn hue_to_rgb(hue: f32) -> [f32; 3] {
+    let channel = |offset: f32| {
+        let k = (offset + hue * 6.0) % 6.0;
+        1.0 - (k.min(4.0 - k).min(1.0)).max(0.0)
+    };
+
+    [channel(5.0), channel(3.0), channel(1.0)]
+}
+
+fn interpolate_color(from: [f32; 4], to: [f32; 4], t: f32) -> [f32; 4] {
+    [
+        from[0] + (to[0] - from[0]) * t,
+        from[1] + (to[1] - from[1]) * t,
+        from[2] + (to[2] - from[2]) * t,
+        from[3] + (to[3] - from[3]) * t,
+    ]
+}
+
+#[derive(Clone, Deserialize, Serialize, PartialEq, PartialOrd)]
 #[serde(tag = "type", content = "options")]
+#[serde(remote = "Self")]
 pub enum EspColor {
     HealthBasedRainbow,
-    HealthBased { max: Color, min: Color },
-    Static { value: Color },
+    HealthBased {
+        max: Color,
+        min: Color,
+
+        /// Color at half health.
+        /// Without a midpoint the color will be interpolated between min and max directly.
+        #[serde(default)]
+        mid: Option<Color>,
+    },
+    Static {
+        value: Color,
+    },
+    DistanceBased {
+        #[serde(default = "default_distance_near_color")]
+        near: Color,
+        #[serde(default = "default_distance_far_color")]
+        far: Color,
+
+        /// Distance (in meters) at which the near color is used
+        #[serde(default = "default_distance_near")]
+        near_distance: f32,
+
+        /// Distance (in meters) at which the far color is used
+        #[serde(default = "default_distance_far")]
+        far_distance: f32,
+    },
+
+    /// Color changing over time
+    Animated {
+        #[serde(default)]
+        mode: EspColorAnimation,
+
+        /// Animation cycles per second
+        #[serde(default = "default_animation_speed")]
+        speed: f32,
+
+        /// Pulse start color. The alpha value is also used for the rainbow.
+        #[serde(default = "default_animation_from")]
+        from: Color,
+
+        /// Pulse end color
+        #[serde(default = "default_animation_to")]
+        to: Color,
+    },
+
+    /// Reference to a color of the color palette.
+    /// The fallback is used if the palette does not contain the referenced color.
+    Palette {
+        name: String,
+        #[serde(default = "default_palette_fallback")]
+        fallback: Color,
+    },
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, PartialOrd)]
+pub enum EspColorAnimation {
+    /// Cycle through all hues
+    #[default]
+    Rainbow,
+
+    /// Fade back and forth between two colors
+    Pulse,
+}
+
+/// Distance based colors used to be stored without any options
+#[derive(Deserialize)]
+#[serde(tag = "type")]
+enum LegacyEspColor {
     DistanceBased,
 }
 
+impl Serialize for EspColor {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        EspColor::serialize(self, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for EspColor {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum EspColorValue {
+            Current(#[serde(deserialize_with = "EspColor::deserialize")] EspColor),
+            Legacy(LegacyEspColor),
+        }
+
+        Ok(match EspColorValue::deserialize(deserializer)? {
+            EspColorValue::Current(value) => value,
+            EspColorValue::Legacy(LegacyEspColor::DistanceBased) => EspColor::distance_based(),
+        })
+    }
+}
+
 impl Default for EspColor {
     fn default() -> Self {
         Self::Static {
@@ -76,21 +230,50 @@ impl EspColor {
         }
     }
 
+    /// Distance based color with the default gradient
+    pub fn distance_based() -> Self {
+        Self::DistanceBased {
+            near: default_distance_near_color(),
+            far: default_distance_far_color(),
+            near_distance: default_distance_near(),
+            far_distance: default_distance_far(),
+        }
+    }
+
+    /// Animated rainbow color with the default speed
+    pub fn animated() -> Self {
+        Self::Animated {
+            mode: EspColorAnimation::Rainbow,
+            speed: default_animation_speed(),
+            from: default_animation_from(),
+            to: default_animation_to(),
+        }
+    }
+
     /// Calculate the target color.
-    /// Health should be in [0.0;1.0]
-    pub fn calculate_color(&self, health: f32, distance: f32) -> [f32; 4] {
+    /// Health should be in [0.0;1.0], time is the current time in seconds
+    /// and only used for animated colors.
+    pub fn calculate_color(
+        &self,
+        palette: &ColorPalette,
+        health: f32,
+        distance: f32,
+        time: f32,
+    ) -> [f32; 4] {
         match self {
             Self::Static { value } => value.as_f32(),
-            Self::HealthBased { max, min } => {
-                let min_rgb = min.as_f32();
-                let max_rgb = max.as_f32();
-
-                [
-                    min_rgb[0] + (max_rgb[0] - min_rgb[0]) * health,
-                    min_rgb[1] + (max_rgb[1] - min_rgb[1]) * health,
-                    min_rgb[2] + (max_rgb[2] - min_rgb[2]) * health,
-                    min_rgb[3] + (max_rgb[3] - min_rgb[3]) * health,
-                ]
+            Self::Palette { name, fallback } => palette.get(name).unwrap_or(*fallback).as_f32(),
+            Self::HealthBased { max, min, mid } => {
+                let health = health.clamp(0.0, 1.0);
+                match mid {
+                    Some(mid) if health < 0.5 => {
+                        interpolate_color(min.as_f32(), mid.as_f32(), health * 2.0)
+                    }
+                    Some(mid) => {
+                        interpolate_color(mid.as_f32(), max.as_f32(), (health - 0.5) * 2.0)
+                    }
+                    None => interpolate_color(min.as_f32(), max.as_f32(), health),
+                }
             }
             Self::HealthBasedRainbow => {
                 let sin_value = |offset: f32| {
@@ -101,22 +284,39 @@ impl EspColor {
                 let b: f32 = sin_value(4.0 * std::f32::consts::PI / 3.0);
                 [r, g, b, 1.0]
             }
-            Self::DistanceBased => {
-                let max_distance = 80.0;
-                let min_distance = 0.0;
-
-                let color_near = [1.0, 0.0, 0.0, 0.75];
-                let color_far = [0.0, 1.0, 0.0, 0.75];
-
-                let t = (distance - min_distance) / (max_distance - min_distance);
-                let t = t.clamp(0.0, 1.0);
-
-                [
-                    color_near[0] + t * (color_far[0] - color_near[0]),
-                    color_near[1] + t * (color_far[1] - color_near[1]),
-                    color_near[2] + t * (color_far[2] - color_near[2]),
-                    0.75,
-                ]
+            Self::DistanceBased {
+                near,
+                far,
+                near_distance,
+                far_distance,
+            } => {
+                let t = if far_distance > near_distance {
+                    (distance - near_distance) / (far_distance - near_distance)
+                } else if distance < *near_distance {
+                    0.0
+                } else {
+                    1.0
+                };
+
+                interpolate_color(near.as_f32(), far.as_f32(), t.clamp(0.0, 1.0))
+            }
+            Self::Animated {
+                mode,
+                speed,
+                from,
+                to,
+            } => {
+                let phase = (time * speed).rem_euclid(1.0);
+                match mode {
+                    EspColorAnimation::Rainbow => {
+                        let [r, g, b] = hue_to_rgb(phase);
+                        [r, g, b, from.as_f32()[3]]
+                    }
+                    EspColorAnimation::Pulse => {
+                        let t = 0.5 - (2.0 * std::f32::consts::PI * phase).cos() * 0.5;
+                        interpolate_color(from.as_f32(), to.as_f32(), t)
+                    }
+                }
             }
         }
     }
@@ -128,6 +328,8 @@ pub enum EspColorType {
     HealthBased,
     HealthBasedRainbow,
     DistanceBased,
+    Animated,
+    Palette,
 }
 
 impl EspColorType {
@@ -136,7 +338,9 @@ impl EspColorType {
             EspColor::Static { .. } => Self::Static,
             EspColor::HealthBased { .. } => Self::HealthBased,
             EspColor::HealthBasedRainbow => Self::HealthBasedRainbow,
-            EspColor::DistanceBased => Self::DistanceBased,
+            EspColor::DistanceBased { .. } => Self::DistanceBased,
+            EspColor::Animated { .. } => Self::Animated,
+            EspColor::Palette { .. } => Self::Palette,
         }
     }
 }
@@ -150,6 +354,216 @@ pub enum EspHealthBar {
     Right,
 }
 
+#[derive(Clone, Copy, Deserialize, Serialize, PartialEq, PartialOrd)]
+pub enum EspArmorBar {
+    None,
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
+impl Default for EspArmorBar {
+    fn default() -> Self {
+        Self::None
+    }
+}
+
+#[derive(Clone, Copy, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Hash)]
+pub enum EspSkeletonMode {
+    /// All bones which are part of a hitbox
+    Full,
+
+    /// Only the spine, head, arms and legs
+    Simplified,
+
+    /// Bones specified in skeleton_custom_bones
+    Custom,
+}
+
+impl Default for EspSkeletonMode {
+    fn default() -> Self {
+        Self::Full
+    }
+}
+
+#[derive(Clone, Copy, Deserialize, Serialize, PartialEq, PartialOrd)]
+pub enum EspBoxFit {
+    /// Use the static model hull
+    Hull,
+
+    /// Fit the box around the hitbox bones
+    Bones,
+}
+
+impl Default for EspBoxFit {
+    fn default() -> Self {
+        Self::Hull
+    }
+}
+
+#[derive(Clone, Copy, Deserialize, Serialize, PartialEq, PartialOrd)]
+pub enum EspHeadDisplay {
+    None,
+    Hitbox3D,
+}
+
+impl Default for EspHeadDisplay {
+    fn default() -> Self {
+        Self::None
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, PartialOrd)]
+pub enum EspInfoLine {
+    Name,
+    Rank,
+    KillsDeaths,
+    Weapon,
+    Ammo,
+    Health,
+    Money,
+    Speed,
+    Ping,
+    Flags,
+    Distance,
+}
+
+impl EspInfoLine {
+    pub const ALL: [EspInfoLine; 11] = [
+        Self::Name,
+        Self::Rank,
+        Self::KillsDeaths,
+        Self::Weapon,
+        Self::Ammo,
+        Self::Health,
+        Self::Money,
+        Self::Speed,
+        Self::Ping,
+        Self::Flags,
+        Self::Distance,
+    ];
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Self::Name => "Name",
+            Self::Rank => "Rank",
+            Self::KillsDeaths => "Kills / Deaths",
+            Self::Weapon => "Weapon",
+            Self::Ammo => "Ammo",
+            Self::Health => "Health",
+            Self::Money => "Money",
+            Self::Speed => "Speed",
+            Self::Ping => "Ping",
+            Self::Flags => "Flags",
+            Self::Distance => "Distance",
+        }
+    }
+}
+
+fn default_info_line_order() -> Vec<EspInfoLine> {
+    EspInfoLine::ALL.to_vec()
+}
+
+/// Deserialize the info line order while ignoring unknown entries
+/// and appending lines which are missing (e.g. added after the config has been saved).
+fn deserialize_info_line_order<'de, D>(deserializer: D) -> Result<Vec<EspInfoLine>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum InfoLineEntry {
+        Known(EspInfoLine),
+        Unknown(serde::de::IgnoredAny),
+    }
+
+    let mut result = Vec::with_capacity(EspInfoLine::ALL.len());
+    for entry in Vec::<InfoLineEntry>::deserialize(deserializer)? {
+        if let InfoLineEntry::Known(line) = entry {
+            if !result.contains(&line) {
+                result.push(line);
+            }
+        }
+    }
+
+    for line in EspInfoLine::ALL {
+        if !result.contains(&line) {
+            result.push(line);
+        }
+    }
+
+    Ok(result)
+}
+
+#[derive(Clone, Copy, Deserialize, Serialize, PartialEq, PartialOrd)]
+pub enum EspBotFilter {
+    /// Show bots like any other player
+    Show,
+
+    /// Do not show bots at all
+    Hide,
+
+    /// Show bots with a "BOT" flag
+    TagOnly,
+}
+
+impl Default for EspBotFilter {
+    fn default() -> Self {
+        Self::Show
+    }
+}
+
+#[derive(Clone, Copy, Deserialize, Serialize, PartialEq, PartialOrd)]
+pub enum EspWeaponStyle {
+    Text,
+
+    /// Weapon icon glyphs. Weapons without an icon or a missing icon font fall back to their name.
+    Icon,
+}
+
+impl Default for EspWeaponStyle {
+    fn default() -> Self {
+        Self::Text
+    }
+}
+
+#[derive(Clone, Copy, Deserialize, Serialize, PartialEq, PartialOrd)]
+pub enum EspInfoAnchor {
+    /// Centered below the box
+    Bottom,
+
+    /// Centered above the box
+    Top,
+
+    /// Right aligned left of the box
+    Left,
+
+    /// Left aligned right of the box
+    Right,
+
+    /// Right of the 2D box (below otherwise) but flipped to keep the text on screen
+    Auto,
+}
+
+impl Default for EspInfoAnchor {
+    fn default() -> Self {
+        Self::Auto
+    }
+}
+
+#[derive(Clone, Copy, Deserialize, Serialize, PartialEq, PartialOrd)]
+pub enum EspSpeedUnit {
+    UnitsPerSecond,
+    KilometersPerHour,
+}
+
+impl Default for EspSpeedUnit {
+    fn default() -> Self {
+        Self::UnitsPerSecond
+    }
+}
+
 #[derive(Clone, Copy, Deserialize, Serialize, PartialEq, PartialOrd)]
 pub enum EspBoxType {
     /// Disabled player box
@@ -158,6 +572,9 @@ pub enum EspBoxType {
     /// 2D player box
     Box2D,
 
+    /// Only the corners of the 2D player box
+    Box2DCorners,
+
     /// 3D player box
     Box3D,
 }
@@ -174,41 +591,363 @@ pub enum EspTracePosition {
     BottomRight,
 }
 
-#[derive(Clone, Copy, Deserialize, Serialize, PartialEq, PartialOrd)]
+#[derive(Clone, Deserialize, Serialize, PartialEq, PartialOrd)]
 pub struct EspPlayerSettings {
     pub box_type: EspBoxType,
     pub box_color: EspColor,
     pub box_width: f32,
+    /// Length of the box corner lines relative to the box width/height
+    #[serde(default = "default_box_corner_length_ratio")]
+    pub box_corner_length_ratio: f32,
+
+    #[serde(default)]
+    pub box_fit: EspBoxFit,
+    /// Padding in pixels around the box when fitted to the bones
+    #[serde(default = "default_box_fit_margin")]
+    pub box_fit_margin: f32,
+
+    /// Pulse the box color when the player is shooting
+    #[serde(default)]
+    pub shot_indicator: bool,
+    #[serde(default = "default_shot_indicator_color")]
+    pub shot_indicator_color: EspColor,
+    /// Pulse duration in seconds
+    #[serde(default = "default_shot_indicator_duration")]
+    pub shot_indicator_duration: f32,
+
+    /// Override the box and skeleton color for players at or below this health.
+    /// A value of zero disables the override.
+    #[serde(default)]
+    pub low_health_threshold: i32,
+    #[serde(default = "default_low_health_color")]
+    pub low_health_color: EspColor,
+    /// Blink the HP text of players at or below the low health threshold
+    #[serde(default)]
+    pub low_health_blink: bool,
+
+    /// Override the box color of the player carrying the C4
+    #[serde(default)]
+    pub bomb_carrier_highlight: bool,
+    #[serde(default = "default_bomb_carrier_box_color")]
+    pub bomb_carrier_box_color: EspColor,
+
+    /// Highlight enemies which are aiming at the local player
+    #[serde(default)]
+    pub aiming_at_you: bool,
+    /// Max angle in degrees between the enemy view direction and the local player
+    #[serde(default = "default_aiming_at_you_fov")]
+    pub aiming_at_you_fov: f32,
+    /// Max distance in meters
+    #[serde(default = "default_aiming_at_you_max_distance")]
+    pub aiming_at_you_max_distance: f32,
+    #[serde(default = "default_aiming_at_you_color")]
+    pub aiming_at_you_color: EspColor,
+    /// Show a warning banner at the top of the screen
+    #[serde(default)]
+    pub aiming_at_you_banner: bool,
+
+    /// Draw a black outline around the 2D box and the player info text
+    #[serde(default)]
+    pub outline: bool,
+
+    /// Fill the 2D box or the faces of the 3D box
+    #[serde(default)]
+    pub box_fill: bool,
+    #[serde(default)]
+    pub box_fill_color: EspColor,
+    /// Opacity of the box fill which will be applied on top of the fill color
+    #[serde(default = "default_box_fill_alpha")]
+    pub box_fill_alpha: f32,
 
     pub skeleton: bool,
     pub skeleton_color: EspColor,
     pub skeleton_width: f32,
+    #[serde(default)]
+    pub skeleton_mode: EspSkeletonMode,
+    /// Comma separated list of bone names used for EspSkeletonMode::Custom
+    #[serde(default)]
+    pub skeleton_custom_bones: String,
+
+    /// Draw a dot at every skeleton bone
+    #[serde(default)]
+    pub skeleton_joints: bool,
+    #[serde(default)]
+    pub skeleton_joints_color: EspColor,
+    /// Joint radius in pixels at a distance of 10 meters
+    #[serde(default = "default_skeleton_joints_radius")]
+    pub skeleton_joints_radius: f32,
+
+    #[serde(default)]
+    pub head_display: EspHeadDisplay,
+    #[serde(default)]
+    pub head_display_color: EspColor,
+    #[serde(default = "default_head_display_width")]
+    pub head_display_width: f32,
 
     pub health_bar: EspHealthBar,
     pub health_bar_width: f32,
 
+    #[serde(default)]
+    pub armor_bar: EspArmorBar,
+    #[serde(default = "default_armor_bar_width")]
+    pub armor_bar_width: f32,
+
+    /// Show the remaining flash time above the player box
+    #[serde(default)]
+    pub flash_bar: bool,
+    #[serde(default = "default_flash_bar_color")]
+    pub flash_bar_color: EspColor,
+    #[serde(default = "default_flash_bar_height")]
+    pub flash_bar_height: f32,
+
     pub tracer_lines: EspTracePosition,
     pub tracer_lines_color: EspColor,
     pub tracer_lines_width: f32,
 
+    /// Show the path the player walked within the last trail_duration seconds
+    #[serde(default)]
+    pub trail: bool,
+    #[serde(default = "default_trail_duration")]
+    pub trail_duration: f32,
+    #[serde(default)]
+    pub trail_color: EspColor,
+    #[serde(default = "default_trail_width")]
+    pub trail_width: f32,
+
+    #[serde(default)]
+    pub view_direction_line: bool,
+    #[serde(default = "default_view_direction_line_length")]
+    pub view_direction_line_length: f32,
+    #[serde(default)]
+    pub view_direction_line_color: EspColor,
+    #[serde(default = "default_view_direction_line_width")]
+    pub view_direction_line_width: f32,
+
+    /// Scale of the player info text in [0.5;2.0]
+    #[serde(default = "default_info_text_scale")]
+    pub info_text_scale: f32,
+
+    /// Position of the player info lines relative to the box
+    #[serde(default)]
+    pub info_anchor: EspInfoAnchor,
+
+    /// Order in which the player info lines will be shown
+    #[serde(
+        default = "default_info_line_order",
+        deserialize_with = "deserialize_info_line_order"
+    )]
+    pub info_line_order: Vec<EspInfoLine>,
+
     pub info_name: bool,
     pub info_name_color: EspColor,
 
+    #[serde(default)]
+    pub info_show_clan_tag: bool,
+
+    #[serde(default)]
+    pub info_rank: bool,
+    #[serde(default)]
+    pub info_rank_color: EspColor,
+
+    #[serde(default)]
+    pub info_kd: bool,
+    #[serde(default)]
+    pub info_kd_color: EspColor,
+
     pub info_distance: bool,
     pub info_distance_color: EspColor,
 
     pub near_players: bool,
     pub near_players_distance: f32,
 
+    #[serde(default)]
+    pub filter_bots: EspBotFilter,
+
+    /// Fade out the player ESP between fade_distance_start and fade_distance_end (in meters)
+    #[serde(default)]
+    pub distance_fade: bool,
+    #[serde(default = "default_fade_distance_start")]
+    pub fade_distance_start: f32,
+    #[serde(default = "default_fade_distance_end")]
+    pub fade_distance_end: f32,
+
     pub info_weapon: bool,
+    #[serde(default)]
+    pub info_weapon_style: EspWeaponStyle,
     pub info_weapon_color: EspColor,
 
+    #[serde(default)]
+    pub info_ammo: bool,
+    #[serde(default)]
+    pub info_ammo_color: EspColor,
+
+    #[serde(default)]
+    pub info_money: bool,
+    #[serde(default)]
+    pub info_money_color: EspColor,
+
+    #[serde(default)]
+    pub info_ping: bool,
+    #[serde(default)]
+    pub info_ping_color: EspColor,
+
+    #[serde(default)]
+    pub info_speed: bool,
+    #[serde(default)]
+    pub info_speed_unit: EspSpeedUnit,
+    #[serde(default)]
+    pub info_speed_color: EspColor,
+
     pub info_hp_text: bool,
     pub info_hp_text_color: EspColor,
 
     pub info_flag_kit: bool,
     pub info_flag_flashed: bool,
+    #[serde(default)]
+    pub info_flag_scoped: bool,
+    #[serde(default)]
+    pub info_flag_defusing: bool,
+    #[serde(default)]
+    pub info_flag_planting: bool,
+    #[serde(default)]
+    pub info_flag_reloading: bool,
+    #[serde(default)]
+    pub info_flag_airborne: bool,
+    #[serde(default)]
+    pub info_flag_crouched: bool,
+    #[serde(default)]
+    pub info_flag_bomb: bool,
     pub info_flags_color: EspColor,
+
+    #[serde(default)]
+    pub ghost: bool,
+    #[serde(default = "default_ghost_timeout")]
+    pub ghost_timeout: f32,
+    #[serde(default = "default_ghost_color")]
+    pub ghost_color: EspColor,
+
+    #[serde(default)]
+    pub offscreen_arrows: bool,
+    #[serde(default)]
+    pub offscreen_arrows_color: EspColor,
+    #[serde(default = "default_offscreen_arrows_size")]
+    pub offscreen_arrows_size: f32,
+    #[serde(default = "default_offscreen_arrows_edge_distance")]
+    pub offscreen_arrows_edge_distance: f32,
+    #[serde(default = "default_offscreen_arrows_max_distance")]
+    pub offscreen_arrows_max_distance: f32,
+
+    /// Overrides of the settings above by map name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub map_overrides: BTreeMap<String, EspPlayerSettingsPatch>,
+}
+
+fn default_armor_bar_width() -> f32 {
+    10.0
+}
+
+fn default_flash_bar_color() -> EspColor {
+    EspColor::from_rgba(1.0, 1.0, 1.0, 1.0)
+}
+
+fn default_flash_bar_height() -> f32 {
+    4.0
+}
+
+fn default_box_corner_length_ratio() -> f32 {
+    0.25
+}
+
+fn default_box_fit_margin() -> f32 {
+    5.0
+}
+
+fn default_shot_indicator_color() -> EspColor {
+    EspColor::from_rgba(1.0, 1.0, 0.0, 1.0)
+}
+
+fn default_shot_indicator_duration() -> f32 {
+    0.2
+}
+
+fn default_low_health_color() -> EspColor {
+    EspColor::from_rgba(1.0, 0.0, 1.0, 1.0)
+}
+
+fn default_bomb_carrier_box_color() -> EspColor {
+    EspColor::from_rgba(1.0, 0.27, 0.0, 1.0)
+}
+
+fn default_skeleton_joints_radius() -> f32 {
+    3.0
+}
+
+fn default_aiming_at_you_fov() -> f32 {
+    5.0
+}
+
+fn default_aiming_at_you_max_distance() -> f32 {
+    50.0
+}
+
+fn default_aiming_at_you_color() -> EspColor {
+    EspColor::from_rgba(1.0, 0.5, 0.0, 1.0)
+}
+
+fn default_box_fill_alpha() -> f32 {
+    0.25
+}
+
+fn default_head_display_width() -> f32 {
+    1.0
+}
+
+fn default_trail_duration() -> f32 {
+    3.0
+}
+
+fn default_trail_width() -> f32 {
+    2.0
+}
+
+fn default_view_direction_line_length() -> f32 {
+    3.0
+}
+
+fn default_view_direction_line_width() -> f32 {
+    1.0
+}
+
+fn default_info_text_scale() -> f32 {
+    1.0
+}
+
+fn default_fade_distance_start() -> f32 {
+    30.0
+}
+
+fn default_fade_distance_end() -> f32 {
+    50.0
+}
+
+fn default_ghost_timeout() -> f32 {
+    5.0
+}
+
+fn default_ghost_color() -> EspColor {
+    EspColor::from_rgba(0.75, 0.75, 0.75, 0.75)
+}
+
+fn default_offscreen_arrows_size() -> f32 {
+    15.0
+}
+
+fn default_offscreen_arrows_edge_distance() -> f32 {
+    50.0
+}
+
+fn default_offscreen_arrows_max_distance() -> f32 {
+    50.0
 }
 
 const ESP_COLOR_FRIENDLY: EspColor = EspColor::from_rgba(0.0, 1.0, 0.0, 0.75);
@@ -237,53 +976,262 @@ impl EspPlayerSettings {
             box_type: EspBoxType::None,
             box_color: color.clone(),
             box_width: 3.0,
+            box_corner_length_ratio: default_box_corner_length_ratio(),
+
+            box_fit: EspBoxFit::Hull,
+            box_fit_margin: default_box_fit_margin(),
+
+            shot_indicator: false,
+            shot_indicator_color: default_shot_indicator_color(),
+            shot_indicator_duration: default_shot_indicator_duration(),
+
+            low_health_threshold: 0,
+            low_health_color: default_low_health_color(),
+            low_health_blink: false,
+
+            bomb_carrier_highlight: false,
+            bomb_carrier_box_color: default_bomb_carrier_box_color(),
+
+            aiming_at_you: false,
+            aiming_at_you_fov: default_aiming_at_you_fov(),
+            aiming_at_you_max_distance: default_aiming_at_you_max_distance(),
+            aiming_at_you_color: default_aiming_at_you_color(),
+            aiming_at_you_banner: false,
+
+            outline: false,
+
+            box_fill: false,
+            box_fill_color: color.clone(),
+            box_fill_alpha: default_box_fill_alpha(),
 
             skeleton: true,
             skeleton_color: color.clone(),
             skeleton_width: 3.0,
+            skeleton_mode: EspSkeletonMode::Full,
+            skeleton_custom_bones: String::new(),
+            skeleton_joints: false,
+            skeleton_joints_color: color.clone(),
+            skeleton_joints_radius: default_skeleton_joints_radius(),
+
+            head_display: EspHeadDisplay::None,
+            head_display_color: color.clone(),
+            head_display_width: default_head_display_width(),
 
             health_bar: EspHealthBar::None,
             health_bar_width: 10.0,
 
+            armor_bar: EspArmorBar::None,
+            armor_bar_width: default_armor_bar_width(),
+
+            flash_bar: false,
+            flash_bar_color: default_flash_bar_color(),
+            flash_bar_height: default_flash_bar_height(),
+
             tracer_lines: EspTracePosition::None,
             tracer_lines_color: color.clone(),
             tracer_lines_width: 1.0,
 
+            trail: false,
+            trail_duration: default_trail_duration(),
+            trail_color: color.clone(),
+            trail_width: default_trail_width(),
+
+            view_direction_line: false,
+            view_direction_line_length: default_view_direction_line_length(),
+            view_direction_line_color: color.clone(),
+            view_direction_line_width: default_view_direction_line_width(),
+
             info_distance: false,
             info_distance_color: color.clone(),
 
             near_players: false,
             near_players_distance: 20.0,
 
+            filter_bots: EspBotFilter::Show,
+
+            distance_fade: false,
+            fade_distance_start: default_fade_distance_start(),
+            fade_distance_end: default_fade_distance_end(),
+
             info_hp_text: false,
             info_hp_text_color: color.clone(),
 
+            info_text_scale: default_info_text_scale(),
+            info_anchor: EspInfoAnchor::Auto,
+            info_line_order: default_info_line_order(),
+
             info_name: false,
             info_name_color: color.clone(),
 
+            info_show_clan_tag: false,
+
+            info_rank: false,
+            info_rank_color: color.clone(),
+
+            info_kd: false,
+            info_kd_color: color.clone(),
+
             info_weapon: false,
+            info_weapon_style: EspWeaponStyle::Text,
             info_weapon_color: color.clone(),
 
+            info_ammo: false,
+            info_ammo_color: color.clone(),
+
+            info_money: false,
+            info_money_color: color.clone(),
+
+            info_ping: false,
+            info_ping_color: color.clone(),
+
+            info_speed: false,
+            info_speed_unit: EspSpeedUnit::UnitsPerSecond,
+            info_speed_color: color.clone(),
+
             info_flag_kit: false,
             info_flag_flashed: false,
+            info_flag_scoped: false,
+            info_flag_defusing: false,
+            info_flag_planting: false,
+            info_flag_reloading: false,
+            info_flag_airborne: false,
+            info_flag_crouched: false,
+            info_flag_bomb: false,
             info_flags_color: color.clone(),
+
+            ghost: false,
+            ghost_timeout: default_ghost_timeout(),
+            ghost_color: default_ghost_color(),
+
+            offscreen_arrows: false,
+            offscreen_arrows_color: color.clone(),
+            offscreen_arrows_size: default_offscreen_arrows_size(),
+            offscreen_arrows_edge_distance: default_offscreen_arrows_edge_distance(),
+            offscreen_arrows_max_distance: default_offscreen_arrows_max_distance(),
+
+            map_overrides: Default::default(),
         }
     }
+
+    /// Resolve the settings for the given map by applying the map overrides
+    pub fn for_map(&self, map_name: &str) -> Option<Self> {
+        let patch = self.map_overrides.get(map_name)?;
+
+        let mut settings = self.clone();
+        patch.apply(&mut settings);
+        Some(settings)
+    }
+
+    /// Copy of these settings using the colors and map overrides of the given settings.
+    /// Used to mirror the enemy settings onto the team settings.
+    pub fn with_colors_of(&self, colors: &Self) -> Self {
+        let mut settings = self.clone();
+        settings.box_color = colors.box_color.clone();
+        settings.shot_indicator_color = colors.shot_indicator_color.clone();
+        settings.low_health_color = colors.low_health_color.clone();
+        settings.bomb_carrier_box_color = colors.bomb_carrier_box_color.clone();
+        settings.aiming_at_you_color = colors.aiming_at_you_color.clone();
+        settings.box_fill_color = colors.box_fill_color.clone();
+        settings.skeleton_color = colors.skeleton_color.clone();
+        settings.skeleton_joints_color = colors.skeleton_joints_color.clone();
+        settings.head_display_color = colors.head_display_color.clone();
+        settings.flash_bar_color = colors.flash_bar_color.clone();
+        settings.tracer_lines_color = colors.tracer_lines_color.clone();
+        settings.trail_color = colors.trail_color.clone();
+        settings.view_direction_line_color = colors.view_direction_line_color.clone();
+        settings.info_name_color = colors.info_name_color.clone();
+        settings.info_rank_color = colors.info_rank_color.clone();
+        settings.info_kd_color = colors.info_kd_color.clone();
+        settings.info_distance_color = colors.info_distance_color.clone();
+        settings.info_weapon_color = colors.info_weapon_color.clone();
+        settings.info_ammo_color = colors.info_ammo_color.clone();
+        settings.info_money_color = colors.info_money_color.clone();
+        settings.info_ping_color = colors.info_ping_color.clone();
+        settings.info_speed_color = colors.info_speed_color.clone();
+        settings.info_hp_text_color = colors.info_hp_text_color.clone();
+        settings.info_flags_color = colors.info_flags_color.clone();
+        settings.ghost_color = colors.ghost_color.clone();
+        settings.offscreen_arrows_color = colors.offscreen_arrows_color.clone();
+
+        /* map overrides may contain colors as well */
+        settings.map_overrides = colors.map_overrides.clone();
+        settings
+    }
 }
 
-#[derive(Clone, Copy, Deserialize, Serialize, PartialEq, PartialOrd)]
+const ESP_COLOR_CHICKEN: EspColor = EspColor::from_rgba(1.0, 1.0, 1.0, 0.75);
+
+#[derive(Clone, Deserialize, Serialize, PartialEq, PartialOrd)]
 pub struct EspChickenSettings {
+    pub marker: bool,
+    pub marker_color: EspColor,
+
+    pub info_name: bool,
+    pub info_name_color: EspColor,
+
+    /// Max distance in meters
+    pub max_distance: f32,
+}
+
+impl EspChickenSettings {
+    pub fn new() -> Self {
+        Self {
+            marker: true,
+            marker_color: ESP_COLOR_CHICKEN,
+
+            info_name: false,
+            info_name_color: ESP_COLOR_CHICKEN,
+
+            max_distance: 50.0,
+        }
+    }
+}
+
+impl Default for EspChickenSettings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+const ESP_COLOR_HOSTAGE: EspColor = EspColor::from_rgba(1.0, 0.85, 0.3, 0.75);
+
+#[derive(Clone, Deserialize, Serialize, PartialEq, PartialOrd)]
+pub struct EspHostageSettings {
     pub box_type: EspBoxType,
     pub box_color: EspColor,
+    pub box_width: f32,
 
-    pub skeleton: bool,
-    pub skeleton_color: EspColor,
+    pub info_health: bool,
+    pub info_health_color: EspColor,
 
-    pub info_owner: bool,
-    pub info_owner_color: EspColor,
+    /// Show if and by whom the hostage is being carried
+    pub info_carrier: bool,
+    pub info_carrier_color: EspColor,
 }
 
-#[derive(Clone, Copy, Deserialize, Serialize, PartialEq, PartialOrd)]
+impl EspHostageSettings {
+    pub fn new() -> Self {
+        Self {
+            box_type: EspBoxType::Box2D,
+            box_color: ESP_COLOR_HOSTAGE,
+            box_width: 2.0,
+
+            info_health: true,
+            info_health_color: ESP_COLOR_HOSTAGE,
+
+            info_carrier: true,
+            info_carrier_color: ESP_COLOR_HOSTAGE,
+        }
+    }
+}
+
+impl Default for EspHostageSettings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize, PartialEq, PartialOrd)]
 pub struct EspWeaponSettings {
     pub draw_box: bool,
     pub draw_box_color: EspColor,
@@ -292,14 +1240,35 @@ pub struct EspWeaponSettings {
     pub info_name_color: EspColor,
 }
 
-#[derive(Clone, Copy, Deserialize, Serialize, PartialEq, PartialOrd)]
+#[derive(Clone, Deserialize, Serialize, PartialEq, PartialOrd)]
 #[serde(tag = "type")]
 pub enum EspConfig {
     Player(EspPlayerSettings),
     Chicken(EspChickenSettings),
+    Hostage(EspHostageSettings),
     Weapon(EspWeaponSettings),
 }
 
+impl EspConfig {
+    /// Default config for the given target.
+    /// Returns None if the target has no config of its own.
+    pub fn default_for(target: &EspSelector) -> Option<Self> {
+        match target {
+            EspSelector::Player
+            | EspSelector::PlayerTeam { .. }
+            | EspSelector::PlayerTeamVisibility { .. } => {
+                Some(Self::Player(EspPlayerSettings::new(target)))
+            }
+            EspSelector::Chicken => Some(Self::Chicken(Default::default())),
+            EspSelector::Hostage => Some(Self::Hostage(Default::default())),
+            EspSelector::None
+            | EspSelector::Weapon
+            | EspSelector::WeaponGroup { .. }
+            | EspSelector::WeaponSingle { .. } => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
 pub enum EspWeaponType {
     Pistol,
@@ -368,6 +1337,7 @@ pub enum EspSelector {
     },
 
     Chicken,
+    Hostage,
 
     Weapon,
     WeaponGroup {
@@ -380,6 +1350,36 @@ pub enum EspSelector {
 }
 
 impl EspSelector {
+    /// Resolve the selector of the given config key.
+    /// Only selectors which have a config of their own (see EspConfig::default_for) are resolved.
+    pub fn from_config_key(key: &str) -> Option<Self> {
+        [
+            EspSelector::Player,
+            EspSelector::PlayerTeam { enemy: true },
+            EspSelector::PlayerTeam { enemy: false },
+            EspSelector::PlayerTeamVisibility {
+                enemy: true,
+                visible: true,
+            },
+            EspSelector::PlayerTeamVisibility {
+                enemy: true,
+                visible: false,
+            },
+            EspSelector::PlayerTeamVisibility {
+                enemy: false,
+                visible: true,
+            },
+            EspSelector::PlayerTeamVisibility {
+                enemy: false,
+                visible: false,
+            },
+            EspSelector::Chicken,
+            EspSelector::Hostage,
+        ]
+        .into_iter()
+        .find(|selector| selector.config_key() == key)
+    }
+
     pub fn config_key(&self) -> String {
         match self {
             EspSelector::None => "invalid".to_string(),
@@ -393,6 +1393,7 @@ impl EspSelector {
                 if *visible { "visible" } else { "occluded" }
             ),
             EspSelector::Chicken => "chicken".to_string(),
+            EspSelector::Hostage => "hostage".to_string(),
 
             EspSelector::Weapon => format!("weapon"),
             EspSelector::WeaponGroup { group } => format!("weapon.{}", group.config_key()),
@@ -423,6 +1424,7 @@ impl EspSelector {
             }
 
             EspSelector::Chicken => "Chicken".to_string(),
+            EspSelector::Hostage => "Hostage".to_string(),
 
             EspSelector::Weapon => "Weapons".to_string(),
             EspSelector::WeaponGroup { group } => group.display_name(),
@@ -448,6 +1450,7 @@ impl EspSelector {
             ),
 
             EspSelector::Chicken => obfstr!("Enabled ESP for chickens").to_string(),
+            EspSelector::Hostage => obfstr!("Enabled ESP for hostages").to_string(),
 
             EspSelector::Weapon => obfstr!("Enabled ESP for all weapons").to_string(),
             EspSelector::WeaponGroup { group } => {
@@ -476,6 +1479,7 @@ impl EspSelector {
             Self::PlayerTeamVisibility { enemy, .. } => Some(Self::PlayerTeam { enemy: *enemy }),
 
             Self::Chicken => None,
+            Self::Hostage => None,
 
             Self::Weapon => None,
             Self::WeaponGroup { .. } => Some(Self::Weapon),
@@ -490,20 +1494,19 @@ impl EspSelector {
                 EspSelector::PlayerTeam { enemy: false },
                 EspSelector::PlayerTeam { enemy: true },
             ],
-            /* Currently disable visibility as we do not have a proper vis check */
-            EspSelector::PlayerTeam { .. } => vec![],
-            // EspSelector::PlayerTeam { enemy } => vec![
-            //     EspSelector::PlayerTeamVisibility {
-            //         enemy: *enemy,
-            //         visible: true,
-            //     },
-            //     EspSelector::PlayerTeamVisibility {
-            //         enemy: *enemy,
-            //         visible: false,
-            //     },
-            // ],
+            EspSelector::PlayerTeam { enemy } => vec![
+                EspSelector::PlayerTeamVisibility {
+                    enemy: *enemy,
+                    visible: true,
+                },
+                EspSelector::PlayerTeamVisibility {
+                    enemy: *enemy,
+                    visible: false,
+                },
+            ],
             EspSelector::PlayerTeamVisibility { .. } => vec![],
             EspSelector::Chicken => vec![],
+            EspSelector::Hostage => vec![],
 
             EspSelector::Weapon => vec![
                 EspSelector::WeaponGroup {
@@ -537,3 +1540,108 @@ impl EspSelector {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Color,
+        EspColor,
+        EspColorAnimation,
+    };
+    use crate::settings::ColorPalette;
+
+    #[test]
+    fn legacy_distance_color() {
+        let color: EspColor = serde_yaml::from_str("type: DistanceBased").unwrap();
+        assert!(color == EspColor::distance_based());
+
+        let color: EspColor = serde_json::from_str(r#"{"type":"DistanceBased"}"#).unwrap();
+        assert!(color == EspColor::distance_based());
+    }
+
+    #[test]
+    fn legacy_health_color() {
+        let color: EspColor = serde_yaml::from_str(
+            "type: HealthBased\noptions:\n  max: 4278255360\n  min: 4278190335",
+        )
+        .unwrap();
+        assert!(matches!(color, EspColor::HealthBased { mid: None, .. }));
+    }
+
+    #[test]
+    fn color_roundtrip() {
+        let color = EspColor::DistanceBased {
+            near: Color::from_f32([1.0, 1.0, 1.0, 1.0]),
+            far: Color::from_f32([0.0, 0.0, 0.0, 1.0]),
+            near_distance: 10.0,
+            far_distance: 20.0,
+        };
+
+        let serialized = serde_yaml::to_string(&color).unwrap();
+        assert!(serde_yaml::from_str::<EspColor>(&serialized).unwrap() == color);
+
+        let palette = ColorPalette::default();
+        assert_eq!(
+            color.calculate_color(&palette, 1.0, 5.0, 0.0),
+            [1.0, 1.0, 1.0, 1.0]
+        );
+        assert_eq!(color.calculate_color(&palette, 1.0, 15.0, 0.0)[3], 1.0);
+        assert_eq!(
+            color.calculate_color(&palette, 1.0, 30.0, 0.0),
+            [0.0, 0.0, 0.0, 1.0]
+        );
+    }
+
+    #[test]
+    fn animated_color() {
+        let color = EspColor::Animated {
+            mode: EspColorAnimation::Pulse,
+            speed: 0.5,
+            from: Color::from_f32([1.0, 0.0, 0.0, 1.0]),
+            to: Color::from_f32([0.0, 0.0, 1.0, 1.0]),
+        };
+
+        let serialized = serde_yaml::to_string(&color).unwrap();
+        assert!(serde_yaml::from_str::<EspColor>(&serialized).unwrap() == color);
+
+        let palette = ColorPalette::default();
+        assert_eq!(
+            color.calculate_color(&palette, 1.0, 0.0, 0.0),
+            [1.0, 0.0, 0.0, 1.0]
+        );
+        assert_eq!(
+            color.calculate_color(&palette, 1.0, 0.0, 1.0),
+            [0.0, 0.0, 1.0, 1.0]
+        );
+
+        let rainbow: EspColor = serde_yaml::from_str("type: Animated\noptions: {}").unwrap();
+        assert!(rainbow == EspColor::animated());
+        assert_eq!(
+            rainbow.calculate_color(&palette, 1.0, 0.0, 0.0),
+            [1.0, 0.0, 0.0, 1.0]
+        );
+    }
+
+    #[test]
+    fn health_midpoint() {
+        let color = EspColor::HealthBased {
+            max: Color::from_f32([0.0, 1.0, 0.0, 1.0]),
+            min: Color::from_f32([1.0, 0.0, 0.0, 1.0]),
+            mid: Some(Color::from_f32([0.0, 0.0, 1.0, 1.0])),
+        };
+
+        let palette = ColorPalette::default();
+        assert_eq!(
+            color.calculate_color(&palette, 0.5, 0.0, 0.0),
+            [0.0, 0.0, 1.0, 1.0]
+        );
+        assert_eq!(
+            color.calculate_color(&palette, 1.0, 0.0, 0.0),
+            [0.0, 1.0, 0.0, 1.0]
+        );
+        assert_eq!(
+            color.calculate_color(&palette, 0.0, 0.0, 0.0),
+            [1.0, 0.0, 0.0, 1.0]
+        );
+    }
+}
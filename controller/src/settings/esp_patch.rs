@@ -0,0 +1,187 @@
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use super::{
+    EspArmorBar,
+    EspBotFilter,
+    EspBoxFit,
+    EspBoxType,
+    EspColor,
+    EspHeadDisplay,
+    EspHealthBar,
+    EspInfoAnchor,
+    EspInfoLine,
+    EspPlayerSettings,
+    EspSkeletonMode,
+    EspSpeedUnit,
+    EspTracePosition,
+    EspWeaponStyle,
+};
+
+macro_rules! define_settings_patch {
+    ($(#[$meta:meta])* $name:ident for $target:ident { $($field:ident: $type:ty,)* }) => {
+        $(#[$meta])*
+        #[derive(Clone, Default, Deserialize, Serialize, PartialEq, PartialOrd)]
+        pub struct $name {
+            $(
+                #[serde(default, skip_serializing_if = "Option::is_none")]
+                pub $field: Option<$type>,
+            )*
+        }
+
+        impl $name {
+            /// Apply all overridden values onto the target settings
+            pub fn apply(&self, target: &mut $target) {
+                $(
+                    if let Some(value) = &self.$field {
+                        target.$field = value.clone();
+                    }
+                )*
+            }
+
+            /// Update the patch with the changes between the base and the edited settings.
+            /// Values which are already overridden will always be kept.
+            pub fn update(&mut self, base: &$target, edited: &$target) {
+                $(
+                    if self.$field.is_some() || base.$field != edited.$field {
+                        self.$field = Some(edited.$field.clone());
+                    }
+                )*
+            }
+
+            /// Names of all overridden values
+            pub fn overridden_fields(&self) -> Vec<&'static str> {
+                let mut fields = Vec::new();
+                $(
+                    if self.$field.is_some() {
+                        fields.push(stringify!($field));
+                    }
+                )*
+                fields
+            }
+
+            /// Remove the override of the given value
+            pub fn clear_field(&mut self, field: &str) {
+                match field {
+                    $(stringify!($field) => self.$field = None,)*
+                    _ => {}
+                }
+            }
+
+            pub fn is_empty(&self) -> bool {
+                $(self.$field.is_none())&&*
+            }
+        }
+    };
+}
+
+define_settings_patch! {
+    /// Overrides for a subset of the player ESP settings (e.g. for a specific map).
+    /// Values which are not set will be taken from the base settings.
+    EspPlayerSettingsPatch for EspPlayerSettings {
+        box_type: EspBoxType,
+        box_color: EspColor,
+        box_width: f32,
+        box_corner_length_ratio: f32,
+        box_fit: EspBoxFit,
+        box_fit_margin: f32,
+        shot_indicator: bool,
+        shot_indicator_color: EspColor,
+        shot_indicator_duration: f32,
+        low_health_threshold: i32,
+        low_health_color: EspColor,
+        low_health_blink: bool,
+        bomb_carrier_highlight: bool,
+        bomb_carrier_box_color: EspColor,
+        aiming_at_you: bool,
+        aiming_at_you_fov: f32,
+        aiming_at_you_max_distance: f32,
+        aiming_at_you_color: EspColor,
+        aiming_at_you_banner: bool,
+        outline: bool,
+        box_fill: bool,
+        box_fill_color: EspColor,
+        box_fill_alpha: f32,
+        skeleton: bool,
+        skeleton_color: EspColor,
+        skeleton_width: f32,
+        skeleton_mode: EspSkeletonMode,
+        skeleton_custom_bones: String,
+        skeleton_joints: bool,
+        skeleton_joints_color: EspColor,
+        skeleton_joints_radius: f32,
+        head_display: EspHeadDisplay,
+        head_display_color: EspColor,
+        head_display_width: f32,
+        health_bar: EspHealthBar,
+        health_bar_width: f32,
+        armor_bar: EspArmorBar,
+        armor_bar_width: f32,
+        flash_bar: bool,
+        flash_bar_color: EspColor,
+        flash_bar_height: f32,
+        tracer_lines: EspTracePosition,
+        tracer_lines_color: EspColor,
+        tracer_lines_width: f32,
+        trail: bool,
+        trail_duration: f32,
+        trail_color: EspColor,
+        trail_width: f32,
+        view_direction_line: bool,
+        view_direction_line_length: f32,
+        view_direction_line_color: EspColor,
+        view_direction_line_width: f32,
+        info_text_scale: f32,
+        info_anchor: EspInfoAnchor,
+        info_line_order: Vec<EspInfoLine>,
+        info_name: bool,
+        info_name_color: EspColor,
+        info_show_clan_tag: bool,
+        info_rank: bool,
+        info_rank_color: EspColor,
+        info_kd: bool,
+        info_kd_color: EspColor,
+        info_distance: bool,
+        info_distance_color: EspColor,
+        near_players: bool,
+        near_players_distance: f32,
+        filter_bots: EspBotFilter,
+        distance_fade: bool,
+        fade_distance_start: f32,
+        fade_distance_end: f32,
+        info_weapon: bool,
+        info_weapon_style: EspWeaponStyle,
+        info_weapon_color: EspColor,
+        info_ammo: bool,
+        info_ammo_color: EspColor,
+        info_money: bool,
+        info_money_color: EspColor,
+        info_ping: bool,
+        info_ping_color: EspColor,
+        info_speed: bool,
+        info_speed_unit: EspSpeedUnit,
+        info_speed_color: EspColor,
+        info_hp_text: bool,
+        info_hp_text_color: EspColor,
+        info_flag_kit: bool,
+        info_flag_flashed: bool,
+        info_flag_scoped: bool,
+        info_flag_defusing: bool,
+        info_flag_planting: bool,
+        info_flag_reloading: bool,
+        info_flag_airborne: bool,
+        info_flag_crouched: bool,
+        info_flag_bomb: bool,
+        info_flags_color: EspColor,
+        ghost: bool,
+        ghost_timeout: f32,
+        ghost_color: EspColor,
+        offscreen_arrows: bool,
+        offscreen_arrows_color: EspColor,
+        offscreen_arrows_size: f32,
+        offscreen_arrows_edge_distance: f32,
+        offscreen_arrows_max_distance: f32,
+    }
+}
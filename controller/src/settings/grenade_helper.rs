@@ -0,0 +1,152 @@
+use std::{
+    collections::BTreeMap,
+    fs::File,
+    io::{
+        BufReader,
+        BufWriter,
+    },
+    path::{
+        Path,
+        PathBuf,
+    },
+};
+
+use anyhow::Context;
+use cs2::WeaponId;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, PartialOrd)]
+pub enum GrenadeSpotType {
+    Smoke,
+    Flashbang,
+    HighExplosive,
+    /// Molotov as well as incendiary grenades
+    Molotov,
+}
+
+impl GrenadeSpotType {
+    pub fn from_weapon(weapon: WeaponId) -> Option<Self> {
+        match weapon {
+            WeaponId::SmokeGranade => Some(Self::Smoke),
+            WeaponId::Flashbang => Some(Self::Flashbang),
+            WeaponId::HZGranade => Some(Self::HighExplosive),
+            WeaponId::Molotov | WeaponId::Incendiary => Some(Self::Molotov),
+            _ => None,
+        }
+    }
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Self::Smoke => "Smoke",
+            Self::Flashbang => "Flash",
+            Self::HighExplosive => "HE",
+            Self::Molotov => "Molotov",
+        }
+    }
+}
+
+impl Default for GrenadeSpotType {
+    fn default() -> Self {
+        Self::Smoke
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, PartialOrd)]
+pub enum GrenadeThrowType {
+    Normal,
+    Jump,
+    Run,
+    RunJump,
+    Crouch,
+}
+
+impl GrenadeThrowType {
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Self::Normal => "Throw",
+            Self::Jump => "Jumpthrow",
+            Self::Run => "Run + Throw",
+            Self::RunJump => "Run + Jumpthrow",
+            Self::Crouch => "Crouch + Throw",
+        }
+    }
+}
+
+impl Default for GrenadeThrowType {
+    fn default() -> Self {
+        Self::Normal
+    }
+}
+
+/// A saved grenade lineup
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct GrenadeSpot {
+    pub name: String,
+
+    #[serde(default)]
+    pub description: String,
+
+    pub grenade_type: GrenadeSpotType,
+
+    #[serde(default)]
+    pub throw_type: GrenadeThrowType,
+
+    /// World position where the player has to stand
+    pub position: [f32; 3],
+
+    /// View angles (pitch, yaw) in degrees which are required for the throw
+    pub eye_angles: [f32; 2],
+}
+
+/// Grenade spots grouped by the map name
+pub type GrenadeSpots = BTreeMap<String, Vec<GrenadeSpot>>;
+
+pub fn get_grenade_spots_path() -> anyhow::Result<PathBuf> {
+    let exe_file = std::env::current_exe().context("missing current exe path")?;
+    let base_dir = exe_file.parent().context("could not get exe directory")?;
+
+    Ok(base_dir.join("grenade_spots.json"))
+}
+
+pub fn export_grenade_spots(path: &Path, spots: &GrenadeSpots) -> anyhow::Result<()> {
+    let file = File::options()
+        .create(true)
+        .truncate(true)
+        .write(true)
+        .open(path)
+        .with_context(|| format!("failed to open {}", path.to_string_lossy()))?;
+    let mut file = BufWriter::new(file);
+
+    serde_json::to_writer_pretty(&mut file, spots).context("failed to serialize grenade spots")?;
+    Ok(())
+}
+
+/// Import grenade spots from the given file into the target collection.
+/// Spots which are already present will be skipped.
+/// Returns the number of imported spots.
+pub fn import_grenade_spots(path: &Path, target: &mut GrenadeSpots) -> anyhow::Result<usize> {
+    let file =
+        File::open(path).with_context(|| format!("failed to open {}", path.to_string_lossy()))?;
+    let mut file = BufReader::new(file);
+
+    let spots: GrenadeSpots =
+        serde_json::from_reader(&mut file).context("failed to parse grenade spots")?;
+
+    let mut imported = 0;
+    for (map_name, spots) in spots {
+        let map_spots = target.entry(map_name).or_default();
+        for spot in spots {
+            if map_spots.contains(&spot) {
+                continue;
+            }
+
+            map_spots.push(spot);
+            imported += 1;
+        }
+    }
+
+    Ok(imported)
+}
@@ -1,15 +1,62 @@
+use std::fmt::Display;
+
 use serde::{
     de::Visitor,
     Deserialize,
     Serialize,
 };
 
-#[derive(Clone, Debug)]
-pub struct HotKey(pub imgui::Key);
+use crate::KeyboardInput;
+
+/// Separator between the keys of a key combination
+const KEY_SEPARATOR: char = '+';
+
+/// A single key or a key combination like `LeftAlt+X`.
+/// The hotkey triggers when the primary key is pressed while all modifiers are held down.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HotKey {
+    /// Keys which must be held down
+    pub modifiers: Vec<imgui::Key>,
+
+    /// Key which triggers the hotkey
+    pub key: imgui::Key,
+}
+
+impl HotKey {
+    pub fn new(key: imgui::Key, modifiers: Vec<imgui::Key>) -> Self {
+        Self { modifiers, key }
+    }
+
+    fn modifiers_down(&self, input: &dyn KeyboardInput) -> bool {
+        self.modifiers
+            .iter()
+            .all(|modifier| input.is_key_down(*modifier))
+    }
+
+    /// Returns true if the primary key and all modifiers are held down
+    pub fn is_down(&self, input: &dyn KeyboardInput) -> bool {
+        self.modifiers_down(input) && input.is_key_down(self.key)
+    }
+
+    /// Returns true if the primary key has been pressed while all modifiers are held down
+    pub fn is_pressed(&self, input: &dyn KeyboardInput, repeating: bool) -> bool {
+        self.modifiers_down(input) && input.is_key_pressed(self.key, repeating)
+    }
+}
 
 impl From<imgui::Key> for HotKey {
     fn from(value: imgui::Key) -> Self {
-        Self(value)
+        Self::new(value, Vec::new())
+    }
+}
+
+impl Display for HotKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for modifier in self.modifiers.iter() {
+            write!(f, "{:?}{}", modifier, KEY_SEPARATOR)?;
+        }
+
+        write!(f, "{:?}", self.key)
     }
 }
 
@@ -18,30 +65,38 @@ impl Serialize for HotKey {
     where
         S: serde::Serializer,
     {
-        serializer.serialize_str(&format!("{:?}", self.0))
+        serializer.serialize_str(&self.to_string())
     }
 }
 
+fn parse_key(value: &str) -> Option<imgui::Key> {
+    imgui::Key::VARIANTS
+        .iter()
+        .find(|key| format!("{:?}", key) == value)
+        .cloned()
+}
+
 struct HotKeyVisitor;
 
 impl<'de> Visitor<'de> for HotKeyVisitor {
     type Value = HotKey;
 
     fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
-        formatter.write_str("a config key")
+        formatter.write_str("a config key or key combination")
     }
 
     fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
     where
         E: serde::de::Error,
     {
-        for key in imgui::Key::VARIANTS.iter() {
-            if format!("{:?}", key) == v {
-                return Ok(HotKey(key.clone()));
-            }
-        }
+        /* single keys are a combination without any modifiers */
+        let mut keys = v
+            .split(KEY_SEPARATOR)
+            .map(|key| parse_key(key.trim()).ok_or_else(|| E::custom("unknown key value")))
+            .collect::<Result<Vec<_>, _>>()?;
 
-        Err(E::custom("unknown key value"))
+        let key = keys.pop().ok_or_else(|| E::custom("missing key value"))?;
+        Ok(HotKey::new(key, keys))
     }
 }
 
@@ -53,3 +108,34 @@ impl<'de> Deserialize<'de> for HotKey {
         deserializer.deserialize_str(HotKeyVisitor)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use imgui::Key;
+
+    use super::HotKey;
+
+    #[test]
+    fn single_key() {
+        let key: HotKey = serde_yaml::from_str("F1").unwrap();
+        assert_eq!(key, HotKey::from(Key::F1));
+        assert_eq!(serde_yaml::to_string(&key).unwrap().trim(), "F1");
+    }
+
+    #[test]
+    fn key_combination() {
+        let key: HotKey = serde_yaml::from_str("LeftAlt+X").unwrap();
+        assert_eq!(key, HotKey::new(Key::X, vec![Key::LeftAlt]));
+        assert_eq!(key.to_string(), "LeftAlt+X");
+
+        let key = HotKey::new(Key::LeftShift, vec![Key::MouseX1]);
+        let serialized = serde_yaml::to_string(&key).unwrap();
+        assert_eq!(serde_yaml::from_str::<HotKey>(&serialized).unwrap(), key);
+    }
+
+    #[test]
+    fn unknown_key() {
+        assert!(serde_yaml::from_str::<HotKey>("LeftAlt+Unknown").is_err());
+        assert!(serde_yaml::from_str::<HotKey>("").is_err());
+    }
+}
@@ -0,0 +1,231 @@
+use anyhow::Context;
+use serde_yaml::{
+    Mapping,
+    Value,
+};
+
+/// Current version of the config format.
+/// Increase this value and add a migration whenever existing config values
+/// change their meaning, get renamed or are removed.
+pub const CONFIG_VERSION: u32 = 4;
+
+const CONFIG_VERSION_KEY: &'static str = "config_version";
+
+type Migration = fn(&mut Mapping) -> anyhow::Result<()>;
+
+/// The migration at index N upgrades a config from version N to version N + 1.
+/// Configs without a version are considered to be version 0.
+const MIGRATIONS: [Migration; CONFIG_VERSION as usize] = [
+    migrate_v0_to_v1,
+    migrate_v1_to_v2,
+    migrate_v2_to_v3,
+    migrate_v3_to_v4,
+];
+
+fn rename_field(config: &mut Mapping, name: &str, new_name: &str) {
+    if let Some(value) = config.remove(name) {
+        if !config.contains_key(new_name) {
+            config.insert(Value::String(new_name.to_string()), value);
+        }
+    }
+}
+
+/// Version 0 are all unversioned configs.
+/// The option to retest the trigger bot target after the delay has been replaced by
+/// cancelling a pending shot as soon as the target leaves the crosshair.
+fn migrate_v0_to_v1(config: &mut Mapping) -> anyhow::Result<()> {
+    config.remove("trigger_bot_check_target_after_delay");
+    Ok(())
+}
+
+/// Fix the spelling of the ESP toggle key
+fn migrate_v1_to_v2(config: &mut Mapping) -> anyhow::Result<()> {
+    rename_field(config, "esp_toogle", "esp_toggle");
+    Ok(())
+}
+
+/// The key toggle modes have been renamed to activation modes
+fn migrate_v2_to_v3(config: &mut Mapping) -> anyhow::Result<()> {
+    for field in [
+        "esp_mode",
+        "trigger_bot_mode",
+        "economy_panel_mode",
+        "watermark_mode",
+    ] {
+        let value = match config.get_mut(field) {
+            Some(value) => value,
+            None => continue,
+        };
+
+        let new_value = match value.as_str() {
+            Some("AlwaysOn") => "Always",
+            Some("Trigger") => "Hold",
+            Some("TriggerInverted") => "HoldInverted",
+            _ => continue,
+        };
+        *value = Value::String(new_value.to_string());
+    }
+
+    Ok(())
+}
+
+/// The trigger bot delay fields carry their unit within the name
+fn migrate_v3_to_v4(config: &mut Mapping) -> anyhow::Result<()> {
+    rename_field(config, "trigger_bot_delay_min", "trigger_delay_min_ms");
+    rename_field(config, "trigger_bot_delay_max", "trigger_delay_max_ms");
+    Ok(())
+}
+
+fn read_config_version(config: &Mapping) -> anyhow::Result<u32> {
+    let version = match config.get(CONFIG_VERSION_KEY) {
+        Some(version) => version,
+        None => return Ok(0),
+    };
+
+    version
+        .as_u64()
+        .and_then(|version| u32::try_from(version).ok())
+        .context("invalid config version")
+}
+
+/// Upgrade the given config to the current config version.
+/// Returns the original version of the config.
+pub fn migrate_config(config: &mut Value) -> anyhow::Result<u32> {
+    if config.is_null() {
+        /* empty config file */
+        *config = Value::Mapping(Default::default());
+    }
+
+    let config = config.as_mapping_mut().context("config is not a mapping")?;
+
+    let version = read_config_version(config)?;
+    if version > CONFIG_VERSION {
+        anyhow::bail!(
+            "config version {} is newer than the supported version {}. Please update the controller or delete the config.",
+            version,
+            CONFIG_VERSION
+        );
+    }
+
+    for (index, migration) in MIGRATIONS.iter().enumerate().skip(version as usize) {
+        migration(config).with_context(|| {
+            format!(
+                "failed to migrate config from version {} to {}",
+                index,
+                index + 1
+            )
+        })?;
+    }
+
+    config.insert(
+        Value::String(CONFIG_VERSION_KEY.to_string()),
+        Value::Number(CONFIG_VERSION.into()),
+    );
+    Ok(version)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_yaml::Value;
+
+    use super::{
+        migrate_config,
+        CONFIG_VERSION,
+    };
+    use crate::settings::{
+        ActivationMode,
+        AppSettings,
+    };
+
+    fn load_fixture(content: &str) -> (u32, Value, AppSettings) {
+        let mut config: Value = serde_yaml::from_str(content).unwrap();
+        let version = migrate_config(&mut config).unwrap();
+        let settings = serde_yaml::from_value(config.clone()).unwrap();
+        (version, config, settings)
+    }
+
+    #[test]
+    fn migrate_v0() {
+        let (version, config, settings) = load_fixture(include_str!("fixtures/config_v0.yaml"));
+        assert_eq!(version, 0);
+        assert!(config.get("trigger_bot_check_target_after_delay").is_none());
+        assert!(config.get("esp_toogle").is_none());
+
+        assert_eq!(settings.config_version, CONFIG_VERSION);
+        assert_eq!(settings.esp_mode, ActivationMode::Toggle);
+        assert_eq!(settings.esp_toggle.map(|key| key.key), Some(imgui::Key::F1));
+        assert_eq!(settings.trigger_delay_min_ms, 30);
+        assert_eq!(settings.trigger_delay_max_ms, 60);
+    }
+
+    #[test]
+    fn migrate_v1() {
+        let (version, config, settings) = load_fixture(include_str!("fixtures/config_v1.yaml"));
+        assert_eq!(version, 1);
+        assert!(config.get("esp_toogle").is_none());
+
+        assert_eq!(settings.config_version, CONFIG_VERSION);
+        assert_eq!(settings.esp_mode, ActivationMode::Hold);
+        assert_eq!(
+            settings.esp_toggle.map(|key| key.key),
+            Some(imgui::Key::LeftAlt)
+        );
+    }
+
+    #[test]
+    fn migrate_v2() {
+        let (version, _config, settings) = load_fixture(
+            "config_version: 2\nesp_mode: TriggerInverted\ntrigger_bot_mode: AlwaysOn\nwatermark_mode: Toggle",
+        );
+        assert_eq!(version, 2);
+        assert_eq!(settings.esp_mode, ActivationMode::HoldInverted);
+        assert_eq!(settings.trigger_bot_mode, ActivationMode::Always);
+        assert_eq!(settings.watermark_mode, ActivationMode::Toggle);
+        assert_eq!(settings.economy_panel_mode, ActivationMode::Off);
+    }
+
+    #[test]
+    fn migrate_v3() {
+        let (version, config, settings) =
+            load_fixture("config_version: 3\ntrigger_bot_delay_min: 40\ntrigger_bot_delay_max: 80");
+        assert_eq!(version, 3);
+        assert!(config.get("trigger_bot_delay_min").is_none());
+        assert!(config.get("trigger_bot_delay_max").is_none());
+
+        assert_eq!(settings.trigger_delay_min_ms, 40);
+        assert_eq!(settings.trigger_delay_max_ms, 80);
+    }
+
+    #[test]
+    fn migrate_empty() {
+        let (version, _config, settings) = load_fixture("");
+        assert_eq!(version, 0);
+        assert_eq!(settings.config_version, CONFIG_VERSION);
+    }
+
+    #[test]
+    fn current_version_unchanged() {
+        let settings: AppSettings = serde_yaml::from_str("").unwrap();
+        let mut config = serde_yaml::to_value(&settings).unwrap();
+        let original = config.clone();
+
+        assert_eq!(migrate_config(&mut config).unwrap(), CONFIG_VERSION);
+        assert_eq!(config, original);
+    }
+
+    #[test]
+    fn newer_version_fails() {
+        let mut config: Value =
+            serde_yaml::from_str(&format!("config_version: {}", CONFIG_VERSION + 1)).unwrap();
+        assert!(migrate_config(&mut config).is_err());
+    }
+
+    #[test]
+    fn existing_field_not_overwritten() {
+        let mut config: Value =
+            serde_yaml::from_str("config_version: 1\nesp_toogle: F1\nesp_toggle: F2").unwrap();
+        migrate_config(&mut config).unwrap();
+        assert_eq!(config.get("esp_toggle").and_then(Value::as_str), Some("F2"));
+        assert!(config.get("esp_toogle").is_none());
+    }
+}
@@ -7,5 +7,38 @@ pub use ui::*;
 mod config;
 pub use config::*;
 
+mod autosave;
+pub use autosave::*;
+
+mod backups;
+pub use backups::*;
+
+mod migration;
+pub use migration::*;
+
+mod overrides;
+pub use overrides::*;
+
+mod validation;
+pub use validation::*;
+
+mod profiles;
+pub use profiles::*;
+
+mod share;
+pub use share::*;
+
+mod search;
+pub use search::*;
+
 mod esp;
 pub use esp::*;
+
+mod palette;
+pub use palette::*;
+
+mod esp_patch;
+pub use esp_patch::*;
+
+mod grenade_helper;
+pub use grenade_helper::*;
@@ -0,0 +1,233 @@
+use anyhow::Context;
+use serde_yaml::Value;
+
+use super::AppSettings;
+
+/// Shorthands and former names of the first path segment
+const PATH_ALIASES: [(&'static str, &'static str); 3] = [
+    ("esp", "esp_settings"),
+    ("trigger_bot_delay_min", "trigger_delay_min_ms"),
+    ("trigger_bot_delay_max", "trigger_delay_max_ms"),
+];
+
+/// Setting which has been overridden via the command line (`--set path=value`).
+/// Overrides only apply to the current session and will not be saved.
+#[derive(Debug, Clone)]
+pub struct SettingsOverride {
+    /// Keys leading to the overridden value
+    keys: Vec<String>,
+
+    /// Value before the override has been applied
+    original: Value,
+
+    /// Overridden value
+    value: Value,
+}
+
+fn value_at_mut<'a>(root: &'a mut Value, keys: &[String]) -> Option<&'a mut Value> {
+    keys.iter().try_fold(root, |value, key| {
+        value.as_mapping_mut()?.get_mut(key.as_str())
+    })
+}
+
+/// Resolve a dotted path like `esp.player.enemy.box_type` into the keys of the config.
+/// Map keys may contain dots themselves (e.g. `player.enemy`), therefore the longest
+/// existing key will be used.
+fn resolve_path(root: &Value, path: &str) -> anyhow::Result<Vec<String>> {
+    let segments = path.split('.').collect::<Vec<_>>();
+    if segments.iter().any(|segment| segment.is_empty()) {
+        anyhow::bail!("invalid setting path {}", path);
+    }
+
+    let mut keys = Vec::new();
+    let mut current = root;
+    let mut index = 0;
+    while index < segments.len() {
+        let mapping = current.as_mapping().with_context(|| {
+            format!(
+                "{} is not a group of settings",
+                if keys.is_empty() {
+                    "the root".to_string()
+                } else {
+                    keys.join(".")
+                }
+            )
+        })?;
+
+        let (key, value, length) = (index + 1..=segments.len())
+            .rev()
+            .find_map(|end| {
+                let mut key = segments[index..end].join(".");
+                if keys.is_empty() {
+                    if let Some((_, target)) = PATH_ALIASES.iter().find(|(alias, _)| *alias == key)
+                    {
+                        key = target.to_string();
+                    }
+                }
+
+                mapping
+                    .get(key.as_str())
+                    .map(|value| (key, value, end - index))
+            })
+            .with_context(|| format!("unknown setting {}", path))?;
+
+        keys.push(key);
+        current = value;
+        index += length;
+    }
+
+    Ok(keys)
+}
+
+/// Parse an override in the format `path=value`.
+/// The value will be parsed as YAML, e.g. `true`, `3` or `Box3D`.
+fn parse_override(root: &Value, entry: &str) -> anyhow::Result<SettingsOverride> {
+    let (path, value) = entry
+        .split_once('=')
+        .context("expected an override in the format path=value")?;
+
+    let keys = resolve_path(root, path.trim())?;
+    let value: Value = serde_yaml::from_str(value.trim()).context("invalid value")?;
+
+    let mut root = root.clone();
+    let target = value_at_mut(&mut root, &keys).context("unknown setting")?;
+    let original = std::mem::replace(target, value.clone());
+
+    /* make sure the value is valid for the setting */
+    serde_path_to_error::deserialize::<_, AppSettings>(root)
+        .map_err(|error| anyhow::anyhow!("{}: {}", error.path(), error.into_inner()))?;
+
+    Ok(SettingsOverride {
+        keys,
+        original,
+        value,
+    })
+}
+
+/// Apply the given command line overrides onto the settings
+pub fn apply_settings_overrides(
+    settings: &mut AppSettings,
+    overrides: &[String],
+) -> anyhow::Result<Vec<SettingsOverride>> {
+    if overrides.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut config = serde_yaml::to_value(&*settings).context("failed to serialize settings")?;
+
+    let mut result = Vec::with_capacity(overrides.len());
+    for entry in overrides {
+        let entry_override = parse_override(&config, entry)
+            .with_context(|| format!("invalid setting override {}", entry))?;
+
+        if let Some(value) = value_at_mut(&mut config, &entry_override.keys) {
+            *value = entry_override.value.clone();
+        }
+
+        log::info!(
+            "Overriding setting {} for this session",
+            entry_override.keys.join(".")
+        );
+        result.push(entry_override);
+    }
+
+    *settings = serde_yaml::from_value(config).context("failed to apply setting overrides")?;
+    Ok(result)
+}
+
+/// Serialize the settings without the command line overrides.
+/// Overridden values which have been changed since will be kept.
+pub fn serialize_app_settings(
+    settings: &AppSettings,
+    overrides: &[SettingsOverride],
+) -> anyhow::Result<String> {
+    if overrides.is_empty() {
+        return serde_yaml::to_string(settings).context("failed to serialize config");
+    }
+
+    let mut config = serde_yaml::to_value(settings).context("failed to serialize config")?;
+    for entry in overrides {
+        if let Some(value) = value_at_mut(&mut config, &entry.keys) {
+            if *value == entry.value {
+                *value = entry.original.clone();
+            }
+        }
+    }
+
+    serde_yaml::to_string(&config).context("failed to serialize config")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        apply_settings_overrides,
+        serialize_app_settings,
+    };
+    use crate::settings::{
+        AppSettings,
+        EspBoxType,
+        EspConfig,
+    };
+
+    fn default_settings() -> AppSettings {
+        serde_yaml::from_str("").unwrap()
+    }
+
+    #[test]
+    fn override_values() {
+        let mut settings = default_settings();
+        apply_settings_overrides(
+            &mut settings,
+            &[
+                "bomb_timer=false".to_string(),
+                "esp.player.enemy.box_type=Box3D".to_string(),
+            ],
+        )
+        .unwrap();
+
+        assert!(!settings.bomb_timer);
+        match settings.esp_settings.get("player.enemy") {
+            Some(EspConfig::Player(config)) => assert!(config.box_type == EspBoxType::Box3D),
+            _ => panic!("missing enemy config"),
+        }
+    }
+
+    #[test]
+    fn invalid_overrides() {
+        for entry in [
+            "unknown_setting=1",
+            "bomb_timer",
+            "bomb_timer=maybe",
+            "esp.player.enemy.box_type=3d",
+            "bomb_timer.value=true",
+        ] {
+            let mut settings = default_settings();
+            assert!(
+                apply_settings_overrides(&mut settings, &[entry.to_string()]).is_err(),
+                "{}",
+                entry
+            );
+        }
+    }
+
+    #[test]
+    fn overrides_not_saved() {
+        let mut settings = default_settings();
+        let overrides = apply_settings_overrides(
+            &mut settings,
+            &[
+                "bomb_timer=false".to_string(),
+                "esp_max_enemies=5".to_string(),
+            ],
+        )
+        .unwrap();
+
+        /* values changed after the override should be saved */
+        settings.esp_max_enemies = 7;
+
+        let saved: AppSettings =
+            serde_yaml::from_str(&serialize_app_settings(&settings, &overrides).unwrap()).unwrap();
+        assert!(saved.bomb_timer);
+        assert_eq!(saved.esp_max_enemies, 7);
+    }
+}
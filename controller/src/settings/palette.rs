@@ -0,0 +1,75 @@
+use std::collections::BTreeMap;
+
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use super::Color;
+
+/// Named colors which can be referenced by color settings.
+/// Changing a palette color changes all settings referencing it.
+#[derive(Clone, Default, Deserialize, Serialize, PartialEq)]
+#[serde(transparent)]
+pub struct ColorPalette {
+    pub colors: BTreeMap<String, Color>,
+}
+
+impl ColorPalette {
+    fn from_colors(colors: &[(&str, [f32; 4])]) -> Self {
+        Self {
+            colors: colors
+                .iter()
+                .map(|(name, color)| (name.to_string(), Color::from_f32(*color)))
+                .collect(),
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<Color> {
+        self.colors.get(name).cloned()
+    }
+
+    /// Red enemies and green teammates
+    pub fn classic() -> Self {
+        Self::from_colors(&[
+            ("enemy-primary", [1.0, 0.0, 0.0, 0.75]),
+            ("enemy-secondary", [1.0, 0.5, 0.0, 0.75]),
+            ("team-primary", [0.0, 1.0, 0.0, 0.75]),
+            ("team-secondary", [0.0, 0.8, 0.8, 0.75]),
+            ("accent", [1.0, 1.0, 0.0, 1.0]),
+            ("text", [1.0, 1.0, 1.0, 1.0]),
+        ])
+    }
+
+    /// Orange enemies and blue teammates which are distinguishable with the common color vision deficiencies
+    pub fn colorblind() -> Self {
+        Self::from_colors(&[
+            ("enemy-primary", [0.9, 0.6, 0.0, 0.85]),
+            ("enemy-secondary", [0.8, 0.4, 0.0, 0.85]),
+            ("team-primary", [0.0, 0.45, 0.7, 0.85]),
+            ("team-secondary", [0.35, 0.7, 0.9, 0.85]),
+            ("accent", [0.95, 0.9, 0.25, 1.0]),
+            ("text", [1.0, 1.0, 1.0, 1.0]),
+        ])
+    }
+
+    pub fn neon() -> Self {
+        Self::from_colors(&[
+            ("enemy-primary", [1.0, 0.0, 0.8, 0.9]),
+            ("enemy-secondary", [0.6, 0.0, 1.0, 0.9]),
+            ("team-primary", [0.0, 1.0, 1.0, 0.9]),
+            ("team-secondary", [0.0, 0.6, 1.0, 0.9]),
+            ("accent", [0.6, 1.0, 0.0, 1.0]),
+            ("text", [1.0, 1.0, 1.0, 1.0]),
+        ])
+    }
+
+    /// All built-in palettes
+    pub fn presets() -> [(&'static str, Self); 3] {
+        [
+            ("Classic", Self::classic()),
+            ("Colorblind", Self::colorblind()),
+            ("Neon", Self::neon()),
+        ]
+    }
+}
@@ -0,0 +1,231 @@
+use std::{
+    fs::File,
+    io::{
+        BufReader,
+        BufWriter,
+    },
+    path::PathBuf,
+};
+
+use anyhow::Context;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use super::{
+    get_settings_path,
+    save_app_settings,
+    AppSettings,
+};
+
+/// The default profile is stored within the regular config file
+pub const DEFAULT_PROFILE: &'static str = "default";
+
+/// Directory (next to the executable) containing all additional profiles
+const PROFILES_DIRECTORY: &'static str = "profiles";
+
+/// File (next to the executable) containing the name of the active profile
+const PROFILE_META_FILE: &'static str = "profile.yaml";
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ProfileMeta {
+    #[serde(default)]
+    active_profile: Option<String>,
+}
+
+/// Profile operation requested by the settings UI.
+/// Operations are applied by the application before the next update.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProfileAction {
+    /// Switch to the given profile
+    Switch(String),
+
+    /// Create a new profile from the current settings and switch to it
+    Create(String),
+
+    /// Rename the active profile
+    Rename(String),
+
+    /// Delete the active profile and switch back to the default profile
+    Delete,
+}
+
+fn get_base_directory() -> anyhow::Result<PathBuf> {
+    let exe_file = std::env::current_exe().context("missing current exe path")?;
+    let base_dir = exe_file.parent().context("could not get exe directory")?;
+
+    Ok(base_dir.to_path_buf())
+}
+
+pub fn get_profile_path(name: &str) -> anyhow::Result<PathBuf> {
+    if name == DEFAULT_PROFILE {
+        return get_settings_path();
+    }
+
+    Ok(get_base_directory()?
+        .join(PROFILES_DIRECTORY)
+        .join(format!("{}.yaml", name)))
+}
+
+/// Profile names are used as file names and must therefore only contain
+/// alphanumeric characters, spaces, dashes and underscores.
+pub fn validate_profile_name(name: &str) -> anyhow::Result<()> {
+    if name.trim().is_empty() {
+        anyhow::bail!("profile name must not be empty");
+    }
+
+    if name.trim() != name {
+        anyhow::bail!("profile name must not start or end with a space");
+    }
+
+    if !name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, ' ' | '-' | '_'))
+    {
+        anyhow::bail!(
+            "profile name may only contain letters, digits, spaces, dashes and underscores"
+        );
+    }
+
+    if name.eq_ignore_ascii_case(DEFAULT_PROFILE) {
+        anyhow::bail!("profile name is reserved");
+    }
+
+    Ok(())
+}
+
+/// List all available profiles.
+/// The default profile will always be the first entry.
+pub fn list_profiles() -> anyhow::Result<Vec<String>> {
+    let mut profiles = Vec::new();
+
+    let profiles_directory = get_base_directory()?.join(PROFILES_DIRECTORY);
+    if profiles_directory.is_dir() {
+        let entries = std::fs::read_dir(&profiles_directory).with_context(|| {
+            format!(
+                "failed to read profiles directory {}",
+                profiles_directory.to_string_lossy()
+            )
+        })?;
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().map(|ext| ext != "yaml").unwrap_or(true) {
+                continue;
+            }
+
+            let name = match path.file_stem().and_then(|name| name.to_str()) {
+                Some(name) => name,
+                None => continue,
+            };
+
+            if validate_profile_name(name).is_ok() {
+                profiles.push(name.to_string());
+            }
+        }
+    }
+
+    profiles.sort_by_key(|name| name.to_lowercase());
+    profiles.insert(0, DEFAULT_PROFILE.to_string());
+    Ok(profiles)
+}
+
+/// Load the name of the last active profile.
+/// Falls back to the default profile if the profile does no longer exist.
+pub fn load_active_profile() -> String {
+    let meta = get_base_directory()
+        .map(|base_dir| base_dir.join(PROFILE_META_FILE))
+        .and_then(|path| File::open(&path).context("failed to open profile meta"))
+        .and_then(|file| {
+            serde_yaml::from_reader::<_, ProfileMeta>(BufReader::new(file))
+                .context("failed to parse profile meta")
+        });
+
+    let meta = match meta {
+        Ok(meta) => meta,
+        Err(error) => {
+            log::debug!("Using default profile: {:#}", error);
+            return DEFAULT_PROFILE.to_string();
+        }
+    };
+
+    match meta.active_profile {
+        Some(profile)
+            if validate_profile_name(&profile).is_ok()
+                && get_profile_path(&profile)
+                    .map(|path| path.is_file())
+                    .unwrap_or(false) =>
+        {
+            profile
+        }
+        _ => DEFAULT_PROFILE.to_string(),
+    }
+}
+
+pub fn save_active_profile(name: &str) -> anyhow::Result<()> {
+    let meta_path = get_base_directory()?.join(PROFILE_META_FILE);
+    let meta = File::options()
+        .create(true)
+        .truncate(true)
+        .write(true)
+        .open(&meta_path)
+        .with_context(|| {
+            format!(
+                "failed to open profile meta at {}",
+                meta_path.to_string_lossy()
+            )
+        })?;
+    let mut meta = BufWriter::new(meta);
+
+    serde_yaml::to_writer(
+        &mut meta,
+        &ProfileMeta {
+            active_profile: Some(name.to_string()),
+        },
+    )
+    .context("failed to serialize profile meta")?;
+
+    Ok(())
+}
+
+/// Create a new profile containing the given settings
+pub fn create_profile(name: &str, settings: &AppSettings) -> anyhow::Result<()> {
+    validate_profile_name(name)?;
+
+    let profile_path = get_profile_path(name)?;
+    if profile_path.exists() {
+        anyhow::bail!("profile {} already exists", name);
+    }
+
+    if let Some(parent) = profile_path.parent() {
+        std::fs::create_dir_all(parent).context("failed to create profiles directory")?;
+    }
+
+    save_app_settings(name, settings)
+}
+
+pub fn rename_profile(name: &str, new_name: &str) -> anyhow::Result<()> {
+    if name == DEFAULT_PROFILE {
+        anyhow::bail!("the default profile can not be renamed");
+    }
+
+    validate_profile_name(new_name)?;
+
+    let new_path = get_profile_path(new_name)?;
+    if new_path.exists() {
+        anyhow::bail!("profile {} already exists", new_name);
+    }
+
+    std::fs::rename(get_profile_path(name)?, &new_path).context("failed to rename profile")?;
+    Ok(())
+}
+
+pub fn delete_profile(name: &str) -> anyhow::Result<()> {
+    if name == DEFAULT_PROFILE {
+        anyhow::bail!("the default profile can not be deleted");
+    }
+
+    std::fs::remove_file(get_profile_path(name)?).context("failed to delete profile")?;
+    Ok(())
+}
@@ -0,0 +1,384 @@
+use std::{
+    cell::{
+        RefCell,
+        RefMut,
+    },
+    ops::Deref,
+    rc::Rc,
+    time::{
+        Duration,
+        Instant,
+    },
+};
+
+use imgui::{
+    internal::DataTypeKind,
+    Slider,
+    TabItemFlags,
+    TabItemToken,
+    TreeNodeFlags,
+};
+
+use super::{
+    EspSelector,
+    HotKey,
+};
+use crate::utils::{
+    ImGuiKey,
+    ImguiComboEnum,
+};
+
+/// Duration of the highlight after jumping to a setting
+const HIGHLIGHT_DURATION: Duration = Duration::from_secs(2);
+const HIGHLIGHT_COLOR: [f32; 4] = [1.0, 0.76, 0.03, 1.0];
+const MATCH_COLOR: [f32; 4] = [1.0, 0.76, 0.03, 0.35];
+
+const MAX_RESULTS: usize = 10;
+
+/// Location of a setting within the settings window
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SettingsLocation {
+    pub tab: String,
+    pub esp_target: Option<EspSelector>,
+    pub section: Option<String>,
+}
+
+impl SettingsLocation {
+    pub fn display(&self) -> String {
+        let mut path = self.tab.clone();
+        if let Some(esp_target) = &self.esp_target {
+            path.push_str(" > ");
+            path.push_str(&esp_target.config_title());
+        }
+
+        if let Some(section) = &self.section {
+            path.push_str(" > ");
+            path.push_str(section);
+        }
+
+        path
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SettingsSearchEntry {
+    pub label: String,
+    pub location: SettingsLocation,
+}
+
+/// Registry of all setting labels and their location.
+/// The registry is populated while rendering the settings UI.
+pub struct SettingsSearch {
+    pub query: String,
+    entries: Vec<SettingsSearchEntry>,
+
+    /// Location of the settings which are currently rendered
+    location: SettingsLocation,
+
+    /// Settings which are not visible will not be registered.
+    /// Therefore all tabs and ESP pages will be rendered (invisible) once before the first search.
+    index_pending: bool,
+    indexing: bool,
+    indexed: bool,
+
+    target: Option<SettingsSearchEntry>,
+    target_tab_pending: bool,
+    target_scroll_pending: bool,
+    highlight_start: Option<Instant>,
+}
+
+impl SettingsSearch {
+    pub fn new() -> Self {
+        Self {
+            query: String::new(),
+            entries: Default::default(),
+
+            location: Default::default(),
+
+            index_pending: false,
+            indexing: false,
+            indexed: false,
+
+            target: None,
+            target_tab_pending: false,
+            target_scroll_pending: false,
+            highlight_start: None,
+        }
+    }
+
+    pub fn begin_frame(&mut self) {
+        self.location = Default::default();
+        self.indexing = self.index_pending;
+        self.index_pending = false;
+        if self.indexing {
+            self.indexed = true;
+        }
+
+        if self
+            .highlight_start
+            .map(|start| start.elapsed() > HIGHLIGHT_DURATION)
+            .unwrap_or(false)
+        {
+            self.target = None;
+            self.highlight_start = None;
+        }
+    }
+
+    pub fn query_changed(&mut self) {
+        if !self.query.is_empty() && !self.indexed {
+            self.index_pending = true;
+        }
+    }
+
+    /// Returns true if all tabs and pages should be rendered to discover their settings
+    pub fn is_indexing(&self) -> bool {
+        self.indexing
+    }
+
+    pub fn set_esp_target(&mut self, esp_target: Option<EspSelector>) {
+        self.location.esp_target = esp_target;
+        self.location.section = None;
+    }
+
+    fn query_matches(&self, label: &str) -> bool {
+        let query = self.query.trim();
+        !query.is_empty() && label.to_lowercase().contains(&query.to_lowercase())
+    }
+
+    pub fn results(&self) -> Vec<SettingsSearchEntry> {
+        self.entries
+            .iter()
+            .filter(|entry| self.query_matches(&entry.label))
+            .take(MAX_RESULTS)
+            .cloned()
+            .collect()
+    }
+
+    /// Select the tab and page of the entry, scroll to the setting and highlight it
+    pub fn jump_to(&mut self, entry: SettingsSearchEntry) {
+        self.target = Some(entry);
+        self.target_tab_pending = true;
+        self.target_scroll_pending = true;
+
+        /* the highlight will be restarted as soon the setting has been found */
+        self.highlight_start = Some(Instant::now());
+    }
+
+    /// Register the setting at the current location.
+    /// Returns true if the setting is the current jump target.
+    fn register(&mut self, label: &str) -> bool {
+        let registered = self
+            .entries
+            .iter()
+            .any(|entry| entry.label == label && entry.location == self.location);
+        if !registered {
+            self.entries.push(SettingsSearchEntry {
+                label: label.to_string(),
+                location: self.location.clone(),
+            });
+        }
+
+        self.target
+            .as_ref()
+            .map(|target| target.label == label && target.location == self.location)
+            .unwrap_or(false)
+    }
+}
+
+/// Token of a tab which is either selected or only rendered for indexing
+pub struct SearchableTabToken<'ui> {
+    _token: Option<TabItemToken<'ui>>,
+}
+
+/// Wrapper around the imgui UI which registers all rendered settings
+/// within the settings search and highlights the search results.
+pub struct SearchableUi<'ui> {
+    ui: &'ui imgui::Ui,
+    search: Rc<RefCell<SettingsSearch>>,
+}
+
+impl<'ui> Deref for SearchableUi<'ui> {
+    type Target = imgui::Ui;
+
+    fn deref(&self) -> &Self::Target {
+        self.ui
+    }
+}
+
+impl<'ui> SearchableUi<'ui> {
+    pub fn new(ui: &'ui imgui::Ui, search: Rc<RefCell<SettingsSearch>>) -> Self {
+        Self { ui, search }
+    }
+
+    pub fn search(&self) -> RefMut<'_, SettingsSearch> {
+        self.search.borrow_mut()
+    }
+
+    fn track_rect(&self, label: &str, rect_min: [f32; 2], rect_max: [f32; 2]) {
+        let label = label.split("##").next().unwrap_or_default().trim();
+        if label.is_empty() {
+            return;
+        }
+
+        let mut search = self.search.borrow_mut();
+        let color = if search.register(label) {
+            if search.target_scroll_pending {
+                search.target_scroll_pending = false;
+                search.highlight_start = Some(Instant::now());
+                self.ui.set_scroll_here_y_with_ratio(0.5);
+            }
+
+            let elapsed = search
+                .highlight_start
+                .map(|start| start.elapsed().as_secs_f32())
+                .unwrap_or_default();
+
+            let mut color = HIGHLIGHT_COLOR;
+            color[3] *= (1.0 - elapsed / HIGHLIGHT_DURATION.as_secs_f32()).clamp(0.0, 1.0);
+            color
+        } else if search.query_matches(label) {
+            MATCH_COLOR
+        } else {
+            return;
+        };
+
+        self.ui
+            .get_window_draw_list()
+            .add_rect(
+                [rect_min[0] - 2.0, rect_min[1] - 2.0],
+                [rect_max[0] + 2.0, rect_max[1] + 2.0],
+                color,
+            )
+            .rounding(3.0)
+            .thickness(2.0)
+            .build();
+    }
+
+    /// Register the last rendered item as setting
+    pub fn track(&self, label: &str) {
+        self.track_rect(label, self.ui.item_rect_min(), self.ui.item_rect_max());
+    }
+
+    pub fn tab_item(&self, label: impl AsRef<str>) -> Option<SearchableTabToken<'ui>> {
+        let label = label.as_ref();
+
+        let select = {
+            let mut search = self.search.borrow_mut();
+            let select = search.target_tab_pending
+                && search
+                    .target
+                    .as_ref()
+                    .map(|target| target.location.tab == label)
+                    .unwrap_or(false);
+            if select {
+                search.target_tab_pending = false;
+            }
+            select
+        };
+
+        let flags = if select {
+            TabItemFlags::SET_SELECTED
+        } else {
+            TabItemFlags::empty()
+        };
+
+        let token = self.ui.tab_item_with_flags(label, None, flags);
+        let mut search = self.search.borrow_mut();
+        if token.is_none() && !search.indexing {
+            return None;
+        }
+
+        search.location = SettingsLocation {
+            tab: label.to_string(),
+            ..Default::default()
+        };
+        Some(SearchableTabToken { _token: token })
+    }
+
+    pub fn collapsing_header(&self, label: impl AsRef<str>, flags: TreeNodeFlags) -> bool {
+        let label = label.as_ref();
+
+        let (open_target, indexing) = {
+            let search = self.search.borrow();
+            let open_target = search.target_scroll_pending
+                && search
+                    .target
+                    .as_ref()
+                    .map(|target| {
+                        target.location.section.as_deref() == Some(label)
+                            && target.location.esp_target == search.location.esp_target
+                    })
+                    .unwrap_or(false);
+
+            (open_target, search.indexing)
+        };
+
+        if open_target {
+            unsafe { imgui::sys::igSetNextItemOpen(true, 0) };
+        }
+
+        let open = self.ui.collapsing_header(label, flags);
+        self.search.borrow_mut().location.section = Some(label.to_string());
+        open || indexing
+    }
+
+    pub fn checkbox(&self, label: impl AsRef<str>, value: &mut bool) -> bool {
+        let result = self.ui.checkbox(label.as_ref(), value);
+        self.track(label.as_ref());
+        result
+    }
+
+    pub fn combo_enum<T: PartialEq + Copy>(
+        &self,
+        label: impl AsRef<str>,
+        values: &[(T, &'static str)],
+        value: &mut T,
+    ) -> bool {
+        let result = self.ui.combo_enum(label.as_ref(), values, value);
+        self.track(label.as_ref());
+        result
+    }
+
+    pub fn button_key(&self, label: &str, key: &mut HotKey, size: [f32; 2]) -> bool {
+        let result = self.ui.button_key(label, key, size);
+        self.track(label);
+        result
+    }
+
+    pub fn button_key_optional(
+        &self,
+        label: &str,
+        key: &mut Option<HotKey>,
+        size: [f32; 2],
+    ) -> bool {
+        let result = self.ui.button_key_optional(label, key, size);
+        self.track(label);
+        result
+    }
+
+    pub fn slider_config<T: AsRef<str>, K: DataTypeKind>(
+        &self,
+        label: T,
+        min: K,
+        max: K,
+    ) -> Slider<'ui, T, K> {
+        /* the slider has not been rendered yet, estimate its bounds */
+        let style = self.ui.clone_style();
+        let rect_min = self.ui.cursor_screen_pos();
+        let display_label = label.as_ref().split("##").next().unwrap_or_default();
+        let label_width = self.ui.calc_text_size(display_label)[0];
+        let rect_max = [
+            rect_min[0]
+                + self.ui.calc_item_width()
+                + if label_width > 0.0 {
+                    style.item_inner_spacing[0] + label_width
+                } else {
+                    0.0
+                },
+            rect_min[1] + self.ui.frame_height(),
+        ];
+        self.track_rect(label.as_ref(), rect_min, rect_max);
+
+        self.ui.slider_config(label, min, max)
+    }
+}
@@ -0,0 +1,295 @@
+use std::{
+    collections::BTreeMap,
+    io::Read,
+};
+
+use anyhow::Context;
+use base64::Engine;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use super::{
+    migrate_config,
+    parse_app_settings,
+    ActivationMode,
+    AppSettings,
+    EspConfig,
+    HotKey,
+    CONFIG_VERSION,
+};
+
+/// Prefix of all shared config strings
+const SHARE_PREFIX: &'static str = "VTCFG1:";
+
+/// Max size of a decompressed config to prevent decompression bombs
+const MAX_DECOMPRESSED_SIZE: u64 = 16 * 1024 * 1024;
+
+const COMPRESSION_LEVEL: i32 = 19;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigShareScope {
+    /// All settings
+    Full,
+
+    /// Only the ESP settings
+    Esp,
+}
+
+/// ESP section of the app settings
+#[derive(Clone, Serialize, Deserialize)]
+struct EspShareSettings {
+    esp_mode: ActivationMode,
+    esp_toggle: Option<HotKey>,
+    esp_settings: BTreeMap<String, EspConfig>,
+    esp_settings_enabled: BTreeMap<String, bool>,
+}
+
+impl EspShareSettings {
+    fn from_settings(settings: &AppSettings) -> Self {
+        Self {
+            esp_mode: settings.esp_mode,
+            esp_toggle: settings.esp_toggle.clone(),
+            esp_settings: settings.esp_settings.clone(),
+            esp_settings_enabled: settings.esp_settings_enabled.clone(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct SharedConfigEnvelope {
+    scope: ConfigShareScope,
+
+    /// Config version of the settings (see CONFIG_VERSION).
+    /// Only used if the settings do not contain their own version.
+    #[serde(default)]
+    config_version: u32,
+
+    settings: serde_json::Value,
+}
+
+/// Settings which have been successfully imported from a shared config string
+/// but not yet applied to the live settings.
+pub struct SharedConfig {
+    scope: ConfigShareScope,
+    settings: SharedConfigSettings,
+
+    /// Invalid values which have been reset to their defaults
+    warnings: Vec<String>,
+}
+
+enum SharedConfigSettings {
+    Full(Box<AppSettings>),
+    Esp(EspShareSettings),
+}
+
+impl SharedConfig {
+    pub fn scope(&self) -> ConfigShareScope {
+        self.scope
+    }
+
+    pub fn warnings(&self) -> &[String] {
+        &self.warnings
+    }
+
+    /// Replace the relevant parts of the target settings with the imported settings
+    pub fn apply(self, target: &mut AppSettings) {
+        match self.settings {
+            SharedConfigSettings::Full(mut settings) => {
+                /* the window layout is not part of the shared config */
+                settings.imgui = target.imgui.take();
+                settings.config_version = target.config_version;
+                *target = *settings;
+            }
+            SharedConfigSettings::Esp(settings) => {
+                target.esp_mode = settings.esp_mode;
+                target.esp_toggle = settings.esp_toggle;
+                target.esp_settings = settings.esp_settings;
+                target.esp_settings_enabled = settings.esp_settings_enabled;
+            }
+        }
+    }
+}
+
+/// Serialize the given settings into a compact string which can be shared via the clipboard
+pub fn export_settings_string(
+    settings: &AppSettings,
+    scope: ConfigShareScope,
+) -> anyhow::Result<String> {
+    let settings = match scope {
+        ConfigShareScope::Full => {
+            let mut settings = settings.clone();
+            settings.imgui = None;
+            serde_json::to_value(&settings)
+        }
+        ConfigShareScope::Esp => serde_json::to_value(&EspShareSettings::from_settings(settings)),
+    }
+    .context("failed to serialize settings")?;
+
+    encode_envelope(&SharedConfigEnvelope {
+        scope,
+        config_version: CONFIG_VERSION,
+        settings,
+    })
+}
+
+fn encode_envelope(envelope: &SharedConfigEnvelope) -> anyhow::Result<String> {
+    let payload = serde_json::to_vec(envelope).context("failed to serialize settings")?;
+    let payload = zstd::encode_all(payload.as_slice(), COMPRESSION_LEVEL)
+        .context("failed to compress settings")?;
+
+    Ok(format!(
+        "{}{}",
+        SHARE_PREFIX,
+        base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(payload)
+    ))
+}
+
+/// Parse a shared config string.
+/// The live settings are not touched, call [SharedConfig::apply] to apply the result.
+pub fn import_settings_string(value: &str) -> anyhow::Result<SharedConfig> {
+    let payload = value
+        .trim()
+        .strip_prefix(SHARE_PREFIX)
+        .context("not a valid config string")?;
+
+    let payload = base64::engine::general_purpose::URL_SAFE_NO_PAD
+        .decode(payload)
+        .context("invalid config string encoding")?;
+
+    let mut decoder =
+        zstd::Decoder::new(payload.as_slice()).context("failed to decompress config")?;
+    let mut decompressed = Vec::new();
+    (&mut decoder)
+        .take(MAX_DECOMPRESSED_SIZE)
+        .read_to_end(&mut decompressed)
+        .context("failed to decompress config")?;
+
+    let envelope: SharedConfigEnvelope =
+        serde_json::from_slice(&decompressed).context("failed to parse config")?;
+
+    /* shared settings go through the same migration and validation as a config file */
+    let mut config = serde_yaml::to_value(&envelope.settings).context("failed to parse config")?;
+    let mapping = config.as_mapping_mut().context("config is not a mapping")?;
+    if !mapping.contains_key("config_version") {
+        mapping.insert(
+            serde_yaml::Value::String("config_version".to_string()),
+            serde_yaml::Value::Number(envelope.config_version.into()),
+        );
+    }
+
+    migrate_config(&mut config).context("failed to migrate the shared config")?;
+    let (settings, warnings) = parse_app_settings(config).context("failed to parse config")?;
+    for warning in warnings.iter() {
+        log::warn!("Invalid shared config value {}", warning);
+    }
+
+    let settings = match envelope.scope {
+        ConfigShareScope::Full => SharedConfigSettings::Full(Box::new(settings)),
+        ConfigShareScope::Esp => {
+            SharedConfigSettings::Esp(EspShareSettings::from_settings(&settings))
+        }
+    };
+
+    Ok(SharedConfig {
+        scope: envelope.scope,
+        settings,
+        warnings,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        encode_envelope,
+        export_settings_string,
+        import_settings_string,
+        ConfigShareScope,
+        SharedConfigEnvelope,
+    };
+    use crate::settings::{
+        ActivationMode,
+        AppSettings,
+        CONFIG_VERSION,
+    };
+
+    fn share_string(scope: ConfigShareScope, config_version: u32, settings: &str) -> String {
+        let settings: serde_yaml::Value = serde_yaml::from_str(settings).unwrap();
+        encode_envelope(&SharedConfigEnvelope {
+            scope,
+            config_version,
+            settings: serde_json::to_value(&settings).unwrap(),
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn import_v1() {
+        let value = share_string(
+            ConfigShareScope::Full,
+            0,
+            include_str!("fixtures/config_v1.yaml"),
+        );
+        let config = import_settings_string(&value).unwrap();
+        assert_eq!(config.scope(), ConfigShareScope::Full);
+        assert!(config.warnings().is_empty());
+
+        let mut settings: AppSettings = serde_yaml::from_str("").unwrap();
+        config.apply(&mut settings);
+
+        assert_eq!(settings.config_version, CONFIG_VERSION);
+        assert_eq!(settings.esp_mode, ActivationMode::Hold);
+        assert_eq!(
+            settings.esp_toggle.map(|key| key.key),
+            Some(imgui::Key::LeftAlt)
+        );
+        assert_eq!(settings.trigger_bot_mode, ActivationMode::Toggle);
+        assert_eq!(settings.trigger_delay_min_ms, 10);
+        assert_eq!(settings.trigger_delay_max_ms, 20);
+    }
+
+    #[test]
+    fn import_v1_esp() {
+        let value = share_string(
+            ConfigShareScope::Esp,
+            1,
+            "esp_mode: Trigger\nesp_toogle: LeftAlt\nesp_settings: {}\nesp_settings_enabled: {}",
+        );
+        let config = import_settings_string(&value).unwrap();
+
+        let mut settings: AppSettings = serde_yaml::from_str("").unwrap();
+        config.apply(&mut settings);
+
+        assert_eq!(settings.esp_mode, ActivationMode::Hold);
+        assert_eq!(
+            settings.esp_toggle.map(|key| key.key),
+            Some(imgui::Key::LeftAlt)
+        );
+    }
+
+    #[test]
+    fn import_newer_version_fails() {
+        let value = share_string(
+            ConfigShareScope::Full,
+            0,
+            &format!("config_version: {}", CONFIG_VERSION + 1),
+        );
+        assert!(import_settings_string(&value).is_err());
+    }
+
+    #[test]
+    fn export_import_roundtrip() {
+        let mut settings: AppSettings = serde_yaml::from_str("").unwrap();
+        settings.esp_mode = ActivationMode::HoldInverted;
+
+        let value = export_settings_string(&settings, ConfigShareScope::Esp).unwrap();
+        let config = import_settings_string(&value).unwrap();
+        assert!(config.warnings().is_empty());
+
+        let mut target: AppSettings = serde_yaml::from_str("").unwrap();
+        config.apply(&mut target);
+        assert_eq!(target.esp_mode, ActivationMode::HoldInverted);
+    }
+}
@@ -1,16 +1,31 @@
 use std::{
-    collections::btree_map::Entry,
+    cell::RefCell,
+    collections::{
+        btree_map::Entry,
+        hash_map::DefaultHasher,
+    },
+    hash::{
+        Hash,
+        Hasher,
+    },
+    path::Path,
+    rc::Rc,
     sync::{
         atomic::Ordering,
         Arc,
         Mutex,
     },
-    time::Instant,
+    time::{
+        Duration,
+        Instant,
+    },
 };
 
+use anyhow::Context;
 use cs2::{
     BuildInfo,
     CS2Handle,
+    CurrentMapState,
 };
 use imgui::{
     Condition,
@@ -27,25 +42,69 @@ use obfstr::obfstr;
 use url::Url;
 
 use super::{
+    ActivationMode,
     Color,
+    ColorPalette,
     EspColor,
+    EspColorAnimation,
     EspColorType,
     EspConfig,
     EspSelector,
-    KeyToggleMode,
+    SearchableUi,
+    SettingsSearch,
 };
 use crate::{
+    enhancements::get_local_pawn_info,
+    fonts::{
+        list_custom_fonts,
+        AppFonts,
+    },
     radar::{
         self,
         WebRadar,
         WebRadarState,
     },
     settings::{
+        changed_config_sections,
+        export_grenade_spots,
+        export_settings_string,
+        get_grenade_spots_path,
+        import_grenade_spots,
+        import_settings_string,
+        list_config_backups,
+        list_profiles,
+        load_config_backup,
+        validate_profile_name,
         AppSettings,
+        CompassColorMode,
+        CompassPosition,
+        ConfigBackup,
+        ConfigBackupOptions,
+        ConfigShareScope,
+        EspArmorBar,
+        EspBotFilter,
+        EspBoxFit,
         EspBoxType,
+        EspChickenSettings,
+        EspHeadDisplay,
         EspHealthBar,
+        EspHostageSettings,
+        EspInfoAnchor,
         EspPlayerSettings,
+        EspSkeletonMode,
+        EspSpeedUnit,
         EspTracePosition,
+        EspWeaponStyle,
+        EspWeaponType,
+        GrenadeSpot,
+        GrenadeSpotType,
+        GrenadeThrowType,
+        OverlayCorner,
+        ProfileAction,
+        SniperCrosshairStyle,
+        SpectatorsListPosition,
+        TriggerBotHitboxFilter,
+        DEFAULT_PROFILE,
     },
     utils::{
         self,
@@ -53,8 +112,50 @@ use crate::{
         ImguiComboEnum,
     },
     Application,
+    ScreenCaptureState,
 };
 
+const ACTIVATION_MODES: [(ActivationMode, &'static str); 7] = [
+    (ActivationMode::Off, "Always Off"),
+    (ActivationMode::Hold, "Hold"),
+    (ActivationMode::HoldInverted, "Hold Inverted"),
+    (ActivationMode::Toggle, "Toggle"),
+    (ActivationMode::DoubleTap, "Double Tap"),
+    (ActivationMode::LongPress, "Long Press"),
+    (ActivationMode::Always, "Always On"),
+];
+
+/// All ESP selectors which hold player settings
+const ESP_PLAYER_SELECTORS: [EspSelector; 7] = [
+    EspSelector::Player,
+    EspSelector::PlayerTeam { enemy: true },
+    EspSelector::PlayerTeamVisibility {
+        enemy: true,
+        visible: true,
+    },
+    EspSelector::PlayerTeamVisibility {
+        enemy: true,
+        visible: false,
+    },
+    EspSelector::PlayerTeam { enemy: false },
+    EspSelector::PlayerTeamVisibility {
+        enemy: false,
+        visible: true,
+    },
+    EspSelector::PlayerTeamVisibility {
+        enemy: false,
+        visible: false,
+    },
+];
+
+/// Backup selected in the restore dialog
+struct ConfigBackupPreview {
+    backup: ConfigBackup,
+
+    /// Restored settings, changed top level settings and warnings about invalid values
+    result: Result<(AppSettings, Vec<String>, Vec<String>), String>,
+}
+
 enum EspPlayerActiveHeader {
     Features,
     Style,
@@ -68,8 +169,50 @@ pub struct SettingsUI {
     esp_pending_target: Option<EspSelector>,
 
     esp_player_active_header: EspPlayerActiveHeader,
+
+    /// Map of the ESP overrides currently edited (None for the base settings)
+    esp_override_map: Option<String>,
+    esp_current_map: Option<String>,
+
+    /// Player settings copied via the "Copy" action
+    esp_player_clipboard: Option<EspPlayerSettings>,
+
+    palette_color_name: String,
+
+    player_blacklist_input: String,
+
+    grenade_helper_editor_visible: bool,
+    grenade_spot_name: String,
+    grenade_spot_description: String,
+    grenade_spot_type: GrenadeSpotType,
+    grenade_spot_throw_type: GrenadeThrowType,
+    grenade_spots_file: String,
+    grenade_spots_status: Option<String>,
+
+    /// Cached list of the available profiles
+    profiles: Option<Vec<String>>,
+    profile_name: String,
+    pub profile_status: Option<String>,
+
+    share_scope: ConfigShareScope,
+    share_status: Option<String>,
+    share_import_error: Option<String>,
+
+    /// Cached list of the backups of the active profile
+    backups: Option<Vec<ConfigBackup>>,
+    backup_preview: Option<ConfigBackupPreview>,
+    backup_status: Option<String>,
+
+    search: Rc<RefCell<SettingsSearch>>,
+
+    /// Hash of the serialized settings when they have been checked for changes the last time
+    settings_fingerprint: Option<u64>,
+    settings_fingerprint_checked: Option<Instant>,
 }
 
+/// Interval in which the settings are checked for changes made through the UI
+const CHANGE_DETECTION_INTERVAL: Duration = Duration::from_millis(500);
+
 const VERSION: &str = env!("CARGO_PKG_VERSION");
 impl SettingsUI {
     pub fn new() -> Self {
@@ -81,12 +224,60 @@ impl SettingsUI {
             esp_pending_target: None,
 
             esp_player_active_header: EspPlayerActiveHeader::Features,
+
+            esp_override_map: None,
+            esp_current_map: None,
+
+            esp_player_clipboard: None,
+
+            palette_color_name: String::new(),
+
+            player_blacklist_input: String::new(),
+
+            grenade_helper_editor_visible: false,
+            grenade_spot_name: String::new(),
+            grenade_spot_description: String::new(),
+            grenade_spot_type: Default::default(),
+            grenade_spot_throw_type: Default::default(),
+            grenade_spots_file: get_grenade_spots_path()
+                .map(|path| path.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            grenade_spots_status: None,
+
+            profiles: None,
+            profile_name: String::new(),
+            profile_status: None,
+
+            share_scope: ConfigShareScope::Full,
+            share_status: None,
+            share_import_error: None,
+
+            backups: None,
+            backup_preview: None,
+            backup_status: None,
+
+            search: Rc::new(RefCell::new(SettingsSearch::new())),
+
+            settings_fingerprint: None,
+            settings_fingerprint_checked: None,
         }
     }
 
+    /// Reload the profile list the next time the settings are rendered
+    pub fn profiles_changed(&mut self) {
+        self.profiles = None;
+        self.profile_status = None;
+    }
+
     pub fn render(&mut self, app: &Application, ui: &imgui::Ui) {
-        let content_font = ui.current_font().id();
-        let _title_font = ui.push_font(app.fonts.valthrun);
+        self.search.borrow_mut().begin_frame();
+        let ui = &SearchableUi::new(ui, self.search.clone());
+
+        let (title_font, content_font) = match app.app_state.resolve::<AppFonts>(()) {
+            Ok(fonts) => (fonts.valthrun, fonts.menu),
+            Err(_) => return,
+        };
+        let _title_font = ui.push_font(title_font);
 
         ui.window(obfstr!("Valthrun"))
             .size([600.0, 300.0], Condition::FirstUseEver)
@@ -107,6 +298,17 @@ impl SettingsUI {
                         ui.same_line();
                     }
 
+                    {
+                        let _search_font = ui.push_font(content_font);
+                        ui.same_line_with_pos(ui.window_content_region_max()[0] - 200.0);
+                        ui.set_next_item_width(200.0);
+
+                        let mut search = ui.search();
+                        if ui.input_text("##settings_search", &mut search.query).hint(obfstr!("Search settings")).build() {
+                            search.query_changed();
+                        }
+                    }
+
                     ui.new_line();
                     ui.dummy([ 0.0, 5.0 ]);
                 }
@@ -114,6 +316,13 @@ impl SettingsUI {
                 let _content_font = ui.push_font(content_font);
                 let mut settings = app.settings_mut();
 
+                self.render_search_results(ui);
+
+                /* all tabs will be rendered once to discover their settings */
+                let indexing = ui.search().is_indexing();
+                let _indexing_alpha = indexing.then(|| ui.push_style_var(StyleVar::Alpha(0.0)));
+                let _indexing_disabled = ui.begin_disabled(indexing);
+
                 if let Some(_tab_bar) = ui.tab_bar("main") {
                     if let Some(_tab) = ui.tab_item("Information") {
                         let build_info = app.app_state.resolve::<BuildInfo>(()).ok();
@@ -149,77 +358,468 @@ impl SettingsUI {
 
                     if let Some(_) = ui.tab_item("Hotkeys") {
                         ui.button_key(obfstr!("Toggle Settings"), &mut settings.key_settings, [150.0, 0.0]);
+                        ui.button_key_optional(obfstr!("Cycle Profile"), &mut settings.key_profile_cycle, [150.0, 0.0]);
+                        ui.button_key_optional(obfstr!("Panic Key"), &mut settings.panic_key, [150.0, 0.0]);
+                        ui.button_key_optional(obfstr!("Screen capture protection"), &mut settings.key_capture_protection, [150.0, 0.0]);
 
                         {
-                            let _enabled = ui.begin_enabled(matches!(settings.esp_mode, KeyToggleMode::Toggle | KeyToggleMode::Trigger));
-                            ui.button_key_optional(obfstr!("ESP toggle/trigger"), &mut settings.esp_toogle, [ 150.0, 0.0 ]);
+                            let _enabled = ui.begin_enabled(settings.esp_mode.uses_key());
+                            ui.button_key_optional(obfstr!("ESP toggle/hold"), &mut settings.esp_toggle, [ 150.0, 0.0 ]);
+                        }
+
+                        ui.set_next_item_width(150.0);
+                        ui.slider_config(obfstr!("Double tap window"), 100, 1000).display_format("%d ms").build(&mut settings.key_double_tap_window);
+                        ui.set_next_item_width(150.0);
+                        ui.slider_config(obfstr!("Long press duration"), 100, 2000).display_format("%d ms").build(&mut settings.key_long_press_duration);
+
+                        ui.separator();
+                        if Self::render_reset_button(ui, obfstr!("Reset section"), obfstr!("hotkey")) {
+                            settings.reset_hotkeys();
+                            app.settings_changed.store(true, Ordering::Relaxed);
                         }
                     }
 
                     if let Some(_tab) = ui.tab_item(obfstr!("Visuals")) {
                         ui.set_next_item_width(150.0);
-                        ui.combo_enum(obfstr!("ESP"), &[
-                            (KeyToggleMode::Off, "Always Off"),
-                            (KeyToggleMode::Trigger, "Trigger"),
-                            (KeyToggleMode::TriggerInverted, "Trigger Inverted"),
-                            (KeyToggleMode::Toggle, "Toggle"),
-                            (KeyToggleMode::AlwaysOn, "Always On"),
-                        ], &mut settings.esp_mode);
+                        ui.combo_enum(obfstr!("ESP"), &ACTIVATION_MODES, &mut settings.esp_mode);
+                        if settings.esp_mode.uses_key() {
+                            ui.button_key_optional(obfstr!("ESP toggle/hold"), &mut settings.esp_toggle, [150.0, 0.0]);
+                        }
+
+                        ui.set_next_item_width(150.0);
+                        ui.slider_config(obfstr!("Max enemies"), 0, 32)
+                            .display_format(if settings.esp_max_enemies == 0 { "Unlimited" } else { "%d" })
+                            .build(&mut settings.esp_max_enemies);
+
+                        ui.set_next_item_width(150.0);
+                        ui.slider_config(obfstr!("Max teammates"), 0, 32)
+                            .display_format(if settings.esp_max_teammates == 0 { "Unlimited" } else { "%d" })
+                            .build(&mut settings.esp_max_teammates);
+
+                        ui.checkbox(obfstr!("Draw nearer players on top"), &mut settings.esp_render_far_first);
+
+                        ui.set_next_item_width(150.0);
+                        ui.slider_config(obfstr!("ESP scale"), 0.0, 3.0)
+                            .display_format(if settings.ui_scale_override <= 0.0 { "Auto" } else { "%.2f" })
+                            .build(&mut settings.ui_scale_override);
+                        if ui.is_item_hovered() {
+                            ui.tooltip_text(obfstr!("Scales line widths and text of the ESP.\nAuto derives the scale from the screen resolution."));
+                        }
+
+                        ui.set_next_item_width(150.0);
+                        ui.slider_config(obfstr!("Overlay opacity"), 0.1, 1.0)
+                            .display_format("%.2f")
+                            .build(&mut settings.overlay_opacity);
+                        if ui.is_item_hovered() {
+                            ui.tooltip_text(obfstr!("Opacity of everything drawn by the overlay.\nThe settings menu stays fully opaque."));
+                        }
 
                         ui.checkbox(obfstr!("Bomb Timer"), &mut settings.bomb_timer);
+                        ui.same_line();
+                        ui.checkbox(obfstr!("Timer HUD"), &mut settings.bomb_timer_hud);
+                        ui.checkbox(obfstr!("Bomb ESP"), &mut settings.bomb_esp);
+                        if settings.bomb_esp {
+                            ui.same_line();
+                            ui.checkbox(obfstr!("Timer Panel"), &mut settings.bomb_esp_panel);
+                        }
+
+                        ui.checkbox(obfstr!("Grenade ESP"), &mut settings.grenade_esp);
+                        if settings.grenade_esp {
+                            let _id = ui.push_id("grenade_esp");
+                            ui.indent();
+
+                            ui.checkbox(obfstr!("Enemy grenades only"), &mut settings.grenade_esp_enemy_only);
+                            ui.checkbox(obfstr!("Predicted trajectory"), &mut settings.grenade_esp_prediction);
+
+                            for (label, color) in [
+                                (obfstr!("HE").to_string(), &mut settings.grenade_esp_color_he),
+                                (obfstr!("Flash").to_string(), &mut settings.grenade_esp_color_flash),
+                                (obfstr!("Smoke").to_string(), &mut settings.grenade_esp_color_smoke),
+                                (obfstr!("Molotov").to_string(), &mut settings.grenade_esp_color_fire),
+                                (obfstr!("Decoy").to_string(), &mut settings.grenade_esp_color_decoy),
+                            ] {
+                                let mut value = color.as_f32();
+                                if ui.color_edit4_config(&label, &mut value).alpha_bar(true).inputs(false).build() {
+                                    *color = Color::from_f32(value);
+                                }
+                            }
+
+                            ui.unindent();
+                        }
+
+                        ui.checkbox(obfstr!("Incoming Grenade Warning"), &mut settings.grenade_warning);
+                        if settings.grenade_warning {
+                            let _id = ui.push_id("grenade_warning");
+                            ui.indent();
+
+                            let mut color = settings.grenade_warning_color.as_f32();
+                            if ui.color_edit4_config(obfstr!("Color"), &mut color).alpha_bar(true).inputs(false).build() {
+                                settings.grenade_warning_color = Color::from_f32(color);
+                            }
+
+                            ui.set_next_item_width(150.0);
+                            ui.slider_config(obfstr!("Radius"), 1.0, 30.0).display_format("%.0fm").build(&mut settings.grenade_warning_radius);
+
+                            ui.checkbox(obfstr!("Flash"), &mut settings.grenade_warning_flash);
+                            ui.same_line();
+                            ui.checkbox(obfstr!("HE"), &mut settings.grenade_warning_he);
+                            ui.same_line();
+                            ui.checkbox(obfstr!("Molotov"), &mut settings.grenade_warning_molotov);
+
+                            ui.unindent();
+                        }
+
+                        ui.checkbox(obfstr!("Molotov Area"), &mut settings.inferno_esp);
+                        if settings.inferno_esp {
+                            let _id = ui.push_id("inferno_esp");
+                            ui.indent();
+
+                            let mut color = settings.inferno_esp_color.as_f32();
+                            if ui.color_edit4_config(obfstr!("Color"), &mut color).alpha_bar(true).inputs(false).build() {
+                                settings.inferno_esp_color = Color::from_f32(color);
+                            }
+
+                            ui.checkbox(obfstr!("Show burn time"), &mut settings.inferno_esp_timer);
+
+                            ui.unindent();
+                        }
+
+                        ui.checkbox(obfstr!("Smoke Volume"), &mut settings.smoke_esp);
+                        if settings.smoke_esp {
+                            let _id = ui.push_id("smoke_esp");
+                            ui.indent();
+
+                            let mut color = settings.smoke_esp_color.as_f32();
+                            if ui.color_edit4_config(obfstr!("Color"), &mut color).alpha_bar(true).inputs(false).build() {
+                                settings.smoke_esp_color = Color::from_f32(color);
+                            }
+
+                            ui.checkbox(obfstr!("Show remaining time"), &mut settings.smoke_esp_timer);
+
+                            ui.unindent();
+                        }
+
+                        ui.checkbox(obfstr!("Grenade Helper"), &mut settings.grenade_helper);
+                        if settings.grenade_helper {
+                            let _id = ui.push_id("grenade_helper");
+                            ui.indent();
+
+                            ui.checkbox(obfstr!("Only while holding a grenade"), &mut settings.grenade_helper_holding_only);
+
+                            ui.set_next_item_width(150.0);
+                            ui.slider_config(obfstr!("Max distance"), 1.0, 50.0).display_format("%.0fm").build(&mut settings.grenade_helper_max_distance);
+
+                            let mut color = settings.grenade_helper_color.as_f32();
+                            if ui.color_edit4_config(obfstr!("Color"), &mut color).alpha_bar(true).inputs(false).build() {
+                                settings.grenade_helper_color = Color::from_f32(color);
+                            }
+
+                            if ui.button(obfstr!("Open spot editor")) {
+                                self.grenade_helper_editor_visible = true;
+                            }
+
+                            ui.unindent();
+                        }
+
+                        ui.checkbox(obfstr!("Defuse Kit ESP"), &mut settings.defuse_kit_esp);
+                        if settings.defuse_kit_esp {
+                            let _id = ui.push_id("defuse_kit_esp");
+                            ui.indent();
+
+                            let mut color = settings.defuse_kit_esp_color.as_f32();
+                            if ui.color_edit4_config(obfstr!("Color"), &mut color).alpha_bar(true).inputs(false).build() {
+                                settings.defuse_kit_esp_color = Color::from_f32(color);
+                            }
+
+                            ui.set_next_item_width(150.0);
+                            ui.slider_config(obfstr!("Max distance"), 5.0, 200.0).display_format("%.0fm").build(&mut settings.defuse_kit_esp_max_distance);
+
+                            ui.checkbox(obfstr!("Show when not CT"), &mut settings.defuse_kit_esp_always);
+
+                            ui.unindent();
+                        }
+                        ui.checkbox(obfstr!("Sniper Crosshair"), &mut settings.sniper_crosshair);
+                        if settings.sniper_crosshair {
+                            let _id = ui.push_id("sniper_crosshair");
+                            ui.indent();
+
+                            ui.set_next_item_width(150.0);
+                            ui.combo_enum(obfstr!("Style"), &[
+                                (SniperCrosshairStyle::Cross, "Cross"),
+                                (SniperCrosshairStyle::Dot, "Dot"),
+                                (SniperCrosshairStyle::Circle, "Circle"),
+                            ], &mut settings.sniper_crosshair_style);
+
+                            let mut color = settings.sniper_crosshair_color.as_f32();
+                            if ui.color_edit4_config(obfstr!("Color"), &mut color).alpha_bar(true).inputs(false).build() {
+                                settings.sniper_crosshair_color = Color::from_f32(color);
+                            }
+
+                            ui.set_next_item_width(150.0);
+                            ui.slider_config(obfstr!("Size"), 1.0, 30.0).display_format("%.1f").build(&mut settings.sniper_crosshair_size);
+                            ui.set_next_item_width(150.0);
+                            ui.slider_config(obfstr!("Gap"), 0.0, 20.0).display_format("%.1f").build(&mut settings.sniper_crosshair_gap);
+                            ui.set_next_item_width(150.0);
+                            ui.slider_config(obfstr!("Thickness"), 0.5, 5.0).display_format("%.1f").build(&mut settings.sniper_crosshair_thickness);
+
+                            ui.unindent();
+                        }
+
+                        ui.checkbox(obfstr!("Recoil Crosshair"), &mut settings.recoil_crosshair);
+                        if settings.recoil_crosshair {
+                            let _id = ui.push_id("recoil_crosshair");
+                            ui.indent();
+
+                            let mut color = settings.recoil_crosshair_color.as_f32();
+                            if ui.color_edit4_config(obfstr!("Color"), &mut color).alpha_bar(true).inputs(false).build() {
+                                settings.recoil_crosshair_color = Color::from_f32(color);
+                            }
+
+                            ui.set_next_item_width(150.0);
+                            ui.slider_config(obfstr!("Dot size"), 1.0, 10.0).display_format("%.1f").build(&mut settings.recoil_crosshair_size);
+
+                            ui.checkbox(obfstr!("Only while firing"), &mut settings.recoil_crosshair_firing_only);
+
+                            ui.unindent();
+                        }
+
                         ui.checkbox(obfstr!("Spectators List"), &mut settings.spectators_list);
+                        if settings.spectators_list {
+                            let _id = ui.push_id("spectators_list");
+                            ui.indent();
+
+                            ui.set_next_item_width(150.0);
+                            ui.combo_enum(obfstr!("Position"), &[
+                                (SpectatorsListPosition::Left, "Left"),
+                                (SpectatorsListPosition::TopLeft, "Top left"),
+                                (SpectatorsListPosition::TopRight, "Top right"),
+                                (SpectatorsListPosition::BottomLeft, "Bottom left"),
+                                (SpectatorsListPosition::BottomRight, "Bottom right"),
+                                (SpectatorsListPosition::Custom, "Custom (movable)"),
+                            ], &mut settings.spectators_list_position);
+
+                            ui.set_next_item_width(150.0);
+                            ui.slider_config(obfstr!("Background alpha"), 0.0, 1.0).display_format("%.2f").build(&mut settings.spectators_list_background_alpha);
+
+                            ui.unindent();
+                        }
+
+                        ui.checkbox(obfstr!("Footstep ESP"), &mut settings.sound_esp);
+                        if settings.sound_esp {
+                            let _id = ui.push_id("sound_esp");
+                            ui.indent();
+
+                            let mut color = settings.sound_esp_color.as_f32();
+                            if ui.color_edit4_config(obfstr!("Color"), &mut color).alpha_bar(true).inputs(false).build() {
+                                settings.sound_esp_color = Color::from_f32(color);
+                            }
+
+                            ui.set_next_item_width(150.0);
+                            ui.slider_config(obfstr!("Radius scale"), 0.25, 4.0).build(&mut settings.sound_esp_radius_scale);
+
+                            ui.set_next_item_width(150.0);
+                            ui.slider_config(obfstr!("Max distance"), 5.0, 100.0).display_format("%.0fm").build(&mut settings.sound_esp_max_distance);
+
+                            ui.unindent();
+                        }
+
+                        ui.checkbox(obfstr!("Damage Numbers"), &mut settings.damage_indicator);
+                        if settings.damage_indicator {
+                            let _id = ui.push_id("damage_indicator");
+                            ui.indent();
+
+                            let mut color = settings.damage_indicator_color.as_f32();
+                            if ui.color_edit4_config(obfstr!("Color"), &mut color).alpha_bar(true).inputs(false).build() {
+                                settings.damage_indicator_color = Color::from_f32(color);
+                            }
+
+                            ui.set_next_item_width(150.0);
+                            ui.slider_config(obfstr!("Text size"), 0.5, 3.0).build(&mut settings.damage_indicator_text_scale);
+
+                            ui.checkbox(obfstr!("Hitmarker"), &mut settings.damage_indicator_hitmarker);
+                            ui.checkbox(obfstr!("Only while shooting"), &mut settings.damage_indicator_local_only);
+
+                            ui.unindent();
+                        }
+
+                        ui.checkbox(obfstr!("Damage Summary"), &mut settings.damage_summary);
+                        if settings.damage_summary {
+                            let _id = ui.push_id("damage_summary");
+                            ui.indent();
+
+                            ui.checkbox(obfstr!("Show at round end"), &mut settings.damage_summary_round_end);
+                            ui.button_key_optional(obfstr!("Show while pressed"), &mut settings.damage_summary_key, [150.0, 0.0]);
+
+                            ui.unindent();
+                        }
+
+                        if let Some(_node) = ui.tree_node(obfstr!("Ignored players")) {
+                            let mut remove_index = None;
+                            for (index, entry) in settings.player_blacklist.iter().enumerate() {
+                                let _id = ui.push_id_usize(index);
+                                if ui.small_button("x") {
+                                    remove_index = Some(index);
+                                }
+                                ui.same_line();
+                                ui.text(entry);
+                            }
+                            if let Some(index) = remove_index {
+                                settings.player_blacklist.remove(index);
+                            }
+
+                            ui.set_next_item_width(200.0);
+                            let submitted = ui.input_text("##player_blacklist_input", &mut self.player_blacklist_input)
+                                .hint(obfstr!("Name or SteamID64"))
+                                .enter_returns_true(true)
+                                .build();
+                            ui.same_line();
+                            if ui.button(obfstr!("Add")) || submitted {
+                                let entry = self.player_blacklist_input.trim();
+                                if !entry.is_empty() && !settings.player_blacklist.iter().any(|value| value == entry) {
+                                    settings.player_blacklist.push(entry.to_string());
+                                }
+                                self.player_blacklist_input.clear();
+                            }
+                        }
+
+                        if ui.collapsing_header(obfstr!("Color Palette"), TreeNodeFlags::empty()) {
+                            self.render_color_palette(settings, ui);
+                        }
+
+                        ui.separator();
+                        if Self::render_reset_button(ui, obfstr!("Reset to defaults"), obfstr!("visuals")) {
+                            settings.reset_visuals();
+                            app.settings_changed.store(true, Ordering::Relaxed);
+                        }
                     }
 
                     if let Some(_tab) = ui.tab_item(obfstr!("ESP")) {
-                        if settings.esp_mode == KeyToggleMode::Off {
+                        if settings.esp_mode == ActivationMode::Off {
                             let _style = ui.push_style_color(StyleColor::Text, [ 1.0, 0.76, 0.03, 1.0 ]);
                             ui.text("ESP has been disabled.");
                             ui.text("Please enable ESP under \"Visuals\" \"ESP\"");
                         } else {
-                            self.render_esp_settings(&mut *settings, ui);
+                            self.esp_current_map = app
+                                .app_state
+                                .resolve::<CurrentMapState>(())
+                                .ok()
+                                .and_then(|state| state.current_map.clone());
+                            self.render_esp_settings(app, &mut *settings, ui);
                         }
                     }
 
                     if let Some(_) = ui.tab_item(obfstr!("Aim Assist")) {
                         ui.set_next_item_width(150.0);
-                        ui.combo_enum(obfstr!("Trigger Bot"), &[
-                            (KeyToggleMode::Off, "Always Off"),
-                            (KeyToggleMode::Trigger, "Trigger"),
-                            (KeyToggleMode::TriggerInverted, "Trigger Inverted"),
-                            (KeyToggleMode::Toggle, "Toggle"),
-                            (KeyToggleMode::AlwaysOn, "Always On"),
-                        ], &mut settings.trigger_bot_mode);
-
-                        if !matches!(settings.trigger_bot_mode, KeyToggleMode::Off | KeyToggleMode::AlwaysOn) {
+                        ui.combo_enum(obfstr!("Trigger Bot"), &ACTIVATION_MODES, &mut settings.trigger_bot_mode);
+
+                        if settings.trigger_bot_mode.uses_key() {
                             ui.button_key_optional(obfstr!("Trigger bot key"), &mut settings.key_trigger_bot, [150.0, 0.0]);
                         }
-                        if !matches!(settings.trigger_bot_mode, KeyToggleMode::Off) {
+                        if !matches!(settings.trigger_bot_mode, ActivationMode::Off) {
                             let mut values_updated = false;
 
                             ui.text(obfstr!("Trigger delay: ")); ui.same_line();
 
                             let slider_width = (ui.current_column_width() / 2.0 - 20.0).min(300.0).max(50.0);
                             ui.set_next_item_width(slider_width);
-                            values_updated |= ui.slider_config("##delay_min", 0, 250).display_format("%dms").build(&mut settings.trigger_bot_delay_min); ui.same_line();
+                            values_updated |= ui.slider_config("##delay_min", 0, 250).display_format("%dms").build(&mut settings.trigger_delay_min_ms); ui.same_line();
+                            ui.text(" - "); ui.same_line();
+                            ui.set_next_item_width(slider_width);
+                            values_updated |= ui.slider_config("##delay_max", 0, 250).display_format("%dms").build(&mut settings.trigger_delay_max_ms); 
+
+                            if values_updated {
+                                /* fixup min/max */
+                                let delay_min = settings.trigger_delay_min_ms.min(settings.trigger_delay_max_ms);
+                                let delay_max = settings.trigger_delay_min_ms.max(settings.trigger_delay_max_ms);
+
+                                settings.trigger_delay_min_ms = delay_min;
+                                settings.trigger_delay_max_ms = delay_max;
+                            }
+
+                            let mut values_updated = false;
+
+                            ui.text(obfstr!("Shot duration: ")); ui.same_line();
+
+                            ui.set_next_item_width(slider_width);
+                            values_updated |= ui.slider_config("##shot_duration_min", 0, 500).display_format("%dms").build(&mut settings.trigger_bot_shot_duration_min); ui.same_line();
                             ui.text(" - "); ui.same_line();
                             ui.set_next_item_width(slider_width);
-                            values_updated |= ui.slider_config("##delay_max", 0, 250).display_format("%dms").build(&mut settings.trigger_bot_delay_max); 
+                            values_updated |= ui.slider_config("##shot_duration_max", 0, 500).display_format("%dms").build(&mut settings.trigger_bot_shot_duration_max);
 
                             if values_updated {
                                 /* fixup min/max */
-                                let delay_min = settings.trigger_bot_delay_min.min(settings.trigger_bot_delay_max);
-                                let delay_max = settings.trigger_bot_delay_min.max(settings.trigger_bot_delay_max);
+                                let duration_min = settings.trigger_bot_shot_duration_min.min(settings.trigger_bot_shot_duration_max);
+                                let duration_max = settings.trigger_bot_shot_duration_min.max(settings.trigger_bot_shot_duration_max);
 
-                                settings.trigger_bot_delay_min = delay_min;
-                                settings.trigger_bot_delay_max = delay_max;
+                                settings.trigger_bot_shot_duration_min = duration_min;
+                                settings.trigger_bot_shot_duration_max = duration_max;
                             }
 
-                            ui.checkbox(obfstr!("Retest trigger target after delay"), &mut settings.trigger_bot_check_target_after_delay);
+                            ui.set_next_item_width(150.0);
+                            ui.slider_config(obfstr!("Shot jitter"), 0, 100).display_format("%dms").build(&mut settings.trigger_bot_jitter);
+
                             ui.checkbox(obfstr!("Team Check"), &mut settings.trigger_bot_team_check);
+
+                            ui.set_next_item_width(150.0);
+                            ui.combo_enum(obfstr!("Hitbox filter"), &[
+                                (TriggerBotHitboxFilter::Head, "Head only"),
+                                (TriggerBotHitboxFilter::HeadChest, "Head + Chest"),
+                                (TriggerBotHitboxFilter::Any, "Any"),
+                            ], &mut settings.trigger_bot_hitbox_filter);
+
+                            ui.checkbox(obfstr!("Only while scoped"), &mut settings.trigger_bot_scoped_only);
+
+                            ui.checkbox(obfstr!("Velocity limit"), &mut settings.trigger_bot_velocity_gate);
+                            if settings.trigger_bot_velocity_gate {
+                                ui.same_line();
+                                ui.set_next_item_width(150.0);
+                                ui.slider_config("##max_velocity", 0.0, 250.0).display_format("%.0f u/s").build(&mut settings.trigger_bot_max_velocity);
+                            }
+
+                            ui.checkbox(obfstr!("Aim punch limit"), &mut settings.trigger_bot_punch_gate);
+                            if settings.trigger_bot_punch_gate {
+                                ui.same_line();
+                                ui.set_next_item_width(150.0);
+                                ui.slider_config("##max_punch", 0.0, 5.0).display_format("%.1f deg").build(&mut settings.trigger_bot_max_punch);
+                            }
+                            ui.checkbox(obfstr!("Weapon whitelist"), &mut settings.trigger_bot_weapon_whitelist);
+                            if settings.trigger_bot_weapon_whitelist {
+                                let _id = ui.push_id("trigger_bot_weapons");
+                                ui.indent();
+
+                                for group in [
+                                    EspWeaponType::Pistol,
+                                    EspWeaponType::SMG,
+                                    EspWeaponType::Shotgun,
+                                    EspWeaponType::Rifle,
+                                    EspWeaponType::SniperRifle,
+                                    EspWeaponType::MachineGun,
+                                ] {
+                                    if let Some(_node) = ui.tree_node(group.display_name()) {
+                                        for weapon in group.weapons() {
+                                            let mut enabled = settings.trigger_bot_weapons.iter().any(|name| name == weapon.name());
+                                            if ui.checkbox(weapon.display_name(), &mut enabled) {
+                                                if enabled {
+                                                    settings.trigger_bot_weapons.push(weapon.name().to_string());
+                                                } else {
+                                                    settings.trigger_bot_weapons.retain(|name| name != weapon.name());
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+
+                                ui.unindent();
+                            }
                             ui.separator();
                         }
 
                         //ui.checkbox("Simle Recoil Helper", &mut settings.aim_assist_recoil);
+
+                        if Self::render_reset_button(ui, obfstr!("Reset section"), obfstr!("aim assist")) {
+                            settings.reset_trigger_bot();
+                            app.settings_changed.store(true, Ordering::Relaxed);
+                        }
                     }
 
                     if let Some(_) = ui.tab_item("Web Radar") {
@@ -230,86 +830,1207 @@ impl SettingsUI {
                     if let Some(_) = ui.tab_item("Misc") {
                         ui.checkbox(obfstr!("Valthrun Watermark"), &mut settings.valthrun_watermark);
 
-                        if ui.checkbox(obfstr!("Hide overlay from screen capture"), &mut settings.hide_overlay_from_screen_capture) {
-                            app.settings_screen_capture_changed.store(true, Ordering::Relaxed);
-                        }
+                        ui.checkbox(obfstr!("Active Features List"), &mut settings.bind_list);
+                        if settings.bind_list {
+                            let _id = ui.push_id("bind_list");
+                            ui.indent();
+
+                            ui.set_next_item_width(150.0);
+                            ui.combo_enum(obfstr!("Position"), &[
+                                (OverlayCorner::TopLeft, "Top left"),
+                                (OverlayCorner::TopRight, "Top right"),
+                                (OverlayCorner::BottomLeft, "Bottom left"),
+                                (OverlayCorner::BottomRight, "Bottom right"),
+                            ], &mut settings.bind_list_position);
+
+                            let mut color = settings.bind_list_color.as_f32();
+                            if ui.color_edit4_config(obfstr!("Color"), &mut color).alpha_bar(true).inputs(false).build() {
+                                settings.bind_list_color = Color::from_f32(color);
+                            }
 
-                        if ui.checkbox(obfstr!("Show render debug overlay"), &mut settings.render_debug_window) {
-                            app.settings_render_debug_window_changed.store(true, Ordering::Relaxed);
+                            ui.set_next_item_width(150.0);
+                            ui.slider_config(obfstr!("Background alpha"), 0.0, 1.0).display_format("%.2f").build(&mut settings.bind_list_background_alpha);
+
+                            ui.unindent();
                         }
-                    }
-                }
-            });
-    }
 
-    fn render_web_radar(
-        &mut self,
-        settings: &mut AppSettings,
-        web_radar: &mut Option<Arc<Mutex<WebRadar>>>,
-        cs2: &Arc<CS2Handle>,
-        ui: &imgui::Ui,
-    ) {
-        match web_radar {
-            Some(radar) => {
-                let mut radar = radar.lock().unwrap();
-                match radar.connection_state() {
-                    WebRadarState::Connecting => {
-                        ui.text(format!("Connecting to {}", radar.endpoint()));
-                        ui.text("Please wait...");
-                    }
-                    WebRadarState::Connected { session_id } => {
-                        let mut radar_url = radar.endpoint().clone();
-                        radar_url.set_path(&format!("/session/{}", session_id));
-                        if radar_url.scheme() == "wss" {
-                            let _ = radar_url.set_scheme("https");
-                        } else {
-                            let _ = radar_url.set_scheme("http");
+                        ui.checkbox(obfstr!("Round Timer HUD"), &mut settings.round_hud);
+                        if settings.round_hud {
+                            let _id = ui.push_id("round_hud");
+                            ui.indent();
+
+                            ui.set_next_item_width(150.0);
+                            ui.combo_enum(obfstr!("Position"), &[
+                                (OverlayCorner::TopLeft, "Top left"),
+                                (OverlayCorner::TopRight, "Top right"),
+                                (OverlayCorner::BottomLeft, "Bottom left"),
+                                (OverlayCorner::BottomRight, "Bottom right"),
+                            ], &mut settings.round_hud_position);
+
+                            let mut color = settings.round_hud_color.as_f32();
+                            if ui.color_edit4_config(obfstr!("Color"), &mut color).alpha_bar(true).inputs(false).build() {
+                                settings.round_hud_color = Color::from_f32(color);
+                            }
+
+                            ui.checkbox(obfstr!("Show buy time"), &mut settings.round_hud_buy_time);
+
+                            ui.unindent();
                         }
 
-                        ui.text(format!("You're sharing this game."));
-                        {
-                            let mut session_id = session_id.clone();
-                            ui.text("Session ID");
+                        ui.checkbox(obfstr!("Kill Feed"), &mut settings.kill_feed);
+                        if settings.kill_feed {
+                            let _id = ui.push_id("kill_feed");
+                            ui.indent();
+
+                            ui.set_next_item_width(150.0);
+                            ui.combo_enum(obfstr!("Position"), &[
+                                (OverlayCorner::TopLeft, "Top left"),
+                                (OverlayCorner::TopRight, "Top right"),
+                                (OverlayCorner::BottomLeft, "Bottom left"),
+                                (OverlayCorner::BottomRight, "Bottom right"),
+                            ], &mut settings.kill_feed_position);
+
+                            let mut color = settings.kill_feed_color.as_f32();
+                            if ui.color_edit4_config(obfstr!("Color"), &mut color).alpha_bar(true).inputs(false).build() {
+                                settings.kill_feed_color = Color::from_f32(color);
+                            }
 
-                            ui.same_line_with_pos(100.0);
-                            ui.set_next_item_width(300.0);
-                            ui.input_text("##session_id", &mut session_id)
-                                .read_only(true)
-                                .build();
+                            ui.set_next_item_width(150.0);
+                            ui.slider_config(obfstr!("Max entries"), 1, 10).build(&mut settings.kill_feed_max_entries);
 
-                            let show_copied = self
-                                .radar_session_copied
-                                .as_ref()
-                                .map(|time| time.elapsed().as_millis() < 3_000)
-                                .unwrap_or(false);
+                            ui.set_next_item_width(150.0);
+                            ui.slider_config(obfstr!("Display duration"), 1.0, 20.0).display_format("%.1fs").build(&mut settings.kill_feed_duration);
 
-                            let copy_session_text = if show_copied {
-                                "Session id copied"
-                            } else {
-                                "Copy session id"
-                            };
+                            ui.checkbox(obfstr!("Enemies only"), &mut settings.kill_feed_enemies_only);
 
-                            ui.same_line();
-                            if ui.button(copy_session_text) {
-                                ui.set_clipboard_text(format!("{}", session_id));
-                                self.radar_session_copied = Some(Instant::now());
+                            ui.unindent();
+                        }
+
+                        ui.checkbox(obfstr!("Death Markers"), &mut settings.death_markers);
+                        if settings.death_markers {
+                            let _id = ui.push_id("death_markers");
+                            ui.indent();
+
+                            let mut color = settings.death_markers_color.as_f32();
+                            if ui.color_edit4_config(obfstr!("Color"), &mut color).alpha_bar(true).inputs(false).build() {
+                                settings.death_markers_color = Color::from_f32(color);
                             }
+
+                            ui.checkbox(obfstr!("Show player name"), &mut settings.death_markers_name);
+
+                            ui.set_next_item_width(150.0);
+                            ui.slider_config(obfstr!("Max distance"), 5.0, 200.0).display_format("%.0fm").build(&mut settings.death_markers_max_distance);
+
+                            ui.set_next_item_width(150.0);
+                            ui.slider_config(obfstr!("Max age"), 10.0, 300.0).display_format("%.0fs").build(&mut settings.death_markers_max_age);
+
+                            ui.unindent();
                         }
-                        {
-                            let mut radar_url = format!("{}", radar_url);
-                            ui.set_next_item_width(100.0);
-                            ui.text("URL");
 
-                            ui.same_line_with_pos(100.0);
-                            ui.set_next_item_width(300.0);
-                            ui.input_text("##url", &mut radar_url)
-                                .read_only(true)
-                                .build();
+                        ui.checkbox(obfstr!("Proximity Alert"), &mut settings.proximity_alert);
+                        if settings.proximity_alert {
+                            let _id = ui.push_id("proximity_alert");
+                            ui.indent();
 
-                            ui.same_line();
-                            if ui.button("Open URL") {
-                                ui.set_clipboard_text(&radar_url);
-                                utils::open_url(&radar_url);
+                            let mut color = settings.proximity_alert_color.as_f32();
+                            if ui.color_edit4_config(obfstr!("Color"), &mut color).alpha_bar(true).inputs(false).build() {
+                                settings.proximity_alert_color = Color::from_f32(color);
+                            }
+
+                            ui.set_next_item_width(150.0);
+                            ui.slider_config(obfstr!("Alert radius"), 1.0, 50.0).display_format("%.0fm").build(&mut settings.proximity_alert_radius);
+
+                            ui.checkbox(obfstr!("Pulse within radius"), &mut settings.proximity_alert_pulse);
+
+                            ui.unindent();
+                        }
+
+                        ui.checkbox(obfstr!("Compass"), &mut settings.compass);
+                        if settings.compass {
+                            let _id = ui.push_id("compass");
+                            ui.indent();
+
+                            ui.set_next_item_width(150.0);
+                            ui.combo_enum(obfstr!("Position"), &[
+                                (CompassPosition::Top, "Top"),
+                                (CompassPosition::Bottom, "Bottom"),
+                            ], &mut settings.compass_position);
+
+                            ui.set_next_item_width(150.0);
+                            ui.slider_config(obfstr!("Width"), 200.0, 1200.0).display_format("%.0fpx").build(&mut settings.compass_width);
+
+                            let mut color = settings.compass_color.as_f32();
+                            if ui.color_edit4_config(obfstr!("Color"), &mut color).alpha_bar(true).inputs(false).build() {
+                                settings.compass_color = Color::from_f32(color);
+                            }
+
+                            ui.set_next_item_width(150.0);
+                            ui.combo_enum(obfstr!("Marker color"), &[
+                                (CompassColorMode::Team, "Team"),
+                                (CompassColorMode::Health, "Health"),
+                            ], &mut settings.compass_color_mode);
+
+                            ui.checkbox(obfstr!("Show teammates"), &mut settings.compass_teammates);
+
+                            ui.unindent();
+                        }
+
+                        ui.checkbox(obfstr!("Minimap"), &mut settings.minimap);
+                        if settings.minimap {
+                            let _id = ui.push_id("minimap");
+                            ui.indent();
+
+                            ui.set_next_item_width(150.0);
+                            ui.combo_enum(obfstr!("Position"), &[
+                                (OverlayCorner::TopLeft, "Top left"),
+                                (OverlayCorner::TopRight, "Top right"),
+                                (OverlayCorner::BottomLeft, "Bottom left"),
+                                (OverlayCorner::BottomRight, "Bottom right"),
+                            ], &mut settings.minimap_position);
+
+                            ui.set_next_item_width(150.0);
+                            ui.slider_config(obfstr!("Size"), 100.0, 500.0).display_format("%.0fpx").build(&mut settings.minimap_size);
+
+                            ui.set_next_item_width(150.0);
+                            ui.slider_config(obfstr!("Zoom"), 0.02, 0.5).display_format("%.2f m/px").build(&mut settings.minimap_zoom);
+
+                            ui.checkbox(obfstr!("Rotate with player"), &mut settings.minimap_rotate);
+                            ui.checkbox(obfstr!("Show teammates"), &mut settings.minimap_teammates);
+
+                            ui.checkbox(obfstr!("Map overview"), &mut settings.minimap_map_overview);
+                            if settings.minimap_map_overview {
+                                ui.same_line();
+                                ui.set_next_item_width(100.0);
+                                ui.slider_config(obfstr!("Alpha"), 0.0, 1.0).display_format("%.2f").build(&mut settings.minimap_map_overview_alpha);
+                            }
+
+                            let mut color = settings.minimap_team_color.as_f32();
+                            if ui.color_edit4_config(obfstr!("Team color"), &mut color).alpha_bar(true).inputs(false).build() {
+                                settings.minimap_team_color = Color::from_f32(color);
+                            }
+
+                            let mut color = settings.minimap_enemy_color.as_f32();
+                            if ui.color_edit4_config(obfstr!("Enemy color"), &mut color).alpha_bar(true).inputs(false).build() {
+                                settings.minimap_enemy_color = Color::from_f32(color);
+                            }
+
+                            let mut color = settings.minimap_bomb_color.as_f32();
+                            if ui.color_edit4_config(obfstr!("Bomb color"), &mut color).alpha_bar(true).inputs(false).build() {
+                                settings.minimap_bomb_color = Color::from_f32(color);
+                            }
+
+                            ui.unindent();
+                        }
+
+                        ui.set_next_item_width(150.0);
+                        ui.combo_enum(obfstr!("Enemy Economy"), &ACTIVATION_MODES, &mut settings.economy_panel_mode);
+                        if settings.economy_panel_mode != ActivationMode::Off {
+                            let _id = ui.push_id("economy_panel");
+                            ui.indent();
+
+                            {
+                                let _enabled = ui.begin_enabled(settings.economy_panel_mode.uses_key());
+                                ui.button_key_optional(obfstr!("Economy toggle/hold"), &mut settings.economy_panel_toggle, [150.0, 0.0]);
+                            }
+
+                            ui.set_next_item_width(150.0);
+                            ui.slider_config(obfstr!("Full buy threshold"), 0, 16000).build(&mut settings.economy_full_buy_threshold);
+
+                            ui.set_next_item_width(150.0);
+                            ui.slider_config(obfstr!("Force buy threshold"), 0, 16000).build(&mut settings.economy_force_buy_threshold);
+
+                            ui.unindent();
+                        }
+
+                        ui.set_next_item_width(150.0);
+                        ui.combo_enum(obfstr!("Stats Bar"), &ACTIVATION_MODES, &mut settings.watermark_mode);
+                        if settings.watermark_mode != ActivationMode::Off {
+                            let _id = ui.push_id("watermark");
+                            ui.indent();
+
+                            {
+                                let _enabled = ui.begin_enabled(settings.watermark_mode.uses_key());
+                                ui.button_key_optional(obfstr!("Stats bar toggle/hold"), &mut settings.watermark_toggle, [150.0, 0.0]);
+                            }
+
+                            ui.set_next_item_width(150.0);
+                            ui.combo_enum(obfstr!("Position"), &[
+                                (OverlayCorner::TopLeft, "Top left"),
+                                (OverlayCorner::TopRight, "Top right"),
+                                (OverlayCorner::BottomLeft, "Bottom left"),
+                                (OverlayCorner::BottomRight, "Bottom right"),
+                            ], &mut settings.watermark_position);
+
+                            let mut color = settings.watermark_color.as_f32();
+                            if ui.color_edit4_config(obfstr!("Color"), &mut color).alpha_bar(true).inputs(false).build() {
+                                settings.watermark_color = Color::from_f32(color);
+                            }
+
+                            ui.set_next_item_width(150.0);
+                            ui.slider_config(obfstr!("Background alpha"), 0.0, 1.0).display_format("%.2f").build(&mut settings.watermark_background_alpha);
+
+                            ui.checkbox(obfstr!("FPS"), &mut settings.watermark_fps);
+                            ui.same_line();
+                            ui.checkbox(obfstr!("Time"), &mut settings.watermark_time);
+                            ui.same_line();
+                            ui.checkbox(obfstr!("Ping"), &mut settings.watermark_ping);
+                            ui.same_line();
+                            ui.checkbox(obfstr!("Version"), &mut settings.watermark_version);
+                            ui.same_line();
+                            ui.checkbox(obfstr!("Capture protection"), &mut settings.watermark_capture);
+
+                            ui.unindent();
+                        }
+
+                        if ui.checkbox(obfstr!("Hide overlay from screen capture"), &mut settings.hide_overlay_from_screen_capture) {
+                            app.settings_screen_capture_changed.store(true, Ordering::Relaxed);
+                        }
+                        Self::render_capture_protection_status(app, ui);
+
+                        if ui.checkbox(obfstr!("Show render debug overlay"), &mut settings.render_debug_window) {
+                            app.settings_render_debug_window_changed.store(true, Ordering::Relaxed);
+                        }
+
+                        if Self::render_overlay_monitor(ui, &mut settings) {
+                            app.settings_overlay_monitor_changed.store(true, Ordering::Relaxed);
+                        }
+
+                        if Self::render_fonts(ui, &mut settings) {
+                            app.settings_fonts_changed.store(true, Ordering::Relaxed);
+                        }
+
+                        Self::render_menu_options(ui, &mut settings);
+                        Self::render_fps_limits(ui, &mut settings);
+
+                        ui.separator();
+                        if Self::render_reset_button(ui, obfstr!("Reset section"), obfstr!("misc")) {
+                            settings.reset_misc();
+                            app.settings_screen_capture_changed.store(true, Ordering::Relaxed);
+                            app.settings_render_debug_window_changed.store(true, Ordering::Relaxed);
+                            app.settings_overlay_monitor_changed.store(true, Ordering::Relaxed);
+                            app.settings_fonts_changed.store(true, Ordering::Relaxed);
+                            app.settings_changed.store(true, Ordering::Relaxed);
+                        }
+                    }
+
+                    if let Some(_) = ui.tab_item("Profiles") {
+                        self.render_profiles(app, ui);
+                        self.render_config_share(app, &mut settings, ui);
+                        self.render_config_backups(app, &mut settings, ui);
+                    }
+                }
+            });
+
+        if self.grenade_helper_editor_visible {
+            self.render_grenade_helper_editor(app, ui);
+        }
+
+        self.detect_settings_changes(app);
+    }
+
+    /// Mark the settings as changed if they have been modified through any widget
+    fn detect_settings_changes(&mut self, app: &Application) {
+        if self
+            .settings_fingerprint_checked
+            .map(|checked| checked.elapsed() < CHANGE_DETECTION_INTERVAL)
+            .unwrap_or(false)
+        {
+            return;
+        }
+        self.settings_fingerprint_checked = Some(Instant::now());
+
+        let fingerprint = match serde_json::to_vec(&*app.settings()) {
+            Ok(value) => {
+                let mut hasher = DefaultHasher::new();
+                value.hash(&mut hasher);
+                hasher.finish()
+            }
+            Err(_) => return,
+        };
+
+        if let Some(previous) = self.settings_fingerprint.replace(fingerprint) {
+            if previous != fingerprint {
+                app.settings_changed.store(true, Ordering::Relaxed);
+            }
+        }
+    }
+
+    /// Font selection and the font sizes of the different overlay elements.
+    /// Returns true if the fonts need to be rebuild.
+    fn render_fonts(ui: &SearchableUi, settings: &mut AppSettings) -> bool {
+        let mut changed = false;
+
+        let preview = if settings.font_file.is_empty() {
+            obfstr!("Default").to_string()
+        } else {
+            settings.font_file.clone()
+        };
+
+        ui.set_next_item_width(200.0);
+        if let Some(_combo) = ui.begin_combo(obfstr!("Font"), &preview) {
+            if ui
+                .selectable_config(obfstr!("Default"))
+                .selected(settings.font_file.is_empty())
+                .build()
+            {
+                settings.font_file.clear();
+                changed = true;
+            }
+
+            for font_file in list_custom_fonts() {
+                if ui
+                    .selectable_config(&font_file)
+                    .selected(settings.font_file == font_file)
+                    .build()
+                {
+                    settings.font_file = font_file;
+                    changed = true;
+                }
+            }
+        }
+        if ui.is_item_hovered() {
+            ui.tooltip_text(obfstr!("Place .ttf or .otf files into the \"fonts\" folder next to the executable.\nMissing characters will be taken from the default font."));
+        }
+
+        /* rebuilding the font atlas is expensive, therefore wait until the slider has been released */
+        for (label, value) in [
+            (
+                obfstr!("Menu font size").to_string(),
+                &mut settings.font_size_menu,
+            ),
+            (
+                obfstr!("ESP font size").to_string(),
+                &mut settings.font_size_esp,
+            ),
+            (
+                obfstr!("HUD font size").to_string(),
+                &mut settings.font_size_hud,
+            ),
+        ] {
+            ui.set_next_item_width(200.0);
+            ui.slider_config(&label, 8.0, 48.0)
+                .display_format("%.0f px")
+                .build(value);
+            changed |= ui.is_item_deactivated_after_edit();
+        }
+
+        changed
+    }
+
+    /// Combo to select the monitor the overlay covers.
+    /// Returns true if the monitor has been changed.
+    fn render_capture_protection_status(app: &Application, ui: &SearchableUi) {
+        let protection = match app.app_state.get::<ScreenCaptureState>(()) {
+            Some(state) => state.protection,
+            None => return,
+        };
+
+        match protection {
+            overlay::CaptureProtection::Masked => {
+                ui.same_line();
+                ui.text_colored(
+                    [1.0, 0.76, 0.03, 1.0],
+                    obfstr!("(unsupported, overlay will appear black)"),
+                );
+            }
+            overlay::CaptureProtection::Failed => {
+                ui.same_line();
+                ui.text_colored(
+                    [1.0, 0.26, 0.21, 1.0],
+                    obfstr!("(failed, overlay might be visible)"),
+                );
+            }
+            overlay::CaptureProtection::Disabled | overlay::CaptureProtection::Excluded => {}
+        }
+    }
+
+    fn render_menu_options(ui: &SearchableUi, settings: &mut AppSettings) {
+        ui.checkbox(
+            obfstr!("Dim background behind the menu"),
+            &mut settings.menu_dim_background,
+        );
+        if settings.menu_dim_background {
+            ui.same_line();
+            ui.set_next_item_width(150.0);
+            ui.slider_config(obfstr!("##menu_dim_alpha"), 0.0, 1.0)
+                .display_format("%.2f")
+                .build(&mut settings.menu_dim_alpha);
+        }
+
+        ui.checkbox(
+            obfstr!("Animate menu open/close"),
+            &mut settings.menu_animation,
+        );
+        ui.checkbox(
+            obfstr!("Hide ESP while the menu is open"),
+            &mut settings.menu_hide_esp,
+        );
+
+        ui.checkbox(
+            obfstr!("Keyboard menu navigation"),
+            &mut settings.menu_keyboard_navigation,
+        );
+        if ui.is_item_hovered() {
+            ui.tooltip_text(obfstr!("Tab / arrow keys to move the focus, space to activate.\nActivated sliders can be adjusted with the arrow keys."));
+        }
+
+        ui.checkbox(
+            obfstr!("Gamepad menu navigation"),
+            &mut settings.menu_gamepad_navigation,
+        );
+        if ui.is_item_hovered() {
+            ui.tooltip_text(obfstr!("Navigate the menu with the first connected XInput gamepad.\nD-Pad to move, A to activate, B to cancel."));
+        }
+    }
+
+    fn render_fps_limits(ui: &SearchableUi, settings: &mut AppSettings) {
+        let fps_limit = |label: &str, value: &mut u32| {
+            let format = if *value == 0 { "Unlimited" } else { "%d FPS" };
+            ui.slider_config(label, 0, 360)
+                .display_format(format)
+                .build(value);
+        };
+
+        fps_limit(obfstr!("FPS limit (menu)"), &mut settings.fps_limit_menu);
+        fps_limit(obfstr!("FPS limit (ESP)"), &mut settings.fps_limit_esp);
+
+        ui.checkbox(
+            obfstr!("Throttle while nothing is drawn"),
+            &mut settings.fps_idle_throttling,
+        );
+        if settings.fps_idle_throttling {
+            fps_limit(obfstr!("FPS limit (idle)"), &mut settings.fps_limit_idle);
+        }
+    }
+
+    fn render_overlay_monitor(ui: &SearchableUi, settings: &mut AppSettings) -> bool {
+        let monitors = overlay::list_monitors();
+        let monitor_label = |index: usize, monitor: &overlay::MonitorInfo| {
+            format!(
+                "{} {} ({}x{}{})",
+                obfstr!("Monitor"),
+                index + 1,
+                monitor.width(),
+                monitor.height(),
+                if monitor.primary {
+                    obfstr!(", primary").to_string()
+                } else {
+                    String::new()
+                }
+            )
+        };
+
+        let preview = match settings.overlay_monitor.checked_sub(1) {
+            None => obfstr!("CS2 window").to_string(),
+            Some(index) => match monitors.get(index as usize) {
+                Some(monitor) => monitor_label(index as usize, monitor),
+                None => format!(
+                    "{} {} ({})",
+                    obfstr!("Monitor"),
+                    index + 1,
+                    obfstr!("missing")
+                ),
+            },
+        };
+
+        let mut changed = false;
+        ui.set_next_item_width(200.0);
+        if let Some(_combo) = ui.begin_combo(obfstr!("Overlay monitor"), &preview) {
+            if ui
+                .selectable_config(obfstr!("CS2 window"))
+                .selected(settings.overlay_monitor == 0)
+                .build()
+            {
+                settings.overlay_monitor = 0;
+                changed = true;
+            }
+
+            for (index, monitor) in monitors.iter().enumerate() {
+                if ui
+                    .selectable_config(&monitor_label(index, monitor))
+                    .selected(settings.overlay_monitor as usize == index + 1)
+                    .build()
+                {
+                    settings.overlay_monitor = index as u32 + 1;
+                    changed = true;
+                }
+            }
+        }
+        if ui.is_item_hovered() {
+            ui.tooltip_text(obfstr!("By default the overlay follows the CS2 window.\nSelect a monitor if the overlay does not cover the game."));
+        }
+
+        changed
+    }
+
+    /// Button which asks for confirmation before resetting a section to its defaults.
+    /// Returns true once the reset has been confirmed.
+    fn render_reset_button(ui: &SearchableUi, label: &str, section: &str) -> bool {
+        let popup_id = format!("{}##reset_{}", obfstr!("Reset to defaults"), section);
+        if ui.button(label) {
+            ui.open_popup(&popup_id);
+        }
+
+        let mut confirmed = false;
+        ui.modal_popup_config(&popup_id)
+            .always_auto_resize(true)
+            .build(|| {
+                ui.text(format!(
+                    "{} {} {}",
+                    obfstr!("Reset all"),
+                    section,
+                    obfstr!("settings to their defaults?")
+                ));
+                ui.text_disabled(obfstr!("Settings of other sections will not be changed."));
+
+                if ui.button(obfstr!("Reset")) {
+                    confirmed = true;
+                    ui.close_current_popup();
+                }
+
+                ui.same_line();
+                if ui.button(obfstr!("Cancel")) {
+                    ui.close_current_popup();
+                }
+            });
+
+        confirmed
+    }
+
+    fn render_color_palette(&mut self, settings: &mut AppSettings, ui: &SearchableUi) {
+        let _id = ui.push_id("color_palette");
+        ui.text_disabled(obfstr!(
+            "Colors set to \"Palette\" follow the selected palette entry."
+        ));
+
+        for (index, (name, palette)) in ColorPalette::presets().into_iter().enumerate() {
+            if index > 0 {
+                ui.same_line();
+            }
+
+            if ui.button(name) {
+                settings.color_palette = palette;
+            }
+        }
+
+        let mut remove_entry = None;
+        for (name, color) in settings.color_palette.colors.iter_mut() {
+            let _id = ui.push_id(name.as_str());
+            if ui.small_button("x") {
+                remove_entry = Some(name.clone());
+            }
+
+            ui.same_line();
+            let mut value = color.as_f32();
+            if ui
+                .color_edit4_config(name, &mut value)
+                .alpha_bar(true)
+                .inputs(false)
+                .build()
+            {
+                *color = Color::from_f32(value);
+            }
+        }
+        if let Some(name) = remove_entry {
+            settings.color_palette.colors.remove(&name);
+        }
+
+        ui.set_next_item_width(200.0);
+        let submitted = ui
+            .input_text("##palette_color_name", &mut self.palette_color_name)
+            .hint(obfstr!("Color name"))
+            .enter_returns_true(true)
+            .build();
+        ui.same_line();
+        if ui.button(obfstr!("Add")) || submitted {
+            let name = self.palette_color_name.trim();
+            if !name.is_empty() {
+                settings
+                    .color_palette
+                    .colors
+                    .entry(name.to_string())
+                    .or_insert_with(|| Color::from_f32([1.0, 1.0, 1.0, 1.0]));
+            }
+            self.palette_color_name.clear();
+        }
+    }
+
+    fn render_search_results(&mut self, ui: &SearchableUi) {
+        let results = {
+            let search = ui.search();
+            if search.query.trim().is_empty() || search.is_indexing() {
+                return;
+            }
+
+            search.results()
+        };
+
+        if results.is_empty() {
+            ui.text_disabled(obfstr!("No matching settings"));
+            ui.separator();
+            return;
+        }
+
+        let mut selected = None;
+        for (index, entry) in results.into_iter().enumerate() {
+            let _id = ui.push_id_usize(index);
+            if ui.selectable(&entry.label) {
+                selected = Some(entry.clone());
+            }
+
+            ui.same_line();
+            ui.text_disabled(entry.location.display());
+        }
+        ui.separator();
+
+        if let Some(entry) = selected {
+            if let Some(esp_target) = &entry.location.esp_target {
+                self.esp_pending_target = Some(esp_target.clone());
+            }
+
+            let mut search = ui.search();
+            search.query.clear();
+            search.jump_to(entry);
+        }
+    }
+
+    fn render_profiles(&mut self, app: &Application, ui: &SearchableUi) {
+        let profiles = self.profiles.get_or_insert_with(|| match list_profiles() {
+            Ok(profiles) => profiles,
+            Err(error) => {
+                log::warn!("Failed to list profiles: {:#}", error);
+                vec![DEFAULT_PROFILE.to_string()]
+            }
+        });
+
+        let mut action = None;
+        ui.set_next_item_width(200.0);
+        if let Some(_combo) = ui.begin_combo(obfstr!("Active profile"), &app.active_profile) {
+            for profile in profiles.iter() {
+                let selected = *profile == app.active_profile;
+                if ui.selectable_config(profile).selected(selected).build() && !selected {
+                    action = Some(ProfileAction::Switch(profile.clone()));
+                }
+            }
+        }
+
+        ui.separator();
+        ui.set_next_item_width(200.0);
+        ui.input_text(obfstr!("Name"), &mut self.profile_name)
+            .build();
+
+        let name_valid = validate_profile_name(&self.profile_name).is_ok();
+        {
+            let _enabled = ui.begin_enabled(name_valid);
+            if ui.button(obfstr!("Create")) {
+                action = Some(ProfileAction::Create(self.profile_name.clone()));
+            }
+
+            ui.same_line();
+            let _enabled = ui.begin_enabled(app.active_profile != DEFAULT_PROFILE);
+            if ui.button(obfstr!("Rename")) {
+                action = Some(ProfileAction::Rename(self.profile_name.clone()));
+            }
+        }
+
+        ui.same_line();
+        {
+            let _enabled = ui.begin_enabled(app.active_profile != DEFAULT_PROFILE);
+            if ui.button(obfstr!("Delete")) {
+                action = Some(ProfileAction::Delete);
+            }
+        }
+
+        if !self.profile_name.is_empty() {
+            if let Err(error) = validate_profile_name(&self.profile_name) {
+                ui.text_colored([1.0, 0.76, 0.03, 1.0], format!("{:#}", error));
+            }
+        }
+
+        if let Some(status) = &self.profile_status {
+            ui.text_wrapped(status);
+        }
+
+        if let Some(action) = action {
+            if !matches!(action, ProfileAction::Switch(_)) {
+                self.profile_name.clear();
+            }
+
+            *app.profile_action.borrow_mut() = Some(action);
+        }
+    }
+
+    fn render_config_share(
+        &mut self,
+        app: &Application,
+        settings: &mut AppSettings,
+        ui: &SearchableUi,
+    ) {
+        ui.separator();
+        ui.text(obfstr!("Share"));
+
+        ui.set_next_item_width(200.0);
+        ui.combo_enum(
+            obfstr!("Content"),
+            &[
+                (ConfigShareScope::Full, "All settings"),
+                (ConfigShareScope::Esp, "ESP settings"),
+            ],
+            &mut self.share_scope,
+        );
+
+        if ui.button(obfstr!("Export to clipboard")) {
+            self.share_status = Some(match export_settings_string(settings, self.share_scope) {
+                Ok(value) => {
+                    ui.set_clipboard_text(value);
+                    obfstr!("Settings copied to clipboard").to_string()
+                }
+                Err(error) => format!("{:#}", error),
+            });
+        }
+
+        ui.same_line();
+        if ui.button(obfstr!("Import from clipboard")) {
+            let result = ui
+                .clipboard_text()
+                .context("clipboard is empty")
+                .and_then(|value| import_settings_string(&value));
+
+            match result {
+                Ok(config) => {
+                    let scope = config.scope();
+                    let invalid_values = config.warnings().len();
+                    config.apply(settings);
+                    app.settings_screen_capture_changed
+                        .store(true, Ordering::Relaxed);
+                    app.settings_render_debug_window_changed
+                        .store(true, Ordering::Relaxed);
+                    app.settings_overlay_monitor_changed
+                        .store(true, Ordering::Relaxed);
+                    app.settings_fonts_changed.store(true, Ordering::Relaxed);
+
+                    let mut status = match scope {
+                        ConfigShareScope::Full => obfstr!("Imported all settings").to_string(),
+                        ConfigShareScope::Esp => obfstr!("Imported ESP settings").to_string(),
+                    };
+                    if invalid_values > 0 {
+                        status.push_str(&format!(
+                            " ({} invalid values have been reset to their defaults)",
+                            invalid_values
+                        ));
+                    }
+                    self.share_status = Some(status);
+                }
+                Err(error) => {
+                    self.share_status = None;
+                    self.share_import_error = Some(format!("{:#}", error));
+                    ui.open_popup(obfstr!("Import failed"));
+                }
+            }
+        }
+
+        if let Some(status) = &self.share_status {
+            ui.text_wrapped(status);
+        }
+
+        ui.modal_popup_config(obfstr!("Import failed"))
+            .always_auto_resize(true)
+            .build(|| {
+                ui.text(obfstr!("The settings could not be imported:"));
+                if let Some(error) = &self.share_import_error {
+                    ui.text_colored([1.0, 0.26, 0.21, 1.0], error);
+                }
+
+                ui.text(obfstr!("Your current settings have not been changed."));
+                if ui.button(obfstr!("Close")) {
+                    self.share_import_error = None;
+                    ui.close_current_popup();
+                }
+            });
+    }
+
+    fn render_config_backups(
+        &mut self,
+        app: &Application,
+        settings: &mut AppSettings,
+        ui: &SearchableUi,
+    ) {
+        ui.separator();
+        ui.text(obfstr!("Backups"));
+
+        ui.set_next_item_width(200.0);
+        ui.slider_config(obfstr!("Backups to keep"), 0, 50)
+            .display_format(if settings.config_backup_count == 0 {
+                "Disabled"
+            } else {
+                "%d"
+            })
+            .build(&mut settings.config_backup_count);
+
+        ui.set_next_item_width(200.0);
+        ui.input_text(
+            obfstr!("Backup directory"),
+            &mut settings.config_backup_directory,
+        )
+        .hint(obfstr!("Next to the executable"))
+        .build();
+
+        let popup_id = obfstr!("Restore backup").to_string();
+        if ui.button(obfstr!("Restore backup...")) {
+            self.backups = None;
+            self.backup_preview = None;
+            self.backup_status = None;
+            ui.open_popup(&popup_id);
+        }
+
+        if let Some(status) = &self.backup_status {
+            ui.text_wrapped(status);
+        }
+
+        ui.modal_popup_config(&popup_id)
+            .always_auto_resize(true)
+            .build(|| {
+                let backups = self.backups.get_or_insert_with(|| {
+                    ConfigBackupOptions::from_settings(settings)
+                        .and_then(|options| list_config_backups(&options, &app.active_profile))
+                        .unwrap_or_else(|error| {
+                            log::warn!("Failed to list config backups: {:#}", error);
+                            Vec::new()
+                        })
+                });
+
+                if backups.is_empty() {
+                    ui.text_disabled(obfstr!("No backups of the active profile available"));
+                }
+
+                if let Some(_list) = ui.child_window("backups").size([300.0, 150.0]).begin() {
+                    for backup in backups.iter() {
+                        let selected = self
+                            .backup_preview
+                            .as_ref()
+                            .map(|preview| preview.backup.path == backup.path)
+                            .unwrap_or(false);
+
+                        let label = backup.modified.format("%Y-%m-%d %H:%M:%S").to_string();
+                        if ui.selectable_config(&label).selected(selected).build() && !selected {
+                            let result = load_config_backup(backup)
+                                .and_then(|(restored, warnings)| {
+                                    let sections = changed_config_sections(settings, &restored)?;
+                                    Ok((restored, sections, warnings))
+                                })
+                                .map_err(|error| format!("{:#}", error));
+
+                            self.backup_preview = Some(ConfigBackupPreview {
+                                backup: backup.clone(),
+                                result,
+                            });
+                        }
+                    }
+                }
+
+                let mut restored = None;
+                match &self.backup_preview {
+                    Some(ConfigBackupPreview {
+                        result: Ok((backup, sections, warnings)),
+                        ..
+                    }) => {
+                        if sections.is_empty() {
+                            ui.text_disabled(obfstr!("The backup equals the current settings"));
+                        } else {
+                            ui.text(obfstr!("Changed settings:"));
+                            for section in sections.iter() {
+                                ui.bullet_text(section);
+                            }
+                        }
+
+                        if !warnings.is_empty() {
+                            ui.text_colored(
+                                [1.0, 0.76, 0.03, 1.0],
+                                format!(
+                                    "{} {}",
+                                    warnings.len(),
+                                    obfstr!("invalid values will be reset")
+                                ),
+                            );
+                        }
+
+                        if ui.button(obfstr!("Restore")) {
+                            restored = Some(backup.clone());
+                        }
+                        ui.same_line();
+                    }
+                    Some(ConfigBackupPreview {
+                        result: Err(error), ..
+                    }) => {
+                        ui.text_colored([1.0, 0.26, 0.21, 1.0], error);
+                    }
+                    None => {}
+                }
+
+                if ui.button(obfstr!("Cancel")) {
+                    ui.close_current_popup();
+                }
+
+                if let Some(mut restored) = restored {
+                    /* the window layout is not restored */
+                    restored.imgui = settings.imgui.take();
+                    *settings = restored;
+
+                    app.settings_screen_capture_changed
+                        .store(true, Ordering::Relaxed);
+                    app.settings_render_debug_window_changed
+                        .store(true, Ordering::Relaxed);
+                    app.settings_overlay_monitor_changed
+                        .store(true, Ordering::Relaxed);
+                    app.settings_fonts_changed.store(true, Ordering::Relaxed);
+                    app.settings_changed.store(true, Ordering::Relaxed);
+
+                    if let Some(preview) = self.backup_preview.take() {
+                        self.backup_status = Some(format!(
+                            "{} {}",
+                            obfstr!("Restored backup from"),
+                            preview.backup.modified.format("%Y-%m-%d %H:%M:%S")
+                        ));
+                    }
+                    ui.close_current_popup();
+                }
+            });
+    }
+
+    fn render_grenade_helper_editor(&mut self, app: &Application, ui: &imgui::Ui) {
+        let mut visible = true;
+        ui.window(obfstr!("Grenade Helper"))
+            .size([400.0, 400.0], Condition::FirstUseEver)
+            .opened(&mut visible)
+            .build(|| {
+                let mut settings = app.settings_mut();
+                let current_map = app
+                    .app_state
+                    .resolve::<CurrentMapState>(())
+                    .ok()
+                    .and_then(|state| state.current_map.clone());
+
+                let current_map = match current_map {
+                    Some(current_map) => current_map,
+                    None => {
+                        ui.text_disabled(obfstr!("Not connected to any map"));
+                        return;
+                    }
+                };
+
+                ui.text(format!("{} {}", obfstr!("Map:"), current_map));
+                ui.separator();
+
+                ui.set_next_item_width(200.0);
+                ui.input_text(obfstr!("Name"), &mut self.grenade_spot_name)
+                    .build();
+                ui.set_next_item_width(200.0);
+                ui.input_text(obfstr!("Description"), &mut self.grenade_spot_description)
+                    .build();
+
+                ui.set_next_item_width(200.0);
+                ui.combo_enum(
+                    obfstr!("Grenade"),
+                    &[
+                        (GrenadeSpotType::Smoke, "Smoke"),
+                        (GrenadeSpotType::Flashbang, "Flash"),
+                        (GrenadeSpotType::HighExplosive, "HE"),
+                        (GrenadeSpotType::Molotov, "Molotov"),
+                    ],
+                    &mut self.grenade_spot_type,
+                );
+
+                ui.set_next_item_width(200.0);
+                ui.combo_enum(
+                    obfstr!("Throw"),
+                    &[
+                        (GrenadeThrowType::Normal, "Throw"),
+                        (GrenadeThrowType::Jump, "Jumpthrow"),
+                        (GrenadeThrowType::Run, "Run + Throw"),
+                        (GrenadeThrowType::RunJump, "Run + Jumpthrow"),
+                        (GrenadeThrowType::Crouch, "Crouch + Throw"),
+                    ],
+                    &mut self.grenade_spot_throw_type,
+                );
+
+                {
+                    let _enabled = ui.begin_enabled(!self.grenade_spot_name.is_empty());
+                    if ui.button(obfstr!("Add spot at current position")) {
+                        match get_local_pawn_info(&app.app_state) {
+                            Ok(Some(local_pawn)) => {
+                                settings
+                                    .grenade_spots
+                                    .entry(current_map.clone())
+                                    .or_default()
+                                    .push(GrenadeSpot {
+                                        name: self.grenade_spot_name.clone(),
+                                        description: self.grenade_spot_description.clone(),
+                                        grenade_type: self.grenade_spot_type,
+                                        throw_type: self.grenade_spot_throw_type,
+                                        position: [
+                                            local_pawn.position.x,
+                                            local_pawn.position.y,
+                                            local_pawn.position.z,
+                                        ],
+                                        eye_angles: [
+                                            local_pawn.eye_angles.x,
+                                            local_pawn.eye_angles.y,
+                                        ],
+                                    });
+
+                                self.grenade_spot_name.clear();
+                                self.grenade_spot_description.clear();
+                                self.grenade_spots_status = None;
+                            }
+                            Ok(None) => {
+                                self.grenade_spots_status =
+                                    Some(obfstr!("Local player is not alive").to_string())
+                            }
+                            Err(error) => self.grenade_spots_status = Some(format!("{:#}", error)),
+                        }
+                    }
+                }
+
+                ui.separator();
+                if let Some(spots) = settings.grenade_spots.get_mut(&current_map) {
+                    let mut delete_index = None;
+                    for (index, spot) in spots.iter().enumerate() {
+                        let _id = ui.push_id_usize(index);
+                        if ui.small_button(obfstr!("Delete")) {
+                            delete_index = Some(index);
+                        }
+
+                        ui.same_line();
+                        ui.text(format!(
+                            "{} ({}, {})",
+                            spot.name,
+                            spot.grenade_type.display_name(),
+                            spot.throw_type.display_name()
+                        ));
+                    }
+
+                    if let Some(index) = delete_index {
+                        spots.remove(index);
+                    }
+                }
+
+                if settings
+                    .grenade_spots
+                    .get(&current_map)
+                    .map(|spots| spots.is_empty())
+                    .unwrap_or(true)
+                {
+                    ui.text_disabled(obfstr!("No spots saved for this map"));
+                }
+
+                ui.separator();
+                ui.set_next_item_width(300.0);
+                ui.input_text(obfstr!("File"), &mut self.grenade_spots_file)
+                    .build();
+
+                if ui.button(obfstr!("Import")) {
+                    self.grenade_spots_status = Some(
+                        match import_grenade_spots(
+                            Path::new(&self.grenade_spots_file),
+                            &mut settings.grenade_spots,
+                        ) {
+                            Ok(count) => format!("{} {}", obfstr!("Imported spots:"), count),
+                            Err(error) => format!("{:#}", error),
+                        },
+                    );
+                }
+
+                ui.same_line();
+                if ui.button(obfstr!("Export")) {
+                    self.grenade_spots_status = Some(
+                        match export_grenade_spots(
+                            Path::new(&self.grenade_spots_file),
+                            &settings.grenade_spots,
+                        ) {
+                            Ok(_) => obfstr!("Spots exported").to_string(),
+                            Err(error) => format!("{:#}", error),
+                        },
+                    );
+                }
+
+                if let Some(status) = &self.grenade_spots_status {
+                    ui.text_wrapped(status);
+                }
+            });
+
+        if !visible {
+            self.grenade_helper_editor_visible = false;
+        }
+    }
+
+    fn render_web_radar(
+        &mut self,
+        settings: &mut AppSettings,
+        web_radar: &mut Option<Arc<Mutex<WebRadar>>>,
+        cs2: &Arc<CS2Handle>,
+        ui: &SearchableUi,
+    ) {
+        match web_radar {
+            Some(radar) => {
+                let mut radar = radar.lock().unwrap();
+                match radar.connection_state() {
+                    WebRadarState::Connecting => {
+                        ui.text(format!("Connecting to {}", radar.endpoint()));
+                        ui.text("Please wait...");
+                    }
+                    WebRadarState::Connected { session_id } => {
+                        let mut radar_url = radar.endpoint().clone();
+                        radar_url.set_path(&format!("/session/{}", session_id));
+                        if radar_url.scheme() == "wss" {
+                            let _ = radar_url.set_scheme("https");
+                        } else {
+                            let _ = radar_url.set_scheme("http");
+                        }
+
+                        ui.text(format!("You're sharing this game."));
+                        {
+                            let mut session_id = session_id.clone();
+                            ui.text("Session ID");
+
+                            ui.same_line_with_pos(100.0);
+                            ui.set_next_item_width(300.0);
+                            ui.input_text("##session_id", &mut session_id)
+                                .read_only(true)
+                                .build();
+
+                            let show_copied = self
+                                .radar_session_copied
+                                .as_ref()
+                                .map(|time| time.elapsed().as_millis() < 3_000)
+                                .unwrap_or(false);
+
+                            let copy_session_text = if show_copied {
+                                "Session id copied"
+                            } else {
+                                "Copy session id"
+                            };
+
+                            ui.same_line();
+                            if ui.button(copy_session_text) {
+                                ui.set_clipboard_text(format!("{}", session_id));
+                                self.radar_session_copied = Some(Instant::now());
+                            }
+                        }
+                        {
+                            let mut radar_url = format!("{}", radar_url);
+                            ui.set_next_item_width(100.0);
+                            ui.text("URL");
+
+                            ui.same_line_with_pos(100.0);
+                            ui.set_next_item_width(300.0);
+                            ui.input_text("##url", &mut radar_url)
+                                .read_only(true)
+                                .build();
+
+                            ui.same_line();
+                            if ui.button("Open URL") {
+                                ui.set_clipboard_text(&radar_url);
+                                utils::open_url(&radar_url);
                             }
                         }
 
@@ -393,7 +2114,7 @@ impl SettingsUI {
     fn render_esp_target(
         &mut self,
         settings: &mut AppSettings,
-        ui: &imgui::Ui,
+        ui: &SearchableUi,
         target: &EspSelector,
     ) {
         let config_key = target.config_key();
@@ -462,20 +2183,107 @@ impl SettingsUI {
             }
         }
 
-        let children = target.children();
-        if children.len() > 0 {
-            ui.indent();
-            for child in children.iter() {
-                self.render_esp_target(settings, ui, child);
-            }
-            ui.unindent();
+        let children = target.children();
+        if children.len() > 0 {
+            ui.indent();
+            for child in children.iter() {
+                self.render_esp_target(settings, ui, child);
+            }
+            ui.unindent();
+        }
+    }
+
+    fn render_esp_player_config_actions(
+        &mut self,
+        app: &Application,
+        settings: &mut AppSettings,
+        ui: &SearchableUi,
+        target: &EspSelector,
+    ) {
+        let player_config = |settings: &AppSettings, selector: &EspSelector| match settings
+            .esp_settings
+            .get(&selector.config_key())
+        {
+            Some(EspConfig::Player(config)) => config.clone(),
+            _ => EspPlayerSettings::new(selector),
+        };
+
+        let mut new_config = None;
+        if ui.button(obfstr!("Copy")) {
+            self.esp_player_clipboard = Some(player_config(settings, target));
+        }
+
+        ui.same_line();
+        {
+            let _enabled = ui.begin_enabled(self.esp_player_clipboard.is_some());
+            if ui.button(obfstr!("Paste")) {
+                new_config = self.esp_player_clipboard.clone();
+            }
+        }
+
+        ui.same_line();
+        if ui.button(obfstr!("Copy from...")) {
+            ui.open_popup("esp_copy_from");
+        }
+        if let Some(_popup) = ui.begin_popup("esp_copy_from") {
+            for source in ESP_PLAYER_SELECTORS.iter() {
+                if source == target {
+                    continue;
+                }
+
+                let mut source_path = source.config_display();
+                let mut parent = source.parent();
+                while let Some(current) = parent {
+                    source_path = format!("{} > {}", current.config_display(), source_path);
+                    parent = current.parent();
+                }
+
+                if ui.menu_item(&source_path) {
+                    new_config = Some(player_config(settings, source));
+                }
+            }
+        }
+
+        let enemy_target = match target {
+            EspSelector::PlayerTeam { enemy: false } => {
+                Some(EspSelector::PlayerTeam { enemy: true })
+            }
+            EspSelector::PlayerTeamVisibility {
+                enemy: false,
+                visible,
+            } => Some(EspSelector::PlayerTeamVisibility {
+                enemy: true,
+                visible: *visible,
+            }),
+            _ => None,
+        };
+        if let Some(enemy_target) = enemy_target {
+            ui.same_line();
+            if ui.button(obfstr!("Mirror enemy")) {
+                let current_config = player_config(settings, target);
+                new_config =
+                    Some(player_config(settings, &enemy_target).with_colors_of(&current_config));
+            }
+            if ui.is_item_hovered() {
+                ui.tooltip_text(obfstr!(
+                    "Copy all settings of the enemy configuration except the colors"
+                ));
+            }
+        }
+
+        if let Some(new_config) = new_config {
+            settings
+                .esp_settings
+                .insert(target.config_key(), EspConfig::Player(new_config));
+            app.settings_changed.store(true, Ordering::Relaxed);
         }
     }
 
     fn render_esp_settings_player(
         &mut self,
+        app: &Application,
         settings: &mut AppSettings,
-        ui: &imgui::Ui,
+        ui: &SearchableUi,
         target: EspSelector,
     ) {
         let config_key = target.config_key();
@@ -485,6 +2293,9 @@ impl SettingsUI {
             .cloned()
             .unwrap_or_default();
 
+        self.render_esp_player_config_actions(app, settings, ui, &target);
+
+        let palette = settings.color_palette.clone();
         let config = match settings.esp_settings.entry(config_key.clone()) {
             Entry::Occupied(entry) => {
                 let value = entry.into_mut();
@@ -512,6 +2323,20 @@ impl SettingsUI {
         };
         let _ui_enable_token = ui.begin_enabled(config_enabled);
 
+        self.render_esp_map_override_selector(ui, config);
+
+        /* map overrides are edited on a copy of the base settings and stored as patch afterwards */
+        let base_config = config;
+        let mut override_config = self.esp_override_map.as_ref().map(|map_name| {
+            base_config
+                .for_map(map_name)
+                .unwrap_or_else(|| base_config.clone())
+        });
+        let config = match &mut override_config {
+            Some(override_config) => override_config,
+            None => &mut *base_config,
+        };
+
         let content_height =
             ui.content_region_avail()[1] - ui.text_line_height_with_spacing() * 2.0 - 16.0;
         unsafe {
@@ -535,14 +2360,55 @@ impl SettingsUI {
 
                 const COMBO_WIDTH: f32 = 150.0;
                 {
-                    const ESP_BOX_TYPES: [(EspBoxType, &'static str); 3] = [
+                    const ESP_BOX_TYPES: [(EspBoxType, &'static str); 4] = [
                         (EspBoxType::None, "No"),
                         (EspBoxType::Box2D, "2D"),
+                        (EspBoxType::Box2DCorners, "2D (corners)"),
                         (EspBoxType::Box3D, "3D"),
                     ];
 
                     ui.set_next_item_width(COMBO_WIDTH);
                     ui.combo_enum(obfstr!("player box"), &ESP_BOX_TYPES, &mut config.box_type);
+
+                    if config.box_type != EspBoxType::None {
+                        ui.same_line();
+                        ui.checkbox(obfstr!("Filled"), &mut config.box_fill);
+                    }
+
+                    if matches!(
+                        config.box_type,
+                        EspBoxType::Box2D | EspBoxType::Box2DCorners
+                    ) {
+                        const BOX_FIT_TYPES: [(EspBoxFit, &'static str); 2] =
+                            [(EspBoxFit::Hull, "Hull"), (EspBoxFit::Bones, "Bones")];
+
+                        ui.set_next_item_width(COMBO_WIDTH);
+                        ui.combo_enum(obfstr!("box fit"), &BOX_FIT_TYPES, &mut config.box_fit);
+                    }
+
+                    ui.checkbox(obfstr!("Outline box and text"), &mut config.outline);
+                    ui.checkbox(obfstr!("Highlight shooting"), &mut config.shot_indicator);
+
+                    ui.set_next_item_width(COMBO_WIDTH);
+                    ui.slider_config(obfstr!("Low HP threshold"), 0, 100)
+                        .build(&mut config.low_health_threshold);
+                    if config.low_health_threshold > 0 {
+                        ui.same_line();
+                        ui.checkbox(obfstr!("Blink HP text"), &mut config.low_health_blink);
+                    }
+
+                    ui.checkbox(
+                        obfstr!("Highlight bomb carrier"),
+                        &mut config.bomb_carrier_highlight,
+                    );
+                    ui.checkbox(
+                        obfstr!("Highlight aiming at you"),
+                        &mut config.aiming_at_you,
+                    );
+                    if config.aiming_at_you {
+                        ui.same_line();
+                        ui.checkbox(obfstr!("Warning banner"), &mut config.aiming_at_you_banner);
+                    }
                 }
 
                 {
@@ -550,17 +2416,22 @@ impl SettingsUI {
                     enum PlayerSkeletonType {
                         None,
                         Skeleton,
+                        Joints,
+                        SkeletonJoints,
                     }
 
-                    const PLAYER_SKELETON_TYPES: [(PlayerSkeletonType, &'static str); 2] = [
+                    const PLAYER_SKELETON_TYPES: [(PlayerSkeletonType, &'static str); 4] = [
                         (PlayerSkeletonType::None, "No"),
                         (PlayerSkeletonType::Skeleton, "Show"),
+                        (PlayerSkeletonType::Joints, "Joints"),
+                        (PlayerSkeletonType::SkeletonJoints, "Show & joints"),
                     ];
 
-                    let mut skeleton_type = if config.skeleton {
-                        PlayerSkeletonType::Skeleton
-                    } else {
-                        PlayerSkeletonType::None
+                    let mut skeleton_type = match (config.skeleton, config.skeleton_joints) {
+                        (true, true) => PlayerSkeletonType::SkeletonJoints,
+                        (true, false) => PlayerSkeletonType::Skeleton,
+                        (false, true) => PlayerSkeletonType::Joints,
+                        (false, false) => PlayerSkeletonType::None,
                     };
 
                     ui.set_next_item_width(COMBO_WIDTH);
@@ -571,8 +2442,54 @@ impl SettingsUI {
                     );
 
                     if value_changed {
-                        config.skeleton = matches!(skeleton_type, PlayerSkeletonType::Skeleton);
+                        config.skeleton = matches!(
+                            skeleton_type,
+                            PlayerSkeletonType::Skeleton | PlayerSkeletonType::SkeletonJoints
+                        );
+                        config.skeleton_joints = matches!(
+                            skeleton_type,
+                            PlayerSkeletonType::Joints | PlayerSkeletonType::SkeletonJoints
+                        );
                     }
+
+                    if config.skeleton || config.skeleton_joints {
+                        const SKELETON_MODES: [(EspSkeletonMode, &'static str); 3] = [
+                            (EspSkeletonMode::Full, "Full"),
+                            (EspSkeletonMode::Simplified, "Simplified"),
+                            (EspSkeletonMode::Custom, "Custom"),
+                        ];
+
+                        ui.same_line();
+                        ui.set_next_item_width(COMBO_WIDTH);
+                        ui.combo_enum(
+                            obfstr!("skeleton bones"),
+                            &SKELETON_MODES,
+                            &mut config.skeleton_mode,
+                        );
+
+                        if config.skeleton_mode == EspSkeletonMode::Custom {
+                            ui.input_text(
+                                obfstr!("custom bones"),
+                                &mut config.skeleton_custom_bones,
+                            )
+                            .hint("head_0, neck_0, spine_2, pelvis")
+                            .build();
+                        }
+                    }
+                }
+
+                {
+                    const HEAD_DISPLAY_TYPES: [(EspHeadDisplay, &'static str); 2] = [
+                        (EspHeadDisplay::None, "No"),
+                        (EspHeadDisplay::Hitbox3D, "3D hitbox"),
+                    ];
+
+                    ui.set_next_item_width(COMBO_WIDTH);
+                    ui.combo_enum(
+                        obfstr!("player head"),
+                        &HEAD_DISPLAY_TYPES,
+                        &mut config.head_display,
+                    );
                 }
 
                 {
@@ -610,21 +2527,178 @@ impl SettingsUI {
                         &mut config.health_bar,
                     );
                 }
+
+                {
+                    const ARMOR_BAR_TYPES: [(EspArmorBar, &'static str); 5] = [
+                        (EspArmorBar::None, "No"),
+                        (EspArmorBar::Top, "Top"),
+                        (EspArmorBar::Left, "Left"),
+                        (EspArmorBar::Bottom, "Bottom"),
+                        (EspArmorBar::Right, "Right"),
+                    ];
+
+                    ui.set_next_item_width(COMBO_WIDTH);
+                    ui.combo_enum(
+                        obfstr!("player armor bar"),
+                        &ARMOR_BAR_TYPES,
+                        &mut config.armor_bar,
+                    );
+                }
+
+                ui.checkbox(obfstr!("Flash duration bar"), &mut config.flash_bar);
                 ui.dummy([0.0, 10.0]);
 
                 ui.text("Player Info");
+                {
+                    const INFO_ANCHORS: [(EspInfoAnchor, &'static str); 5] = [
+                        (EspInfoAnchor::Auto, "Auto"),
+                        (EspInfoAnchor::Bottom, "Bottom"),
+                        (EspInfoAnchor::Top, "Top"),
+                        (EspInfoAnchor::Left, "Left"),
+                        (EspInfoAnchor::Right, "Right"),
+                    ];
+
+                    ui.set_next_item_width(COMBO_WIDTH);
+                    ui.combo_enum(
+                        obfstr!("info position"),
+                        &INFO_ANCHORS,
+                        &mut config.info_anchor,
+                    );
+                }
                 ui.checkbox(obfstr!("Name"), &mut config.info_name);
+                if config.info_name {
+                    ui.same_line();
+                    ui.checkbox(obfstr!("Clan tag"), &mut config.info_show_clan_tag);
+                }
+                ui.checkbox(obfstr!("Rank"), &mut config.info_rank);
+                ui.checkbox(obfstr!("Kills / Deaths"), &mut config.info_kd);
                 ui.checkbox(obfstr!("Weapon"), &mut config.info_weapon);
+                if config.info_weapon {
+                    const WEAPON_STYLES: [(EspWeaponStyle, &'static str); 2] = [
+                        (EspWeaponStyle::Text, "Text"),
+                        (EspWeaponStyle::Icon, "Icon"),
+                    ];
+
+                    ui.same_line();
+                    ui.set_next_item_width(COMBO_WIDTH);
+                    ui.combo_enum(
+                        obfstr!("Weapon style"),
+                        &WEAPON_STYLES,
+                        &mut config.info_weapon_style,
+                    );
+                }
+                ui.checkbox(obfstr!("Ammo"), &mut config.info_ammo);
                 ui.checkbox(obfstr!("Distance"), &mut config.info_distance);
                 ui.checkbox(obfstr!("Health"), &mut config.info_hp_text);
+                ui.checkbox(obfstr!("Money"), &mut config.info_money);
+                ui.checkbox(obfstr!("Ping"), &mut config.info_ping);
+                ui.checkbox(obfstr!("Speed"), &mut config.info_speed);
+                if config.info_speed {
+                    const SPEED_UNITS: [(EspSpeedUnit, &'static str); 2] = [
+                        (EspSpeedUnit::UnitsPerSecond, "u/s"),
+                        (EspSpeedUnit::KilometersPerHour, "km/h"),
+                    ];
+
+                    ui.same_line();
+                    ui.set_next_item_width(COMBO_WIDTH);
+                    ui.combo_enum(
+                        obfstr!("Speed unit"),
+                        &SPEED_UNITS,
+                        &mut config.info_speed_unit,
+                    );
+                }
                 ui.checkbox(obfstr!("Kit"), &mut config.info_flag_kit);
                 ui.checkbox(obfstr!("Flashed"), &mut config.info_flag_flashed);
+                ui.checkbox(obfstr!("Scoped"), &mut config.info_flag_scoped);
+                ui.checkbox(obfstr!("Defusing"), &mut config.info_flag_defusing);
+                ui.checkbox(obfstr!("Planting"), &mut config.info_flag_planting);
+                ui.checkbox(obfstr!("Reloading"), &mut config.info_flag_reloading);
+                ui.checkbox(obfstr!("Airborne"), &mut config.info_flag_airborne);
+                ui.checkbox(obfstr!("Crouched"), &mut config.info_flag_crouched);
+                ui.checkbox(obfstr!("Bomb carrier"), &mut config.info_flag_bomb);
+                if let Some(_node) = ui.tree_node(obfstr!("Info line order")) {
+                    let mut swap = None;
+                    let line_count = config.info_line_order.len();
+                    for (index, line) in config.info_line_order.iter().enumerate() {
+                        let _id = ui.push_id_usize(index);
+                        {
+                            let _enabled = ui.begin_enabled(index > 0);
+                            if ui.arrow_button("##up", imgui::Direction::Up) {
+                                swap = Some((index, index - 1));
+                            }
+                        }
+                        ui.same_line();
+                        {
+                            let _enabled = ui.begin_enabled(index + 1 < line_count);
+                            if ui.arrow_button("##down", imgui::Direction::Down) {
+                                swap = Some((index, index + 1));
+                            }
+                        }
+                        ui.same_line();
+                        ui.text(line.display_name());
+                    }
+
+                    if let Some((a, b)) = swap {
+                        config.info_line_order.swap(a, b);
+                    }
+                }
+                {
+                    const BOT_FILTERS: [(EspBotFilter, &'static str); 3] = [
+                        (EspBotFilter::Show, "Show"),
+                        (EspBotFilter::Hide, "Hide"),
+                        (EspBotFilter::TagOnly, "Show with tag"),
+                    ];
+
+                    ui.set_next_item_width(COMBO_WIDTH);
+                    ui.combo_enum(obfstr!("Bots"), &BOT_FILTERS, &mut config.filter_bots);
+                }
                 ui.checkbox(obfstr!("Near only"), &mut config.near_players);
                 if config.near_players {
                     ui.same_line();
                     ui.slider_config("Max distance", 0.0, 50.0)
                         .build(&mut config.near_players_distance);
                 }
+                ui.checkbox(obfstr!("Distance fade"), &mut config.distance_fade);
+                if config.distance_fade {
+                    ui.same_line();
+                    ui.set_next_item_width(COMBO_WIDTH);
+                    ui.slider_config("Fade start", 0.0, 100.0)
+                        .display_format("%.0fm")
+                        .build(&mut config.fade_distance_start);
+
+                    ui.same_line();
+                    ui.set_next_item_width(COMBO_WIDTH);
+                    ui.slider_config("Fade end", 0.0, 100.0)
+                        .display_format("%.0fm")
+                        .build(&mut config.fade_distance_end);
+                }
+                ui.checkbox(obfstr!("Last known position"), &mut config.ghost);
+                if config.ghost {
+                    ui.same_line();
+                    ui.slider_config("Timeout", 1.0, 30.0)
+                        .display_format("%.1fs")
+                        .build(&mut config.ghost_timeout);
+                }
+                ui.checkbox(obfstr!("Movement trail"), &mut config.trail);
+                if config.trail {
+                    ui.same_line();
+                    ui.slider_config("Trail duration", 0.5, 10.0)
+                        .display_format("%.1fs")
+                        .build(&mut config.trail_duration);
+                }
+                ui.checkbox(obfstr!("View direction"), &mut config.view_direction_line);
+                if config.view_direction_line {
+                    ui.same_line();
+                    ui.slider_config("Line length", 0.5, 20.0)
+                        .display_format("%.1fm")
+                        .build(&mut config.view_direction_line_length);
+                }
+                ui.checkbox(obfstr!("Off-screen arrows"), &mut config.offscreen_arrows);
+                if config.offscreen_arrows {
+                    ui.same_line();
+                    ui.slider_config("Max arrow distance", 0.0, 100.0)
+                        .build(&mut config.offscreen_arrows_max_distance);
+                }
             }
         }
 
@@ -665,6 +2739,7 @@ impl SettingsUI {
                     ui.table_next_row();
                     Self::render_esp_settings_player_style_color(
                         ui,
+                        &palette,
                         obfstr!("ESP box color"),
                         &mut config.box_color,
                     );
@@ -681,6 +2756,105 @@ impl SettingsUI {
                     ui.table_next_row();
                     Self::render_esp_settings_player_style_color(
                         ui,
+                        &palette,
+                        obfstr!("Shooting highlight color"),
+                        &mut config.shot_indicator_color,
+                    );
+
+                    ui.table_next_row();
+                    Self::render_esp_settings_player_style_width(
+                        ui,
+                        obfstr!("Shooting highlight duration"),
+                        0.05,
+                        1.0,
+                        &mut config.shot_indicator_duration,
+                    );
+
+                    ui.table_next_row();
+                    Self::render_esp_settings_player_style_color(
+                        ui,
+                        &palette,
+                        obfstr!("Low HP color"),
+                        &mut config.low_health_color,
+                    );
+
+                    ui.table_next_row();
+                    Self::render_esp_settings_player_style_color(
+                        ui,
+                        &palette,
+                        obfstr!("Bomb carrier box color"),
+                        &mut config.bomb_carrier_box_color,
+                    );
+
+                    ui.table_next_row();
+                    Self::render_esp_settings_player_style_color(
+                        ui,
+                        &palette,
+                        obfstr!("Aiming at you color"),
+                        &mut config.aiming_at_you_color,
+                    );
+
+                    ui.table_next_row();
+                    Self::render_esp_settings_player_style_width(
+                        ui,
+                        obfstr!("Aiming at you FOV"),
+                        1.0,
+                        45.0,
+                        &mut config.aiming_at_you_fov,
+                    );
+
+                    ui.table_next_row();
+                    Self::render_esp_settings_player_style_width(
+                        ui,
+                        obfstr!("Aiming at you max distance"),
+                        1.0,
+                        200.0,
+                        &mut config.aiming_at_you_max_distance,
+                    );
+
+                    ui.table_next_row();
+                    Self::render_esp_settings_player_style_color(
+                        ui,
+                        &palette,
+                        obfstr!("ESP box fill color"),
+                        &mut config.box_fill_color,
+                    );
+
+                    ui.table_next_row();
+                    Self::render_esp_settings_player_style_width(
+                        ui,
+                        obfstr!("ESP box fill alpha"),
+                        0.0,
+                        1.0,
+                        &mut config.box_fill_alpha,
+                    );
+
+                    if config.box_fit == EspBoxFit::Bones {
+                        ui.table_next_row();
+                        Self::render_esp_settings_player_style_width(
+                            ui,
+                            obfstr!("ESP box margin"),
+                            0.0,
+                            30.0,
+                            &mut config.box_fit_margin,
+                        );
+                    }
+
+                    if config.box_type == EspBoxType::Box2DCorners {
+                        ui.table_next_row();
+                        Self::render_esp_settings_player_style_width(
+                            ui,
+                            obfstr!("ESP box corner length"),
+                            0.05,
+                            0.5,
+                            &mut config.box_corner_length_ratio,
+                        );
+                    }
+
+                    ui.table_next_row();
+                    Self::render_esp_settings_player_style_color(
+                        ui,
+                        &palette,
                         obfstr!("Player skeleton color"),
                         &mut config.skeleton_color,
                     );
@@ -688,47 +2862,169 @@ impl SettingsUI {
                     ui.table_next_row();
                     Self::render_esp_settings_player_style_width(
                         ui,
-                        obfstr!("Player skeleton width"),
+                        obfstr!("Player skeleton width"),
+                        1.0,
+                        10.0,
+                        &mut config.skeleton_width,
+                    );
+
+                    ui.table_next_row();
+                    Self::render_esp_settings_player_style_color(
+                        ui,
+                        &palette,
+                        obfstr!("Skeleton joints color"),
+                        &mut config.skeleton_joints_color,
+                    );
+
+                    ui.table_next_row();
+                    Self::render_esp_settings_player_style_width(
+                        ui,
+                        obfstr!("Skeleton joints radius"),
+                        1.0,
+                        10.0,
+                        &mut config.skeleton_joints_radius,
+                    );
+
+                    ui.table_next_row();
+                    Self::render_esp_settings_player_style_color(
+                        ui,
+                        &palette,
+                        obfstr!("Player head color"),
+                        &mut config.head_display_color,
+                    );
+
+                    ui.table_next_row();
+                    Self::render_esp_settings_player_style_width(
+                        ui,
+                        obfstr!("Player head width"),
+                        1.0,
+                        10.0,
+                        &mut config.head_display_width,
+                    );
+
+                    ui.table_next_row();
+                    Self::render_esp_settings_player_style_width(
+                        ui,
+                        obfstr!("Health bar width"),
+                        5.0,
+                        30.0,
+                        &mut config.health_bar_width,
+                    );
+
+                    ui.table_next_row();
+                    Self::render_esp_settings_player_style_width(
+                        ui,
+                        obfstr!("Armor bar width"),
+                        5.0,
+                        30.0,
+                        &mut config.armor_bar_width,
+                    );
+
+                    ui.table_next_row();
+                    Self::render_esp_settings_player_style_color(
+                        ui,
+                        &palette,
+                        obfstr!("Flash bar color"),
+                        &mut config.flash_bar_color,
+                    );
+
+                    ui.table_next_row();
+                    Self::render_esp_settings_player_style_width(
+                        ui,
+                        obfstr!("Flash bar height"),
+                        2.0,
+                        20.0,
+                        &mut config.flash_bar_height,
+                    );
+
+                    ui.table_next_row();
+                    Self::render_esp_settings_player_style_color(
+                        ui,
+                        &palette,
+                        obfstr!("Tracer line color"),
+                        &mut config.tracer_lines_color,
+                    );
+
+                    ui.table_next_row();
+                    Self::render_esp_settings_player_style_width(
+                        ui,
+                        obfstr!("Tracer line width"),
+                        1.0,
+                        10.0,
+                        &mut config.tracer_lines_width,
+                    );
+
+                    ui.table_next_row();
+                    Self::render_esp_settings_player_style_color(
+                        ui,
+                        &palette,
+                        obfstr!("Trail color"),
+                        &mut config.trail_color,
+                    );
+
+                    ui.table_next_row();
+                    Self::render_esp_settings_player_style_width(
+                        ui,
+                        obfstr!("Trail width"),
+                        1.0,
+                        10.0,
+                        &mut config.trail_width,
+                    );
+
+                    ui.table_next_row();
+                    Self::render_esp_settings_player_style_color(
+                        ui,
+                        &palette,
+                        obfstr!("View direction color"),
+                        &mut config.view_direction_line_color,
+                    );
+
+                    ui.table_next_row();
+                    Self::render_esp_settings_player_style_width(
+                        ui,
+                        obfstr!("View direction width"),
                         1.0,
                         10.0,
-                        &mut config.skeleton_width,
+                        &mut config.view_direction_line_width,
                     );
 
                     ui.table_next_row();
                     Self::render_esp_settings_player_style_width(
                         ui,
-                        obfstr!("Health bar width"),
-                        5.0,
-                        30.0,
-                        &mut config.health_bar_width,
+                        obfstr!("Info text scale"),
+                        0.5,
+                        2.0,
+                        &mut config.info_text_scale,
                     );
 
                     ui.table_next_row();
                     Self::render_esp_settings_player_style_color(
                         ui,
-                        obfstr!("Tracer line color"),
-                        &mut config.tracer_lines_color,
+                        &palette,
+                        obfstr!("Color info name"),
+                        &mut config.info_name_color,
                     );
 
                     ui.table_next_row();
-                    Self::render_esp_settings_player_style_width(
+                    Self::render_esp_settings_player_style_color(
                         ui,
-                        obfstr!("Tracer line width"),
-                        1.0,
-                        10.0,
-                        &mut config.tracer_lines_width,
+                        &palette,
+                        obfstr!("Color info rank"),
+                        &mut config.info_rank_color,
                     );
 
                     ui.table_next_row();
                     Self::render_esp_settings_player_style_color(
                         ui,
-                        obfstr!("Color info name"),
-                        &mut config.info_name_color,
+                        &palette,
+                        obfstr!("Color info kills / deaths"),
+                        &mut config.info_kd_color,
                     );
 
                     ui.table_next_row();
                     Self::render_esp_settings_player_style_color(
                         ui,
+                        &palette,
                         obfstr!("Color info distance"),
                         &mut config.info_distance_color,
                     );
@@ -736,6 +3032,7 @@ impl SettingsUI {
                     ui.table_next_row();
                     Self::render_esp_settings_player_style_color(
                         ui,
+                        &palette,
                         obfstr!("Color info weapon"),
                         &mut config.info_weapon_color,
                     );
@@ -743,6 +3040,15 @@ impl SettingsUI {
                     ui.table_next_row();
                     Self::render_esp_settings_player_style_color(
                         ui,
+                        &palette,
+                        obfstr!("Color info ammo"),
+                        &mut config.info_ammo_color,
+                    );
+
+                    ui.table_next_row();
+                    Self::render_esp_settings_player_style_color(
+                        ui,
+                        &palette,
                         obfstr!("Color info health"),
                         &mut config.info_hp_text_color,
                     );
@@ -750,18 +3056,189 @@ impl SettingsUI {
                     ui.table_next_row();
                     Self::render_esp_settings_player_style_color(
                         ui,
+                        &palette,
+                        obfstr!("Color info money"),
+                        &mut config.info_money_color,
+                    );
+
+                    ui.table_next_row();
+                    Self::render_esp_settings_player_style_color(
+                        ui,
+                        &palette,
+                        obfstr!("Color info ping"),
+                        &mut config.info_ping_color,
+                    );
+
+                    ui.table_next_row();
+                    Self::render_esp_settings_player_style_color(
+                        ui,
+                        &palette,
+                        obfstr!("Color info speed"),
+                        &mut config.info_speed_color,
+                    );
+
+                    ui.table_next_row();
+                    Self::render_esp_settings_player_style_color(
+                        ui,
+                        &palette,
                         obfstr!("Color info player flags"),
                         &mut config.info_flags_color,
                     );
+
+                    ui.table_next_row();
+                    Self::render_esp_settings_player_style_color(
+                        ui,
+                        &palette,
+                        obfstr!("Last known position color"),
+                        &mut config.ghost_color,
+                    );
+
+                    ui.table_next_row();
+                    Self::render_esp_settings_player_style_color(
+                        ui,
+                        &palette,
+                        obfstr!("Off-screen arrow color"),
+                        &mut config.offscreen_arrows_color,
+                    );
+
+                    ui.table_next_row();
+                    Self::render_esp_settings_player_style_width(
+                        ui,
+                        obfstr!("Off-screen arrow size"),
+                        5.0,
+                        50.0,
+                        &mut config.offscreen_arrows_size,
+                    );
+
+                    ui.table_next_row();
+                    Self::render_esp_settings_player_style_width(
+                        ui,
+                        obfstr!("Off-screen arrow edge distance"),
+                        0.0,
+                        500.0,
+                        &mut config.offscreen_arrows_edge_distance,
+                    );
                 }
             }
         }
 
+        if let (Some(map_name), Some(override_config)) = (&self.esp_override_map, override_config) {
+            let mut patch = base_config
+                .map_overrides
+                .remove(map_name)
+                .unwrap_or_default();
+            patch.update(base_config, &override_config);
+            if !patch.is_empty() {
+                base_config.map_overrides.insert(map_name.clone(), patch);
+            }
+        }
+
         drop(_ui_enable_token);
     }
 
+    fn render_esp_map_override_selector(
+        &mut self,
+        ui: &SearchableUi,
+        config: &mut EspPlayerSettings,
+    ) {
+        const KNOWN_MAPS: [&'static str; 10] = [
+            "de_ancient",
+            "de_anubis",
+            "de_dust2",
+            "de_inferno",
+            "de_mirage",
+            "de_nuke",
+            "de_overpass",
+            "de_vertigo",
+            "cs_italy",
+            "cs_office",
+        ];
+
+        let mut maps = KNOWN_MAPS
+            .iter()
+            .map(|map_name| map_name.to_string())
+            .chain(config.map_overrides.keys().cloned())
+            .chain(self.esp_current_map.clone())
+            .collect::<Vec<_>>();
+        maps.sort();
+        maps.dedup();
+
+        let preview = self
+            .esp_override_map
+            .clone()
+            .unwrap_or_else(|| obfstr!("All maps").to_string());
+
+        ui.set_next_item_width(150.0);
+        if let Some(_combo) = ui.begin_combo(obfstr!("Map override"), &preview) {
+            if ui
+                .selectable_config(obfstr!("All maps"))
+                .selected(self.esp_override_map.is_none())
+                .build()
+            {
+                self.esp_override_map = None;
+            }
+
+            for map_name in maps.iter() {
+                let label = if config.map_overrides.contains_key(map_name) {
+                    format!("{} *", map_name)
+                } else {
+                    map_name.clone()
+                };
+
+                if ui
+                    .selectable_config(&label)
+                    .selected(self.esp_override_map.as_ref() == Some(map_name))
+                    .build()
+                {
+                    self.esp_override_map = Some(map_name.clone());
+                }
+            }
+        }
+
+        let map_name = match &self.esp_override_map {
+            Some(map_name) => map_name,
+            None => return,
+        };
+
+        let patch = match config.map_overrides.get_mut(map_name) {
+            Some(patch) => patch,
+            None => {
+                ui.same_line();
+                ui.text_disabled(obfstr!("Changes will only apply to this map"));
+                return;
+            }
+        };
+
+        ui.same_line();
+        if ui.small_button(obfstr!("Clear all")) {
+            config.map_overrides.remove(map_name);
+            return;
+        }
+
+        ui.text(obfstr!("Overridden:"));
+        let style = ui.clone_style();
+        for field in patch.overridden_fields() {
+            let label = format!("{} x", field);
+
+            /* wrap the buttons if they exceed the available width */
+            let button_width = ui.calc_text_size(&label)[0] + style.frame_padding[0] * 2.0;
+            ui.same_line();
+            if ui.content_region_avail()[0] < button_width {
+                ui.new_line();
+            }
+
+            if ui.small_button(&label) {
+                patch.clear_field(field);
+            }
+        }
+
+        if patch.is_empty() {
+            config.map_overrides.remove(map_name);
+        }
+    }
+
     fn render_esp_settings_player_style_width(
-        ui: &imgui::Ui,
+        ui: &SearchableUi,
         label: &str,
         min: f32,
         max: f32,
@@ -769,6 +3246,7 @@ impl SettingsUI {
     ) -> bool {
         ui.table_next_column();
         ui.text(label);
+        ui.track(label);
 
         ui.table_next_column();
         ui.text(&format!("{:.2} - {:.2}", min, max));
@@ -785,9 +3263,28 @@ impl SettingsUI {
         }
     }
 
-    fn render_esp_settings_player_style_color(ui: &imgui::Ui, label: &str, color: &mut EspColor) {
+    fn render_color_edit(ui: &SearchableUi, id: &str, color: &mut Color) {
+        let mut value = color.as_f32();
+        if {
+            ui.color_edit4_config(&format!("##{}_{}", ui.table_row_index(), id), &mut value)
+                .alpha_bar(true)
+                .inputs(false)
+                .label(false)
+                .build()
+        } {
+            *color = Color::from_f32(value);
+        }
+    }
+
+    fn render_esp_settings_player_style_color(
+        ui: &SearchableUi,
+        palette: &ColorPalette,
+        label: &str,
+        color: &mut EspColor,
+    ) {
         ui.table_next_column();
         ui.text(label);
+        ui.track(label);
 
         ui.table_next_column();
         {
@@ -800,6 +3297,8 @@ impl SettingsUI {
                     (EspColorType::HealthBased, "Health based"),
                     (EspColorType::HealthBasedRainbow, "Rainbow"),
                     (EspColorType::DistanceBased, "Distance"),
+                    (EspColorType::Animated, "Animated"),
+                    (EspColorType::Palette, "Palette"),
                 ],
                 &mut color_type,
             );
@@ -812,9 +3311,23 @@ impl SettingsUI {
                     EspColorType::HealthBased => EspColor::HealthBased {
                         max: Color::from_f32([0.0, 1.0, 0.0, 1.0]),
                         min: Color::from_f32([1.0, 0.0, 0.0, 1.0]),
+                        mid: None,
                     },
                     EspColorType::HealthBasedRainbow => EspColor::HealthBasedRainbow,
-                    EspColorType::DistanceBased => EspColor::DistanceBased,
+                    EspColorType::DistanceBased => EspColor::distance_based(),
+                    EspColorType::Animated => EspColor::animated(),
+                    EspColorType::Palette => {
+                        let (name, fallback) = palette
+                            .colors
+                            .iter()
+                            .next()
+                            .map(|(name, color)| (name.clone(), *color))
+                            .unwrap_or_else(|| {
+                                ("accent".to_string(), Color::from_f32([1.0, 1.0, 1.0, 1.0]))
+                            });
+
+                        EspColor::Palette { name, fallback }
+                    }
                 }
             }
         }
@@ -839,63 +3352,344 @@ impl SettingsUI {
                         *value = Color::from_f32(color_value);
                     }
                 }
-                EspColor::HealthBased { max, min } => {
-                    let mut max_value = max.as_f32();
-                    if {
-                        ui.color_edit4_config(
-                            &format!("##{}_health_max", ui.table_row_index()),
-                            &mut max_value,
-                        )
-                        .alpha_bar(true)
-                        .inputs(false)
-                        .label(false)
-                        .build()
-                    } {
-                        *max = Color::from_f32(max_value);
+                EspColor::HealthBased { max, min, mid } => {
+                    Self::render_color_edit(ui, "health_max", max);
+                    if let Some(mid) = mid {
+                        ui.same_line();
+                        Self::render_color_edit(ui, "health_mid", mid);
                     }
+                    ui.same_line();
+                    Self::render_color_edit(ui, "health_min", min);
 
                     ui.same_line();
-                    ui.text(" => ");
+                    let popup_id = format!("##{}_health_options", ui.table_row_index());
+                    if ui.small_button(&format!("...{}", popup_id)) {
+                        ui.open_popup(&popup_id);
+                    }
+                    ui.popup(&popup_id, || {
+                        let mut use_mid = mid.is_some();
+                        if ui.checkbox(obfstr!("Color at half health"), &mut use_mid) {
+                            *mid = if use_mid {
+                                Some(Color::from_f32(
+                                    EspColor::HealthBased {
+                                        max: *max,
+                                        min: *min,
+                                        mid: None,
+                                    }
+                                    .calculate_color(palette, 0.5, 0.0, 0.0),
+                                ))
+                            } else {
+                                None
+                            };
+                        }
+                    });
+                }
+                EspColor::DistanceBased {
+                    near,
+                    far,
+                    near_distance,
+                    far_distance,
+                } => {
+                    Self::render_color_edit(ui, "distance_near", near);
                     ui.same_line();
+                    Self::render_color_edit(ui, "distance_far", far);
 
-                    let mut min_value = min.as_f32();
-                    if {
-                        ui.color_edit4_config(
-                            &format!("##{}_health_min", ui.table_row_index()),
-                            &mut min_value,
-                        )
-                        .alpha_bar(true)
-                        .inputs(false)
-                        .label(false)
-                        .build()
-                    } {
-                        *min = Color::from_f32(min_value);
+                    ui.same_line();
+                    let popup_id = format!("##{}_distance_options", ui.table_row_index());
+                    if ui.small_button(&format!("...{}", popup_id)) {
+                        ui.open_popup(&popup_id);
+                    }
+                    ui.popup(&popup_id, || {
+                        ui.set_next_item_width(150.0);
+                        ui.slider_config(obfstr!("Near distance"), 0.0, 200.0)
+                            .display_format("%.0fm")
+                            .build(near_distance);
+
+                        ui.set_next_item_width(150.0);
+                        ui.slider_config(obfstr!("Far distance"), 0.0, 200.0)
+                            .display_format("%.0fm")
+                            .build(far_distance);
+                    });
+                }
+                EspColor::Animated {
+                    mode,
+                    speed,
+                    from,
+                    to,
+                } => {
+                    if *mode == EspColorAnimation::Pulse {
+                        Self::render_color_edit(ui, "animation_from", from);
+                        ui.same_line();
+                        Self::render_color_edit(ui, "animation_to", to);
+                    } else {
+                        ui.text("Rainbow");
+                    }
+
+                    ui.same_line();
+                    let popup_id = format!("##{}_animation_options", ui.table_row_index());
+                    if ui.small_button(&format!("...{}", popup_id)) {
+                        ui.open_popup(&popup_id);
+                    }
+                    ui.popup(&popup_id, || {
+                        const ANIMATION_MODES: [(EspColorAnimation, &'static str); 2] = [
+                            (EspColorAnimation::Rainbow, "Rainbow"),
+                            (EspColorAnimation::Pulse, "Pulse"),
+                        ];
+
+                        ui.set_next_item_width(150.0);
+                        ui.combo_enum(obfstr!("Animation"), &ANIMATION_MODES, mode);
+
+                        ui.set_next_item_width(150.0);
+                        ui.slider_config(obfstr!("Speed"), 0.05, 5.0)
+                            .display_format("%.2f/s")
+                            .build(speed);
+
+                        if *mode == EspColorAnimation::Rainbow {
+                            let mut alpha = from.as_f32()[3];
+                            ui.set_next_item_width(150.0);
+                            if ui
+                                .slider_config(obfstr!("Alpha"), 0.0, 1.0)
+                                .build(&mut alpha)
+                            {
+                                let mut value = from.as_f32();
+                                value[3] = alpha;
+                                *from = Color::from_f32(value);
+                            }
+                        }
+                    });
+                }
+                EspColor::Palette { name, fallback } => {
+                    let preview = if palette.get(name).is_some() {
+                        name.clone()
+                    } else {
+                        format!("{} (missing)", name)
+                    };
+
+                    ui.set_next_item_width(ui.content_region_avail()[0]);
+                    if let Some(_combo) =
+                        ui.begin_combo(&format!("##{}_palette", ui.table_row_index()), &preview)
+                    {
+                        for (entry_name, entry_color) in palette.colors.iter() {
+                            let selected = *entry_name == *name;
+                            if ui.selectable_config(entry_name).selected(selected).build() {
+                                *name = entry_name.clone();
+                                *fallback = *entry_color;
+                            }
+                        }
                     }
                 }
-                EspColor::DistanceBased => ui.text("Distance"),
             }
         }
     }
 
     fn render_esp_settings_chicken(
         &mut self,
-        _settings: &mut AppSettings,
-        ui: &imgui::Ui,
-        _target: EspSelector,
+        settings: &mut AppSettings,
+        ui: &SearchableUi,
+        target: EspSelector,
+    ) {
+        let config_key = target.config_key();
+        let config_enabled = settings
+            .esp_settings_enabled
+            .get(&config_key)
+            .cloned()
+            .unwrap_or_default();
+
+        let palette = settings.color_palette.clone();
+        let config = match settings.esp_settings.entry(config_key.clone()) {
+            Entry::Occupied(entry) => {
+                let value = entry.into_mut();
+                if !matches!(value, EspConfig::Chicken(_)) {
+                    log::warn!("Detected invalid chicken config for {}", config_key);
+                    *value = EspConfig::Chicken(EspChickenSettings::new());
+                }
+
+                if let EspConfig::Chicken(value) = value {
+                    value
+                } else {
+                    unreachable!()
+                }
+            }
+            Entry::Vacant(entry) => {
+                if let EspConfig::Chicken(value) =
+                    entry.insert(EspConfig::Chicken(EspChickenSettings::new()))
+                {
+                    value
+                } else {
+                    unreachable!()
+                }
+            }
+        };
+        let _ui_enable_token = ui.begin_enabled(config_enabled);
+
+        ui.indent_by(5.0);
+        ui.dummy([0.0, 5.0]);
+
+        ui.checkbox(obfstr!("Show marker"), &mut config.marker);
+        ui.checkbox(obfstr!("Show name"), &mut config.info_name);
+
+        ui.set_next_item_width(150.0);
+        ui.slider_config(obfstr!("Max distance"), 5.0, 200.0)
+            .display_format("%.0fm")
+            .build(&mut config.max_distance);
+
+        ui.dummy([0.0, 5.0]);
+        if let Some(_token) = {
+            let mut column_type = TableColumnSetup::new("Type");
+            column_type.init_width_or_weight = 100.0;
+            column_type.flags = TableColumnFlags::WIDTH_FIXED;
+
+            let mut column_value = TableColumnSetup::new("Value");
+            column_value.init_width_or_weight = 100.0;
+            column_value.flags = TableColumnFlags::WIDTH_FIXED;
+
+            ui.begin_table_header_with_flags(
+                "chicken_styles_table",
+                [TableColumnSetup::new("Name"), column_type, column_value],
+                TableFlags::ROW_BG | TableFlags::BORDERS | TableFlags::SIZING_STRETCH_PROP,
+            )
+        } {
+            ui.table_next_row();
+            Self::render_esp_settings_player_style_color(
+                ui,
+                &palette,
+                obfstr!("Marker color"),
+                &mut config.marker_color,
+            );
+
+            ui.table_next_row();
+            Self::render_esp_settings_player_style_color(
+                ui,
+                &palette,
+                obfstr!("Name color"),
+                &mut config.info_name_color,
+            );
+        }
+    }
+
+    fn render_esp_settings_hostage(
+        &mut self,
+        settings: &mut AppSettings,
+        ui: &SearchableUi,
+        target: EspSelector,
     ) {
-        ui.text("Chicken!");
+        let config_key = target.config_key();
+        let config_enabled = settings
+            .esp_settings_enabled
+            .get(&config_key)
+            .cloned()
+            .unwrap_or_default();
+
+        let palette = settings.color_palette.clone();
+        let config = match settings.esp_settings.entry(config_key.clone()) {
+            Entry::Occupied(entry) => {
+                let value = entry.into_mut();
+                if !matches!(value, EspConfig::Hostage(_)) {
+                    log::warn!("Detected invalid hostage config for {}", config_key);
+                    *value = EspConfig::Hostage(EspHostageSettings::new());
+                }
+
+                if let EspConfig::Hostage(value) = value {
+                    value
+                } else {
+                    unreachable!()
+                }
+            }
+            Entry::Vacant(entry) => {
+                if let EspConfig::Hostage(value) =
+                    entry.insert(EspConfig::Hostage(EspHostageSettings::new()))
+                {
+                    value
+                } else {
+                    unreachable!()
+                }
+            }
+        };
+        let _ui_enable_token = ui.begin_enabled(config_enabled);
+
+        ui.indent_by(5.0);
+        ui.dummy([0.0, 5.0]);
+
+        {
+            const ESP_BOX_TYPES: [(EspBoxType, &'static str); 3] = [
+                (EspBoxType::None, "No"),
+                (EspBoxType::Box2D, "2D"),
+                (EspBoxType::Box3D, "3D"),
+            ];
+
+            ui.set_next_item_width(150.0);
+            ui.combo_enum(obfstr!("hostage box"), &ESP_BOX_TYPES, &mut config.box_type);
+
+            ui.checkbox(obfstr!("Show health"), &mut config.info_health);
+            ui.checkbox(obfstr!("Show carrier"), &mut config.info_carrier);
+        }
+
+        ui.dummy([0.0, 5.0]);
+        if let Some(_token) = {
+            let mut column_type = TableColumnSetup::new("Type");
+            column_type.init_width_or_weight = 100.0;
+            column_type.flags = TableColumnFlags::WIDTH_FIXED;
+
+            let mut column_value = TableColumnSetup::new("Value");
+            column_value.init_width_or_weight = 100.0;
+            column_value.flags = TableColumnFlags::WIDTH_FIXED;
+
+            ui.begin_table_header_with_flags(
+                "hostage_styles_table",
+                [TableColumnSetup::new("Name"), column_type, column_value],
+                TableFlags::ROW_BG | TableFlags::BORDERS | TableFlags::SIZING_STRETCH_PROP,
+            )
+        } {
+            ui.table_next_row();
+            Self::render_esp_settings_player_style_color(
+                ui,
+                &palette,
+                obfstr!("ESP box color"),
+                &mut config.box_color,
+            );
+
+            ui.table_next_row();
+            Self::render_esp_settings_player_style_width(
+                ui,
+                obfstr!("ESP box width"),
+                1.0,
+                10.0,
+                &mut config.box_width,
+            );
+
+            ui.table_next_row();
+            Self::render_esp_settings_player_style_color(
+                ui,
+                &palette,
+                obfstr!("Health color"),
+                &mut config.info_health_color,
+            );
+
+            ui.table_next_row();
+            Self::render_esp_settings_player_style_color(
+                ui,
+                &palette,
+                obfstr!("Carrier color"),
+                &mut config.info_carrier_color,
+            );
+        }
     }
 
     fn render_esp_settings_weapon(
         &mut self,
         _settings: &mut AppSettings,
-        ui: &imgui::Ui,
+        ui: &SearchableUi,
         _target: EspSelector,
     ) {
         ui.text("Weapon!");
     }
 
-    fn render_esp_settings(&mut self, settings: &mut AppSettings, ui: &imgui::Ui) {
+    fn render_esp_settings(
+        &mut self,
+        app: &Application,
+        settings: &mut AppSettings,
+        ui: &SearchableUi,
+    ) {
         if let Some(target) = self.esp_pending_target.take() {
             self.esp_selected_target = target;
         }
@@ -926,9 +3720,9 @@ impl SettingsUI {
             ui.same_line_with_pos(total_width - reset_text_width);
 
             let _enabled = ui.begin_enabled(*target_enabled);
-            if ui.button(reset_text) {
-                /* just removing the key will work as a default config will be emplaced later */
-                settings.esp_settings.remove(&target_key);
+            if Self::render_reset_button(ui, reset_text, obfstr!("ESP target")) {
+                settings.reset_esp_config(&self.esp_selected_target);
+                app.settings_changed.store(true, Ordering::Relaxed);
             }
         } else {
             ui.text("Target Configuration");
@@ -958,7 +3752,8 @@ impl SettingsUI {
             );
 
             self.render_esp_target(settings, ui, &EspSelector::Player);
-            // self.render_esp_target(settings, ui, &EspSelector::Chicken);
+            self.render_esp_target(settings, ui, &EspSelector::Hostage);
+            self.render_esp_target(settings, ui, &EspSelector::Chicken);
             // self.render_esp_target(settings, ui, &EspSelector::Weapon)
         }
         ui.same_line();
@@ -968,16 +3763,55 @@ impl SettingsUI {
                 .scroll_bar(true)
                 .begin()
         } {
+            if ui.search().is_indexing() {
+                /* render all pages once to discover their settings */
+                let selected_header = std::mem::replace(
+                    &mut self.esp_player_active_header,
+                    EspPlayerActiveHeader::Features,
+                );
+                for target in [
+                    EspSelector::Player,
+                    EspSelector::Chicken,
+                    EspSelector::Hostage,
+                ] {
+                    ui.search().set_esp_target(Some(target.clone()));
+                    match &target {
+                        EspSelector::Player => {
+                            self.render_esp_settings_player(app, settings, ui, target.clone())
+                        }
+                        EspSelector::Chicken => {
+                            self.render_esp_settings_chicken(settings, ui, target.clone())
+                        }
+                        EspSelector::Hostage => {
+                            self.render_esp_settings_hostage(settings, ui, target.clone())
+                        }
+                        _ => {}
+                    }
+                }
+                self.esp_player_active_header = selected_header;
+                return;
+            }
+
+            if !matches!(self.esp_selected_target, EspSelector::None) {
+                ui.search()
+                    .set_esp_target(Some(self.esp_selected_target.clone()));
+            }
             match &self.esp_selected_target {
                 EspSelector::None => {}
                 EspSelector::Player
                 | EspSelector::PlayerTeam { .. }
-                | EspSelector::PlayerTeamVisibility { .. } => {
-                    self.render_esp_settings_player(settings, ui, self.esp_selected_target.clone())
-                }
+                | EspSelector::PlayerTeamVisibility { .. } => self.render_esp_settings_player(
+                    app,
+                    settings,
+                    ui,
+                    self.esp_selected_target.clone(),
+                ),
                 EspSelector::Chicken => {
                     self.render_esp_settings_chicken(settings, ui, self.esp_selected_target.clone())
                 }
+                EspSelector::Hostage => {
+                    self.render_esp_settings_hostage(settings, ui, self.esp_selected_target.clone())
+                }
                 EspSelector::Weapon
                 | EspSelector::WeaponGroup { .. }
                 | EspSelector::WeaponSingle { .. } => {
@@ -0,0 +1,248 @@
+use serde_path_to_error::Segment;
+use serde_yaml::Value;
+
+use super::{
+    AppSettings,
+    EspConfig,
+    EspSelector,
+};
+
+/// Max amount of invalid values which will be reset before giving up
+const MAX_INVALID_VALUES: usize = 32;
+
+/// Values behind unknown segments can not be resolved, only their parent can
+fn known_path(path: &[Segment]) -> &[Segment] {
+    match path
+        .iter()
+        .position(|segment| matches!(segment, Segment::Unknown))
+    {
+        Some(index) => &path[..index],
+        None => path,
+    }
+}
+
+fn lookup_value<'a>(config: &'a mut Value, path: &[Segment]) -> Option<&'a mut Value> {
+    let mut current = config;
+    for segment in path {
+        current = match (segment, current) {
+            (Segment::Map { key }, Value::Mapping(mapping)) => mapping.get_mut(key.as_str())?,
+            (Segment::Seq { index }, Value::Sequence(sequence)) => sequence.get_mut(*index)?,
+            (Segment::Enum { variant }, Value::Mapping(mapping)) => {
+                mapping.get_mut(variant.as_str())?
+            }
+            (Segment::Enum { .. }, Value::Tagged(tagged)) => &mut tagged.value,
+            _ => return None,
+        };
+    }
+
+    Some(current)
+}
+
+/// Default value of the config entry at the given path.
+/// Returns None if the default is not known.
+fn default_value(path: &[Segment]) -> Option<Value> {
+    match path {
+        [Segment::Map { key: root }, Segment::Map { key }, nested @ ..]
+            if root == "esp_settings" =>
+        {
+            let selector = EspSelector::from_config_key(key)?;
+            let mut value = serde_yaml::to_value(EspConfig::default_for(&selector)?).ok()?;
+            lookup_value(&mut value, nested).cloned()
+        }
+        _ => None,
+    }
+}
+
+fn is_missing_field(message: &str) -> bool {
+    message.contains("missing field `")
+}
+
+/// Insert the default value of every missing field into the struct at the given path.
+/// Returns the names of the inserted fields.
+fn insert_default_fields(config: &mut Value, path: &[Segment]) -> Vec<String> {
+    let path = known_path(path);
+    let defaults = match default_value(path) {
+        Some(Value::Mapping(defaults)) => defaults,
+        _ => return Vec::new(),
+    };
+
+    let target = match lookup_value(config, path) {
+        Some(Value::Mapping(target)) => target,
+        _ => return Vec::new(),
+    };
+
+    let mut inserted = Vec::new();
+    for (key, value) in defaults {
+        if target.contains_key(&key) {
+            continue;
+        }
+
+        if let Some(name) = key.as_str() {
+            inserted.push(name.to_string());
+        }
+        target.insert(key, value);
+    }
+
+    inserted
+}
+
+/// Remove the value at the given path so it will be replaced by its default.
+/// Returns false if nothing could be removed.
+fn remove_value(config: &mut Value, path: &[Segment]) -> bool {
+    let (target, parents) = match known_path(path).split_last() {
+        Some(value) => value,
+        None => return false,
+    };
+
+    let current = match lookup_value(config, parents) {
+        Some(value) => value,
+        None => return false,
+    };
+
+    match (target, current) {
+        (Segment::Map { key }, Value::Mapping(mapping)) => mapping.remove(key.as_str()).is_some(),
+        (Segment::Seq { index }, Value::Sequence(sequence)) if *index < sequence.len() => {
+            sequence.remove(*index);
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Deserialize the app settings.
+/// Invalid values will be replaced by their defaults and unknown values will be ignored.
+/// Returns the settings and a warning for every ignored or reset value.
+pub fn parse_app_settings(mut config: Value) -> anyhow::Result<(AppSettings, Vec<String>)> {
+    let mut warnings = Vec::new();
+    for _ in 0..MAX_INVALID_VALUES {
+        let mut unknown_values = Vec::new();
+        let mut track_unknown = |path: serde_ignored::Path| unknown_values.push(path.to_string());
+
+        let deserializer = serde_ignored::Deserializer::new(config.clone(), &mut track_unknown);
+        let error = match serde_path_to_error::deserialize::<_, AppSettings>(deserializer) {
+            Ok(settings) => {
+                warnings.extend(
+                    unknown_values
+                        .into_iter()
+                        .map(|path| format!("{}: unknown setting", path)),
+                );
+                return Ok((settings, warnings));
+            }
+            Err(error) => error,
+        };
+
+        let path = error.path().iter().cloned().collect::<Vec<_>>();
+        let path_display = error.path().to_string();
+        let message = format!("{}: {}", path_display, error.into_inner());
+
+        /* only reset the missing fields and keep the valid values of the struct */
+        if is_missing_field(&message) {
+            let inserted = insert_default_fields(&mut config, &path);
+            if !inserted.is_empty() {
+                warnings.extend(inserted.into_iter().map(|field| {
+                    format!(
+                        "{}.{}: missing value (using the default value)",
+                        path_display, field
+                    )
+                }));
+                continue;
+            }
+        }
+
+        if !remove_value(&mut config, &path) {
+            anyhow::bail!("{}", message);
+        }
+
+        warnings.push(format!("{} (using the default value)", message));
+    }
+
+    anyhow::bail!("the config contains too many invalid values")
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_yaml::Value;
+
+    use super::parse_app_settings;
+    use crate::settings::{
+        migrate_config,
+        ActivationMode,
+        AppSettings,
+        EspConfig,
+        EspPlayerSettings,
+        EspSelector,
+    };
+
+    fn load_fixture(content: &str) -> (AppSettings, Vec<String>) {
+        let mut config: Value = serde_yaml::from_str(content).unwrap();
+        migrate_config(&mut config).unwrap();
+        parse_app_settings(config).unwrap()
+    }
+
+    #[test]
+    fn enum_typo() {
+        let (settings, warnings) = load_fixture(include_str!("fixtures/config_invalid_enum.yaml"));
+
+        assert_eq!(settings.esp_mode, ActivationMode::Always);
+        assert_eq!(settings.trigger_bot_mode, ActivationMode::Toggle);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].starts_with("esp_mode: unknown variant `Toggel`"));
+    }
+
+    #[test]
+    fn wrong_types() {
+        let (settings, warnings) = load_fixture(include_str!("fixtures/config_invalid_types.yaml"));
+
+        assert_eq!(settings.esp_max_enemies, 0);
+        assert!(!settings.bomb_timer);
+        assert_eq!(settings.trigger_delay_min_ms, 10);
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings
+            .iter()
+            .any(|warning| warning.starts_with("esp_max_enemies:")));
+        assert!(warnings
+            .iter()
+            .any(|warning| warning.starts_with("trigger_delay_max_ms:")));
+    }
+
+    #[test]
+    fn missing_sections() {
+        let (settings, warnings) =
+            load_fixture(include_str!("fixtures/config_invalid_sections.yaml"));
+
+        /* only the missing fields of the incomplete ESP config are reset */
+        let enemy = match settings.esp_settings.get("player.enemy") {
+            Some(EspConfig::Player(config)) => config,
+            _ => panic!("expected the player.enemy config to be kept"),
+        };
+        assert_eq!(enemy.box_width, 5.5);
+        assert!(!enemy.skeleton);
+        assert!(
+            enemy.box_color
+                == EspPlayerSettings::new(&EspSelector::PlayerTeam { enemy: true }).box_color
+        );
+
+        assert!(settings.esp_settings.get("chicken").is_some());
+        assert!(!settings.valthrun_watermark);
+        assert!(warnings
+            .iter()
+            .any(|warning| warning.starts_with("esp_settings.player.enemy.box_color:")));
+        assert!(!warnings
+            .iter()
+            .any(|warning| warning.starts_with("esp_settings.player.enemy.box_width:")));
+    }
+
+    #[test]
+    fn unknown_values() {
+        let (settings, warnings) = load_fixture("bomb_timer: false\nunknown_value: 1\n");
+
+        assert!(!settings.bomb_timer);
+        assert_eq!(warnings, vec!["unknown_value: unknown setting".to_string()]);
+    }
+
+    #[test]
+    fn invalid_root() {
+        let config: Value = serde_yaml::from_str("[1, 2, 3]").unwrap();
+        assert!(parse_app_settings(config).is_err());
+    }
+}
@@ -73,10 +73,49 @@ impl ImguiComboEnum for imgui::Ui {
 }
 
 mod hotkey {
+    use std::cell::RefCell;
+
     use imgui::Key;
 
     use crate::settings::HotKey;
 
+    thread_local! {
+        /// Keys pressed (in order) since the key capture popup has been opened.
+        /// Only one popup can be open at the same time.
+        static CAPTURED_KEYS: RefCell<Vec<Key>> = RefCell::new(Vec::new());
+    }
+
+    /// The ModXXX keys are aliases for the left/right modifier keys
+    fn is_capturable(key: Key) -> bool {
+        !format!("{:?}", key).starts_with("Mod")
+    }
+
+    /// Record the currently pressed keys.
+    /// Returns the key combination once the first of the pressed keys has been released.
+    fn capture_key_combination(ui: &imgui::Ui) -> Option<HotKey> {
+        CAPTURED_KEYS.with(|captured_keys| {
+            let mut captured_keys = captured_keys.borrow_mut();
+            for key_variant in Key::VARIANTS {
+                if is_capturable(key_variant)
+                    && ui.is_key_pressed_no_repeat(key_variant)
+                    && !captured_keys.contains(&key_variant)
+                {
+                    captured_keys.push(key_variant);
+                }
+            }
+
+            /* keys which have been pressed before opening the popup (e.g. the mouse click) are ignored */
+            if !captured_keys.iter().any(|key| ui.is_key_released(*key)) {
+                return None;
+            }
+
+            /* the last pressed key is the primary key, all other keys are modifiers */
+            let mut keys = std::mem::take(&mut *captured_keys);
+            let key = keys.pop()?;
+            Some(HotKey::new(key, keys))
+        })
+    }
+
     pub fn render_button_key(
         ui: &imgui::Ui,
         label: &str,
@@ -87,7 +126,7 @@ mod hotkey {
         let _container = ui.push_id(label);
 
         let button_label = if let Some(key) = &key {
-            format!("{:?}", key.0)
+            key.to_string()
         } else {
             "None".to_string()
         };
@@ -100,6 +139,7 @@ mod hotkey {
         let mut updated = false;
         if optional {
             if ui.button_with_size(&button_label, [size[0] - 35.0, size[1]]) {
+                CAPTURED_KEYS.with(|keys| keys.borrow_mut().clear());
                 ui.open_popup(label);
             }
 
@@ -113,6 +153,7 @@ mod hotkey {
             });
         } else {
             if ui.button_with_size(&button_label, size) {
+                CAPTURED_KEYS.with(|keys| keys.borrow_mut().clear());
                 ui.open_popup(label);
             }
         }
@@ -125,21 +166,90 @@ mod hotkey {
             .resizable(false)
             .title_bar(false)
             .build(|| {
-                ui.text("Press any key or ESC to exit");
+                ui.text("Press any key or key combination or ESC to exit");
 
                 if ui.is_key_pressed(Key::Escape) {
                     ui.close_current_popup();
-                } else {
-                    for key_variant in Key::VARIANTS {
-                        if ui.is_key_pressed(key_variant) {
-                            *key = Some(HotKey(key_variant));
-                            updated = true;
-                            ui.close_current_popup();
-                        }
-                    }
+                } else if let Some(combination) = capture_key_combination(ui) {
+                    *key = Some(combination);
+                    updated = true;
+                    ui.close_current_popup();
                 }
             });
 
         updated
     }
 }
+
+/// Multiplies the alpha of everything drawn while the guard is alive.
+/// The alpha of the generated vertices will be adjusted when the guard gets dropped,
+/// therefore explicit draw list colors as well as ImGui widgets are covered.
+pub struct DrawOpacity {
+    opacity: f32,
+
+    /// Draw list of the current window (null if the opacity is not reduced)
+    draw_list: *mut imgui::sys::ImDrawList,
+
+    /// Index of the first vertex which should be adjusted
+    vertex_start: usize,
+}
+
+impl DrawOpacity {
+    fn new(opacity: f32, whole_window: bool) -> Self {
+        let opacity = opacity.clamp(0.0, 1.0);
+        if opacity >= 1.0 {
+            return Self {
+                opacity,
+                draw_list: std::ptr::null_mut(),
+                vertex_start: 0,
+            };
+        }
+
+        let draw_list = unsafe { imgui::sys::igGetWindowDrawList() };
+        let vertex_start = if whole_window || draw_list.is_null() {
+            0
+        } else {
+            unsafe { (*draw_list).VtxBuffer.Size as usize }
+        };
+
+        Self {
+            opacity,
+            draw_list,
+            vertex_start,
+        }
+    }
+
+    /// Cover everything drawn into the current window while the guard is alive
+    pub fn begin(_ui: &imgui::Ui, opacity: f32) -> Self {
+        Self::new(opacity, false)
+    }
+
+    /// Cover the whole current window including its background.
+    /// Must be created within the window build callback.
+    pub fn window(_ui: &imgui::Ui, opacity: f32) -> Self {
+        Self::new(opacity, true)
+    }
+}
+
+impl Drop for DrawOpacity {
+    fn drop(&mut self) {
+        if self.draw_list.is_null() {
+            return;
+        }
+
+        let vertices = unsafe {
+            let buffer = &(*self.draw_list).VtxBuffer;
+            if buffer.Data.is_null() {
+                return;
+            }
+
+            std::slice::from_raw_parts_mut(buffer.Data, buffer.Size as usize)
+        };
+
+        for vertex in vertices.iter_mut().skip(self.vertex_start) {
+            /* vertex colors are packed as ABGR */
+            let alpha = ((vertex.col >> 24) as f32 * self.opacity) as u32;
+            vertex.col = (vertex.col & 0x00FF_FFFF) | (alpha << 24);
+        }
+    }
+}
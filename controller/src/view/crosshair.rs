@@ -4,9 +4,11 @@ use anyhow::Context;
 use cs2::{
     CEntityIdentityEx,
     CS2HandleState,
+    CS2Model,
     CS2Offsets,
     ClassNameCache,
     EntitySystem,
+    PlayerPawnState,
 };
 use cs2_schema_generated::{
     cs2::client::CEntityInstance,
@@ -18,11 +20,47 @@ use utils_state::{
     StateRegistry,
 };
 
+use super::ViewController;
+use crate::enhancements::eye_direction;
+
+/// Body part of a player which is being targeted
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HitboxGroup {
+    Head,
+    Chest,
+    Stomach,
+    Arms,
+    Legs,
+}
+
+impl HitboxGroup {
+    /// Resolve the hitbox group by the bone name of the player model
+    pub fn from_bone_name(name: &str) -> Option<Self> {
+        if name.starts_with("head") || name.starts_with("neck") {
+            Some(Self::Head)
+        } else if name == "spine_2" || name == "spine_3" || name.starts_with("clavicle") {
+            Some(Self::Chest)
+        } else if name == "pelvis" || name == "spine_0" || name == "spine_1" {
+            Some(Self::Stomach)
+        } else if name.starts_with("arm_") || name.starts_with("hand_") {
+            Some(Self::Arms)
+        } else if name.starts_with("leg_") || name.starts_with("ankle_") {
+            Some(Self::Legs)
+        } else {
+            None
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct CrosshairTarget {
     pub entity_id: u32,
     pub entity_type: Option<String>,
     pub timestamp: Instant,
+
+    /// Hitbox group of the targeted player.
+    /// None if the target is not a player or the group could not be determined.
+    pub hitbox_group: Option<HitboxGroup>,
 }
 
 pub struct LocalCrosshair {
@@ -72,9 +110,19 @@ impl State for LocalCrosshair {
                 entity_id: crosshair_entity_handle.get_entity_index(),
                 entity_type: target_type.cloned(),
                 timestamp: Instant::now(),
+                hitbox_group: None,
             });
         }
 
+        if let Some(target) = &mut self.current_target {
+            /* the targeted body part changes even if the target entity stays the same */
+            target.hitbox_group = if target.entity_type.as_deref() == Some("C_CSPlayerPawn") {
+                Self::resolve_hitbox_group(states, target.entity_id)?
+            } else {
+                None
+            };
+        }
+
         Ok(())
     }
 }
@@ -84,6 +132,65 @@ impl LocalCrosshair {
         self.current_target.as_ref()
     }
 
+    /// Find the hitbox group of the target pawn bone which is closest to the local view ray
+    fn resolve_hitbox_group(
+        states: &StateRegistry,
+        target_entity_id: u32,
+    ) -> anyhow::Result<Option<HitboxGroup>> {
+        let entities = states.resolve::<EntitySystem>(())?;
+        let local_player_controller = entities.get_local_player_controller()?;
+        if local_player_controller.is_null()? {
+            return Ok(None);
+        }
+
+        let local_pawn_index = local_player_controller
+            .reference_schema()?
+            .m_hPlayerPawn()?
+            .get_entity_index();
+
+        let eye_angles = match &*states.resolve::<PlayerPawnState>(local_pawn_index)? {
+            PlayerPawnState::Alive(info) => info.eye_angles,
+            _ => return Ok(None),
+        };
+
+        let view_origin = match states
+            .resolve::<ViewController>(())?
+            .get_camera_world_position()
+        {
+            Some(position) => position,
+            None => return Ok(None),
+        };
+        let view_direction = eye_direction(&eye_angles);
+
+        let target_pawn = states.resolve::<PlayerPawnState>(target_entity_id)?;
+        let target_pawn = match &*target_pawn {
+            PlayerPawnState::Alive(info) => info,
+            _ => return Ok(None),
+        };
+
+        let model = states.resolve::<CS2Model>(target_pawn.model_address)?;
+        let closest_bone = model
+            .bones
+            .iter()
+            .zip(target_pawn.bone_states.iter())
+            .filter_map(|(bone, state)| {
+                let group = HitboxGroup::from_bone_name(&bone.name)?;
+
+                let offset = state.position - view_origin;
+                let projection = offset.dot(&view_direction);
+                if projection < 0.0 {
+                    /* bone is behind the camera */
+                    return None;
+                }
+
+                let distance = (offset - view_direction * projection).norm();
+                Some((group, distance))
+            })
+            .min_by(|(_, a), (_, b)| a.total_cmp(b));
+
+        Ok(closest_bone.map(|(group, _)| group))
+    }
+
     fn read_crosshair_entity(&self, states: &StateRegistry) -> anyhow::Result<Option<u32>> {
         let offsets = states.resolve::<CS2Offsets>(())?;
         let cs2 = states.resolve::<CS2HandleState>(())?;
@@ -2,8 +2,8 @@ use std::time::Instant;
 
 use crate::{
     settings::{
+        ActivationMode,
         HotKey,
-        KeyToggleMode,
     },
     KeyboardInput,
 };
@@ -11,6 +11,13 @@ use crate::{
 pub struct KeyToggle {
     pub last_state_changed: Instant,
     pub enabled: bool,
+
+    /// Time of the first press of a potential double tap
+    last_press: Option<Instant>,
+
+    /// Start of the current long press.
+    /// Reset once the long press has been handled so releasing the key does nothing.
+    press_start: Option<Instant>,
 }
 
 impl KeyToggle {
@@ -18,27 +25,30 @@ impl KeyToggle {
         Self {
             enabled: false,
             last_state_changed: Instant::now(),
+
+            last_press: None,
+            press_start: None,
         }
     }
 
     pub fn update(
         &mut self,
-        mode: &KeyToggleMode,
+        mode: &ActivationMode,
         input: &dyn KeyboardInput,
         hotkey: &Option<HotKey>,
     ) -> bool {
         let new_state = match mode {
-            KeyToggleMode::AlwaysOn => true,
-            KeyToggleMode::Trigger | KeyToggleMode::TriggerInverted => {
+            ActivationMode::Always => true,
+            ActivationMode::Hold | ActivationMode::HoldInverted => {
                 if let Some(hotkey) = hotkey {
-                    input.is_key_down(hotkey.0) == (*mode == KeyToggleMode::Trigger)
+                    hotkey.is_down(input) == (*mode == ActivationMode::Hold)
                 } else {
                     false
                 }
             }
-            KeyToggleMode::Toggle => {
+            ActivationMode::Toggle => {
                 if let Some(hotkey) = hotkey {
-                    if input.is_key_pressed(hotkey.0, false) {
+                    if hotkey.is_pressed(input, false) {
                         if self.last_state_changed.elapsed().as_millis() > 250 {
                             self.last_state_changed = Instant::now();
                             !self.enabled
@@ -53,7 +63,52 @@ impl KeyToggle {
                     false
                 }
             }
-            KeyToggleMode::Off => false,
+            ActivationMode::DoubleTap => match hotkey {
+                Some(hotkey) => {
+                    if hotkey.is_pressed(input, false) {
+                        let now = input.now();
+                        match self.last_press.take() {
+                            Some(last_press)
+                                if now.saturating_duration_since(last_press)
+                                    <= input.double_tap_window() =>
+                            {
+                                !self.enabled
+                            }
+                            _ => {
+                                self.last_press = Some(now);
+                                self.enabled
+                            }
+                        }
+                    } else {
+                        self.enabled
+                    }
+                }
+                None => false,
+            },
+            ActivationMode::LongPress => match hotkey {
+                Some(hotkey) => {
+                    let now = input.now();
+                    if hotkey.is_pressed(input, false) {
+                        self.press_start = Some(now);
+                    } else if !hotkey.is_down(input) {
+                        /* released before the long press duration */
+                        self.press_start = None;
+                    }
+
+                    match self.press_start {
+                        Some(press_start)
+                            if now.saturating_duration_since(press_start)
+                                >= input.long_press_duration() =>
+                        {
+                            self.press_start = None;
+                            !self.enabled
+                        }
+                        _ => self.enabled,
+                    }
+                }
+                None => false,
+            },
+            ActivationMode::Off => false,
         };
 
         if self.enabled == new_state {
@@ -65,3 +120,234 @@ impl KeyToggle {
         true
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::{
+        Duration,
+        Instant,
+    };
+
+    use imgui::Key;
+
+    use super::KeyToggle;
+    use crate::{
+        settings::{
+            ActivationMode,
+            HotKey,
+        },
+        KeyboardInput,
+    };
+
+    #[derive(Default)]
+    struct TestInput {
+        down: Vec<Key>,
+        pressed: Vec<Key>,
+        time: Option<Instant>,
+    }
+
+    impl TestInput {
+        fn at(mut self, time: Instant) -> Self {
+            self.time = Some(time);
+            self
+        }
+    }
+
+    impl KeyboardInput for TestInput {
+        fn is_key_down(&self, key: Key) -> bool {
+            self.down.contains(&key)
+        }
+
+        fn is_key_pressed(&self, key: Key, _repeating: bool) -> bool {
+            self.pressed.contains(&key)
+        }
+
+        fn now(&self) -> Instant {
+            self.time.unwrap_or_else(Instant::now)
+        }
+    }
+
+    fn released() -> TestInput {
+        TestInput::default()
+    }
+
+    fn held(keys: &[Key]) -> TestInput {
+        TestInput {
+            down: keys.to_vec(),
+            ..Default::default()
+        }
+    }
+
+    fn pressed(keys: &[Key], key: Key) -> TestInput {
+        let mut down = keys.to_vec();
+        down.push(key);
+        TestInput {
+            down,
+            pressed: vec![key],
+            ..Default::default()
+        }
+    }
+
+    fn hotkey() -> Option<HotKey> {
+        Some(Key::F1.into())
+    }
+
+    /// Pretend the last state change happened long enough ago to accept the next press
+    fn expire_debounce(toggle: &mut KeyToggle) {
+        toggle.last_state_changed = Instant::now() - Duration::from_secs(1);
+    }
+
+    #[test]
+    fn always() {
+        let mut toggle = KeyToggle::new();
+        assert!(toggle.update(&ActivationMode::Always, &released(), &None));
+        assert!(toggle.enabled);
+
+        assert!(!toggle.update(&ActivationMode::Always, &pressed(&[], Key::F1), &hotkey()));
+        assert!(toggle.enabled);
+    }
+
+    #[test]
+    fn off() {
+        let mut toggle = KeyToggle::new();
+        assert!(!toggle.update(&ActivationMode::Off, &pressed(&[], Key::F1), &hotkey()));
+        assert!(!toggle.enabled);
+    }
+
+    #[test]
+    fn hold() {
+        let mut toggle = KeyToggle::new();
+        assert!(!toggle.update(&ActivationMode::Hold, &released(), &hotkey()));
+        assert!(!toggle.enabled);
+
+        assert!(toggle.update(&ActivationMode::Hold, &held(&[Key::F1]), &hotkey()));
+        assert!(toggle.enabled);
+
+        assert!(!toggle.update(&ActivationMode::Hold, &held(&[Key::F1]), &hotkey()));
+        assert!(toggle.enabled);
+
+        assert!(toggle.update(&ActivationMode::Hold, &released(), &hotkey()));
+        assert!(!toggle.enabled);
+    }
+
+    #[test]
+    fn hold_inverted() {
+        let mut toggle = KeyToggle::new();
+        assert!(toggle.update(&ActivationMode::HoldInverted, &released(), &hotkey()));
+        assert!(toggle.enabled);
+
+        assert!(toggle.update(&ActivationMode::HoldInverted, &held(&[Key::F1]), &hotkey()));
+        assert!(!toggle.enabled);
+
+        assert!(toggle.update(&ActivationMode::HoldInverted, &released(), &hotkey()));
+        assert!(toggle.enabled);
+    }
+
+    #[test]
+    fn hold_without_key() {
+        let mut toggle = KeyToggle::new();
+        assert!(!toggle.update(&ActivationMode::Hold, &held(&[Key::F1]), &None));
+        assert!(!toggle.update(&ActivationMode::HoldInverted, &released(), &None));
+        assert!(!toggle.enabled);
+    }
+
+    #[test]
+    fn toggle() {
+        let mut toggle = KeyToggle::new();
+        expire_debounce(&mut toggle);
+        assert!(toggle.update(&ActivationMode::Toggle, &pressed(&[], Key::F1), &hotkey()));
+        assert!(toggle.enabled);
+
+        /* holding the key must not toggle again */
+        expire_debounce(&mut toggle);
+        assert!(!toggle.update(&ActivationMode::Toggle, &held(&[Key::F1]), &hotkey()));
+        assert!(!toggle.update(&ActivationMode::Toggle, &released(), &hotkey()));
+        assert!(toggle.enabled);
+
+        /* presses shortly after the last state change are ignored */
+        toggle.last_state_changed = Instant::now();
+        assert!(!toggle.update(&ActivationMode::Toggle, &pressed(&[], Key::F1), &hotkey()));
+        assert!(toggle.enabled);
+
+        expire_debounce(&mut toggle);
+        assert!(toggle.update(&ActivationMode::Toggle, &pressed(&[], Key::F1), &hotkey()));
+        assert!(!toggle.enabled);
+    }
+
+    #[test]
+    fn double_tap() {
+        let start = Instant::now();
+        let after = |millis: u64| start + Duration::from_millis(millis);
+        let mode = ActivationMode::DoubleTap;
+
+        let mut toggle = KeyToggle::new();
+        assert!(!toggle.update(&mode, &pressed(&[], Key::F1).at(after(0)), &hotkey()));
+        assert!(!toggle.update(&mode, &released().at(after(100)), &hotkey()));
+        assert!(toggle.update(&mode, &pressed(&[], Key::F1).at(after(200)), &hotkey()));
+        assert!(toggle.enabled);
+
+        /* a third press starts a new double tap */
+        assert!(!toggle.update(&mode, &pressed(&[], Key::F1).at(after(300)), &hotkey()));
+        assert!(toggle.enabled);
+
+        /* the second press is outside of the double tap window */
+        assert!(!toggle.update(&mode, &pressed(&[], Key::F1).at(after(1000)), &hotkey()));
+        assert!(toggle.enabled);
+
+        assert!(toggle.update(&mode, &pressed(&[], Key::F1).at(after(1100)), &hotkey()));
+        assert!(!toggle.enabled);
+    }
+
+    #[test]
+    fn long_press() {
+        let start = Instant::now();
+        let after = |millis: u64| start + Duration::from_millis(millis);
+        let mode = ActivationMode::LongPress;
+
+        let mut toggle = KeyToggle::new();
+
+        /* a quick tap does nothing */
+        assert!(!toggle.update(&mode, &pressed(&[], Key::F1).at(after(0)), &hotkey()));
+        assert!(!toggle.update(&mode, &released().at(after(100)), &hotkey()));
+        assert!(!toggle.update(&mode, &held(&[Key::F1]).at(after(1000)), &hotkey()));
+        assert!(!toggle.enabled);
+
+        assert!(!toggle.update(&mode, &pressed(&[], Key::F1).at(after(2000)), &hotkey()));
+        assert!(!toggle.update(&mode, &held(&[Key::F1]).at(after(2400)), &hotkey()));
+        assert!(toggle.update(&mode, &held(&[Key::F1]).at(after(2500)), &hotkey()));
+        assert!(toggle.enabled);
+
+        /* holding the key longer or releasing it must not toggle again */
+        assert!(!toggle.update(&mode, &held(&[Key::F1]).at(after(3500)), &hotkey()));
+        assert!(!toggle.update(&mode, &released().at(after(3600)), &hotkey()));
+        assert!(toggle.enabled);
+
+        assert!(!toggle.update(&mode, &pressed(&[], Key::F1).at(after(4000)), &hotkey()));
+        assert!(toggle.update(&mode, &held(&[Key::F1]).at(after(4500)), &hotkey()));
+        assert!(!toggle.enabled);
+    }
+
+    #[test]
+    fn key_combination() {
+        let hotkey = Some(HotKey::new(Key::X, vec![Key::LeftAlt]));
+
+        let mut toggle = KeyToggle::new();
+        assert!(!toggle.update(&ActivationMode::Hold, &held(&[Key::X]), &hotkey));
+        assert!(toggle.update(
+            &ActivationMode::Hold,
+            &held(&[Key::LeftAlt, Key::X]),
+            &hotkey
+        ));
+        assert!(toggle.update(&ActivationMode::Hold, &held(&[Key::LeftAlt]), &hotkey));
+
+        let mut toggle = KeyToggle::new();
+        expire_debounce(&mut toggle);
+        assert!(!toggle.update(&ActivationMode::Toggle, &pressed(&[], Key::X), &hotkey));
+        assert!(toggle.update(
+            &ActivationMode::Toggle,
+            &pressed(&[Key::LeftAlt], Key::X),
+            &hotkey
+        ));
+        assert!(toggle.enabled);
+    }
+}
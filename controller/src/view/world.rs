@@ -3,6 +3,9 @@ use cs2::{
     CS2Offsets,
 };
 use imgui::ImColor32;
+
+/// Screen height the ESP sizes have been designed for
+const UI_SCALE_REFERENCE_HEIGHT: f32 = 1080.0;
 use utils_state::{
     State,
     StateCacheType,
@@ -13,6 +16,12 @@ use utils_state::{
 /// coordinates into 2d screen coordinates.
 pub struct ViewController {
     view_matrix: nalgebra::Matrix4<f32>,
+
+    /// Position of the game viewport within the overlay.
+    /// Non zero if the overlay is larger than the CS2 window.
+    pub screen_offset: mint::Vector2<f32>,
+
+    /// Size of the game viewport
     pub screen_bounds: mint::Vector2<f32>,
 }
 
@@ -22,6 +31,7 @@ impl State for ViewController {
     fn create(_states: &StateRegistry, _param: Self::Parameter) -> anyhow::Result<Self> {
         Ok(Self {
             view_matrix: Default::default(),
+            screen_offset: mint::Vector2 { x: 0.0, y: 0.0 },
             screen_bounds: mint::Vector2 { x: 0.0, y: 0.0 },
         })
     }
@@ -40,10 +50,40 @@ impl State for ViewController {
 }
 
 impl ViewController {
-    pub fn update_screen_bounds(&mut self, bounds: mint::Vector2<f32>) {
+    pub fn update_screen_viewport(
+        &mut self,
+        offset: mint::Vector2<f32>,
+        bounds: mint::Vector2<f32>,
+    ) {
+        self.screen_offset = offset;
         self.screen_bounds = bounds;
     }
 
+    /// Resolve a position relative to the game viewport (0.0 - 1.0) into overlay coordinates
+    pub fn screen_position(&self, relative_x: f32, relative_y: f32) -> [f32; 2] {
+        [
+            self.screen_offset.x + self.screen_bounds.x * relative_x,
+            self.screen_offset.y + self.screen_bounds.y * relative_y,
+        ]
+    }
+
+    /// Scale for line widths, radii and spacings relative to a 1080p screen.
+    /// A positive override will be used instead of the resolution based scale.
+    pub fn ui_scale(&self, scale_override: f32) -> f32 {
+        if scale_override > 0.0 {
+            return scale_override;
+        }
+
+        if self.screen_bounds.y <= 0.0 {
+            return 1.0;
+        }
+
+        self.screen_bounds.y / UI_SCALE_REFERENCE_HEIGHT
+    }
+
+    /// Position of the camera the game currently renders from.
+    /// The view matrix always belongs to the active camera, therefore this also
+    /// covers observer cameras (dead, free-look, chase and GOTV).
     pub fn get_camera_world_position(&self) -> Option<nalgebra::Vector3<f32>> {
         let view_matrix = self.view_matrix;
         let a = view_matrix.m22 * view_matrix.m33 - view_matrix.m32 * view_matrix.m23;
@@ -69,6 +109,18 @@ impl ViewController {
         Some(nalgebra::Vector3::new(-x, -y, -z))
     }
 
+    /// Yaw (in degrees) of the camera view direction
+    pub fn get_camera_yaw(&self) -> Option<f32> {
+        /* the last column of the view matrix contains the forward axis of the camera */
+        let forward_x = self.view_matrix[(0, 3)];
+        let forward_y = self.view_matrix[(1, 3)];
+        if forward_x.abs() < 0.0001 && forward_y.abs() < 0.0001 {
+            return None;
+        }
+
+        Some(forward_y.atan2(forward_x).to_degrees())
+    }
+
     /// Returning an mint::Vector2<f32> as the result should be used via ImGui.
     pub fn world_to_screen(
         &self,
@@ -78,6 +130,32 @@ impl ViewController {
         let screen_coords =
             nalgebra::Vector4::new(vec.x, vec.y, vec.z, 1.0).transpose() * self.view_matrix;
 
+        self.clip_to_screen(&screen_coords.transpose(), allow_of_screen)
+    }
+
+    /// Project multiple world positions at once.
+    /// The result contains the screen position for every input position (see [Self::world_to_screen]).
+    pub fn project_batch(
+        &self,
+        positions: &[nalgebra::Vector3<f32>],
+        allow_of_screen: bool,
+    ) -> Vec<Option<mint::Vector2<f32>>> {
+        /* the view matrix is applied to row vectors */
+        let projection = self.view_matrix.transpose();
+        positions
+            .iter()
+            .map(|position| {
+                let screen_coords = projection * position.push(1.0);
+                self.clip_to_screen(&screen_coords, allow_of_screen)
+            })
+            .collect()
+    }
+
+    fn clip_to_screen(
+        &self,
+        screen_coords: &nalgebra::Vector4<f32>,
+        allow_of_screen: bool,
+    ) -> Option<mint::Vector2<f32>> {
         if screen_coords.w < 0.1 {
             return None;
         }
@@ -95,18 +173,81 @@ impl ViewController {
             screen_coords.x / screen_coords.w,
             screen_coords.y / screen_coords.w,
         ]);
-        screen_pos.x = (screen_pos.x + 1.0) * self.screen_bounds.x / 2.0;
-        screen_pos.y = (-screen_pos.y + 1.0) * self.screen_bounds.y / 2.0;
+        screen_pos.x = self.screen_offset.x + (screen_pos.x + 1.0) * self.screen_bounds.x / 2.0;
+        screen_pos.y = self.screen_offset.y + (-screen_pos.y + 1.0) * self.screen_bounds.y / 2.0;
         Some(screen_pos)
     }
 
+    /// Convert an angle offset (pitch and yaw in degrees) relative to the current view direction
+    /// into a screen space offset (in pixels) from the screen center.
+    /// Positive pitch points downwards, positive yaw to the left.
+    pub fn angles_to_screen_delta(&self, pitch: f32, yaw: f32) -> Option<mint::Vector2<f32>> {
+        /*
+         * The view matrix contains the camera axes scaled by the projection.
+         * As the camera axes are unit vectors, the projection scale is the ratio
+         * between the horizontal/vertical axis and the forward (w) axis.
+         */
+        let forward_length = self.view_matrix.fixed_view::<3, 1>(0, 3).norm();
+        if forward_length < 0.0001 {
+            return None;
+        }
+
+        let scale_x = self.view_matrix.fixed_view::<3, 1>(0, 0).norm() / forward_length;
+        let scale_y = self.view_matrix.fixed_view::<3, 1>(0, 1).norm() / forward_length;
+
+        Some(mint::Vector2 {
+            x: -yaw.to_radians().tan() * scale_x * self.screen_bounds.x / 2.0,
+            y: pitch.to_radians().tan() * scale_y * self.screen_bounds.y / 2.0,
+        })
+    }
+
+    /// Project the world position onto the screen and clamp the result to the screen edges.
+    /// The screen edges will be shrunk by `edge_offset` pixels.
+    ///
+    /// Returns the clamped screen position and the angle (in radians) pointing from the screen center towards the target.
+    /// Positions behind the camera will be projected onto the edge they're closest to.
+    pub fn world_to_screen_clamped(
+        &self,
+        vec: &nalgebra::Vector3<f32>,
+        edge_offset: f32,
+    ) -> Option<(mint::Vector2<f32>, f32)> {
+        let screen_coords =
+            nalgebra::Vector4::new(vec.x, vec.y, vec.z, 1.0).transpose() * self.view_matrix;
+
+        /*
+         * Dividing by w does not change the direction for positions in front of the camera.
+         * For positions behind the camera the raw clip coordinates still point towards the correct side.
+         */
+        let mut direction = nalgebra::Vector2::new(
+            screen_coords.x * self.screen_bounds.x / 2.0,
+            -screen_coords.y * self.screen_bounds.y / 2.0,
+        );
+        if direction.norm() < 0.0001 {
+            /* target is directly behind us */
+            direction = nalgebra::Vector2::new(0.0, 1.0);
+        }
+
+        let half_width = self.screen_bounds.x / 2.0 - edge_offset;
+        let half_height = self.screen_bounds.y / 2.0 - edge_offset;
+        if half_width <= 0.0 || half_height <= 0.0 {
+            return None;
+        }
+
+        let scale = (half_width / direction.x.abs()).min(half_height / direction.y.abs());
+        let screen_pos = mint::Vector2 {
+            x: self.screen_offset.x + self.screen_bounds.x / 2.0 + direction.x * scale,
+            y: self.screen_offset.y + self.screen_bounds.y / 2.0 + direction.y * scale,
+        };
+
+        Some((screen_pos, direction.y.atan2(direction.x)))
+    }
+
     pub fn calculate_box_2d(
         &self,
         vmin: &nalgebra::Vector3<f32>,
         vmax: &nalgebra::Vector3<f32>,
     ) -> Option<(nalgebra::Vector2<f32>, nalgebra::Vector2<f32>)> {
         type Vec3 = nalgebra::Vector3<f32>;
-        type Vec2 = nalgebra::Vector2<f32>;
 
         let points = [
             /* bottom */
@@ -121,11 +262,22 @@ impl ViewController {
             Vec3::new(vmax.x, vmax.y, vmax.z),
         ];
 
+        self.calculate_points_box_2d(points.iter())
+    }
+
+    /// Calculate the screen space bounding box of all given world points.
+    /// Points which are not on the screen will be ignored.
+    pub fn calculate_points_box_2d<'a>(
+        &self,
+        points: impl Iterator<Item = &'a nalgebra::Vector3<f32>>,
+    ) -> Option<(nalgebra::Vector2<f32>, nalgebra::Vector2<f32>)> {
+        type Vec2 = nalgebra::Vector2<f32>;
+
         let mut min2d = Vec2::new(f32::MAX, f32::MAX);
         let mut max2d = Vec2::new(-f32::MAX, -f32::MAX);
 
         for point in points {
-            if let Some(point) = self.world_to_screen(&point, true) {
+            if let Some(point) = self.world_to_screen(point, true) {
                 min2d.x = min2d.x.min(point.x);
                 min2d.y = min2d.y.min(point.y);
 
@@ -152,6 +304,105 @@ impl ViewController {
         vmax: &nalgebra::Vector3<f32>,
         color: ImColor32,
         thickness: f32,
+    ) {
+        self.draw_box_3d_oriented(
+            draw,
+            &nalgebra::Isometry3::identity(),
+            vmin,
+            vmax,
+            color,
+            thickness,
+        );
+    }
+
+    /// Fill the faces of the 3D box (vmin, vmax).
+    /// Faces with corners behind the camera will be skipped as they can not be projected.
+    pub fn fill_box_3d(
+        &self,
+        draw: &imgui::DrawListMut,
+        vmin: &nalgebra::Vector3<f32>,
+        vmax: &nalgebra::Vector3<f32>,
+        color: ImColor32,
+    ) {
+        type Vec3 = nalgebra::Vector3<f32>;
+
+        let faces = [
+            /* bottom & top */
+            [
+                Vec3::new(vmin.x, vmin.y, vmin.z),
+                Vec3::new(vmax.x, vmin.y, vmin.z),
+                Vec3::new(vmax.x, vmax.y, vmin.z),
+                Vec3::new(vmin.x, vmax.y, vmin.z),
+            ],
+            [
+                Vec3::new(vmin.x, vmin.y, vmax.z),
+                Vec3::new(vmax.x, vmin.y, vmax.z),
+                Vec3::new(vmax.x, vmax.y, vmax.z),
+                Vec3::new(vmin.x, vmax.y, vmax.z),
+            ],
+            /* sides */
+            [
+                Vec3::new(vmin.x, vmin.y, vmin.z),
+                Vec3::new(vmax.x, vmin.y, vmin.z),
+                Vec3::new(vmax.x, vmin.y, vmax.z),
+                Vec3::new(vmin.x, vmin.y, vmax.z),
+            ],
+            [
+                Vec3::new(vmin.x, vmax.y, vmin.z),
+                Vec3::new(vmax.x, vmax.y, vmin.z),
+                Vec3::new(vmax.x, vmax.y, vmax.z),
+                Vec3::new(vmin.x, vmax.y, vmax.z),
+            ],
+            [
+                Vec3::new(vmin.x, vmin.y, vmin.z),
+                Vec3::new(vmin.x, vmax.y, vmin.z),
+                Vec3::new(vmin.x, vmax.y, vmax.z),
+                Vec3::new(vmin.x, vmin.y, vmax.z),
+            ],
+            [
+                Vec3::new(vmax.x, vmin.y, vmin.z),
+                Vec3::new(vmax.x, vmax.y, vmin.z),
+                Vec3::new(vmax.x, vmax.y, vmax.z),
+                Vec3::new(vmax.x, vmin.y, vmax.z),
+            ],
+        ];
+
+        let mut faces = faces
+            .iter()
+            .filter_map(|face| {
+                let mut points = Vec::with_capacity(face.len());
+                for corner in face.iter() {
+                    points.push(self.world_to_screen(corner, true)?);
+                }
+
+                let center = face.iter().sum::<Vec3>() / face.len() as f32;
+                Some((center, points))
+            })
+            .collect::<Vec<_>>();
+
+        /* render the farthest faces first */
+        if let Some(camera_position) = self.get_camera_world_position() {
+            faces.sort_by(|(a, _), (b, _)| {
+                let a = (a - camera_position).norm_squared();
+                let b = (b - camera_position).norm_squared();
+                b.total_cmp(&a)
+            });
+        }
+
+        for (_, points) in faces {
+            draw.add_polyline(points, color).filled(true).build();
+        }
+    }
+
+    /// Draw a box which bounds (vmin, vmax) are given in the local space of transform
+    pub fn draw_box_3d_oriented(
+        &self,
+        draw: &imgui::DrawListMut,
+        transform: &nalgebra::Isometry3<f32>,
+        vmin: &nalgebra::Vector3<f32>,
+        vmax: &nalgebra::Vector3<f32>,
+        color: ImColor32,
+        thickness: f32,
     ) {
         type Vec3 = nalgebra::Vector3<f32>;
 
@@ -210,6 +461,8 @@ impl ViewController {
         ];
 
         for (start, end) in lines {
+            let start = transform.transform_point(&start.into()).coords;
+            let end = transform.transform_point(&end.into()).coords;
             if let (Some(start), Some(end)) = (
                 self.world_to_screen(&start, true),
                 self.world_to_screen(&end, true),
@@ -221,3 +474,58 @@ impl ViewController {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::ViewController;
+
+    fn test_view() -> ViewController {
+        let projection = nalgebra::Perspective3::new(16.0 / 9.0, 1.3, 0.1, 10_000.0);
+        ViewController {
+            /* the view matrix is applied to row vectors */
+            view_matrix: projection.to_homogeneous().transpose(),
+            screen_offset: mint::Vector2 { x: 0.0, y: 0.0 },
+            screen_bounds: mint::Vector2 {
+                x: 1920.0,
+                y: 1080.0,
+            },
+        }
+    }
+
+    fn test_positions(count: usize) -> Vec<nalgebra::Vector3<f32>> {
+        (0..count)
+            .map(|index| {
+                let index = index as f32;
+                nalgebra::Vector3::new(
+                    (index * 7.3) % 400.0 - 200.0,
+                    (index * 3.1) % 200.0 - 100.0,
+                    /* includes positions behind the camera */
+                    50.0 - (index * 11.7) % 1000.0,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn project_batch_matches_single() {
+        let view = test_view();
+        let positions = test_positions(256);
+
+        for allow_of_screen in [true, false] {
+            let batch = view.project_batch(&positions, allow_of_screen);
+            assert_eq!(batch.len(), positions.len());
+
+            for (position, projected) in positions.iter().zip(batch) {
+                let expected = view.world_to_screen(position, allow_of_screen);
+                match (expected, projected) {
+                    (None, None) => {}
+                    (Some(expected), Some(projected)) => {
+                        assert!((expected.x - projected.x).abs() < 0.01);
+                        assert!((expected.y - projected.y).abs() < 0.01);
+                    }
+                    _ => panic!("projection mismatch for {:?}", position),
+                }
+            }
+        }
+    }
+}
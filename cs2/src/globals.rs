@@ -15,6 +15,9 @@ use crate::{
     CS2Offsets,
 };
 
+/// Duration of a server tick in seconds
+pub const TICK_INTERVAL: f32 = 1.0 / 64.0;
+
 define_schema! {
     pub struct EngineBuildInfo[0x28] {
         pub revision: PtrCStr = 0x00,
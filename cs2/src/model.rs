@@ -46,10 +46,20 @@ pub struct Bone {
     pub parent: Option<usize>,
 }
 
+/// Hit volume attached to a bone, in the bone space
+#[derive(Debug, Clone, Copy)]
+pub struct Hitbox {
+    pub bone: usize,
+    pub min: nalgebra::Vector3<f32>,
+    pub max: nalgebra::Vector3<f32>,
+    pub radius: f32,
+}
+
 #[derive(Debug, Default)]
 pub struct CS2Model {
     pub name: String,
     pub bones: Vec<Bone>,
+    pub hitboxes: Vec<Hitbox>,
 
     pub vhull_min: nalgebra::Vector3<f32>,
     pub vhull_max: nalgebra::Vector3<f32>,
@@ -83,6 +93,16 @@ impl State for CS2Model {
 }
 
 impl CS2Model {
+    /// Find the index of a bone by its name
+    pub fn find_bone(&self, name: &str) -> Option<usize> {
+        self.bones.iter().position(|bone| bone.name == name)
+    }
+
+    /// Find the hitbox attached to the bone
+    pub fn find_hitbox(&self, bone: usize) -> Option<&Hitbox> {
+        self.hitboxes.iter().find(|hitbox| hitbox.bone == bone)
+    }
+
     fn do_read(&mut self, cs2: &CS2Handle, address: u64) -> anyhow::Result<()> {
         [
             self.vhull_min,
@@ -140,8 +160,88 @@ impl CS2Model {
                 flags,
             });
         }
+
+        self.hitboxes = match self.read_hitboxes(cs2, address) {
+            Ok(hitboxes) => hitboxes,
+            Err(error) => {
+                log::warn!(
+                    "{} {}: {:#}",
+                    obfstr!("Failed to read hitboxes of"),
+                    self.name,
+                    error
+                );
+                Vec::new()
+            }
+        };
         Ok(())
     }
+
+    /// Read the hitboxes of the default hitbox set
+    fn read_hitboxes(&self, cs2: &CS2Handle, address: u64) -> anyhow::Result<Vec<Hitbox>> {
+        /* struct layouts according to the CHitBoxSet and CHitBox schema */
+        const HITBOX_SET_HITBOXES: u64 = 0x10;
+        const HITBOX_SIZE: u64 = 0x70;
+        const HITBOX_BONE_NAME: u64 = 0x10;
+        const HITBOX_BOUNDS: u64 = 0x18;
+        const HITBOX_RADIUS: u64 = 0x30;
+
+        let render_mesh_count =
+            cs2.read_sized::<u32>(&[address + offsets_manual::client::CModel::RENDER_MESHES])?;
+        if render_mesh_count == 0 {
+            return Ok(Vec::new());
+        }
+
+        let render_mesh = cs2.read_sized::<u64>(&[
+            address + offsets_manual::client::CModel::RENDER_MESHES + 0x08,
+            0,
+        ])?;
+
+        let hitbox_set_count = cs2
+            .read_sized::<u32>(&[render_mesh + offsets_manual::client::CRenderMesh::HITBOX_SETS])?;
+        if hitbox_set_count == 0 {
+            return Ok(Vec::new());
+        }
+
+        let hitbox_set = cs2.read_sized::<u64>(&[render_mesh
+            + offsets_manual::client::CRenderMesh::HITBOX_SETS
+            + 0x08])?;
+
+        let hitbox_count = cs2.read_sized::<u32>(&[hitbox_set + HITBOX_SET_HITBOXES])? as usize;
+        if hitbox_count > 256 {
+            anyhow::bail!(
+                "{} ({})",
+                obfstr!("model contains too many hitboxes"),
+                hitbox_count
+            );
+        }
+
+        log::trace!("Reading {} hitboxes", hitbox_count);
+        let hitbox_base = cs2.read_sized::<u64>(&[hitbox_set + HITBOX_SET_HITBOXES + 0x08])?;
+
+        let mut hitboxes = Vec::with_capacity(hitbox_count);
+        for hitbox_index in 0..hitbox_count {
+            let hitbox_address = hitbox_base + HITBOX_SIZE * hitbox_index as u64;
+
+            let bone_name = cs2.read_string(&[hitbox_address + HITBOX_BONE_NAME, 0], None)?;
+            let bone = match self.find_bone(&bone_name) {
+                Some(bone) => bone,
+                None => continue,
+            };
+
+            let [min, max] =
+                cs2.read_sized::<[nalgebra::Vector3<f32>; 2]>(&[hitbox_address + HITBOX_BOUNDS])?;
+            let radius = cs2.read_sized::<f32>(&[hitbox_address + HITBOX_RADIUS])?;
+
+            hitboxes.push(Hitbox {
+                bone,
+                min,
+                max,
+                radius,
+            });
+        }
+
+        Ok(hitboxes)
+    }
 }
 
 impl Drop for CS2Model {
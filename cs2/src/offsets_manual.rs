@@ -13,5 +13,14 @@ pub mod client {
 
         /* UC sig does not work. Offset is array of u16 */
         pub const BONE_PARENT: u64 = 0x180;
+
+        /* Offset is CUtlVector<*const CRenderMesh> */
+        pub const RENDER_MESHES: u64 = 0x78;
+    }
+
+    #[allow(non_snake_case)]
+    pub mod CRenderMesh {
+        /* Offset is CHitBoxSetList */
+        pub const HITBOX_SETS: u64 = 0x130;
     }
 }
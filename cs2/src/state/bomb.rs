@@ -1,7 +1,11 @@
 use std::ffi::CStr;
 
 use anyhow::Context;
-use cs2_schema_generated::cs2::client::C_PlantedC4;
+use cs2_schema_generated::cs2::client::{
+    CCSPlayer_ItemServices,
+    C_PlantedC4,
+    C_C4,
+};
 use obfstr::obfstr;
 use utils_state::{
     State,
@@ -23,6 +27,9 @@ pub struct BombDefuser {
 
     /// The defusers player name
     pub player_name: String,
+
+    /// The defuser is using a defuse kit
+    pub has_kit: bool,
 }
 
 #[derive(Debug)]
@@ -55,6 +62,12 @@ pub struct PlantedC4 {
 
     /// Current bomb defuser
     pub defuser: Option<BombDefuser>,
+
+    /// World position of the planted C4
+    pub position: nalgebra::Vector3<f32>,
+
+    /// Total time (in seconds) from planting until detonation
+    pub timer_length: f32,
 }
 
 impl State for PlantedC4 {
@@ -89,11 +102,20 @@ impl State for PlantedC4 {
             }
 
             let bomb_site = bomb.m_nBombSite()? as u8;
+            let position = nalgebra::Vector3::<f32>::from_column_slice(
+                &bomb
+                    .m_pGameSceneNode()?
+                    .reference_schema()?
+                    .m_vecAbsOrigin()?,
+            );
+            let timer_length = bomb.m_flTimerLength()?;
             if bomb.m_bBombDefused()? {
                 return Ok(Self {
                     bomb_site,
                     defuser: None,
                     state: PlantedC4State::Defused,
+                    position,
+                    timer_length,
                 });
             }
 
@@ -104,6 +126,8 @@ impl State for PlantedC4 {
                     bomb_site,
                     defuser: None,
                     state: PlantedC4State::Detonated,
+                    position,
+                    timer_length,
                 });
             }
 
@@ -118,6 +142,12 @@ impl State for PlantedC4 {
                     .entity()?
                     .reference_schema()?;
 
+                let has_kit = defuser
+                    .m_pItemServices()?
+                    .cast::<CCSPlayer_ItemServices>()
+                    .reference_schema()?
+                    .m_bHasDefuser()?;
+
                 let defuser_controller = defuser.m_hController()?;
                 let defuser_controller = entities
                     .get_by_handle(&defuser_controller)?
@@ -135,6 +165,7 @@ impl State for PlantedC4 {
                 Some(BombDefuser {
                     time_remaining: time_defuse - globals.time_2()?,
                     player_name: defuser_name,
+                    has_kit,
                 })
             } else {
                 None
@@ -146,6 +177,8 @@ impl State for PlantedC4 {
                 state: PlantedC4State::Active {
                     time_detonation: time_blow - globals.time_2()?,
                 },
+                position,
+                timer_length,
             });
         }
 
@@ -153,6 +186,8 @@ impl State for PlantedC4 {
             bomb_site: 0,
             defuser: None,
             state: PlantedC4State::NotPlanted,
+            position: Default::default(),
+            timer_length: 0.0,
         });
     }
 
@@ -160,3 +195,60 @@ impl State for PlantedC4 {
         StateCacheType::Volatile
     }
 }
+
+/// The C4 lying on the ground
+pub struct DroppedC4 {
+    /// World position of the dropped C4.
+    /// None if the C4 is carried by a player or has been planted.
+    pub position: Option<nalgebra::Vector3<f32>>,
+}
+
+impl State for DroppedC4 {
+    type Parameter = ();
+
+    fn create(states: &StateRegistry, _param: Self::Parameter) -> anyhow::Result<Self> {
+        let entities = states.resolve::<EntitySystem>(())?;
+        let class_name_cache = states.resolve::<ClassNameCache>(())?;
+
+        for entity_identity in entities.all_identities().iter() {
+            let class_name = class_name_cache
+                .lookup(&entity_identity.entity_class_info()?)
+                .context("class name")?;
+
+            if !class_name.map(|name| name == "C_C4").unwrap_or(false) {
+                continue;
+            }
+
+            let bomb = entity_identity
+                .entity_ptr::<C_C4>()?
+                .read_schema()
+                .context("bomb schema")?;
+
+            if bomb.m_bBombPlanted()? {
+                continue;
+            }
+
+            let owner = bomb.m_hOwnerEntity()?;
+            if owner.is_valid() && entities.get_by_handle(&owner)?.is_some() {
+                /* bomb is carried by a player */
+                continue;
+            }
+
+            let position = nalgebra::Vector3::<f32>::from_column_slice(
+                &bomb
+                    .m_pGameSceneNode()?
+                    .reference_schema()?
+                    .m_vecAbsOrigin()?,
+            );
+            return Ok(Self {
+                position: Some(position),
+            });
+        }
+
+        Ok(Self { position: None })
+    }
+
+    fn cache_type() -> StateCacheType {
+        StateCacheType::Volatile
+    }
+}
@@ -0,0 +1,249 @@
+use std::ffi::CStr;
+
+use anyhow::Context;
+use cs2_schema_generated::{
+    cs2::client::{
+        CCSPlayerController,
+        C_CSPlayerPawn,
+    },
+    EntityHandle,
+};
+use obfstr::obfstr;
+use utils_state::{
+    State,
+    StateCacheType,
+    StateRegistry,
+};
+
+use crate::EntitySystem;
+
+const SKILL_GROUP_NAMES: [&str; 18] = [
+    "S1", "S2", "S3", "S4", "SE", "SEM", "GN1", "GN2", "GN3", "GNM", "MG1", "MG2", "MGE", "DMG",
+    "LE", "LEM", "SMFC", "GE",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PlayerRank {
+    /// Player has no rank (e.g. bots or not enough wins)
+    Unranked,
+
+    /// Premier CS rating
+    Premier { rating: i32 },
+
+    /// Legacy skill group (competitive or wingman)
+    SkillGroup { group: i32 },
+
+    /// Rank type we do not know how to decode
+    Unknown,
+}
+
+impl PlayerRank {
+    pub fn from_raw(rank_type: i8, ranking: i32) -> Self {
+        if ranking <= 0 {
+            return Self::Unranked;
+        }
+
+        match rank_type {
+            11 => Self::Premier { rating: ranking },
+            6 | 7 | 12 => Self::SkillGroup { group: ranking },
+            _ => Self::Unknown,
+        }
+    }
+
+    /// Short human readable representation of the rank.
+    /// Returns None if the player is unranked.
+    pub fn display_name(&self) -> Option<String> {
+        match self {
+            Self::Unranked => None,
+            Self::Premier { rating } => {
+                let digits = rating.to_string();
+                let mut result = String::with_capacity(digits.len() + digits.len() / 3);
+                for (index, digit) in digits.chars().enumerate() {
+                    if index > 0 && (digits.len() - index) % 3 == 0 {
+                        result.push(',');
+                    }
+                    result.push(digit);
+                }
+                Some(result)
+            }
+            Self::SkillGroup { group } => Some(
+                SKILL_GROUP_NAMES
+                    .get((*group as usize).wrapping_sub(1))
+                    .unwrap_or(&"unknown")
+                    .to_string(),
+            ),
+            Self::Unknown => Some("unknown".to_string()),
+        }
+    }
+}
+
+/// Information about the player controller which controls a player pawn
+#[derive(Debug, Clone)]
+pub struct PlayerControllerInfo {
+    pub controller_entity_id: u32,
+
+    /// Current in game money.
+    /// None if the controller has no money services.
+    pub player_money: Option<i32>,
+
+    /// Current ping in milliseconds.
+    /// Bots always have a ping of zero.
+    pub player_ping: u32,
+
+    pub player_rank: PlayerRank,
+
+    /// The player is controlled by a bot
+    pub is_bot: bool,
+
+    /// SteamID64 of the player. Zero for bots.
+    pub steam_id: u64,
+}
+
+impl State for PlayerControllerInfo {
+    type Parameter = u32;
+
+    fn create(states: &StateRegistry, pawn_entity_index: Self::Parameter) -> anyhow::Result<Self> {
+        let entities = states.resolve::<EntitySystem>(())?;
+
+        let player_pawn = entities
+            .get_by_handle::<C_CSPlayerPawn>(&EntityHandle::from_index(pawn_entity_index))?
+            .with_context(|| obfstr!("missing player pawn").to_string())?
+            .entity()?
+            .reference_schema()?;
+
+        let controller_entity_id = player_pawn.m_hController()?.get_entity_index();
+        let player_controller = entities
+            .get_by_handle::<CCSPlayerController>(&EntityHandle::from_index(controller_entity_id))?
+            .with_context(|| obfstr!("missing player controller").to_string())?
+            .entity()?
+            .reference_schema()?;
+
+        let player_money = match player_controller
+            .m_pInGameMoneyServices()?
+            .try_reference_schema()?
+        {
+            Some(money_services) => Some(money_services.m_iAccount()?),
+            None => None,
+        };
+
+        let steam_id = player_controller.m_steamID()?;
+        Ok(Self {
+            controller_entity_id,
+            player_money,
+            player_ping: player_controller.m_iPing()?,
+            player_rank: PlayerRank::from_raw(
+                player_controller.m_iCompetitiveRankType()?,
+                player_controller.m_iCompetitiveRanking()?,
+            ),
+            /* bots do not have a steam id */
+            is_bot: steam_id == 0,
+            steam_id,
+        })
+    }
+
+    fn cache_type() -> StateCacheType {
+        StateCacheType::Volatile
+    }
+}
+
+/// Match statistics of a player controller
+#[derive(Debug, Clone)]
+pub struct PlayerMatchStats {
+    pub kills: i32,
+    pub deaths: i32,
+    pub assists: i32,
+}
+
+impl State for PlayerMatchStats {
+    type Parameter = u32;
+
+    fn create(
+        states: &StateRegistry,
+        controller_entity_index: Self::Parameter,
+    ) -> anyhow::Result<Self> {
+        let entities = states.resolve::<EntitySystem>(())?;
+
+        let player_controller = entities
+            .get_by_handle::<CCSPlayerController>(&EntityHandle::from_index(
+                controller_entity_index,
+            ))?
+            .with_context(|| obfstr!("missing player controller").to_string())?
+            .entity()?
+            .reference_schema()?;
+
+        let match_stats = player_controller
+            .m_pActionTrackingServices()?
+            .try_reference_schema()?
+            .with_context(|| obfstr!("missing action tracking services").to_string())?
+            .m_matchStats()?;
+
+        Ok(Self {
+            kills: match_stats.m_iKills()?,
+            deaths: match_stats.m_iDeaths()?,
+            assists: match_stats.m_iAssists()?,
+        })
+    }
+
+    fn cache_type() -> StateCacheType {
+        StateCacheType::Volatile
+    }
+}
+
+/// Economy of a player controller
+#[derive(Debug, Clone)]
+pub struct PlayerEconomy {
+    pub team_id: u8,
+    pub player_name: String,
+
+    /// Current in game money
+    pub money: i32,
+
+    /// Money at the beginning of the current round
+    pub start_money: i32,
+
+    /// Money spent within the current round
+    pub cash_spent_this_round: i32,
+}
+
+impl State for PlayerEconomy {
+    type Parameter = u32;
+
+    fn create(
+        states: &StateRegistry,
+        controller_entity_index: Self::Parameter,
+    ) -> anyhow::Result<Self> {
+        let entities = states.resolve::<EntitySystem>(())?;
+
+        let player_controller = entities
+            .get_by_handle::<CCSPlayerController>(&EntityHandle::from_index(
+                controller_entity_index,
+            ))?
+            .with_context(|| obfstr!("missing player controller").to_string())?
+            .entity()?
+            .reference_schema()?;
+
+        let money_services = player_controller
+            .m_pInGameMoneyServices()?
+            .try_reference_schema()?
+            .with_context(|| obfstr!("missing money services").to_string())?;
+
+        let player_name = CStr::from_bytes_until_nul(&player_controller.m_iszPlayerName()?)
+            .context("player name missing nul terminator")?
+            .to_str()
+            .context("invalid player name")?
+            .to_string();
+
+        Ok(Self {
+            team_id: player_controller.m_iTeamNum()?,
+            player_name,
+
+            money: money_services.m_iAccount()?,
+            start_money: money_services.m_iStartAccount()?,
+            cash_spent_this_round: money_services.m_iCashSpentThisRound()?,
+        })
+    }
+
+    fn cache_type() -> StateCacheType {
+        StateCacheType::Volatile
+    }
+}
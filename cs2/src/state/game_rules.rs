@@ -0,0 +1,172 @@
+use anyhow::Context;
+use cs2_schema_generated::{
+    cs2::client::C_CSGameRulesProxy,
+    EntityHandle,
+};
+use obfstr::obfstr;
+use utils_state::{
+    State,
+    StateCacheType,
+    StateRegistry,
+};
+
+use crate::{
+    CEntityIdentityEx,
+    ClassNameCache,
+    EntitySystem,
+    Globals,
+    TICK_INTERVAL,
+};
+
+/// Buy time in seconds after the freeze time ended (default value of mp_buytime)
+pub const BUY_TIME: f32 = 20.0;
+
+#[derive(Debug, Clone)]
+pub struct GameRules {
+    pub freeze_period: bool,
+    pub warmup_period: bool,
+
+    pub total_rounds_played: i32,
+
+    /// Incremented every time a round restarts (at the beginning of the freeze time).
+    /// Note: This counter wraps around.
+    pub round_start_count: u8,
+
+    /// Incremented every time a round ends.
+    /// Note: This counter wraps around.
+    pub round_end_count: u8,
+
+    /// Game time when the current round started (end of the freeze time)
+    pub round_start_time: f32,
+
+    /// Server tick when the current round started (end of the freeze time)
+    pub round_start_tick: u32,
+
+    /// Round duration in seconds
+    pub round_time: i32,
+
+    /// Current game time in seconds
+    pub current_time: f32,
+
+    /// Current server tick
+    pub current_tick: u32,
+
+    pub bomb_planted: bool,
+    pub bomb_dropped: bool,
+
+    pub t_cant_buy: bool,
+    pub ct_cant_buy: bool,
+
+    /// Number of consecutive rounds lost by the terrorists
+    pub consecutive_t_losses: i32,
+
+    /// Number of consecutive rounds lost by the counter terrorists
+    pub consecutive_ct_losses: i32,
+}
+
+impl GameRules {
+    /// Time remaining (in seconds) until the freeze time ends
+    pub fn freeze_time_remaining(&self) -> f32 {
+        if self.freeze_period {
+            (self.round_start_time - self.current_time).max(0.0)
+        } else {
+            0.0
+        }
+    }
+
+    /// Time remaining (in seconds) of the current round, excluding the freeze time
+    pub fn round_time_remaining(&self) -> f32 {
+        if self.freeze_period {
+            self.round_time as f32
+        } else {
+            (self.round_start_time + self.round_time as f32 - self.current_time).max(0.0)
+        }
+    }
+
+    /// Time remaining (in seconds) until the buy time ends
+    pub fn buy_time_remaining(&self) -> f32 {
+        (self.round_start_time + BUY_TIME - self.current_time).max(0.0)
+    }
+}
+
+/// Current game rules.
+/// The game rules are not available when not connected to any server.
+pub struct GameRulesState {
+    pub game_rules: Option<GameRules>,
+}
+
+impl State for GameRulesState {
+    type Parameter = ();
+
+    fn create(states: &StateRegistry, _param: Self::Parameter) -> anyhow::Result<Self> {
+        let globals = states.resolve::<Globals>(())?;
+        let entities = states.resolve::<EntitySystem>(())?;
+        let class_name_cache = states.resolve::<ClassNameCache>(())?;
+
+        let mut proxy_index = None;
+        for entity_identity in entities.all_identities().iter() {
+            let class_name = class_name_cache
+                .lookup(&entity_identity.entity_class_info()?)
+                .context("class name")?;
+
+            if class_name
+                .map(|name| name == "C_CSGameRulesProxy")
+                .unwrap_or(false)
+            {
+                proxy_index = Some(entity_identity.handle::<()>()?.get_entity_index());
+                break;
+            }
+        }
+
+        let proxy_index = match proxy_index {
+            Some(index) => index,
+            None => return Ok(Self { game_rules: None }),
+        };
+
+        let identity = entities
+            .get_by_handle::<C_CSGameRulesProxy>(&EntityHandle::from_index(proxy_index))?
+            .with_context(|| obfstr!("missing game rules proxy").to_string())?;
+
+        let game_rules = match identity
+            .entity()?
+            .reference_schema()?
+            .m_pGameRules()?
+            .try_read_schema()?
+        {
+            Some(game_rules) => game_rules,
+            None => return Ok(Self { game_rules: None }),
+        };
+
+        let round_start_time = game_rules.m_fRoundStartTime()?.m_Value()?;
+        Ok(Self {
+            game_rules: Some(GameRules {
+                freeze_period: game_rules.m_bFreezePeriod()?,
+                warmup_period: game_rules.m_bWarmupPeriod()?,
+
+                total_rounds_played: game_rules.m_totalRoundsPlayed()?,
+                round_start_count: game_rules.m_nRoundStartCount()?,
+                round_end_count: game_rules.m_nRoundEndCount()?,
+
+                round_start_time,
+                round_start_tick: (round_start_time.max(0.0) / TICK_INTERVAL) as u32,
+                round_time: game_rules.m_iRoundTime()?,
+
+                current_time: globals.time_2()?,
+                current_tick: globals.frame_count_2()?,
+
+                bomb_planted: game_rules.m_bBombPlanted()?,
+                bomb_dropped: game_rules.m_bBombDropped()?,
+
+                t_cant_buy: game_rules.m_bTCantBuy()?,
+                ct_cant_buy: game_rules.m_bCTCantBuy()?,
+
+                consecutive_t_losses: game_rules.m_iNumConsecutiveTerroristLoses()?,
+                consecutive_ct_losses: game_rules.m_iNumConsecutiveCTLoses()?,
+            }),
+        })
+    }
+
+    fn cache_type() -> StateCacheType {
+        StateCacheType::Volatile
+    }
+}
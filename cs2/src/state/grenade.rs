@@ -0,0 +1,257 @@
+use anyhow::Context;
+use cs2_schema_generated::{
+    cs2::client::{
+        C_BaseCSGrenadeProjectile,
+        C_MolotovProjectile,
+        C_SmokeGrenadeProjectile,
+    },
+    EntityHandle,
+};
+use obfstr::obfstr;
+use utils_state::{
+    State,
+    StateCacheType,
+    StateRegistry,
+};
+
+use crate::{
+    CEntityIdentityEx,
+    ClassNameCache,
+    EntitySystem,
+    Globals,
+    WeaponId,
+    TICK_INTERVAL,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GrenadeType {
+    HighExplosive,
+    Flashbang,
+    Smoke,
+    Molotov,
+    Incendiary,
+    Decoy,
+}
+
+impl GrenadeType {
+    /// Resolve the grenade type by the class name of the projectile entity.
+    /// Molotov and incendiary share the same class and will be resolved as molotov.
+    pub fn from_class_name(class_name: &str) -> Option<Self> {
+        Some(match class_name {
+            "C_HEGrenadeProjectile" => Self::HighExplosive,
+            "C_FlashbangProjectile" => Self::Flashbang,
+            "C_SmokeGrenadeProjectile" => Self::Smoke,
+            "C_MolotovProjectile" => Self::Molotov,
+            "C_DecoyProjectile" => Self::Decoy,
+            _ => return None,
+        })
+    }
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Self::HighExplosive => "HE",
+            Self::Flashbang => "Flash",
+            Self::Smoke => "Smoke",
+            Self::Molotov => "Molotov",
+            Self::Incendiary => "Incendiary",
+            Self::Decoy => "Decoy",
+        }
+    }
+
+    pub fn weapon_id(&self) -> WeaponId {
+        match self {
+            Self::HighExplosive => WeaponId::HZGranade,
+            Self::Flashbang => WeaponId::Flashbang,
+            Self::Smoke => WeaponId::SmokeGranade,
+            Self::Molotov => WeaponId::Molotov,
+            Self::Incendiary => WeaponId::Incendiary,
+            Self::Decoy => WeaponId::Decoy,
+        }
+    }
+}
+
+/// Entity indices of all grenade projectiles currently in the world
+pub struct GrenadeProjectileList {
+    pub projectiles: Vec<u32>,
+}
+
+impl State for GrenadeProjectileList {
+    type Parameter = ();
+
+    fn create(states: &StateRegistry, _param: Self::Parameter) -> anyhow::Result<Self> {
+        let entities = states.resolve::<EntitySystem>(())?;
+        let class_name_cache = states.resolve::<ClassNameCache>(())?;
+
+        let mut projectiles = Vec::new();
+        for entity_identity in entities.all_identities().iter() {
+            let class_name = class_name_cache
+                .lookup(&entity_identity.entity_class_info()?)
+                .context("class name")?;
+
+            if class_name
+                .map(|name| GrenadeType::from_class_name(name).is_some())
+                .unwrap_or(false)
+            {
+                projectiles.push(entity_identity.handle::<()>()?.get_entity_index());
+            }
+        }
+
+        Ok(Self { projectiles })
+    }
+
+    fn cache_type() -> StateCacheType {
+        StateCacheType::Volatile
+    }
+}
+
+/// A thrown grenade
+#[derive(Debug, Clone)]
+pub struct GrenadeProjectile {
+    pub grenade_type: GrenadeType,
+
+    pub position: nalgebra::Vector3<f32>,
+
+    /// Velocity in units per second
+    pub velocity: nalgebra::Vector3<f32>,
+
+    /// Pawn entity index of the player who has thrown the grenade
+    pub thrower_entity_index: Option<u32>,
+    pub thrower_team_id: Option<u8>,
+
+    /// The grenade has already exploded (or started to emit smoke)
+    pub detonated: bool,
+
+    /// Time remaining (in seconds) until the fuse triggers the detonation.
+    /// None if the grenade detonates on impact or the time is unknown.
+    pub time_detonation: Option<f32>,
+}
+
+impl State for GrenadeProjectile {
+    type Parameter = u32;
+
+    fn create(states: &StateRegistry, entity_index: Self::Parameter) -> anyhow::Result<Self> {
+        let globals = states.resolve::<Globals>(())?;
+        let entities = states.resolve::<EntitySystem>(())?;
+        let class_name_cache = states.resolve::<ClassNameCache>(())?;
+
+        let identity = entities
+            .get_by_handle::<C_BaseCSGrenadeProjectile>(&EntityHandle::from_index(entity_index))?
+            .with_context(|| obfstr!("missing grenade projectile").to_string())?;
+
+        let mut grenade_type = class_name_cache
+            .lookup(&identity.entity_class_info()?)?
+            .and_then(|name| GrenadeType::from_class_name(name))
+            .with_context(|| obfstr!("entity is not a grenade projectile").to_string())?;
+
+        let projectile = identity.entity()?.reference_schema()?;
+        if grenade_type == GrenadeType::Molotov
+            && identity
+                .entity()?
+                .cast::<C_MolotovProjectile>()
+                .reference_schema()?
+                .m_bIsIncGrenade()?
+        {
+            grenade_type = GrenadeType::Incendiary;
+        }
+
+        let detonated = if grenade_type == GrenadeType::Smoke {
+            identity
+                .entity()?
+                .cast::<C_SmokeGrenadeProjectile>()
+                .reference_schema()?
+                .m_bDidSmokeEffect()?
+        } else {
+            projectile.m_nExplodeEffectTickBegin()? > 0
+        };
+
+        let thrower = projectile.m_hThrower()?;
+        let thrower_pawn = if thrower.is_valid() {
+            entities.get_by_handle(&thrower)?
+        } else {
+            None
+        };
+        let thrower_team_id = match &thrower_pawn {
+            Some(identity) => Some(identity.entity()?.reference_schema()?.m_iTeamNum()?),
+            None => None,
+        };
+
+        let time_detonation = match projectile.m_flDetonateTime()?.m_Value()? {
+            value if value > 0.0 => Some(value - globals.time_2()?),
+            _ => None,
+        };
+
+        Ok(Self {
+            grenade_type,
+
+            position: nalgebra::Vector3::from_column_slice(
+                &projectile
+                    .m_pGameSceneNode()?
+                    .reference_schema()?
+                    .m_vecAbsOrigin()?,
+            ),
+            velocity: nalgebra::Vector3::from_column_slice(&projectile.m_vecAbsVelocity()?),
+
+            thrower_entity_index: thrower_pawn.map(|_| thrower.get_entity_index()),
+            thrower_team_id,
+
+            detonated,
+            time_detonation,
+        })
+    }
+
+    fn cache_type() -> StateCacheType {
+        StateCacheType::Volatile
+    }
+}
+
+/// Time in seconds a smoke grenade stays active after it started to emit smoke
+pub const SMOKE_DURATION: f32 = 18.0;
+
+/// An active smoke cloud
+#[derive(Debug, Clone)]
+pub struct SmokeVolume {
+    /// Center of the smoke cloud (detonation position)
+    pub position: nalgebra::Vector3<f32>,
+
+    /// Time elapsed (in seconds) since the smoke has been deployed
+    pub time_elapsed: f32,
+
+    /// Time remaining (in seconds) until the smoke dissipates
+    pub time_remaining: f32,
+}
+
+impl State for SmokeVolume {
+    type Parameter = u32;
+
+    fn create(states: &StateRegistry, entity_index: Self::Parameter) -> anyhow::Result<Self> {
+        let globals = states.resolve::<Globals>(())?;
+        let entities = states.resolve::<EntitySystem>(())?;
+
+        let identity = entities
+            .get_by_handle::<C_SmokeGrenadeProjectile>(&EntityHandle::from_index(entity_index))?
+            .with_context(|| obfstr!("missing smoke grenade projectile").to_string())?;
+
+        let smoke = identity.entity()?.reference_schema()?;
+        if !smoke.m_bDidSmokeEffect()? {
+            anyhow::bail!("{}", obfstr!("smoke has not been deployed yet"));
+        }
+
+        /*
+         * The elapsed time is calculated by the recorded tick of the smoke deployment
+         * instead of the time we've first seen the entity as we might have joined mid-round.
+         */
+        let tick_begin = smoke.m_nSmokeEffectTickBegin()?.max(0) as u32;
+        let tick_current = globals.frame_count_2()?;
+        let time_elapsed = tick_current.saturating_sub(tick_begin) as f32 * TICK_INTERVAL;
+
+        Ok(Self {
+            position: nalgebra::Vector3::from_column_slice(&smoke.m_vSmokeDetonationPos()?),
+            time_elapsed,
+            time_remaining: (SMOKE_DURATION - time_elapsed).max(0.0),
+        })
+    }
+
+    fn cache_type() -> StateCacheType {
+        StateCacheType::Volatile
+    }
+}
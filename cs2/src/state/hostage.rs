@@ -0,0 +1,102 @@
+use anyhow::Context;
+use cs2_schema_generated::{
+    cs2::client::C_Hostage,
+    EntityHandle,
+};
+use obfstr::obfstr;
+use utils_state::{
+    State,
+    StateCacheType,
+    StateRegistry,
+};
+
+use crate::{
+    CEntityIdentityEx,
+    ClassNameCache,
+    EntitySystem,
+};
+
+/// Entity indices of all hostages which have not yet been rescued
+pub struct HostageList {
+    pub hostages: Vec<u32>,
+}
+
+impl State for HostageList {
+    type Parameter = ();
+
+    fn create(states: &StateRegistry, _param: Self::Parameter) -> anyhow::Result<Self> {
+        let entities = states.resolve::<EntitySystem>(())?;
+        let class_name_cache = states.resolve::<ClassNameCache>(())?;
+
+        let mut hostages = Vec::new();
+        for entity_identity in entities.all_identities().iter() {
+            let class_name = class_name_cache
+                .lookup(&entity_identity.entity_class_info()?)
+                .context("class name")?;
+
+            if class_name.map(|name| name == "C_Hostage").unwrap_or(false) {
+                hostages.push(entity_identity.handle::<()>()?.get_entity_index());
+            }
+        }
+
+        Ok(Self { hostages })
+    }
+
+    fn cache_type() -> StateCacheType {
+        StateCacheType::Volatile
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Hostage {
+    pub position: nalgebra::Vector3<f32>,
+    pub health: i32,
+
+    /// The hostage has already been rescued
+    pub rescued: bool,
+
+    /// Pawn entity index of the player who carries the hostage
+    pub carrier_entity_index: Option<u32>,
+}
+
+impl State for Hostage {
+    type Parameter = u32;
+
+    fn create(states: &StateRegistry, entity_index: Self::Parameter) -> anyhow::Result<Self> {
+        let entities = states.resolve::<EntitySystem>(())?;
+
+        let identity = entities
+            .get_by_handle::<C_Hostage>(&EntityHandle::from_index(entity_index))?
+            .with_context(|| obfstr!("missing hostage").to_string())?;
+
+        let hostage = identity.entity()?.reference_schema()?;
+
+        let grabber = hostage.m_hHostageGrabber()?;
+        let carrier_entity_index = if grabber.is_valid() {
+            Some(grabber.get_entity_index())
+        } else {
+            let leader = hostage.m_leader()?;
+            if leader.is_valid() {
+                Some(leader.get_entity_index())
+            } else {
+                None
+            }
+        };
+
+        Ok(Self {
+            position: nalgebra::Vector3::from_column_slice(
+                &hostage
+                    .m_pGameSceneNode()?
+                    .reference_schema()?
+                    .m_vecAbsOrigin()?,
+            ),
+            health: hostage.m_iHealth()?,
+            rescued: hostage.m_isRescued()?,
+            carrier_entity_index,
+        })
+    }
+
+    fn cache_type() -> StateCacheType {
+        StateCacheType::Volatile
+    }
+}
@@ -0,0 +1,108 @@
+use anyhow::Context;
+use cs2_schema_generated::{
+    cs2::client::C_Inferno,
+    EntityHandle,
+};
+use obfstr::obfstr;
+use utils_state::{
+    State,
+    StateCacheType,
+    StateRegistry,
+};
+
+use crate::{
+    CEntityIdentityEx,
+    ClassNameCache,
+    EntitySystem,
+    Globals,
+};
+
+/// Entity indices of all active fire areas (molotov and incendiary)
+pub struct InfernoList {
+    pub infernos: Vec<u32>,
+}
+
+impl State for InfernoList {
+    type Parameter = ();
+
+    fn create(states: &StateRegistry, _param: Self::Parameter) -> anyhow::Result<Self> {
+        let entities = states.resolve::<EntitySystem>(())?;
+        let class_name_cache = states.resolve::<ClassNameCache>(())?;
+
+        let mut infernos = Vec::new();
+        for entity_identity in entities.all_identities().iter() {
+            let class_name = class_name_cache
+                .lookup(&entity_identity.entity_class_info()?)
+                .context("class name")?;
+
+            if class_name.map(|name| name == "C_Inferno").unwrap_or(false) {
+                infernos.push(entity_identity.handle::<()>()?.get_entity_index());
+            }
+        }
+
+        Ok(Self { infernos })
+    }
+
+    fn cache_type() -> StateCacheType {
+        StateCacheType::Volatile
+    }
+}
+
+/// A burning fire area
+#[derive(Debug, Clone)]
+pub struct Inferno {
+    pub position: nalgebra::Vector3<f32>,
+
+    /// Positions of all individual fires which are currently burning
+    pub fire_positions: Vec<nalgebra::Vector3<f32>>,
+
+    /// Max radius of a single fire in units
+    pub fire_radius: f32,
+
+    /// Time remaining (in seconds) until the fire burns out
+    pub time_remaining: f32,
+}
+
+impl State for Inferno {
+    type Parameter = u32;
+
+    fn create(states: &StateRegistry, entity_index: Self::Parameter) -> anyhow::Result<Self> {
+        let globals = states.resolve::<Globals>(())?;
+        let entities = states.resolve::<EntitySystem>(())?;
+
+        let identity = entities
+            .get_by_handle::<C_Inferno>(&EntityHandle::from_index(entity_index))?
+            .with_context(|| obfstr!("missing inferno").to_string())?;
+
+        let inferno = identity.entity()?.reference_schema()?;
+
+        let fire_count = (inferno.m_fireCount()?.max(0) as usize).min(0x40);
+        let fire_positions = inferno.m_firePositions()?;
+        let fire_burning = inferno.m_bFireIsBurning()?;
+        let fire_positions = fire_positions
+            .iter()
+            .zip(fire_burning.iter())
+            .take(fire_count)
+            .filter(|(_, burning)| **burning)
+            .map(|(position, _)| nalgebra::Vector3::from_column_slice(position))
+            .collect::<Vec<_>>();
+
+        let time_burn_end = inferno.m_flCreateTime()?.m_Value()? + inferno.m_nFireLifetime()?;
+
+        Ok(Self {
+            position: nalgebra::Vector3::from_column_slice(
+                &inferno
+                    .m_pGameSceneNode()?
+                    .reference_schema()?
+                    .m_vecAbsOrigin()?,
+            ),
+            fire_positions,
+            fire_radius: inferno.m_maxFireHalfWidth()?,
+            time_remaining: (time_burn_end - globals.time_2()?).max(0.0),
+        })
+    }
+
+    fn cache_type() -> StateCacheType {
+        StateCacheType::Volatile
+    }
+}
@@ -6,3 +6,21 @@ pub use observer::*;
 
 mod bomb;
 pub use bomb::*;
+
+mod visibility;
+pub use visibility::*;
+
+mod controller;
+pub use controller::*;
+
+mod grenade;
+pub use grenade::*;
+
+mod inferno;
+pub use inferno::*;
+
+mod hostage;
+pub use hostage::*;
+
+mod game_rules;
+pub use game_rules::*;
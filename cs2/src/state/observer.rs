@@ -15,8 +15,46 @@ use crate::{
     EntitySystem,
 };
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ObserverMode {
+    None,
+    Fixed,
+    FirstPerson,
+    Chase,
+    Roaming,
+    Directed,
+    Unknown,
+}
+
+impl ObserverMode {
+    pub fn from_raw(value: u8) -> Self {
+        match value {
+            0 => Self::None,
+            1 => Self::Fixed,
+            2 => Self::FirstPerson,
+            3 => Self::Chase,
+            4 => Self::Roaming,
+            5 => Self::Directed,
+            _ => Self::Unknown,
+        }
+    }
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Fixed => "fixed",
+            Self::FirstPerson => "first person",
+            Self::Chase => "chase",
+            Self::Roaming => "free",
+            Self::Directed => "directed",
+            Self::Unknown => "unknown",
+        }
+    }
+}
+
 pub struct SpectatorInfo {
     pub spectator_name: String,
+    pub observer_mode: ObserverMode,
 }
 
 pub struct SpectatorList {
@@ -45,14 +83,17 @@ impl State for SpectatorList {
                 .entity_ptr::<C_CSObserverPawn>()?
                 .read_schema()?;
 
-            let observer_target_handle = {
+            let (observer_target_handle, observer_mode) = {
                 let observer_services = observer_pawn
                     .m_pObserverServices()?
                     .try_reference_schema()
                     .with_context(|| obfstr!("failed to read observer services").to_string())?;
 
                 match observer_services {
-                    Some(observer) => observer.m_hObserverTarget()?,
+                    Some(observer) => (
+                        observer.m_hObserverTarget()?,
+                        ObserverMode::from_raw(observer.m_iObserverMode()?),
+                    ),
                     None => {
                         continue;
                     }
@@ -77,7 +118,10 @@ impl State for SpectatorList {
                 .context("invalid player name")?
                 .to_string();
 
-            spectators.push(SpectatorInfo { spectator_name });
+            spectators.push(SpectatorInfo {
+                spectator_name,
+                observer_mode,
+            });
         }
 
         Ok(Self {
@@ -95,6 +139,22 @@ impl State for SpectatorList {
 pub struct LocalCameraControllerTarget {
     pub is_local_entity: bool,
     pub target_entity_id: Option<u32>,
+
+    /// Observer mode of the local player.
+    /// [ObserverMode::None] while our own pawn is alive.
+    pub observer_mode: ObserverMode,
+}
+
+impl LocalCameraControllerTarget {
+    /// Entity id of the pawn we're currently looking through.
+    /// While spectating in chase or free-look mode, the camera is detached from the target.
+    pub fn first_person_entity_id(&self) -> Option<u32> {
+        if self.is_local_entity || self.observer_mode == ObserverMode::FirstPerson {
+            self.target_entity_id
+        } else {
+            None
+        }
+    }
 }
 
 impl State for LocalCameraControllerTarget {
@@ -115,6 +175,7 @@ impl State for LocalCameraControllerTarget {
                 return Ok(Self {
                     target_entity_id: None,
                     is_local_entity: false,
+                    observer_mode: ObserverMode::None,
                 });
             }
         };
@@ -128,6 +189,7 @@ impl State for LocalCameraControllerTarget {
             Ok(Self {
                 target_entity_id: Some(player_controller.m_hPawn()?.get_entity_index()),
                 is_local_entity: true,
+                observer_mode: ObserverMode::None,
             })
         } else {
             let observer_pawn =
@@ -138,19 +200,21 @@ impl State for LocalCameraControllerTarget {
                         return Ok(Self {
                             target_entity_id: None,
                             is_local_entity: false,
+                            observer_mode: ObserverMode::Unknown,
                         });
                     }
                 };
 
-            let observer_target_handle = observer_pawn
-                .m_pObserverServices()?
-                .reference_schema()?
-                .m_hObserverTarget()?;
+            let observer_services = observer_pawn.m_pObserverServices()?.reference_schema()?;
+            let observer_mode = ObserverMode::from_raw(observer_services.m_iObserverMode()?);
+            let observer_target_handle = observer_services.m_hObserverTarget()?;
 
-            if !observer_target_handle.is_valid() {
+            if !observer_target_handle.is_valid() || observer_mode == ObserverMode::Roaming {
+                /* free-look camera which is not attached to any player */
                 return Ok(Self {
                     target_entity_id: None,
                     is_local_entity: false,
+                    observer_mode,
                 });
             }
             let target_entity_id = observer_target_handle.get_entity_index();
@@ -158,6 +222,7 @@ impl State for LocalCameraControllerTarget {
             Ok(Self {
                 is_local_entity: false,
                 target_entity_id: Some(target_entity_id),
+                observer_mode,
             })
         }
     }
@@ -10,10 +10,13 @@ use cs2_schema_declaration::{
 };
 use cs2_schema_generated::{
     cs2::client::{
+        CCSPlayerController,
         CCSPlayer_ItemServices,
         CModelState,
+        CPlayer_WeaponServices,
         CSkeletonInstance,
         C_CSPlayerPawn,
+        C_C4,
     },
     EntityHandle,
 };
@@ -24,25 +27,71 @@ use utils_state::{
 };
 
 use crate::{
+    CEntityIdentityEx,
     CS2Model,
+    ClassNameCache,
     EntitySystem,
     WeaponId,
 };
 
+/// Entity is standing on the ground
+pub const PLAYER_FLAG_ON_GROUND: u32 = 0x01;
+
+/// Entity is crouching
+pub const PLAYER_FLAG_DUCKING: u32 = 0x02;
+
+/// Max amount of weapons we expect a player to carry
+const MAX_PLAYER_WEAPONS: usize = 64;
+
+#[derive(Debug, Clone)]
+pub struct WeaponAmmo {
+    /// Ammo in the current clip
+    pub clip: i32,
+
+    /// Primary reserve ammo
+    pub reserve: i32,
+}
+
 #[derive(Debug, Clone)]
 pub struct PlayerPawnInfo {
     pub controller_entity_id: u32,
     pub team_id: u8,
 
     pub player_health: i32,
+    pub player_armor: i32,
     pub player_has_defuser: bool,
     pub player_name: String,
+    /// Clan tag of the player. Empty if the player has no clan tag.
+    pub player_clan_tag: String,
     pub weapon: WeaponId,
+    /// Ammo of the active weapon.
+    /// None if the weapon does not use a clip (e.g. knives and grenades).
+    pub weapon_ammo: Option<WeaponAmmo>,
+    /// The active weapon is currently being reloaded
+    pub weapon_reloading: bool,
+    /// The C4 is within the weapons of the player (not necessarily the active weapon)
+    pub player_has_bomb: bool,
     pub player_flashtime: f32,
+    pub player_scoped: bool,
+    pub player_defusing: bool,
+    pub player_planting: bool,
+    /// Raw entity flags (see PLAYER_FLAG_*)
+    pub player_flags_raw: u32,
+    /// Shots fired within the current burst. Resets when the player stops shooting.
+    pub player_shots_fired: i32,
 
     pub position: nalgebra::Vector3<f32>,
     pub rotation: f32,
 
+    /// View angles of the player (pitch, yaw, roll) in degrees
+    pub eye_angles: nalgebra::Vector3<f32>,
+
+    /// Current aim punch (pitch, yaw, roll) in degrees caused by recoil and damage
+    pub aim_punch_angle: nalgebra::Vector3<f32>,
+
+    /// Velocity in units per second
+    pub velocity: nalgebra::Vector3<f32>,
+
     pub model_address: u64,
     pub bone_states: Vec<BoneStateData>,
 }
@@ -75,21 +124,75 @@ impl CModelStateEx for CModelState {
 #[derive(Debug, Clone)]
 pub struct BoneStateData {
     pub position: nalgebra::Vector3<f32>,
+    pub rotation: nalgebra::UnitQuaternion<f32>,
+}
+
+impl BoneStateData {
+    /// Transformation from the bone space into world space
+    pub fn transform(&self) -> nalgebra::Isometry3<f32> {
+        nalgebra::Isometry3::from_parts(self.position.into(), self.rotation)
+    }
 }
 
 impl TryFrom<CBoneStateData> for BoneStateData {
     type Error = anyhow::Error;
 
     fn try_from(value: CBoneStateData) -> Result<Self, Self::Error> {
+        let [x, y, z, w] = value.rotation()?;
         Ok(Self {
             position: nalgebra::Vector3::from_row_slice(&value.position()?),
+            rotation: nalgebra::UnitQuaternion::from_quaternion(nalgebra::Quaternion::new(
+                w, x, y, z,
+            )),
         })
     }
 }
 
+/// Entity indices of all player pawns
+#[derive(Debug, Clone)]
+pub struct PlayerPawnList {
+    pub pawns: Vec<u32>,
+}
+
+impl State for PlayerPawnList {
+    type Parameter = ();
+
+    fn create(
+        states: &utils_state::StateRegistry,
+        _param: Self::Parameter,
+    ) -> anyhow::Result<Self> {
+        let entities = states.resolve::<EntitySystem>(())?;
+        let class_name_cache = states.resolve::<ClassNameCache>(())?;
+
+        let mut pawns = Vec::new();
+        for entity_identity in entities.all_identities().iter() {
+            let class_name = class_name_cache
+                .lookup(&entity_identity.entity_class_info()?)
+                .context("class name")?;
+
+            if class_name
+                .map(|name| name == "C_CSPlayerPawn")
+                .unwrap_or(false)
+            {
+                pawns.push(entity_identity.handle::<()>()?.get_entity_index());
+            }
+        }
+
+        Ok(Self { pawns })
+    }
+
+    fn cache_type() -> StateCacheType {
+        StateCacheType::Volatile
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum PlayerPawnState {
     Alive(PlayerPawnInfo),
+
+    /// The pawn is alive but has not been updated by the server recently
+    Dormant,
+
     Dead,
 }
 
@@ -123,20 +226,31 @@ impl State for PlayerPawnState {
             .cast::<CSkeletonInstance>()
             .read_schema()?;
         if game_screen_node.m_bDormant()? {
-            return Ok(Self::Dead);
+            return Ok(Self::Dormant);
         }
 
         let controller_handle = player_pawn.m_hController()?;
         let current_controller = entities.get_by_handle(&controller_handle)?;
 
         let player_team = player_pawn.m_iTeamNum()?;
-        let player_name = if let Some(identity) = &current_controller {
-            let player_controller = identity.entity()?.reference_schema()?;
-            CStr::from_bytes_until_nul(&player_controller.m_iszPlayerName()?)
+        let (player_name, player_clan_tag) = if let Some(identity) = &current_controller {
+            let player_controller = identity
+                .entity()?
+                .cast::<CCSPlayerController>()
+                .reference_schema()?;
+
+            let player_name = CStr::from_bytes_until_nul(&player_controller.m_iszPlayerName()?)
                 .context("player name missing nul terminator")?
                 .to_str()
                 .context("invalid player name")?
-                .to_string()
+                .to_string();
+
+            let player_clan_tag = player_controller
+                .m_szClan()?
+                .try_read_string()?
+                .unwrap_or_default();
+
+            (player_name, player_clan_tag)
         } else {
             /*
              * This is the case for pawns which are not controllel by a player controller.
@@ -177,7 +291,7 @@ impl State for PlayerPawnState {
             .collect::<Result<Vec<_>>>()?;
 
         let weapon = player_pawn.m_pClippingWeapon()?.try_read_schema()?;
-        let weapon_type = if let Some(weapon) = weapon {
+        let weapon_type = if let Some(weapon) = &weapon {
             weapon
                 .m_AttributeManager()?
                 .m_Item()?
@@ -186,20 +300,107 @@ impl State for PlayerPawnState {
             WeaponId::Knife.id()
         };
 
+        let weapon_ammo = match &weapon {
+            Some(weapon) => {
+                let clip = weapon.m_iClip1()?;
+                if clip >= 0 {
+                    Some(WeaponAmmo {
+                        clip,
+                        reserve: weapon.m_pReserveAmmo()?[0],
+                    })
+                } else {
+                    /* weapon does not have a clip */
+                    None
+                }
+            }
+            None => None,
+        };
+        let weapon_reloading = match &weapon {
+            Some(weapon) => weapon.m_bInReload()?,
+            None => false,
+        };
+
+        let player_has_bomb = {
+            let my_weapons = player_pawn
+                .m_pWeaponServices()?
+                .cast::<CPlayer_WeaponServices>()
+                .reference_schema()?
+                .m_hMyWeapons()?;
+
+            /* a player can not carry more then a handful of weapons, limit the count in case we read garbage */
+            let weapon_count =
+                (my_weapons.element_count()?.max(0) as usize).min(MAX_PLAYER_WEAPONS);
+            let mut has_bomb = false;
+            for weapon_handle in my_weapons.elements()?.read_entries(weapon_count)? {
+                let weapon = match entities.get_by_handle(&weapon_handle)? {
+                    Some(identity) => identity.entity()?.reference_schema()?,
+                    None => continue,
+                };
+
+                if weapon
+                    .m_AttributeManager()?
+                    .m_Item()?
+                    .m_iItemDefinitionIndex()?
+                    == WeaponId::C4.id()
+                {
+                    has_bomb = true;
+                    break;
+                }
+            }
+
+            has_bomb
+        };
+
         let player_flashtime = player_pawn.m_flFlashBangTime()?;
+        let player_scoped = player_pawn.m_bIsScoped()?;
+        let player_defusing = player_pawn.m_bIsDefusing()?;
+
+        let weapon = WeaponId::from_id(weapon_type).unwrap_or(WeaponId::Unknown);
+        let player_planting = if weapon == WeaponId::C4 {
+            let started_arming = player_pawn
+                .m_pClippingWeapon()?
+                .cast::<C_C4>()
+                .try_reference_schema()?
+                .map(|c4| c4.m_bStartedArming())
+                .transpose();
+
+            match started_arming {
+                Ok(Some(started_arming)) => started_arming,
+                /* fall back to the bomb zone if we can not read the C4 arming state */
+                _ => player_pawn.m_bInBombZone()?,
+            }
+        } else {
+            false
+        };
+        let player_armor = player_pawn.m_ArmorValue()?;
+        let eye_angles = nalgebra::Vector3::from_column_slice(&player_pawn.m_angEyeAngles()?);
 
         Ok(Self::Alive(PlayerPawnInfo {
             controller_entity_id: controller_handle.get_entity_index(),
             team_id: player_team,
 
             player_name,
+            player_clan_tag,
             player_has_defuser,
             player_health,
-            weapon: WeaponId::from_id(weapon_type).unwrap_or(WeaponId::Unknown),
+            player_armor,
+            weapon,
+            weapon_ammo,
+            weapon_reloading,
+            player_has_bomb,
             player_flashtime,
+            player_scoped,
+            player_defusing,
+            player_planting,
+            player_flags_raw: player_pawn.m_fFlags()?,
+            player_shots_fired: player_pawn.m_iShotsFired()?,
 
             position,
-            rotation: player_pawn.m_angEyeAngles()?[1],
+            rotation: eye_angles.y,
+            eye_angles,
+            aim_punch_angle: nalgebra::Vector3::from_column_slice(&player_pawn.m_aimPunchAngle()?),
+
+            velocity: nalgebra::Vector3::from_column_slice(&player_pawn.m_vecAbsVelocity()?),
 
             bone_states,
             model_address,
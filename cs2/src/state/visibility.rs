@@ -0,0 +1,87 @@
+use anyhow::Context;
+use cs2_schema_generated::{
+    cs2::client::C_CSPlayerPawn,
+    EntityHandle,
+};
+use obfstr::obfstr;
+use utils_state::{
+    State,
+    StateCacheType,
+    StateRegistry,
+};
+
+use crate::{
+    EntitySystem,
+    LocalCameraControllerTarget,
+};
+
+/// Visibility of a player pawn from the perspective of the
+/// pawn the local camera is currently following.
+///
+/// The visibility is based on the spotted mask of the target pawn.
+/// This mask is maintained by the games spotting logic, which already considers smokes.
+pub struct PlayerPawnVisibility {
+    /// Whether the target pawn is currently visible for the viewer
+    pub visible: bool,
+}
+
+impl PlayerPawnVisibility {
+    fn not_visible() -> Self {
+        Self { visible: false }
+    }
+}
+
+impl State for PlayerPawnVisibility {
+    type Parameter = u32;
+
+    fn create(states: &StateRegistry, pawn_entity_index: Self::Parameter) -> anyhow::Result<Self> {
+        let entities = states.resolve::<EntitySystem>(())?;
+
+        /*
+         * The camera target changes when we're spectating somebody else.
+         * As this state is volatile the viewer will be resolved again every tick.
+         */
+        let view_target = states.resolve::<LocalCameraControllerTarget>(())?;
+        let viewer_entity_id = match view_target.target_entity_id {
+            Some(value) => value,
+            None => return Ok(Self::not_visible()),
+        };
+
+        let viewer_pawn = match entities
+            .get_by_handle::<C_CSPlayerPawn>(&EntityHandle::from_index(viewer_entity_id))?
+        {
+            Some(identity) => identity.entity()?.reference_schema()?,
+            None => return Ok(Self::not_visible()),
+        };
+
+        let viewer_controller = viewer_pawn.m_hController()?;
+        if !viewer_controller.is_valid() {
+            return Ok(Self::not_visible());
+        }
+
+        /* The spotted mask is indexed by the player slot which is the controller entity index - 1 */
+        let viewer_slot = viewer_controller.get_entity_index().wrapping_sub(1);
+        if viewer_slot >= 64 {
+            return Ok(Self::not_visible());
+        }
+
+        let target_pawn = match entities
+            .get_by_handle::<C_CSPlayerPawn>(&EntityHandle::from_index(pawn_entity_index))?
+        {
+            Some(identity) => identity.entity()?.reference_schema()?,
+            None => return Ok(Self::not_visible()),
+        };
+
+        let spotted_mask = target_pawn
+            .m_entitySpottedState()?
+            .m_bSpottedByMask()
+            .with_context(|| obfstr!("failed to read pawn spotted mask").to_string())?;
+
+        let visible = (spotted_mask[(viewer_slot / 32) as usize] & (1 << (viewer_slot % 32))) != 0;
+        Ok(Self { visible })
+    }
+
+    fn cache_type() -> StateCacheType {
+        StateCacheType::Volatile
+    }
+}
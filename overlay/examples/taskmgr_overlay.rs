@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use overlay::OverlayTarget;
 
 fn main() -> anyhow::Result<()> {
@@ -10,9 +12,9 @@ fn main() -> anyhow::Result<()> {
     let overlay = overlay::init(&overlay::OverlayOptions {
         title: "Task Manager Overlay".to_string(),
         target: OverlayTarget::WindowTitle("Task Manager".into()),
-        font_init: Some(Box::new(|_imgui| {
-            // imgui.fonts().add_font(font_sources)
-            // imgui.fonts().add_font(&[FontSource::TtfData {
+        font_init: Some(Arc::new(|_atlas| {
+            // atlas.add_font(font_sources)
+            // atlas.add_font(&[FontSource::TtfData {
             //     data: include_bytes!("../resources/unifont-15.1.03.otf"),
             //     size_pixels: 16.0,
             //     config: Some(FontConfig {
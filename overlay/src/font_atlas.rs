@@ -0,0 +1,520 @@
+use std::{
+    ptr::NonNull,
+    thread::JoinHandle,
+};
+
+use ash::{
+    vk,
+    Device,
+};
+use imgui::{
+    sys,
+    FontAtlas,
+    TextureId,
+};
+use imgui_rs_vulkan_renderer::{
+    vulkan::{
+        create_vulkan_descriptor_pool,
+        create_vulkan_descriptor_set,
+        create_vulkan_descriptor_set_layout,
+    },
+    Renderer,
+};
+
+use crate::{
+    add_default_font,
+    vulkan::{
+        PendingTexture,
+        Texture,
+    },
+    FontInitCallback,
+    OverlayError,
+    Result,
+};
+
+/// Steps required to replace the active font atlas.
+pub(crate) trait FontAtlasBackend {
+    type Build;
+    type Atlas;
+    type Upload;
+    type Texture;
+
+    /// Start building a new font atlas
+    fn start_build(&mut self) -> Self::Build;
+
+    fn build_finished(&self, build: &Self::Build) -> bool;
+
+    fn finish_build(&mut self, build: Self::Build) -> Result<Self::Atlas>;
+
+    /// Start uploading the texture of the font atlas
+    fn start_upload(&mut self, atlas: &mut Self::Atlas) -> Result<Self::Upload>;
+
+    fn upload_finished(&self, upload: &Self::Upload) -> bool;
+
+    fn finish_upload(&mut self, upload: Self::Upload) -> Result<Self::Texture>;
+
+    /// Replace the active font atlas
+    fn activate(&mut self, atlas: Self::Atlas, texture: &Self::Texture);
+
+    /// Returns true if submitted frames might still use replaced textures
+    fn textures_in_use(&self) -> bool;
+
+    fn destroy_texture(&mut self, texture: Self::Texture);
+}
+
+enum RebuildState<Build, Atlas, Upload> {
+    Idle,
+    Building(Build),
+    Uploading(Atlas, Upload),
+}
+
+/// Rebuilds the font atlas without blocking the render loop.
+/// The new atlas is built and uploaded in the background while the current
+/// atlas and its texture stay active until the new texture is ready.
+pub(crate) struct FontAtlasRebuild<Build, Atlas, Upload, Texture> {
+    state: RebuildState<Build, Atlas, Upload>,
+    requested: bool,
+
+    /// Texture of the active font atlas.
+    /// None for the initial font atlas which is owned by the renderer.
+    active_texture: Option<Texture>,
+
+    /// Replaced textures which might still be used by submitted frames
+    retired_textures: Vec<Texture>,
+}
+
+impl<Build, Atlas, Upload, Texture> FontAtlasRebuild<Build, Atlas, Upload, Texture> {
+    pub fn new() -> Self {
+        Self {
+            state: RebuildState::Idle,
+            requested: false,
+
+            active_texture: None,
+            retired_textures: Vec::new(),
+        }
+    }
+
+    /// Request a rebuild.
+    /// If a rebuild is already in progress, another rebuild will be started after it has been activated.
+    pub fn request(&mut self) {
+        self.requested = true;
+    }
+
+    /// Advance the rebuild.
+    /// Returns true if a new font atlas has been activated.
+    ///
+    /// A pending request will not be started within the same call the previous atlas gets activated.
+    /// This allows the application to pick up the fonts of the activated atlas before they get replaced.
+    pub fn poll<B>(&mut self, backend: &mut B) -> bool
+    where
+        B: FontAtlasBackend<Build = Build, Atlas = Atlas, Upload = Upload, Texture = Texture>,
+    {
+        let activated = match std::mem::replace(&mut self.state, RebuildState::Idle) {
+            RebuildState::Idle => {
+                if std::mem::take(&mut self.requested) {
+                    self.state = RebuildState::Building(backend.start_build());
+                }
+
+                false
+            }
+            RebuildState::Building(build) if !backend.build_finished(&build) => {
+                self.state = RebuildState::Building(build);
+                false
+            }
+            RebuildState::Building(build) => {
+                let upload = backend.finish_build(build).and_then(|mut atlas| {
+                    let upload = backend.start_upload(&mut atlas)?;
+                    Ok((atlas, upload))
+                });
+
+                match upload {
+                    Ok((atlas, upload)) => self.state = RebuildState::Uploading(atlas, upload),
+                    Err(error) => log::error!("Failed to rebuild the font atlas: {}", error),
+                }
+
+                false
+            }
+            RebuildState::Uploading(atlas, upload) if !backend.upload_finished(&upload) => {
+                self.state = RebuildState::Uploading(atlas, upload);
+                false
+            }
+            RebuildState::Uploading(atlas, upload) => match backend.finish_upload(upload) {
+                Ok(texture) => {
+                    backend.activate(atlas, &texture);
+                    if let Some(texture) = self.active_texture.replace(texture) {
+                        self.retired_textures.push(texture);
+                    }
+
+                    true
+                }
+                Err(error) => {
+                    log::error!("Failed to upload the font atlas: {}", error);
+                    false
+                }
+            },
+        };
+
+        if !self.retired_textures.is_empty() && !backend.textures_in_use() {
+            for texture in self.retired_textures.drain(..) {
+                backend.destroy_texture(texture);
+            }
+        }
+
+        activated
+    }
+}
+
+/// Font atlas which is not owned by an ImGui context (yet).
+pub(crate) struct OwnedFontAtlas(NonNull<sys::ImFontAtlas>);
+
+/* the atlas is not referenced by any context, therefore it can be built on another thread */
+unsafe impl Send for OwnedFontAtlas {}
+
+impl OwnedFontAtlas {
+    pub fn new() -> Self {
+        let atlas = unsafe { sys::ImFontAtlas_ImFontAtlas() };
+        Self(NonNull::new(atlas).expect("font atlas to be allocated"))
+    }
+
+    pub fn atlas_mut(&mut self) -> &mut FontAtlas {
+        unsafe { &mut *(self.0.as_ptr() as *mut FontAtlas) }
+    }
+
+    /// Release the ownership of the atlas
+    fn into_raw(self) -> *mut sys::ImFontAtlas {
+        let atlas = self.0.as_ptr();
+        std::mem::forget(self);
+        atlas
+    }
+}
+
+impl Drop for OwnedFontAtlas {
+    fn drop(&mut self) {
+        unsafe { sys::ImFontAtlas_destroy(self.0.as_ptr()) };
+    }
+}
+
+/// Uploaded texture of a font atlas
+pub(crate) struct FontTexture {
+    texture_id: TextureId,
+    texture: Texture,
+    descriptor_set_layout: vk::DescriptorSetLayout,
+    descriptor_pool: vk::DescriptorPool,
+}
+
+pub(crate) type VulkanFontAtlasRebuild =
+    FontAtlasRebuild<JoinHandle<OwnedFontAtlas>, OwnedFontAtlas, PendingTexture, FontTexture>;
+
+/// Builds the font atlas on a worker thread and uploads its texture to the GPU
+/// without waiting on the graphics queue.
+pub(crate) struct VulkanFontAtlas<'a> {
+    pub device: &'a Device,
+    pub queue: vk::Queue,
+    pub command_pool: vk::CommandPool,
+    pub mem_properties: vk::PhysicalDeviceMemoryProperties,
+
+    /// Fence of the last submitted frame
+    pub render_fence: vk::Fence,
+
+    pub renderer: &'a mut Renderer,
+    pub imgui: &'a mut imgui::Context,
+    pub font_init: Option<&'a FontInitCallback>,
+}
+
+impl FontAtlasBackend for VulkanFontAtlas<'_> {
+    type Build = JoinHandle<OwnedFontAtlas>;
+    type Atlas = OwnedFontAtlas;
+    type Upload = PendingTexture;
+    type Texture = FontTexture;
+
+    fn start_build(&mut self) -> Self::Build {
+        let font_init = self.font_init.cloned();
+        std::thread::spawn(move || {
+            let mut atlas = OwnedFontAtlas::new();
+            add_default_font(atlas.atlas_mut());
+            if let Some(callback) = &font_init {
+                callback(atlas.atlas_mut());
+            }
+
+            /* rasterize the glyphs while still being on the worker thread */
+            atlas.atlas_mut().build_rgba32_texture();
+            atlas
+        })
+    }
+
+    fn build_finished(&self, build: &Self::Build) -> bool {
+        build.is_finished()
+    }
+
+    fn finish_build(&mut self, build: Self::Build) -> Result<Self::Atlas> {
+        build.join().map_err(|_| OverlayError::FontAtlasBuildFailed)
+    }
+
+    fn start_upload(&mut self, atlas: &mut Self::Atlas) -> Result<Self::Upload> {
+        /* the glyphs have already been rasterized, this only returns the texture data */
+        let texture = atlas.atlas_mut().build_rgba32_texture();
+        Ok(Texture::upload_rgba8(
+            self.device,
+            self.queue,
+            self.command_pool,
+            self.mem_properties,
+            texture.width,
+            texture.height,
+            texture.data,
+        )?)
+    }
+
+    fn upload_finished(&self, upload: &Self::Upload) -> bool {
+        /* errors will be reported when submitting the next frame */
+        upload.is_complete(self.device).unwrap_or(true)
+    }
+
+    fn finish_upload(&mut self, upload: Self::Upload) -> Result<Self::Texture> {
+        let texture = upload.finish(self.device, self.command_pool);
+
+        let descriptor_set_layout = create_vulkan_descriptor_set_layout(self.device)?;
+        let descriptor_pool = create_vulkan_descriptor_pool(self.device, 1)?;
+        let descriptor_set = create_vulkan_descriptor_set(
+            self.device,
+            descriptor_set_layout,
+            descriptor_pool,
+            texture.image_view,
+            texture.sampler,
+        )?;
+
+        Ok(FontTexture {
+            texture_id: self.renderer.textures().insert(descriptor_set),
+            texture,
+            descriptor_set_layout,
+            descriptor_pool,
+        })
+    }
+
+    fn activate(&mut self, mut atlas: Self::Atlas, texture: &Self::Texture) {
+        atlas.atlas_mut().tex_id = texture.texture_id;
+
+        /*
+         * The context deletes its current atlas when being destroyed,
+         * therefore it takes over the ownership of the new atlas.
+         * Fonts of the previous atlas are only referenced while rendering a frame.
+         */
+        unsafe {
+            let io = self.imgui.io_mut() as *mut imgui::Io as *mut sys::ImGuiIO;
+            let previous_atlas = std::mem::replace(&mut (*io).Fonts, atlas.into_raw());
+            (*io).FontDefault = std::ptr::null_mut();
+            sys::ImFontAtlas_destroy(previous_atlas);
+        }
+    }
+
+    fn textures_in_use(&self) -> bool {
+        !matches!(
+            unsafe { self.device.get_fence_status(self.render_fence) },
+            Ok(true)
+        )
+    }
+
+    fn destroy_texture(&mut self, mut texture: Self::Texture) {
+        self.renderer.textures().remove(texture.texture_id);
+        unsafe {
+            self.device
+                .destroy_descriptor_pool(texture.descriptor_pool, None);
+            self.device
+                .destroy_descriptor_set_layout(texture.descriptor_set_layout, None);
+        }
+        texture.texture.destroy(self.device);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        FontAtlasBackend,
+        FontAtlasRebuild,
+    };
+    use crate::{
+        OverlayError,
+        Result,
+    };
+
+    /// Backend which finishes every step after the given amount of polls
+    #[derive(Default)]
+    struct TestBackend {
+        build_polls: usize,
+        upload_polls: usize,
+        fail_build: bool,
+        textures_in_use: bool,
+
+        builds_started: usize,
+        active_atlas: Option<usize>,
+        destroyed_textures: Vec<usize>,
+    }
+
+    impl FontAtlasBackend for TestBackend {
+        /* remaining polls and the id of the atlas */
+        type Build = (usize, usize);
+        type Atlas = usize;
+        type Upload = (usize, usize);
+        type Texture = usize;
+
+        fn start_build(&mut self) -> Self::Build {
+            self.builds_started += 1;
+            (self.build_polls, self.builds_started)
+        }
+
+        fn build_finished(&self, build: &Self::Build) -> bool {
+            build.0 == 0
+        }
+
+        fn finish_build(&mut self, build: Self::Build) -> Result<Self::Atlas> {
+            if self.fail_build {
+                return Err(OverlayError::FontAtlasBuildFailed);
+            }
+
+            Ok(build.1)
+        }
+
+        fn start_upload(&mut self, atlas: &mut Self::Atlas) -> Result<Self::Upload> {
+            Ok((self.upload_polls, *atlas))
+        }
+
+        fn upload_finished(&self, upload: &Self::Upload) -> bool {
+            upload.0 == 0
+        }
+
+        fn finish_upload(&mut self, upload: Self::Upload) -> Result<Self::Texture> {
+            Ok(upload.1)
+        }
+
+        fn activate(&mut self, atlas: Self::Atlas, texture: &Self::Texture) {
+            assert_eq!(atlas, *texture);
+            self.active_atlas = Some(atlas);
+        }
+
+        fn textures_in_use(&self) -> bool {
+            self.textures_in_use
+        }
+
+        fn destroy_texture(&mut self, texture: Self::Texture) {
+            self.destroyed_textures.push(texture);
+        }
+    }
+
+    type TestRebuild = FontAtlasRebuild<(usize, usize), usize, (usize, usize), usize>;
+
+    /// Poll until the rebuild activated an atlas (max 16 polls).
+    /// Returns the amount of polls required.
+    fn poll_until_activated(rebuild: &mut TestRebuild, backend: &mut TestBackend) -> usize {
+        for poll in 1..=16 {
+            if rebuild.poll(backend) {
+                return poll;
+            }
+
+            /* progress of the worker thread / GPU */
+            match &mut rebuild.state {
+                super::RebuildState::Building((polls, _)) => *polls = polls.saturating_sub(1),
+                super::RebuildState::Uploading(_, (polls, _)) => *polls = polls.saturating_sub(1),
+                super::RebuildState::Idle => {}
+            }
+        }
+
+        panic!("font atlas has not been activated");
+    }
+
+    #[test]
+    fn keeps_atlas_until_uploaded() {
+        let mut backend = TestBackend {
+            build_polls: 2,
+            upload_polls: 3,
+            ..Default::default()
+        };
+        let mut rebuild = TestRebuild::new();
+
+        assert!(!rebuild.poll(&mut backend));
+        assert_eq!(backend.builds_started, 0);
+
+        rebuild.request();
+        /* 2 polls building, 3 polls uploading and one poll to activate the atlas */
+        assert_eq!(poll_until_activated(&mut rebuild, &mut backend), 6);
+        assert_eq!(backend.builds_started, 1);
+        assert_eq!(backend.active_atlas, Some(1));
+
+        /* the initial texture is owned by the renderer */
+        assert!(backend.destroyed_textures.is_empty());
+        assert!(!rebuild.poll(&mut backend));
+        assert_eq!(backend.builds_started, 1);
+    }
+
+    #[test]
+    fn request_while_rebuilding() {
+        let mut backend = TestBackend {
+            build_polls: 1,
+            upload_polls: 1,
+            ..Default::default()
+        };
+        let mut rebuild = TestRebuild::new();
+
+        rebuild.request();
+        assert!(!rebuild.poll(&mut backend));
+        rebuild.request();
+        rebuild.request();
+
+        poll_until_activated(&mut rebuild, &mut backend);
+        assert_eq!(backend.active_atlas, Some(1));
+
+        /* the fonts of the activated atlas must be picked up before starting the next build */
+        assert_eq!(backend.builds_started, 1);
+        poll_until_activated(&mut rebuild, &mut backend);
+        assert_eq!(backend.builds_started, 2);
+        assert_eq!(backend.active_atlas, Some(2));
+
+        /* multiple requests result in a single rebuild */
+        for _ in 0..4 {
+            assert!(!rebuild.poll(&mut backend));
+        }
+        assert_eq!(backend.builds_started, 2);
+    }
+
+    #[test]
+    fn destroys_replaced_textures_once_unused() {
+        let mut backend = TestBackend {
+            textures_in_use: true,
+            ..Default::default()
+        };
+        let mut rebuild = TestRebuild::new();
+
+        rebuild.request();
+        poll_until_activated(&mut rebuild, &mut backend);
+        rebuild.request();
+        poll_until_activated(&mut rebuild, &mut backend);
+        assert_eq!(backend.active_atlas, Some(2));
+
+        /* the previous frame might still sample the replaced texture */
+        rebuild.poll(&mut backend);
+        assert!(backend.destroyed_textures.is_empty());
+
+        backend.textures_in_use = false;
+        rebuild.poll(&mut backend);
+        assert_eq!(backend.destroyed_textures, vec![1]);
+    }
+
+    #[test]
+    fn failed_build_keeps_atlas() {
+        let mut backend = TestBackend {
+            fail_build: true,
+            ..Default::default()
+        };
+        let mut rebuild = TestRebuild::new();
+
+        rebuild.request();
+        for _ in 0..4 {
+            assert!(!rebuild.poll(&mut backend));
+        }
+        assert_eq!(backend.builds_started, 1);
+        assert_eq!(backend.active_atlas, None);
+
+        backend.fail_build = false;
+        rebuild.request();
+        poll_until_activated(&mut rebuild, &mut backend);
+        assert_eq!(backend.active_atlas, Some(2));
+    }
+}
@@ -1,3 +1,8 @@
+use std::time::{
+    Duration,
+    Instant,
+};
+
 use imgui::{
     Key,
     MouseButton,
@@ -13,23 +18,45 @@ use windows::Win32::{
     },
     Graphics::Gdi::ScreenToClient,
     UI::{
-        Input::KeyboardAndMouse::{
-            GetAsyncKeyState,
-            VIRTUAL_KEY,
-            VK_CONTROL,
-            VK_LBUTTON,
-            VK_LCONTROL,
-            VK_LMENU,
-            VK_LSHIFT,
-            VK_LWIN,
-            VK_MBUTTON,
-            VK_MENU,
-            VK_RBUTTON,
-            VK_RMENU,
-            VK_RSHIFT,
-            VK_RWIN,
-            VK_XBUTTON1,
-            VK_XBUTTON2,
+        Input::{
+            KeyboardAndMouse::{
+                GetAsyncKeyState,
+                VIRTUAL_KEY,
+                VK_CONTROL,
+                VK_LBUTTON,
+                VK_LCONTROL,
+                VK_LMENU,
+                VK_LSHIFT,
+                VK_LWIN,
+                VK_MBUTTON,
+                VK_MENU,
+                VK_RBUTTON,
+                VK_RMENU,
+                VK_RSHIFT,
+                VK_RWIN,
+                VK_XBUTTON1,
+                VK_XBUTTON2,
+            },
+            XboxController::{
+                XInputGetState,
+                XINPUT_GAMEPAD,
+                XINPUT_GAMEPAD_A,
+                XINPUT_GAMEPAD_B,
+                XINPUT_GAMEPAD_BACK,
+                XINPUT_GAMEPAD_BUTTON_FLAGS,
+                XINPUT_GAMEPAD_DPAD_DOWN,
+                XINPUT_GAMEPAD_DPAD_LEFT,
+                XINPUT_GAMEPAD_DPAD_RIGHT,
+                XINPUT_GAMEPAD_DPAD_UP,
+                XINPUT_GAMEPAD_LEFT_SHOULDER,
+                XINPUT_GAMEPAD_LEFT_THUMB,
+                XINPUT_GAMEPAD_RIGHT_SHOULDER,
+                XINPUT_GAMEPAD_RIGHT_THUMB,
+                XINPUT_GAMEPAD_START,
+                XINPUT_GAMEPAD_X,
+                XINPUT_GAMEPAD_Y,
+                XINPUT_STATE,
+            },
         },
         WindowsAndMessaging::GetCursorPos,
     },
@@ -37,6 +64,35 @@ use windows::Win32::{
 
 const VK_KEY_MAX: usize = 256;
 
+/// Amount of gamepads supported by XInput
+const XUSER_MAX_COUNT: u32 = 4;
+
+/// Polling disconnected gamepads is expensive, therefore new gamepads will only be searched periodically
+const GAMEPAD_SEARCH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Thumb stick dead zone as recommended by XInput (XINPUT_GAMEPAD_LEFT_THUMB_DEADZONE)
+const THUMB_DEAD_ZONE: f32 = 7849.0;
+
+/// Trigger threshold as recommended by XInput (XINPUT_GAMEPAD_TRIGGER_THRESHOLD)
+const TRIGGER_THRESHOLD: f32 = 30.0;
+
+const GAMEPAD_BUTTONS: [(XINPUT_GAMEPAD_BUTTON_FLAGS, Key); 14] = [
+    (XINPUT_GAMEPAD_START, Key::GamepadStart),
+    (XINPUT_GAMEPAD_BACK, Key::GamepadBack),
+    (XINPUT_GAMEPAD_A, Key::GamepadFaceDown),
+    (XINPUT_GAMEPAD_B, Key::GamepadFaceRight),
+    (XINPUT_GAMEPAD_X, Key::GamepadFaceLeft),
+    (XINPUT_GAMEPAD_Y, Key::GamepadFaceUp),
+    (XINPUT_GAMEPAD_DPAD_LEFT, Key::GamepadDpadLeft),
+    (XINPUT_GAMEPAD_DPAD_RIGHT, Key::GamepadDpadRight),
+    (XINPUT_GAMEPAD_DPAD_UP, Key::GamepadDpadUp),
+    (XINPUT_GAMEPAD_DPAD_DOWN, Key::GamepadDpadDown),
+    (XINPUT_GAMEPAD_LEFT_SHOULDER, Key::GamepadL1),
+    (XINPUT_GAMEPAD_RIGHT_SHOULDER, Key::GamepadR1),
+    (XINPUT_GAMEPAD_LEFT_THUMB, Key::GamepadL3),
+    (XINPUT_GAMEPAD_RIGHT_THUMB, Key::GamepadR3),
+];
+
 #[derive(Debug, Default)]
 pub struct MouseInputSystem;
 impl MouseInputSystem {
@@ -107,6 +163,122 @@ impl KeyboardInputSystem {
     }
 }
 
+/// Gamepad input using XInput.
+/// The first connected gamepad will be used for the ImGui gamepad navigation.
+#[derive(Debug)]
+pub struct GamepadInputSystem {
+    /// Index of the currently connected gamepad
+    user_index: Option<u32>,
+    last_search: Option<Instant>,
+
+    /// Gamepad state has been reported to ImGui and must be released when disabled
+    state_reported: bool,
+}
+
+impl GamepadInputSystem {
+    pub fn new() -> Self {
+        Self {
+            user_index: None,
+            last_search: None,
+            state_reported: false,
+        }
+    }
+
+    fn read_gamepad(user_index: u32) -> Option<XINPUT_GAMEPAD> {
+        let mut state = XINPUT_STATE::default();
+        if unsafe { XInputGetState(user_index, &mut state) } == 0 {
+            Some(state.Gamepad)
+        } else {
+            None
+        }
+    }
+
+    fn find_gamepad(&mut self) -> Option<XINPUT_GAMEPAD> {
+        if let Some(user_index) = self.user_index {
+            if let Some(gamepad) = Self::read_gamepad(user_index) {
+                return Some(gamepad);
+            }
+
+            log::debug!("Gamepad {} disconnected", user_index);
+            self.user_index = None;
+        }
+
+        if self
+            .last_search
+            .map(|last_search| last_search.elapsed() < GAMEPAD_SEARCH_INTERVAL)
+            .unwrap_or(false)
+        {
+            return None;
+        }
+
+        self.last_search = Some(Instant::now());
+        for user_index in 0..XUSER_MAX_COUNT {
+            if let Some(gamepad) = Self::read_gamepad(user_index) {
+                log::debug!("Using gamepad {} for navigation", user_index);
+                self.user_index = Some(user_index);
+                return Some(gamepad);
+            }
+        }
+
+        None
+    }
+
+    fn report_state(io: &mut imgui::Io, gamepad: &XINPUT_GAMEPAD) {
+        for (button, key) in GAMEPAD_BUTTONS {
+            io.add_key_event(key, gamepad.wButtons.0 & button.0 != 0);
+        }
+
+        let mut analog = |key: Key, value: f32, threshold: f32, max: f32| {
+            let value = ((value - threshold) / (max - threshold)).clamp(0.0, 1.0);
+            io.add_key_analog_event(key, value > 0.1, value);
+        };
+
+        analog(
+            Key::GamepadL2,
+            gamepad.bLeftTrigger as f32,
+            TRIGGER_THRESHOLD,
+            255.0,
+        );
+        analog(
+            Key::GamepadR2,
+            gamepad.bRightTrigger as f32,
+            TRIGGER_THRESHOLD,
+            255.0,
+        );
+
+        let (thumb_x, thumb_y) = (gamepad.sThumbLX as f32, gamepad.sThumbLY as f32);
+        analog(Key::GamepadLStickLeft, -thumb_x, THUMB_DEAD_ZONE, 32767.0);
+        analog(Key::GamepadLStickRight, thumb_x, THUMB_DEAD_ZONE, 32767.0);
+        analog(Key::GamepadLStickUp, thumb_y, THUMB_DEAD_ZONE, 32767.0);
+        analog(Key::GamepadLStickDown, -thumb_y, THUMB_DEAD_ZONE, 32767.0);
+
+        let (thumb_x, thumb_y) = (gamepad.sThumbRX as f32, gamepad.sThumbRY as f32);
+        analog(Key::GamepadRStickLeft, -thumb_x, THUMB_DEAD_ZONE, 32767.0);
+        analog(Key::GamepadRStickRight, thumb_x, THUMB_DEAD_ZONE, 32767.0);
+        analog(Key::GamepadRStickUp, thumb_y, THUMB_DEAD_ZONE, 32767.0);
+        analog(Key::GamepadRStickDown, -thumb_y, THUMB_DEAD_ZONE, 32767.0);
+    }
+
+    pub fn update(&mut self, enabled: bool, io: &mut imgui::Io) {
+        let gamepad = if enabled { self.find_gamepad() } else { None };
+        match gamepad {
+            Some(gamepad) => {
+                io.backend_flags.insert(imgui::BackendFlags::HAS_GAMEPAD);
+                Self::report_state(io, &gamepad);
+                self.state_reported = true;
+            }
+            None => {
+                io.backend_flags.remove(imgui::BackendFlags::HAS_GAMEPAD);
+                if self.state_reported {
+                    /* release all buttons */
+                    Self::report_state(io, &XINPUT_GAMEPAD::default());
+                    self.state_reported = false;
+                }
+            }
+        }
+    }
+}
+
 fn to_imgui_key(keycode: VIRTUAL_KEY) -> Option<Key> {
     use windows::Win32::UI::Input::KeyboardAndMouse::*;
 
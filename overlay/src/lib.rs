@@ -1,5 +1,6 @@
 use std::{
     ffi::CString,
+    sync::Arc,
     time::Instant,
 };
 
@@ -8,11 +9,17 @@ use clipboard::ClipboardSupport;
 use copypasta::ClipboardContext;
 use imgui::{
     Context,
+    FontAtlas,
     FontConfig,
     FontSource,
     Io,
 };
 use imgui_rs_vulkan_renderer::{
+    vulkan::{
+        create_vulkan_descriptor_pool,
+        create_vulkan_descriptor_set,
+        create_vulkan_descriptor_set_layout,
+    },
     Options,
     Renderer,
 };
@@ -37,6 +44,7 @@ use imgui_winit_support::{
     WinitPlatform,
 };
 use input::{
+    GamepadInputSystem,
     KeyboardInputSystem,
     MouseInputSystem,
 };
@@ -78,6 +86,7 @@ use windows::{
                 SWP_NOSIZE,
                 SW_SHOWNOACTIVATE,
                 WDA_EXCLUDEFROMCAPTURE,
+                WDA_MONITOR,
                 WDA_NONE,
                 WS_CLIPSIBLINGS,
                 WS_EX_LAYERED,
@@ -91,18 +100,56 @@ use windows::{
     },
 };
 
+/// Display capture protection of the overlay window
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaptureProtection {
+    /// The overlay is visible in screenshots and streams
+    Disabled,
+
+    /// The overlay is excluded from screenshots and streams
+    Excluded,
+
+    /// The OS does not support excluding windows from capture (prior Windows 10 2004).
+    /// The overlay shows up as a black area in screenshots and streams.
+    Masked,
+
+    /// The display affinity could not be changed.
+    /// The overlay might still be visible in screenshots and streams.
+    Failed,
+}
+
+impl CaptureProtection {
+    /// Returns true if the overlay content does not show up in captures
+    pub fn is_protected(&self) -> bool {
+        matches!(self, Self::Excluded | Self::Masked)
+    }
+}
+
 mod clipboard;
 mod error;
 pub use error::*;
 mod input;
 mod window_tracker;
-pub use window_tracker::OverlayTarget;
+pub use window_tracker::{
+    list_monitors,
+    MonitorInfo,
+    OverlayTarget,
+};
 
 mod vulkan;
 
+mod font_atlas;
+use font_atlas::{
+    VulkanFontAtlas,
+    VulkanFontAtlasRebuild,
+};
+
 mod perf;
 pub use perf::PerfTracker;
 
+mod frame_limiter;
+use frame_limiter::FrameLimiter;
+
 mod vulkan_render;
 use vulkan_render::*;
 
@@ -166,32 +213,32 @@ fn create_window(event_loop: &EventLoop<()>, title: &str) -> Result<Window> {
     Ok(window)
 }
 
+/// Font callback which adds additional fonts to the font atlas.
+/// It will be called again whenever the font atlas gets rebuild.
+/// Rebuilds happen in the background, therefore the callback will be invoked on a worker thread.
+pub type FontInitCallback = Arc<dyn Fn(&mut FontAtlas) -> () + Send + Sync>;
+
 pub struct OverlayOptions {
     pub title: String,
     pub target: OverlayTarget,
-    pub font_init: Option<Box<dyn Fn(&mut imgui::Context) -> ()>>,
+    pub font_init: Option<FontInitCallback>,
 }
 
-fn create_imgui_context(options: &OverlayOptions) -> Result<(WinitPlatform, imgui::Context)> {
-    let mut imgui = Context::create();
-    imgui.set_ini_filename(None);
+/// TTF data of the default overlay font
+pub const DEFAULT_FONT_DATA: &'static [u8] = include_bytes!("../resources/Roboto-Regular.ttf");
 
-    let platform = WinitPlatform::init(&mut imgui);
-
-    match ClipboardContext::new() {
-        Ok(backend) => imgui.set_clipboard_backend(ClipboardSupport(backend)),
-        Err(error) => log::warn!("Failed to initialize clipboard: {}", error),
-    };
+/// Size of the default overlay font in pixels
+pub const DEFAULT_FONT_SIZE: f32 = 18.0;
 
+fn add_default_font(atlas: &mut FontAtlas) {
     // Fixed font size. Note imgui_winit_support uses "logical
     // pixels", which are physical pixels scaled by the devices
     // scaling factor. Meaning, 13.0 pixels should look the same size
     // on two different screens, and thus we do not need to scale this
     // value (as the scaling is handled by winit)
-    let font_size = 18.0;
-    imgui.fonts().add_font(&[FontSource::TtfData {
-        data: include_bytes!("../resources/Roboto-Regular.ttf"),
-        size_pixels: font_size,
+    atlas.add_font(&[FontSource::TtfData {
+        data: DEFAULT_FONT_DATA,
+        size_pixels: DEFAULT_FONT_SIZE,
         config: Some(FontConfig {
             // As imgui-glium-renderer isn't gamma-correct with
             // it's font rendering, we apply an arbitrary
@@ -205,8 +252,22 @@ fn create_imgui_context(options: &OverlayOptions) -> Result<(WinitPlatform, imgu
             ..FontConfig::default()
         }),
     }]);
+}
+
+fn create_imgui_context(options: &OverlayOptions) -> Result<(WinitPlatform, imgui::Context)> {
+    let mut imgui = Context::create();
+    imgui.set_ini_filename(None);
+
+    let platform = WinitPlatform::init(&mut imgui);
+
+    match ClipboardContext::new() {
+        Ok(backend) => imgui.set_clipboard_backend(ClipboardSupport(backend)),
+        Err(error) => log::warn!("Failed to initialize clipboard: {}", error),
+    };
+
+    add_default_font(imgui.fonts());
     if let Some(callback) = &options.font_init {
-        callback(&mut imgui);
+        callback(imgui.fonts());
     }
 
     Ok((platform, imgui))
@@ -229,6 +290,8 @@ pub struct System {
     pub renderer: Renderer,
 
     pub window_tracker: WindowTracker,
+    font_init: Option<FontInitCallback>,
+    exit_callback: Option<Box<dyn FnOnce(&mut SystemRuntimeController)>>,
 }
 
 pub fn init(options: &OverlayOptions) -> Result<System> {
@@ -312,6 +375,8 @@ pub fn init(options: &OverlayOptions) -> Result<System> {
         renderer,
 
         window_tracker,
+        font_init: options.font_init.clone(),
+        exit_callback: None,
     })
 }
 
@@ -356,6 +421,50 @@ impl OverlayActiveTracker {
 const PERF_RECORDS: usize = 2048;
 
 impl System {
+    /// Upload an RGBA8 image and register it as ImGui texture.
+    /// The texture stays valid for the whole lifetime of the overlay.
+    pub fn register_texture(
+        &mut self,
+        width: u32,
+        height: u32,
+        data: &[u8],
+    ) -> Result<imgui::TextureId> {
+        let device = &self.vulkan_context.device;
+        let mem_properties = unsafe {
+            self.vulkan_context
+                .instance
+                .get_physical_device_memory_properties(self.vulkan_context.physical_device)
+        };
+
+        let texture = vulkan::Texture::from_rgba8(
+            device,
+            self.vulkan_context.graphics_queue,
+            self.vulkan_context.command_pool,
+            mem_properties,
+            width,
+            height,
+            data,
+        )?;
+
+        let descriptor_set_layout = create_vulkan_descriptor_set_layout(device)?;
+        let descriptor_pool = create_vulkan_descriptor_pool(device, 1)?;
+        let descriptor_set = create_vulkan_descriptor_set(
+            device,
+            descriptor_set_layout,
+            descriptor_pool,
+            texture.image_view,
+            texture.sampler,
+        )?;
+
+        Ok(self.renderer.textures().insert(descriptor_set))
+    }
+
+    /// Register a callback which will be invoked once the main loop exits.
+    /// The main loop never returns, therefore this is the last chance to e.g. persist state.
+    pub fn on_exit(&mut self, callback: impl FnOnce(&mut SystemRuntimeController) + 'static) {
+        self.exit_callback = Some(Box::new(callback));
+    }
+
     pub fn main_loop<U, R>(self, mut update: U, mut render: R) -> !
     where
         U: FnMut(&mut SystemRuntimeController) -> bool + 'static,
@@ -377,21 +486,37 @@ impl System {
             mut renderer,
 
             window_tracker,
+            font_init,
+            mut exit_callback,
             ..
         } = self;
         let mut last_frame = Instant::now();
 
+        let mem_properties = unsafe {
+            vulkan_context
+                .instance
+                .get_physical_device_memory_properties(vulkan_context.physical_device)
+        };
+        let mut font_atlas_rebuild = VulkanFontAtlasRebuild::new();
+
         let mut runtime_controller = SystemRuntimeController {
             hwnd: HWND(window.hwnd() as isize),
             imgui,
 
             active_tracker: OverlayActiveTracker::new(),
             key_input_system: KeyboardInputSystem::new(),
+            gamepad_input_system: GamepadInputSystem::new(),
+            gamepad_navigation: false,
             mouse_input_system: MouseInputSystem::new(),
             window_tracker,
 
             frame_count: 0,
             debug_overlay_shown: false,
+            fonts_dirty: false,
+            fonts_rebuilt: false,
+
+            frame_limiter: FrameLimiter::new(),
+            last_frame_vertex_count: 0,
         };
 
         let mut dirty_swapchain = false;
@@ -417,6 +542,34 @@ impl System {
                 Event::MainEventsCleared => {
                     perf.mark("events cleared");
 
+                    /* pace before updating, so the rendered frame is based on the latest state */
+                    runtime_controller.frame_limiter.wait_for_frame();
+                    perf.mark("frame pacing");
+
+                    /*
+                     * Rebuild fonts in the background.
+                     * Polled before updating, so the application picks up the fonts before rendering.
+                     */
+                    {
+                        if std::mem::take(&mut runtime_controller.fonts_dirty) {
+                            font_atlas_rebuild.request();
+                        }
+
+                        runtime_controller.fonts_rebuilt =
+                            font_atlas_rebuild.poll(&mut VulkanFontAtlas {
+                                device: &vulkan_context.device,
+                                queue: vulkan_context.graphics_queue,
+                                command_pool: vulkan_context.upload_command_pool,
+                                mem_properties,
+                                render_fence: fence,
+
+                                renderer: &mut renderer,
+                                imgui: &mut runtime_controller.imgui,
+                                font_init: font_init.as_ref(),
+                            });
+                        perf.mark("fonts");
+                    }
+
                     /* Update */
                     {
                         if !runtime_controller.update_state(&window) {
@@ -479,6 +632,21 @@ impl System {
                                     ));
                                     ui.same_line_with_pos(275.0);
 
+                                    ui.text(format!(
+                                        "Jitter: {:.2}ms",
+                                        runtime_controller
+                                            .frame_limiter
+                                            .frame_time_jitter()
+                                            .as_secs_f32()
+                                            * 1000.0
+                                    ));
+
+                                    ui.text(match runtime_controller.frame_limiter.frame_rate() {
+                                        Some(frame_rate) => format!("FPS limit: {}", frame_rate),
+                                        None => "FPS limit: none".to_string(),
+                                    });
+                                    ui.same_line_with_pos(275.0);
+
                                     ui.text("History length:");
                                     ui.same_line();
                                     let mut history_length = perf.history_length();
@@ -496,6 +664,8 @@ impl System {
 
                         platform.prepare_render(ui, &window);
                         let draw_data = runtime_controller.imgui.render();
+                        runtime_controller.last_frame_vertex_count =
+                            draw_data.total_vtx_count as usize;
 
                         unsafe {
                             vulkan_context
@@ -592,10 +762,27 @@ impl System {
                         runtime_controller.frame_rendered();
                     }
                 }
+                Event::WindowEvent {
+                    event: WindowEvent::ScaleFactorChanged { new_inner_size, .. },
+                    ..
+                } => {
+                    /*
+                     * The overlay has been moved onto a monitor with a different scale factor.
+                     * Keep the current size, the window tracker will resize the overlay to match the target again.
+                     */
+                    *new_inner_size = window.inner_size();
+                    runtime_controller.window_tracker.mark_force_update();
+                    dirty_swapchain = true;
+                }
                 Event::WindowEvent {
                     event: WindowEvent::CloseRequested,
                     ..
                 } => *control_flow = ControlFlow::Exit,
+                Event::LoopDestroyed => {
+                    if let Some(callback) = exit_callback.take() {
+                        callback(&mut runtime_controller);
+                    }
+                }
                 _ => {}
             }
         })
@@ -611,16 +798,25 @@ pub struct SystemRuntimeController {
     active_tracker: OverlayActiveTracker,
     mouse_input_system: MouseInputSystem,
     key_input_system: KeyboardInputSystem,
+    gamepad_input_system: GamepadInputSystem,
+    gamepad_navigation: bool,
 
     window_tracker: WindowTracker,
 
     frame_count: u64,
+    fonts_dirty: bool,
+    fonts_rebuilt: bool,
+
+    frame_limiter: FrameLimiter,
+    last_frame_vertex_count: usize,
 }
 
 impl SystemRuntimeController {
     fn update_state(&mut self, window: &Window) -> bool {
         self.mouse_input_system.update(window, self.imgui.io_mut());
         self.key_input_system.update(window, self.imgui.io_mut());
+        self.gamepad_input_system
+            .update(self.gamepad_navigation, self.imgui.io_mut());
         self.active_tracker.update(window, self.imgui.io());
         if !self.window_tracker.update(window) {
             log::info!("Target window has been closed. Exiting overlay.");
@@ -640,24 +836,107 @@ impl SystemRuntimeController {
         }
     }
 
-    pub fn toggle_screen_capture_visibility(&self, should_be_visible: bool) {
+    /// Change whether the overlay should be visible in screenshots and streams.
+    /// Returns the resulting capture protection of the overlay.
+    pub fn toggle_screen_capture_visibility(&self, should_be_visible: bool) -> CaptureProtection {
         unsafe {
-            let (target_state, state_name) = if should_be_visible {
-                (WDA_NONE, "normal")
-            } else {
-                (WDA_EXCLUDEFROMCAPTURE, "exclude from capture")
-            };
+            if should_be_visible {
+                if SetWindowDisplayAffinity(self.hwnd, WDA_NONE).as_bool() {
+                    return CaptureProtection::Disabled;
+                }
 
-            if !SetWindowDisplayAffinity(self.hwnd, target_state).as_bool() {
                 log::warn!(
                     "{} '{}'.",
                     obfstr!("Failed to change overlay display affinity to"),
-                    state_name
+                    obfstr!("normal")
                 );
+                /* the previous affinity is still active, but it's unknown which one */
+                return CaptureProtection::Failed;
+            }
+
+            if SetWindowDisplayAffinity(self.hwnd, WDA_EXCLUDEFROMCAPTURE).as_bool() {
+                return CaptureProtection::Excluded;
+            }
+
+            /* WDA_EXCLUDEFROMCAPTURE requires Windows 10 2004 or newer */
+            log::warn!(
+                "{} '{}'. {}",
+                obfstr!("Failed to change overlay display affinity to"),
+                obfstr!("exclude from capture"),
+                obfstr!("Falling back to masking the overlay.")
+            );
+            if SetWindowDisplayAffinity(self.hwnd, WDA_MONITOR).as_bool() {
+                return CaptureProtection::Masked;
             }
+
+            log::warn!(
+                "{} '{}'.",
+                obfstr!("Failed to change overlay display affinity to"),
+                obfstr!("monitor")
+            );
+            CaptureProtection::Failed
         }
     }
 
+    /// Rebuild the font atlas in the background.
+    /// All fonts will be added again using the font init callback.
+    /// The current fonts stay active until the new font atlas has been uploaded (see `fonts_rebuilt`).
+    pub fn rebuild_fonts(&mut self) {
+        self.fonts_dirty = true;
+    }
+
+    /// Returns true if the font atlas has been replaced for the current frame.
+    /// Font ids of the previous font atlas are invalid and must not be used anymore.
+    pub fn fonts_rebuilt(&self) -> bool {
+        self.fonts_rebuilt
+    }
+
+    /// Client area of the target window relative to the overlay as `[x, y, width, height]`.
+    /// The values are in ImGui coordinates and already account for the monitor scale factor.
+    pub fn game_viewport(&self) -> [f32; 4] {
+        let viewport = self.window_tracker.game_viewport();
+        let [scale_x, scale_y] = self.imgui.io().display_framebuffer_scale;
+        [
+            viewport.left as f32 / scale_x,
+            viewport.top as f32 / scale_y,
+            (viewport.right - viewport.left) as f32 / scale_x,
+            (viewport.bottom - viewport.top) as f32 / scale_y,
+        ]
+    }
+
+    /// Cover the given monitor (index within [list_monitors]) instead of following the target window
+    pub fn set_overlay_monitor(&mut self, monitor: Option<usize>) {
+        self.window_tracker.set_target_monitor(monitor);
+    }
+
+    /// Enable the ImGui keyboard navigation (tab / arrow keys focus traversal)
+    pub fn set_keyboard_navigation(&mut self, enabled: bool) {
+        self.imgui
+            .io_mut()
+            .config_flags
+            .set(imgui::ConfigFlags::NAV_ENABLE_KEYBOARD, enabled);
+    }
+
+    /// Enable the ImGui gamepad navigation using the first connected XInput gamepad.
+    /// Gamepads will only be polled while enabled.
+    pub fn set_gamepad_navigation(&mut self, enabled: bool) {
+        self.gamepad_navigation = enabled;
+        self.imgui
+            .io_mut()
+            .config_flags
+            .set(imgui::ConfigFlags::NAV_ENABLE_GAMEPAD, enabled);
+    }
+
+    /// Limit the overlay frame rate (None or zero = unlimited)
+    pub fn set_frame_rate_limit(&mut self, frame_rate: Option<u32>) {
+        self.frame_limiter.set_frame_rate(frame_rate);
+    }
+
+    /// Returns true if nothing has been drawn within the last frame
+    pub fn last_frame_empty(&self) -> bool {
+        self.last_frame_vertex_count == 0
+    }
+
     pub fn toggle_debug_overlay(&mut self, visible: bool) {
         self.debug_overlay_shown = visible;
     }
@@ -85,6 +85,11 @@ mod buffer {
     }
 }
 
+pub use texture::{
+    PendingTexture,
+    Texture,
+};
+
 mod texture {
 
     use ash::{
@@ -141,6 +146,36 @@ mod texture {
             Ok(texture)
         }
 
+        /// Create a texture from an `u8` array containing an rgba image without
+        /// waiting for the upload to complete.
+        /// The texture may only be used once the upload has been completed (see PendingTexture).
+        ///
+        /// Arguments are the same as for `from_rgba8`.
+        pub fn upload_rgba8(
+            device: &Device,
+            transfer_queue: vk::Queue,
+            command_pool: vk::CommandPool,
+            mem_properties: vk::PhysicalDeviceMemoryProperties,
+            width: u32,
+            height: u32,
+            data: &[u8],
+        ) -> RendererResult<PendingTexture> {
+            let fence = unsafe { device.create_fence(&vk::FenceCreateInfo::builder(), None)? };
+            let (command_buffer, result) =
+                submit_one_time_commands(device, transfer_queue, command_pool, fence, |buffer| {
+                    Self::cmd_from_rgba(device, buffer, mem_properties, width, height, data)
+                })?;
+
+            let (texture, staging_buff, staging_mem) = result?;
+            Ok(PendingTexture {
+                texture,
+                staging_buff,
+                staging_mem,
+                command_buffer,
+                fence,
+            })
+        }
+
         fn cmd_from_rgba(
             device: &Device,
             command_buffer: vk::CommandBuffer,
@@ -331,6 +366,79 @@ mod texture {
         }
     }
 
+    /// Texture which is still being uploaded to the GPU.
+    pub struct PendingTexture {
+        texture: Texture,
+        staging_buff: vk::Buffer,
+        staging_mem: vk::DeviceMemory,
+        command_buffer: vk::CommandBuffer,
+        fence: vk::Fence,
+    }
+
+    impl PendingTexture {
+        /// Check if the upload has been completed without blocking.
+        pub fn is_complete(&self, device: &Device) -> RendererResult<bool> {
+            Ok(unsafe { device.get_fence_status(self.fence)? })
+        }
+
+        /// Free the upload resources and return the texture.
+        /// The upload must have been completed (see `is_complete`).
+        pub fn finish(self, device: &Device, command_pool: vk::CommandPool) -> Texture {
+            unsafe {
+                device.destroy_fence(self.fence, None);
+                device.free_command_buffers(command_pool, &[self.command_buffer]);
+                device.destroy_buffer(self.staging_buff, None);
+                device.free_memory(self.staging_mem, None);
+            }
+
+            self.texture
+        }
+    }
+
+    /// Record and submit a one time command buffer.
+    /// The fence will be signaled once the commands have been executed.
+    /// The returned command buffer must be freed afterwards.
+    fn submit_one_time_commands<R, F: FnOnce(vk::CommandBuffer) -> R>(
+        device: &Device,
+        queue: vk::Queue,
+        pool: vk::CommandPool,
+        fence: vk::Fence,
+        executor: F,
+    ) -> RendererResult<(vk::CommandBuffer, R)> {
+        let command_buffer = {
+            let alloc_info = vk::CommandBufferAllocateInfo::builder()
+                .level(vk::CommandBufferLevel::PRIMARY)
+                .command_pool(pool)
+                .command_buffer_count(1);
+
+            unsafe { device.allocate_command_buffers(&alloc_info)?[0] }
+        };
+        let command_buffers = [command_buffer];
+
+        // Begin recording
+        {
+            let begin_info = vk::CommandBufferBeginInfo::builder()
+                .flags(vk::CommandBufferUsageFlags::ONE_TIME_SUBMIT);
+            unsafe { device.begin_command_buffer(command_buffer, &begin_info)? };
+        }
+
+        // Execute user function
+        let executor_result = executor(command_buffer);
+
+        // End recording
+        unsafe { device.end_command_buffer(command_buffer)? };
+
+        // Submit without waiting
+        {
+            let submit_info = vk::SubmitInfo::builder()
+                .command_buffers(&command_buffers)
+                .build();
+            unsafe { device.queue_submit(queue, &[submit_info], fence)? };
+        }
+
+        Ok((command_buffer, executor_result))
+    }
+
     fn execute_one_time_commands<R, F: FnOnce(vk::CommandBuffer) -> R>(
         device: &Device,
         queue: vk::Queue,
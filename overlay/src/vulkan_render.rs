@@ -48,6 +48,10 @@ pub struct VulkanContext {
     pub graphics_queue: vk::Queue,
     pub present_queue: vk::Queue,
     pub command_pool: vk::CommandPool,
+
+    /// Command pool for uploads which are still executing while the next frames are recorded.
+    /// The command pool of the render loop gets reset every frame.
+    pub upload_command_pool: vk::CommandPool,
 }
 
 impl VulkanContext {
@@ -94,6 +98,12 @@ impl VulkanContext {
                 .flags(vk::CommandPoolCreateFlags::empty());
             unsafe { device.create_command_pool(&command_pool_info, None)? }
         };
+        let upload_command_pool = {
+            let command_pool_info = vk::CommandPoolCreateInfo::builder()
+                .queue_family_index(graphics_q_index)
+                .flags(vk::CommandPoolCreateFlags::TRANSIENT);
+            unsafe { device.create_command_pool(&command_pool_info, None)? }
+        };
 
         Ok(Self {
             _entry: entry,
@@ -109,6 +119,7 @@ impl VulkanContext {
             graphics_queue,
             present_queue,
             command_pool,
+            upload_command_pool,
         })
     }
 }
@@ -118,6 +129,8 @@ impl Drop for VulkanContext {
         log::debug!("Destroying Vulkan Context");
         unsafe {
             self.device.destroy_command_pool(self.command_pool, None);
+            self.device
+                .destroy_command_pool(self.upload_command_pool, None);
             self.device.destroy_device(None);
             self.surface.destroy_surface(self.surface_khr, None);
             self.debug_utils
@@ -7,6 +7,7 @@ use windows::{
     Win32::{
         Foundation::{
             GetLastError,
+            BOOL,
             ERROR_INVALID_WINDOW_HANDLE,
             HWND,
             LPARAM,
@@ -14,7 +15,17 @@ use windows::{
             RECT,
             WPARAM,
         },
-        Graphics::Gdi::ClientToScreen,
+        Graphics::Gdi::{
+            ClientToScreen,
+            EnumDisplayMonitors,
+            GetMonitorInfoW,
+            MonitorFromWindow,
+            HDC,
+            HMONITOR,
+            MONITORINFO,
+            MONITORINFOEXW,
+            MONITOR_DEFAULTTONEAREST,
+        },
         UI::{
             Input::KeyboardAndMouse::GetFocus,
             WindowsAndMessaging::{
@@ -109,11 +120,100 @@ impl OverlayTarget {
     }
 }
 
+/// Monitor which is attached to the system
+#[derive(Debug, Clone)]
+pub struct MonitorInfo {
+    /// Device name of the monitor (e.g. `\\.\DISPLAY1`)
+    pub name: String,
+
+    /// Bounds of the monitor in virtual screen coordinates
+    pub bounds: RECT,
+    pub primary: bool,
+}
+
+impl MonitorInfo {
+    pub fn width(&self) -> i32 {
+        self.bounds.right - self.bounds.left
+    }
+
+    pub fn height(&self) -> i32 {
+        self.bounds.bottom - self.bounds.top
+    }
+}
+
+fn read_monitor_info(monitor: HMONITOR) -> Option<MonitorInfo> {
+    /* MONITORINFOF_PRIMARY */
+    const FLAG_PRIMARY: u32 = 0x01;
+
+    let mut info = MONITORINFOEXW::default();
+    info.monitorInfo.cbSize = std::mem::size_of::<MONITORINFOEXW>() as u32;
+
+    let success =
+        unsafe { GetMonitorInfoW(monitor, &mut info as *mut _ as *mut MONITORINFO).as_bool() };
+    if !success {
+        return None;
+    }
+
+    let name_length = info
+        .szDevice
+        .iter()
+        .position(|value| *value == 0)
+        .unwrap_or(info.szDevice.len());
+
+    Some(MonitorInfo {
+        name: String::from_utf16_lossy(&info.szDevice[..name_length]),
+        bounds: info.monitorInfo.rcMonitor,
+        primary: (info.monitorInfo.dwFlags & FLAG_PRIMARY) != 0,
+    })
+}
+
+unsafe extern "system" fn enum_monitor_callback(
+    monitor: HMONITOR,
+    _hdc: HDC,
+    _bounds: *mut RECT,
+    data: LPARAM,
+) -> BOOL {
+    let monitors = &mut *(data.0 as *mut Vec<HMONITOR>);
+    monitors.push(monitor);
+    BOOL::from(true)
+}
+
+/// List all monitors in the order Windows enumerates them
+pub fn list_monitors() -> Vec<MonitorInfo> {
+    let mut monitors = Vec::<HMONITOR>::with_capacity(4);
+    let success = unsafe {
+        EnumDisplayMonitors(
+            None,
+            None,
+            Some(enum_monitor_callback),
+            LPARAM(&mut monitors as *mut _ as isize),
+        )
+        .as_bool()
+    };
+    if !success {
+        log::warn!("EnumDisplayMonitors failed: {:?}", unsafe {
+            GetLastError()
+        });
+        return Vec::new();
+    }
+
+    monitors.into_iter().filter_map(read_monitor_info).collect()
+}
+
 /// Track the CS2 window and adjust overlay accordingly.
 /// This is only required when playing in windowed mode.
 pub struct WindowTracker {
     cs2_hwnd: HWND,
     current_bounds: RECT,
+
+    /// Client area of the CS2 window in screen coordinates
+    game_bounds: RECT,
+
+    /// Monitor the CS2 window is currently on
+    current_monitor: HMONITOR,
+
+    /// Index of the monitor the overlay should cover instead of the CS2 window
+    target_monitor: Option<usize>,
 }
 
 impl WindowTracker {
@@ -126,6 +226,10 @@ impl WindowTracker {
         Ok(Self {
             cs2_hwnd: hwnd,
             current_bounds: Default::default(),
+            game_bounds: Default::default(),
+
+            current_monitor: Default::default(),
+            target_monitor: None,
         })
     }
 
@@ -133,17 +237,50 @@ impl WindowTracker {
         self.current_bounds = Default::default();
     }
 
-    pub fn update(&mut self, overlay: &Window) -> bool {
+    /// Cover the monitor with the given index (see [list_monitors]) instead of the CS2 window.
+    /// `None` will follow the CS2 window.
+    pub fn set_target_monitor(&mut self, monitor: Option<usize>) {
+        if self.target_monitor == monitor {
+            return;
+        }
+
+        if let Some(index) = monitor {
+            if index >= list_monitors().len() {
+                log::warn!(
+                    "Overlay monitor {} does not exist. Following the CS2 window.",
+                    index + 1
+                );
+            }
+        }
+
+        self.target_monitor = monitor;
+        self.mark_force_update();
+    }
+
+    /// Client area of the CS2 window relative to the overlay window.
+    /// The overlay may be larger than the CS2 window if it covers a whole monitor.
+    pub fn game_viewport(&self) -> RECT {
+        RECT {
+            left: self.game_bounds.left - self.current_bounds.left,
+            top: self.game_bounds.top - self.current_bounds.top,
+            right: self.game_bounds.right - self.current_bounds.left,
+            bottom: self.game_bounds.bottom - self.current_bounds.top,
+        }
+    }
+
+    fn resolve_overlay_bounds(&self, game_bounds: &RECT) -> RECT {
+        /* falls back to the CS2 window if the monitor has been disconnected */
+        self.target_monitor
+            .and_then(|index| list_monitors().into_iter().nth(index))
+            .map(|monitor| monitor.bounds)
+            .unwrap_or(*game_bounds)
+    }
+
+    fn read_game_bounds(&self) -> Option<RECT> {
         let mut rect: RECT = Default::default();
         let success = unsafe { GetClientRect(self.cs2_hwnd, &mut rect) };
         if !success.as_bool() {
-            let error = unsafe { GetLastError() };
-            if error == ERROR_INVALID_WINDOW_HANDLE {
-                return false;
-            }
-
-            log::warn!("GetClientRect failed for tracked window: {:?}", error);
-            return true;
+            return None;
         }
 
         unsafe {
@@ -151,6 +288,41 @@ impl WindowTracker {
             ClientToScreen(self.cs2_hwnd, &mut rect.right as *mut _ as *mut POINT);
         }
 
+        Some(rect)
+    }
+
+    pub fn update(&mut self, overlay: &Window) -> bool {
+        let game_bounds = match self.read_game_bounds() {
+            Some(rect) => rect,
+            None => {
+                let error = unsafe { GetLastError() };
+                if error == ERROR_INVALID_WINDOW_HANDLE {
+                    return false;
+                }
+
+                log::warn!("GetClientRect failed for tracked window: {:?}", error);
+                return true;
+            }
+        };
+
+        let monitor = unsafe { MonitorFromWindow(self.cs2_hwnd, MONITOR_DEFAULTTONEAREST) };
+        if monitor != self.current_monitor {
+            if let Some(info) = read_monitor_info(monitor) {
+                log::debug!(
+                    "CS2 window is on monitor {} ({}x{})",
+                    info.name,
+                    info.width(),
+                    info.height()
+                );
+            }
+
+            self.current_monitor = monitor;
+            self.mark_force_update();
+        }
+
+        self.game_bounds = game_bounds;
+        let mut rect = self.resolve_overlay_bounds(&game_bounds);
+
         if unsafe { GetFocus() } != self.cs2_hwnd {
             /*
              * CS2 will render a black screen as soon as CS2 does not have the focus and is completely covered by